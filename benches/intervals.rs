@@ -0,0 +1,35 @@
+use calends::{CalendarUnit, IntervalLike};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Monthly axis of a decade, one interval per month
+fn decade_of_months(c: &mut Criterion) {
+    let (start, end) = (CalendarUnit::Month(2015, 1), CalendarUnit::Month(2024, 12));
+
+    let mut group = c.benchmark_group("decade of months");
+    group.bench_function("intervals_for_range", |b| {
+        b.iter(|| CalendarUnit::intervals_for_range(black_box(start), black_box(end)))
+    });
+    group.bench_function("into_interval per unit", |b| {
+        b.iter(|| {
+            black_box(start)
+                .range_to(black_box(end))
+                .map(|unit| unit.into_interval())
+                .collect::<Vec<_>>()
+        })
+    });
+    group.finish();
+
+    // Both produce the same boundaries
+    let bulk = CalendarUnit::intervals_for_range(start, end);
+    let each: Vec<_> = start
+        .range_to(end)
+        .map(|unit| unit.into_interval())
+        .collect();
+    assert!(bulk
+        .iter()
+        .zip(&each)
+        .all(|(a, b)| a.start_opt() == b.start_opt() && a.end_opt() == b.end_opt()));
+}
+
+criterion_group!(benches, decade_of_months);
+criterion_main!(benches);
@@ -0,0 +1,708 @@
+//! Calendars of non-working days
+//!
+//! A business day is any weekday (Monday to Friday) which is not a holiday, calendars can use
+//! another [Workweek] with [HolidayCalendar::with_workweek].
+//!
+//! Holidays can be listed as dates or described by a [HolidayRule] in a [HolidaySet], and
+//! calendars can be combined with [HolidayCalendar::union].
+//!
+//! ```
+//! use calends::{
+//!     calendar::{HolidayCalendar, HolidayRule, HolidaySet},
+//!     MonthDay,
+//! };
+//! use chrono::{NaiveDate, Weekday};
+//!
+//! let country = HolidaySet::new()
+//!     .with_rule(HolidayRule::Fixed(MonthDay::new(12, 25).unwrap()))
+//!     .with_rule(HolidayRule::Weekday(5, -1, Weekday::Mon));
+//! let company = HolidaySet::new().with_date(NaiveDate::from_ymd_opt(2022, 7, 1).unwrap());
+//! let calendar = country.union(company);
+//!
+//! assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2022, 5, 30).unwrap()));
+//! assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2022, 7, 1).unwrap()));
+//! assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2031, 12, 25).unwrap()));
+//! ```
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use chrono::{Datelike, Days, Duration, NaiveDate, Weekday};
+
+use crate::{
+    interval::{marker::End, marker::Start, ClosedInterval},
+    util::{checked_month_end, find_weekday_ascending, find_weekday_descending},
+    MonthDay, WeekStart,
+};
+
+/// Furthest a date is moved looking for a business day before giving up
+const MAX_ADJUSTMENT_DAYS: u32 = 366;
+
+/// Source of holidays
+pub trait HolidayCalendar {
+    fn is_holiday(&self, date: NaiveDate) -> bool;
+
+    /// Whether the date is a weekday which is not a holiday
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        Workweek::default().contains(date.weekday()) && !self.is_holiday(date)
+    }
+
+    /// Holidays falling within the interval in order
+    fn holidays_in(&self, interval: &ClosedInterval) -> Vec<NaiveDate> {
+        interval
+            .start()
+            .iter_days()
+            .take_while(|d| *d <= interval.end())
+            .filter(|d| self.is_holiday(*d))
+            .collect()
+    }
+
+    /// A calendar whose holidays are the holidays of either calendar
+    ///
+    /// A date is a business day when it is a business day in both calendars.
+    fn union<C: HolidayCalendar>(self, other: C) -> Union<Self, C>
+    where
+        Self: Sized,
+    {
+        Union(self, other)
+    }
+
+    /// The same holidays with business days on the days of the workweek instead of Monday to
+    /// Friday
+    ///
+    /// ```
+    /// use calends::calendar::{HolidayCalendar, Workweek};
+    /// use chrono::NaiveDate;
+    ///
+    /// let holidays: Vec<NaiveDate> = vec![];
+    /// let calendar = holidays.with_workweek(Workweek::SUNDAY_TO_THURSDAY);
+    ///
+    /// // 2022-01-02 is a Sunday
+    /// assert!(calendar.is_business_day(NaiveDate::from_ymd_opt(2022, 1, 2).unwrap()));
+    /// assert!(!calendar.is_business_day(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap()));
+    /// ```
+    fn with_workweek(self, workweek: Workweek) -> WithWorkweek<Self>
+    where
+        Self: Sized,
+    {
+        WithWorkweek {
+            calendar: self,
+            workweek,
+        }
+    }
+}
+
+/// Holidays of two calendars together, see [HolidayCalendar::union]
+#[derive(Debug, Clone)]
+pub struct Union<A, B>(A, B);
+
+impl<A: HolidayCalendar, B: HolidayCalendar> HolidayCalendar for Union<A, B> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.0.is_holiday(date) || self.1.is_holiday(date)
+    }
+
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        self.0.is_business_day(date) && self.1.is_business_day(date)
+    }
+}
+
+/// Days of the week which are worked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Workweek {
+    /// One bit per weekday starting from Monday
+    days: u8,
+}
+
+impl Workweek {
+    /// Monday to Friday
+    pub const MONDAY_TO_FRIDAY: Workweek = Workweek { days: 0b0011111 };
+    /// Sunday to Thursday, as in much of the Middle East
+    pub const SUNDAY_TO_THURSDAY: Workweek = Workweek { days: 0b1001111 };
+
+    /// A workweek of the weekdays
+    pub fn new(weekdays: &[Weekday]) -> Self {
+        let days = weekdays
+            .iter()
+            .fold(0, |days, d| days | 1 << d.num_days_from_monday());
+        Workweek { days }
+    }
+
+    /// Whether the weekday is worked
+    pub fn contains(&self, weekday: Weekday) -> bool {
+        self.days & 1 << weekday.num_days_from_monday() != 0
+    }
+}
+
+impl Default for Workweek {
+    fn default() -> Self {
+        Workweek::MONDAY_TO_FRIDAY
+    }
+}
+
+/// A calendar with another workweek, see [HolidayCalendar::with_workweek]
+#[derive(Debug, Clone)]
+pub struct WithWorkweek<C> {
+    calendar: C,
+    workweek: Workweek,
+}
+
+impl<C: HolidayCalendar> HolidayCalendar for WithWorkweek<C> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.calendar.is_holiday(date)
+    }
+
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        self.workweek.contains(date.weekday()) && !self.calendar.is_holiday(date)
+    }
+}
+
+/// A holiday which falls on a date worked out from the year
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HolidayRule {
+    /// The same day every year, e.g. December 25
+    Fixed(MonthDay),
+    /// An occurence of a weekday in a month, negative occurences count from the end of the month
+    ///
+    /// The last Monday of May is `Weekday(5, -1, Weekday::Mon)`. Years without the occurence, such
+    /// as a fifth Monday, don't have the holiday.
+    Weekday(u32, i32, Weekday),
+    /// Days after Easter Sunday in the Gregorian calendar, negative days are before Easter e.g.
+    /// `Easter(-2)` is Good Friday
+    Easter(i32),
+}
+
+impl HolidayRule {
+    /// Date of the holiday in the year, [None] if the holiday doesn't occur that year
+    ///
+    /// ```
+    /// use calends::calendar::HolidayRule;
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let thanksgiving = HolidayRule::Weekday(11, 4, Weekday::Thu);
+    /// assert_eq!(thanksgiving.date_in(2022), NaiveDate::from_ymd_opt(2022, 11, 24));
+    ///
+    /// let good_friday = HolidayRule::Easter(-2);
+    /// assert_eq!(good_friday.date_in(2022), NaiveDate::from_ymd_opt(2022, 4, 15));
+    /// ```
+    pub fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            HolidayRule::Fixed(month_day) => month_day.in_year(year),
+            HolidayRule::Weekday(month, occurence, weekday) => {
+                NaiveDate::from_ymd_opt(year, month, 1)?;
+                let date = match occurence {
+                    1..=5 => find_weekday_ascending(weekday, year, month, occurence as u32),
+                    -5..=-1 => {
+                        find_weekday_descending(weekday, year, month, occurence.unsigned_abs())
+                    }
+                    _ => return None,
+                };
+                (date.month() == month).then_some(date)
+            }
+            HolidayRule::Easter(days) => {
+                easter_sunday(year)?.checked_add_signed(Duration::days(days.into()))
+            }
+        }
+    }
+}
+
+/// Easter Sunday in the Gregorian calendar
+///
+/// ```
+/// use calends::calendar::easter_sunday;
+/// use chrono::NaiveDate;
+///
+/// assert_eq!(easter_sunday(2024), NaiveDate::from_ymd_opt(2024, 3, 31));
+/// ```
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    // Anonymous Gregorian algorithm
+    let a = year.rem_euclid(19);
+    let (b, c) = (year.div_euclid(100), year.rem_euclid(100));
+    let (d, e) = (b / 4, b % 4);
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Day a holiday is observed when it falls on a weekend
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Observance {
+    /// On the day itself, even on a weekend
+    #[default]
+    Actual,
+    /// Saturdays are observed on the Friday before and Sundays on the Monday after, as in the US
+    NearestWeekday,
+    /// The following weekday which isn't already a holiday, as for UK substitute days
+    NextWeekday,
+}
+
+impl Observance {
+    /// Day the holiday is observed on, `taken` holds the holidays observed so far
+    fn observe(&self, date: NaiveDate, taken: &BTreeSet<NaiveDate>) -> Option<NaiveDate> {
+        let weekend = |d: &NaiveDate| matches!(d.weekday(), Weekday::Sat | Weekday::Sun);
+
+        match self {
+            Observance::Actual => Some(date),
+            Observance::NearestWeekday => match date.weekday() {
+                Weekday::Sat => date.pred_opt(),
+                Weekday::Sun => date.succ_opt(),
+                _ => Some(date),
+            },
+            Observance::NextWeekday => std::iter::successors(Some(date), NaiveDate::succ_opt)
+                .find(|d| !weekend(d) && !taken.contains(d)),
+        }
+    }
+}
+
+/// Holidays made up of dates and rules
+///
+/// Dates are single holidays such as a company closure, rules are holidays which occur every year.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HolidaySet {
+    dates: BTreeSet<NaiveDate>,
+    rules: Vec<(HolidayRule, Observance)>,
+    observed: ObservedCache,
+}
+
+/// Days the rules are observed on for each year looked up so far
+///
+/// The cache isn't part of the value of a [HolidaySet], so clones start empty and it is ignored
+/// when comparing sets.
+#[derive(Default)]
+struct ObservedCache(Mutex<BTreeMap<i32, Arc<BTreeSet<NaiveDate>>>>);
+
+impl Clone for ObservedCache {
+    fn clone(&self) -> Self {
+        ObservedCache::default()
+    }
+}
+
+impl PartialEq for ObservedCache {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ObservedCache {}
+
+impl fmt::Debug for ObservedCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservedCache").finish_non_exhaustive()
+    }
+}
+
+impl HolidaySet {
+    /// A set without any holidays
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a single holiday
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.dates.insert(date);
+        self
+    }
+
+    /// Add a holiday occuring every year
+    pub fn with_rule(self, rule: HolidayRule) -> Self {
+        self.with_observed_rule(rule, Observance::Actual)
+    }
+
+    /// Add a holiday occuring every year which is moved off weekends
+    ///
+    /// Rules are observed in the order they are added, so with [Observance::NextWeekday] Boxing
+    /// Day added after Christmas moves past the substitute day for Christmas.
+    ///
+    /// ```
+    /// use calends::{
+    ///     calendar::{HolidayCalendar, HolidayRule, HolidaySet, Observance},
+    ///     MonthDay,
+    /// };
+    /// use chrono::NaiveDate;
+    ///
+    /// let christmas = HolidayRule::Fixed(MonthDay::new(12, 25).unwrap());
+    /// let boxing_day = HolidayRule::Fixed(MonthDay::new(12, 26).unwrap());
+    /// let set = HolidaySet::new()
+    ///     .with_observed_rule(christmas, Observance::NextWeekday)
+    ///     .with_observed_rule(boxing_day, Observance::NextWeekday);
+    ///
+    /// // Christmas 2021 is a Saturday
+    /// assert_eq!(
+    ///     set.holidays_in_year(2021),
+    ///     vec![
+    ///         NaiveDate::from_ymd_opt(2021, 12, 27).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2021, 12, 28).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn with_observed_rule(mut self, rule: HolidayRule, observance: Observance) -> Self {
+        self.rules.push((rule, observance));
+        self.observed = ObservedCache::default();
+        self
+    }
+
+    /// Days the rules are observed on within the year, including the holidays of the neighbouring
+    /// years observed in it
+    fn observed_in_year(&self, year: i32) -> Arc<BTreeSet<NaiveDate>> {
+        let mut cache = self
+            .observed
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let observed = cache.entry(year).or_insert_with(|| {
+            let days = [year - 1, year, year + 1]
+                .into_iter()
+                .flat_map(|rule_year| self.observed(rule_year))
+                .filter(|d| d.year() == year)
+                .collect();
+            Arc::new(days)
+        });
+        Arc::clone(observed)
+    }
+
+    /// Days the rules are observed on for the holidays of a year, which can spill into the
+    /// neighbouring years
+    fn observed(&self, year: i32) -> BTreeSet<NaiveDate> {
+        let mut taken = BTreeSet::new();
+        for (rule, observance) in &self.rules {
+            if let Some(date) = rule.date_in(year) {
+                taken.extend(observance.observe(date, &taken));
+            }
+        }
+        taken
+    }
+
+    /// Holidays of the year in order
+    pub fn holidays_in_year(&self, year: i32) -> Vec<NaiveDate> {
+        let mut holidays: BTreeSet<NaiveDate> = self
+            .dates
+            .iter()
+            .copied()
+            .filter(|d| d.year() == year)
+            .collect();
+        holidays.extend(self.observed_in_year(year).iter());
+
+        holidays.into_iter().collect()
+    }
+}
+
+impl HolidayCalendar for HolidaySet {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.dates.contains(&date) || self.observed_in_year(date.year()).contains(&date)
+    }
+}
+
+impl HolidayCalendar for [NaiveDate] {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+impl HolidayCalendar for Vec<NaiveDate> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+impl HolidayCalendar for BTreeSet<NaiveDate> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+impl HolidayCalendar for HashSet<NaiveDate> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+impl<C: HolidayCalendar + ?Sized> HolidayCalendar for &C {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        (**self).is_holiday(date)
+    }
+
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        (**self).is_business_day(date)
+    }
+}
+
+/// How a date falling on a weekend or holiday is moved to a business day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdjustRule {
+    /// The following business day
+    NextBusinessDay,
+    /// The preceding business day
+    PreviousBusinessDay,
+    /// Whichever business day is closest, preferring the following day on a tie
+    NearestBusinessDay,
+    /// The following business day unless it is in the next month, then the preceding business day
+    ModifiedFollowing,
+}
+
+impl AdjustRule {
+    /// Move a date to a business day
+    ///
+    /// Business days are left unchanged. Returns [None] if there isn't a business day within a
+    /// year of the date.
+    ///
+    /// ```
+    /// use calends::calendar::AdjustRule;
+    /// use chrono::NaiveDate;
+    ///
+    /// let holidays = vec![NaiveDate::from_ymd_opt(2022, 12, 26).unwrap()];
+    /// // Saturday 31st December
+    /// let date = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+    ///
+    /// assert_eq!(
+    ///     AdjustRule::NextBusinessDay.adjust(date, &holidays),
+    ///     NaiveDate::from_ymd_opt(2023, 1, 2)
+    /// );
+    /// assert_eq!(
+    ///     AdjustRule::ModifiedFollowing.adjust(date, &holidays),
+    ///     NaiveDate::from_ymd_opt(2022, 12, 30)
+    /// );
+    /// ```
+    pub fn adjust<C>(&self, date: NaiveDate, calendar: &C) -> Option<NaiveDate>
+    where
+        C: HolidayCalendar + ?Sized,
+    {
+        if calendar.is_business_day(date) {
+            return Some(date);
+        }
+
+        match self {
+            AdjustRule::NextBusinessDay => search(date, calendar, NaiveDate::succ_opt),
+            AdjustRule::PreviousBusinessDay => search(date, calendar, NaiveDate::pred_opt),
+            AdjustRule::NearestBusinessDay => {
+                let next = search(date, calendar, NaiveDate::succ_opt);
+                let previous = search(date, calendar, NaiveDate::pred_opt);
+
+                match (previous, next) {
+                    (Some(p), Some(n)) if date - p < n - date => Some(p),
+                    (p, None) => p,
+                    (_, n) => n,
+                }
+            }
+            AdjustRule::ModifiedFollowing => match search(date, calendar, NaiveDate::succ_opt) {
+                Some(next) if next.month() == date.month() => Some(next),
+                _ => search(date, calendar, NaiveDate::pred_opt),
+            },
+        }
+    }
+}
+
+/// Weeks of a month as rows of a month view, with the days of the adjacent months which share
+/// its first and last weeks
+///
+/// A month has 4 to 6 rows depending on the day it starts on. Returns no rows when the month
+/// doesn't exist or its weeks run past the range of [NaiveDate].
+///
+/// ```
+/// use calends::{calendar::month_grid, WeekStart};
+/// use chrono::NaiveDate;
+///
+/// let grid = month_grid(2022, 5, WeekStart::SUNDAY);
+///
+/// assert_eq!(grid.len(), 5);
+/// assert_eq!(grid[0][0], NaiveDate::from_ymd_opt(2022, 5, 1).unwrap());
+/// assert_eq!(grid[4][6], NaiveDate::from_ymd_opt(2022, 6, 4).unwrap());
+/// assert_eq!(month_grid(2022, 5, WeekStart::MONDAY).len(), 6);
+/// ```
+pub fn month_grid(year: i32, month: u32, week_start: WeekStart) -> Vec<[NaiveDate; 7]> {
+    let (Some(first), Some(last)) = (
+        NaiveDate::from_ymd_opt(year, month, 1),
+        checked_month_end(year, month),
+    ) else {
+        return Vec::new();
+    };
+    let leading = Days::new(week_start.days_from_start(first.weekday()).into());
+    let Some(start) = first.checked_sub_days(leading) else {
+        return Vec::new();
+    };
+
+    let days: Vec<NaiveDate> = std::iter::successors(Some(start), NaiveDate::succ_opt)
+        .take_while(|d| *d <= last || d.weekday() != week_start.weekday())
+        .collect();
+    if !days.len().is_multiple_of(7) {
+        return Vec::new();
+    }
+
+    days.chunks_exact(7)
+        .map(|week| week.try_into().expect("chunks are a week long"))
+        .collect()
+}
+
+/// The [month_grid] of every month of a year in order
+pub fn year_grid(year: i32, week_start: WeekStart) -> Vec<Vec<[NaiveDate; 7]>> {
+    (1..=12)
+        .map(|month| month_grid(year, month, week_start))
+        .collect()
+}
+
+fn search<C, F>(date: NaiveDate, calendar: &C, step: F) -> Option<NaiveDate>
+where
+    C: HolidayCalendar + ?Sized,
+    F: Fn(&NaiveDate) -> Option<NaiveDate>,
+{
+    std::iter::successors(step(&date), |d| step(d))
+        .take(MAX_ADJUSTMENT_DAYS as usize)
+        .find(|d| calendar.is_business_day(*d))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_month_grid() {
+        // February 2015 starts on a Sunday and fits in four weeks
+        let february = month_grid(2015, 2, WeekStart::SUNDAY);
+        assert_eq!(february.len(), 4);
+        assert_eq!(
+            february[3][6],
+            NaiveDate::from_ymd_opt(2015, 2, 28).unwrap()
+        );
+
+        for (month, grid) in (1..=12).zip(year_grid(2022, WeekStart::SATURDAY)) {
+            assert!((4..=6).contains(&grid.len()));
+            assert_eq!(grid[0][0].weekday(), Weekday::Sat);
+            assert!(grid[0].iter().any(|d| d.month() == month && d.day() == 1));
+            assert!(grid
+                .iter()
+                .flatten()
+                .zip(grid.iter().flatten().skip(1))
+                .all(|(a, b)| a.succ_opt() == Some(*b)));
+        }
+
+        assert!(month_grid(2022, 13, WeekStart::MONDAY).is_empty());
+        // The first and last weeks would run past the representable days
+        let week_start = WeekStart::new(NaiveDate::MAX.weekday());
+        assert!(month_grid(NaiveDate::MAX.year(), 12, week_start).is_empty());
+        let week_start = WeekStart::new(NaiveDate::MIN.weekday().succ());
+        assert!(month_grid(NaiveDate::MIN.year(), 1, week_start).is_empty());
+    }
+
+    #[test]
+    fn test_adjust() {
+        let holidays = vec![date(4, 15), date(4, 18)];
+
+        // Sunday 17th April is between two holidays
+        let sunday = date(4, 17);
+        assert_eq!(
+            AdjustRule::NextBusinessDay.adjust(sunday, &holidays),
+            Some(date(4, 19))
+        );
+        assert_eq!(
+            AdjustRule::PreviousBusinessDay.adjust(sunday, &holidays),
+            Some(date(4, 14))
+        );
+        assert_eq!(
+            AdjustRule::NearestBusinessDay.adjust(sunday, &holidays),
+            Some(date(4, 19))
+        );
+        assert_eq!(
+            AdjustRule::NearestBusinessDay.adjust(date(4, 16), &holidays),
+            Some(date(4, 14))
+        );
+        assert_eq!(
+            AdjustRule::ModifiedFollowing.adjust(date(4, 14), &holidays),
+            Some(date(4, 14))
+        );
+    }
+
+    #[test]
+    fn test_holiday_rules() {
+        let rule = |month, occurence, weekday| HolidayRule::Weekday(month, occurence, weekday);
+
+        assert_eq!(rule(5, -1, Weekday::Mon).date_in(2022), Some(date(5, 30)));
+        assert_eq!(rule(1, 3, Weekday::Mon).date_in(2022), Some(date(1, 17)));
+        assert_eq!(rule(5, 5, Weekday::Tue).date_in(2022), Some(date(5, 31)));
+        assert_eq!(rule(5, 5, Weekday::Fri).date_in(2022), None);
+        assert_eq!(rule(5, 0, Weekday::Mon).date_in(2022), None);
+        assert_eq!(rule(13, 1, Weekday::Mon).date_in(2022), None);
+
+        let leap_day = HolidayRule::Fixed(MonthDay::new(2, 29).unwrap());
+        assert_eq!(leap_day.date_in(2022), Some(date(2, 28)));
+
+        let easter: Vec<_> = [2000, 2019, 2038, 2285]
+            .into_iter()
+            .map(|year| easter_sunday(year).unwrap().to_string())
+            .collect();
+        assert_eq!(
+            easter,
+            vec!["2000-04-23", "2019-04-21", "2038-04-25", "2285-03-22"]
+        );
+    }
+
+    #[test]
+    fn test_holiday_set() {
+        let set = HolidaySet::new()
+            .with_rule(HolidayRule::Easter(1))
+            .with_rule(HolidayRule::Fixed(MonthDay::new(1, 1).unwrap()))
+            .with_date(date(6, 3))
+            .with_date(NaiveDate::from_ymd_opt(2023, 6, 2).unwrap());
+
+        assert_eq!(
+            set.holidays_in_year(2022),
+            vec![date(1, 1), date(4, 18), date(6, 3)]
+        );
+        assert_eq!(
+            set.holidays_in(&ClosedInterval::exact(date(4, 1), date(12, 31))),
+            vec![date(4, 18), date(6, 3)]
+        );
+        assert!(!set.is_business_day(date(4, 18)));
+
+        // New Year's Day 2022 is a Saturday and is observed in 2021
+        let observed = HolidaySet::new().with_observed_rule(
+            HolidayRule::Fixed(MonthDay::new(1, 1).unwrap()),
+            Observance::NearestWeekday,
+        );
+        assert!(observed.is_holiday(NaiveDate::from_ymd_opt(2021, 12, 31).unwrap()));
+        assert!(!observed.is_holiday(date(1, 1)));
+        // Rules added after a lookup are observed too
+        let observed = observed.with_rule(HolidayRule::Fixed(MonthDay::new(1, 1).unwrap()));
+        assert!(observed.is_holiday(date(1, 1)));
+        assert_eq!(observed.clone(), observed);
+        assert_eq!(
+            observed.holidays_in_year(2021),
+            vec![
+                NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2021, 12, 31).unwrap()
+            ]
+        );
+
+        let union = set.union(vec![date(6, 6)]);
+        assert_eq!(
+            union.holidays_in(&ClosedInterval::exact(date(6, 1), date(6, 30))),
+            vec![date(6, 3), date(6, 6)]
+        );
+    }
+
+    #[test]
+    fn test_adjust_without_business_days() {
+        struct Closed;
+
+        impl HolidayCalendar for Closed {
+            fn is_holiday(&self, _: NaiveDate) -> bool {
+                true
+            }
+        }
+
+        assert_eq!(
+            AdjustRule::NextBusinessDay.adjust(date(1, 1), &Closed),
+            None
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use crate::RelativeDuration;
+use crate::{EomPolicy, RelativeDuration};
 
 pub fn pluralize(unit: &str, num: i32) -> Option<String> {
     if num == 0 {
@@ -16,7 +16,7 @@ pub fn pluralize(unit: &str, num: i32) -> Option<String> {
 
 impl Display for RelativeDuration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let build = vec![
+        let build = [
             pluralize("month", self.num_months()),
             pluralize("week", self.num_weeks()),
             pluralize("day", self.num_days()),
@@ -34,6 +34,13 @@ impl Display for RelativeDuration {
             }
         }
 
+        if self.eom_policy() != EomPolicy::default() {
+            result.push_str(&format!(
+                " ({} at the end of the month)",
+                self.eom_policy().name()
+            ));
+        }
+
         f.write_str(&result)
     }
 }
@@ -1,7 +1,14 @@
 pub mod format;
 pub mod parse;
+// modular_bitfield expands the field types with parentheses
+#[allow(unused_parens)]
 pub mod relative;
 pub mod serde;
+pub mod span;
 
 pub use self::serde::rd_iso8601;
 pub use relative::*;
+pub use span::{
+    complete_months_between, complete_quarters_between, complete_weeks_between, NaiveDateExt,
+    SpanStrategy,
+};
@@ -1,14 +1,116 @@
 use nom::{
-    bytes::complete::tag,
-    character::complete::one_of,
+    bytes::complete::{tag, tag_no_case, take_till},
+    character::complete::{char, digit1, one_of},
     combinator::opt,
-    error::Error,
+    error::{Error, ErrorKind},
     multi::count,
-    sequence::{preceded, tuple},
+    sequence::{delimited, preceded, tuple},
     Err, IResult,
 };
 
-use crate::{parser::take_signed_digits, RelativeDuration};
+use std::ops::Range;
+
+use crate::{parser::take_signed_digits, EomPolicy, RelativeDuration, UnitKind};
+
+/// How closely input has to follow the extended format of ISO8601
+///
+/// [ParseMode::Lenient] also accepts basic format dates such as `20220101` and lowercase
+/// designators such as `p3m`, as emitted by tools that are loose about the standard.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ParseMode {
+    #[default]
+    Strict,
+    Lenient,
+}
+
+/// Options for [parse_duration_with_options] and the interval equivalent
+///
+/// Parsing is strict by default, [ParseOptions::lenient] accepts whatever [ParseMode::Lenient]
+/// does as well as whitespace, and sums repeated units such as `P1M2M` instead of rejecting them.
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    strict: bool,
+    max_len: Option<usize>,
+    allowed_units: Vec<UnitKind>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            strict: true,
+            max_len: None,
+            allowed_units: vec![],
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Strict parsing of any duration units with no limit on the length
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn lenient(mut self) -> Self {
+        self.strict = false;
+        self
+    }
+
+    /// Reject input longer than `max_len` bytes before parsing it
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Only accept duration units of the kinds, out of years, months, weeks and days
+    pub fn allow_units(mut self, units: &[UnitKind]) -> Self {
+        self.allowed_units = units.to_vec();
+        self
+    }
+
+    pub fn mode(&self) -> ParseMode {
+        match self.strict {
+            true => ParseMode::Strict,
+            false => ParseMode::Lenient,
+        }
+    }
+}
+
+impl From<ParseMode> for ParseOptions {
+    fn from(mode: ParseMode) -> Self {
+        ParseOptions {
+            strict: mode == ParseMode::Strict,
+            ..Default::default()
+        }
+    }
+}
+
+/// Failure to parse with [ParseOptions], the spans are byte ranges of the input
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    #[error("input is {len} bytes long, the limit is {max_len}")]
+    TooLong { len: usize, max_len: usize },
+    #[error("invalid syntax at {}..{}", span.start, span.end)]
+    Syntax { span: Range<usize> },
+    #[error("unexpected input at {}..{}", span.start, span.end)]
+    TrailingInput { span: Range<usize> },
+    #[error("unit {unit} at {} is repeated", span.start)]
+    DuplicateUnit { unit: char, span: Range<usize> },
+    #[error("unit {unit} at {} is not allowed", span.start)]
+    UnitNotAllowed { unit: char, span: Range<usize> },
+}
+
+impl ParseError {
+    /// Part of the input the error refers to, for highlighting
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            ParseError::TooLong { len, max_len } => *max_len..*len,
+            ParseError::Syntax { span }
+            | ParseError::TrailingInput { span }
+            | ParseError::DuplicateUnit { span, .. }
+            | ParseError::UnitNotAllowed { span, .. } => span.clone(),
+        }
+    }
+}
 
 #[derive(Debug, PartialEq)]
 pub enum Unit {
@@ -18,9 +120,13 @@ pub enum Unit {
     Days(i32),
 }
 
-fn parse_duration_chunk(input: &[u8]) -> IResult<&[u8], Unit> {
-    let (i, (amt, u)) = tuple((take_signed_digits, one_of("YMWD")))(input)?;
-    match u {
+fn parse_duration_chunk(input: &[u8], mode: ParseMode) -> IResult<&[u8], Unit> {
+    let designators = match mode {
+        ParseMode::Strict => "YMWD",
+        ParseMode::Lenient => "YMWDymwd",
+    };
+    let (i, (amt, u)) = tuple((take_signed_digits, one_of(designators)))(input)?;
+    match u.to_ascii_uppercase() {
         'Y' => Ok((i, Unit::Years(amt))),
         'M' => Ok((i, Unit::Months(amt))),
         'W' => Ok((i, Unit::Weeks(amt))),
@@ -29,23 +135,224 @@ fn parse_duration_chunk(input: &[u8]) -> IResult<&[u8], Unit> {
     }
 }
 
+/// Parse a fractional smallest component such as `1.5M`, into its whole part and the remainder
+/// in the next smaller unit
+///
+/// A fraction of a year is rounded to the nearest month, a fraction of a month counts a month as
+/// 30 days and a fraction of a week as 7 days, both rounded to the nearest day. Days are the
+/// smallest unit so a fractional day is rejected.
+fn parse_fractional_chunk(input: &[u8], mode: ParseMode) -> IResult<&[u8], [Unit; 2]> {
+    let designators = match mode {
+        ParseMode::Strict => "YMW",
+        ParseMode::Lenient => "YMWymw",
+    };
+    let (i, (negative, whole, _, fraction, u)) = tuple((
+        opt(char('-')),
+        digit1,
+        one_of(".,"),
+        digit1,
+        one_of(designators),
+    ))(input)?;
+
+    let invalid = || Err::Error(Error::new(input, ErrorKind::Verify));
+    let value: f64 = String::from_utf8_lossy(&[whole, b".", fraction].concat())
+        .parse()
+        .map_err(|_| invalid())?;
+    let sign = if negative.is_some() { -1.0 } else { 1.0 };
+    let whole = value.trunc();
+    let to_i32 = |v: f64| match v.is_finite() && v.abs() <= f64::from(i32::MAX) {
+        true => Ok((sign * v).round() as i32),
+        false => Err(invalid()),
+    };
+
+    let units = match u.to_ascii_uppercase() {
+        'Y' => [Unit::Months(to_i32(value * 12.0)?), Unit::Days(0)],
+        'M' => [
+            Unit::Months(to_i32(whole)?),
+            Unit::Days(to_i32((value - whole) * 30.0)?),
+        ],
+        _ => [
+            Unit::Weeks(to_i32(whole)?),
+            Unit::Days(to_i32((value - whole) * 7.0)?),
+        ],
+    };
+
+    Ok((i, units))
+}
+
 /// Parse an ISO8601-2:2019 duration
 ///
 /// Returns the leftovers for use in combination with other parsers
 pub fn parse_relative_duration(input: &[u8]) -> IResult<&[u8], RelativeDuration> {
-    let (leftover, units) = preceded(tag("P"), count(opt(parse_duration_chunk), 4))(input)?;
+    parse_relative_duration_with(input, ParseMode::Strict)
+}
+
+/// Parse an ISO8601-2:2019 duration, accepting lowercase designators in [ParseMode::Lenient]
+///
+/// A sign before the `P` applies to the whole duration, so `-P1M2D` is the same as `P-1M-2D`. The
+/// last component can be fractional, `P1.5M` is 1 month and 15 days and `P0.5Y` is 6 months. An
+/// end of month policy follows as a suffix such as `P1M[eom=clamped]`, see
+/// [RelativeDuration::with_eom_policy].
+///
+/// ```
+/// # #[cfg(not(feature = "strict"))] {
+/// use calends::{duration::parse::{parse_relative_duration_with, ParseMode}, RelativeDuration};
+///
+/// let (_, duration) = parse_relative_duration_with(b"p3m1d", ParseMode::Lenient).unwrap();
+/// assert_eq!(duration, RelativeDuration::months(3).with_days(1));
+/// assert!(parse_relative_duration_with(b"p3m1d", ParseMode::Strict).is_err());
+///
+/// let (_, duration) = parse_relative_duration_with(b"-P1.5M", ParseMode::Strict).unwrap();
+/// assert_eq!(duration, RelativeDuration::months(-1).with_days(-15));
+/// # }
+/// ```
+pub fn parse_relative_duration_with(
+    input: &[u8],
+    mode: ParseMode,
+) -> IResult<&[u8], RelativeDuration> {
+    let chunk = |i| parse_duration_chunk(i, mode);
+    let (i, sign) = opt(one_of("+-"))(input)?;
+    let (i, mut units) = match mode {
+        ParseMode::Strict => preceded(tag("P"), count(opt(chunk), 4))(i)?,
+        ParseMode::Lenient => preceded(tag_no_case("P"), count(opt(chunk), 4))(i)?,
+    };
+    let (i, fraction) = opt(|i| parse_fractional_chunk(i, mode))(i)?;
+
+    let invalid = || Err::Error(Error::new(input, ErrorKind::Verify));
+    if let Some(fraction) = fraction {
+        // Only the smallest component can have a fraction
+        if chunk(i).is_ok() {
+            return Err(invalid());
+        }
+        units.extend(fraction.map(Some));
+    }
+
+    let (leftover, policy) = opt(delimited(tag("[eom="), take_till(|b| b == b']'), tag("]")))(i)?;
+    let policy = match policy {
+        Some(name) => EomPolicy::from_name(name).ok_or_else(invalid)?,
+        None => EomPolicy::default(),
+    };
 
     let rd = units
         .iter()
         .flatten()
-        .fold(RelativeDuration::default(), |start, unit| match unit {
-            Unit::Years(y) => start.with_months(y * 12),
-            Unit::Months(m) => start.with_months(*m),
-            Unit::Weeks(w) => start.with_weeks(*w),
-            Unit::Days(d) => start.with_days(*d),
+        .try_fold(RelativeDuration::default(), |start, unit| {
+            let rd = match unit {
+                Unit::Years(y) => RelativeDuration::try_from_mwd(y.checked_mul(12)?, 0, 0),
+                Unit::Months(m) => RelativeDuration::try_from_mwd(*m, 0, 0),
+                Unit::Weeks(w) => RelativeDuration::try_from_mwd(0, *w, 0),
+                Unit::Days(d) => RelativeDuration::try_from_mwd(0, 0, *d),
+            }?;
+            start.checked_add(rd)
+        })
+        .ok_or_else(invalid)?
+        .with_eom_policy(policy);
+
+    match sign {
+        Some('-') => Ok((leftover, rd.checked_mul(-1).ok_or_else(invalid)?)),
+        _ => Ok((leftover, rd)),
+    }
+}
+
+/// Parse the whole of the input as an ISO8601-2:2019 duration
+///
+/// ```
+/// # #[cfg(not(feature = "strict"))] {
+/// use calends::{duration::parse::{parse_duration_with_options, ParseError, ParseOptions}, RelativeDuration};
+///
+/// let lenient = ParseOptions::new().lenient();
+/// assert_eq!(
+///     parse_duration_with_options(" p1m 2w ", &lenient),
+///     Ok(RelativeDuration::months(1).with_weeks(2))
+/// );
+///
+/// let error = parse_duration_with_options("P1M2M", &ParseOptions::new()).unwrap_err();
+/// assert_eq!(error, ParseError::DuplicateUnit { unit: 'M', span: 4..5 });
+/// # }
+/// ```
+pub fn parse_duration_with_options(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<RelativeDuration, ParseError> {
+    parse_with_options(input, options, parse_relative_duration_with)
+}
+
+/// Run a parser over the whole input and check the duration units it contains against the options
+pub(crate) fn parse_with_options<T>(
+    input: &str,
+    options: &ParseOptions,
+    parser: impl Fn(&[u8], ParseMode) -> IResult<&[u8], T>,
+) -> Result<T, ParseError> {
+    if let Some(max_len) = options.max_len.filter(|max_len| input.len() > *max_len) {
+        return Err(ParseError::TooLong {
+            len: input.len(),
+            max_len,
         });
+    }
+
+    // Offsets into the input of the bytes handed to the parser, which skips whitespace when lenient
+    let offsets: Vec<usize> = input
+        .bytes()
+        .enumerate()
+        .filter(|(_, b)| options.strict || !b.is_ascii_whitespace())
+        .map(|(offset, _)| offset)
+        .collect();
+    let bytes: Vec<u8> = offsets
+        .iter()
+        .map(|offset| input.as_bytes()[*offset])
+        .collect();
+    let offset = |position: usize| offsets.get(position).copied().unwrap_or(input.len());
+
+    let (leftover, value) = match parser(&bytes, options.mode()) {
+        Ok(parsed) => parsed,
+        Err(Err::Error(e) | Err::Failure(e)) => {
+            let start = offset(bytes.len() - e.input.len());
+            return Err(ParseError::Syntax {
+                span: start..input.len(),
+            });
+        }
+        Err(Err::Incomplete(_)) => {
+            return Err(ParseError::Syntax {
+                span: input.len()..input.len(),
+            })
+        }
+    };
+    if !leftover.is_empty() {
+        return Err(ParseError::TrailingInput {
+            span: offset(bytes.len() - leftover.len())..input.len(),
+        });
+    }
 
-    Ok((leftover, rd))
+    // Unit designators are the letters following the P of the duration
+    let designators = bytes
+        .iter()
+        .enumerate()
+        .skip_while(|(_, b)| !b.eq_ignore_ascii_case(&b'P'))
+        .skip(1)
+        .take_while(|(_, b)| **b != b'[')
+        .filter(|(_, b)| b.is_ascii_alphabetic());
+    let mut seen = vec![];
+
+    for (position, b) in designators {
+        let unit = b.to_ascii_uppercase() as char;
+        let span = offset(position)..offset(position) + 1;
+        let kind = match unit {
+            'Y' => UnitKind::Year,
+            'M' => UnitKind::Month,
+            'W' => UnitKind::Week,
+            _ => UnitKind::Day,
+        };
+
+        if !options.allowed_units.is_empty() && !options.allowed_units.contains(&kind) {
+            return Err(ParseError::UnitNotAllowed { unit, span });
+        }
+        if options.strict && seen.contains(&unit) {
+            return Err(ParseError::DuplicateUnit { unit, span });
+        }
+        seen.push(unit);
+    }
+
+    Ok(value)
 }
 
 #[cfg(test)]
@@ -54,25 +361,26 @@ mod tests {
 
     #[test]
     fn test_parse_duration_chunk() {
-        let (_input, duration) = parse_duration_chunk("2Y".as_bytes()).unwrap();
+        let (_input, duration) = parse_duration_chunk("2Y".as_bytes(), ParseMode::Strict).unwrap();
         assert_eq!(duration, Unit::Years(2))
     }
 
     #[test]
     fn test_parse_duration_chunk_months() {
-        let (_input, duration) = parse_duration_chunk("2M".as_bytes()).unwrap();
+        let (_input, duration) = parse_duration_chunk("2M".as_bytes(), ParseMode::Strict).unwrap();
         assert_eq!(duration, Unit::Months(2))
     }
 
     #[test]
     fn test_parse_duration_chunk_weeks() {
-        let (_input, duration) = parse_duration_chunk("-1W".as_bytes()).unwrap();
+        let (_input, duration) = parse_duration_chunk("-1W".as_bytes(), ParseMode::Strict).unwrap();
         assert_eq!(duration, Unit::Weeks(-1))
     }
 
     #[test]
     fn test_parse_duration_chunk_days() {
-        let (_input, duration) = parse_duration_chunk("180D".as_bytes()).unwrap();
+        let (_input, duration) =
+            parse_duration_chunk("180D".as_bytes(), ParseMode::Strict).unwrap();
         assert_eq!(duration, Unit::Days(180))
     }
 
@@ -84,4 +392,98 @@ mod tests {
             RelativeDuration::default().with_weeks(3).with_days(2)
         )
     }
+
+    #[test]
+    fn test_parse_lenient() {
+        let expected = RelativeDuration::months(3).with_days(-2);
+
+        for input in ["P3M-2D", "p3m-2d", "P3m-2D"] {
+            let (leftover, duration) =
+                parse_relative_duration_with(input.as_bytes(), ParseMode::Lenient).unwrap();
+            assert!(leftover.is_empty());
+            assert_eq!(duration, expected);
+        }
+
+        let (leftover, _) = parse_relative_duration(b"P3m").unwrap();
+        assert_eq!(leftover, b"3m");
+        assert!(parse_relative_duration(b"p3M").is_err());
+    }
+
+    #[test]
+    fn test_parse_years_and_months() {
+        let (_, duration) = parse_relative_duration(b"P1Y3M").unwrap();
+        assert_eq!(duration, RelativeDuration::months(15));
+    }
+
+    #[test]
+    fn test_parse_signed() {
+        let (_, duration) = parse_relative_duration(b"-P1M2D").unwrap();
+        assert_eq!(duration, RelativeDuration::months(-1).with_days(-2));
+
+        let (_, duration) = parse_relative_duration(b"+P3D").unwrap();
+        assert_eq!(duration, RelativeDuration::days(3));
+
+        let (_, duration) = parse_relative_duration(b"-P-1W").unwrap();
+        assert_eq!(duration, RelativeDuration::weeks(1));
+    }
+
+    #[test]
+    fn test_parse_fractional() {
+        let parse = |input: &str| {
+            let (leftover, duration) = parse_relative_duration(input.as_bytes()).unwrap();
+            assert!(leftover.is_empty(), "{input}");
+            duration
+        };
+
+        assert_eq!(parse("P0.5Y"), RelativeDuration::months(6));
+        assert_eq!(parse("P1.5M"), RelativeDuration::months(1).with_days(15));
+        assert_eq!(parse("P1,5M"), RelativeDuration::months(1).with_days(15));
+        assert_eq!(
+            parse("P2M1.5W"),
+            RelativeDuration::try_from_mwd(2, 1, 4).unwrap()
+        );
+        assert_eq!(parse("P-0.5M"), RelativeDuration::days(-15));
+        assert_eq!(parse("-P1.25Y"), RelativeDuration::months(-15));
+
+        assert!(parse_relative_duration(b"P1.5M2D").is_err());
+        assert!(
+            parse_relative_duration(b"P1.5D").map_or(true, |(leftover, _)| !leftover.is_empty())
+        );
+    }
+
+    #[test]
+    fn test_parse_with_options() {
+        let strict = ParseOptions::new();
+        let lenient = ParseOptions::new().lenient();
+
+        assert_eq!(
+            parse_duration_with_options("P1M2M", &lenient),
+            Ok(RelativeDuration::months(3))
+        );
+        assert_eq!(
+            parse_duration_with_options("P1M 2D", &strict),
+            Err(ParseError::TrailingInput { span: 3..6 })
+        );
+        assert_eq!(
+            parse_duration_with_options("X1M", &strict),
+            Err(ParseError::Syntax { span: 0..3 })
+        );
+        assert_eq!(
+            parse_duration_with_options("P 1m 1y", &lenient),
+            Ok(RelativeDuration::months(13))
+        );
+        assert_eq!(
+            parse_duration_with_options("P1M1D", &strict.clone().max_len(4)),
+            Err(ParseError::TooLong { len: 5, max_len: 4 })
+        );
+
+        let months = ParseOptions::new().allow_units(&[UnitKind::Year, UnitKind::Month]);
+        assert_eq!(
+            parse_duration_with_options("P1Y3W", &months),
+            Err(ParseError::UnitNotAllowed {
+                unit: 'W',
+                span: 4..5
+            })
+        );
+    }
 }
@@ -1,11 +1,13 @@
 //! Implement a Duration that extends chrono and adds Quarter and Month
-use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::ops::Neg;
+#[cfg(not(feature = "strict"))]
+use std::ops::{Add, Div, Mul, Sub};
 
-use chrono::{Datelike, NaiveDate};
+use chrono::{Datelike, Days, Months, NaiveDate};
 use modular_bitfield::bitfield;
-use modular_bitfield::prelude::B20;
+use modular_bitfield::prelude::{B2, B20, B7};
 
-use crate::shift;
+use crate::{shift, EomPolicy};
 
 #[bitfield]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -16,7 +18,8 @@ pub struct RelativeImpl {
     pub months_negative: bool,
     pub weeks_negative: bool,
     pub days_negative: bool,
-    pub pad: bool,
+    pub eom_policy: B2,
+    pub pad: B7,
 }
 
 /// A duration of time which can be positive or negative
@@ -45,18 +48,51 @@ pub struct RelativeImpl {
 ///
 /// ```
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Interval)
+)]
 pub struct RelativeDuration(RelativeImpl);
 
+/// Where the sign of a negative duration is written in its ISO8601 form
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SignStyle {
+    /// Each negative component carries its own sign, `P-1M-2D`
+    #[default]
+    Component,
+    /// A duration whose components are all negative has a single leading sign, `-P1M2D`, other
+    /// durations fall back to [SignStyle::Component]
+    Leading,
+}
+
 impl RelativeDuration {
+    /// The largest duration, 1048575 months, weeks and days
+    pub const MAX: RelativeDuration = RelativeDuration(RelativeImpl::from_bytes([
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x0f, 0x00,
+    ]));
+
+    /// The smallest duration, -1048575 months, weeks and days
+    pub const MIN: RelativeDuration = RelativeDuration(RelativeImpl::from_bytes([
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f, 0x00,
+    ]));
+
     /// Returns a RelativeDuration for a given set of dates
     ///
     /// Calculate the difference between two sets of dates and return back a duration
     ///
+    /// # Panics
+    ///
+    /// If the dates are more than 1048575 months apart, see
+    /// [RelativeDuration::checked_from_duration_between]. This is not available with the `strict`
+    /// feature.
+    ///
     /// # Examples
     ///
     /// ```
     /// # use calends::RelativeDuration;
     /// # use chrono::NaiveDate;
+    /// # #[cfg(not(feature = "strict"))] {
     ///
     /// let duration = RelativeDuration::from_duration_between(
     ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
@@ -64,62 +100,178 @@ impl RelativeDuration {
     ///  );
     ///
     /// assert_eq!(
-    ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap() + duration,
-    ///     NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()
+    ///     duration.checked_add_to(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()),
+    ///     NaiveDate::from_ymd_opt(2023, 1, 1)
     /// );
+    /// # }
     /// ```
+    #[cfg(not(feature = "strict"))]
     pub fn from_duration_between(start: NaiveDate, end: NaiveDate) -> RelativeDuration {
+        RelativeDuration::checked_from_duration_between(start, end)
+            .expect("relative duration is invalid and exceeds bounds")
+    }
+
+    /// Returns a RelativeDuration for a given set of dates
+    ///
+    /// Returns [None] if the dates are more than 1048575 months apart
+    ///
+    /// ```
+    /// # use calends::RelativeDuration;
+    /// # use chrono::NaiveDate;
+    /// assert_eq!(
+    ///     RelativeDuration::checked_from_duration_between(
+    ///         NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2022, 3, 1).unwrap(),
+    ///     ),
+    ///     RelativeDuration::try_from_mwd(2, 0, -30)
+    /// );
+    /// assert_eq!(
+    ///     RelativeDuration::checked_from_duration_between(NaiveDate::MIN, NaiveDate::MAX),
+    ///     None
+    /// );
+    /// ```
+    pub fn checked_from_duration_between(
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Option<RelativeDuration> {
         let mut months = (end.year() - start.year()) * 12;
         months += end.month() as i32 - start.month() as i32;
         let days = end.day() as i32 - start.day() as i32;
-        RelativeDuration::from_raw(months, 0, days).unwrap()
+        RelativeDuration::from_raw(months, 0, days)
     }
 
+    /// Create a RelativeDuration from months, weeks and days
+    ///
+    /// # Panics
+    ///
+    /// If any of the components exceed 20 bits, see [RelativeDuration::try_from_mwd]. This is not
+    /// available with the `strict` feature.
+    #[cfg(not(feature = "strict"))]
     pub fn from_mwd(months: i32, weeks: i32, days: i32) -> RelativeDuration {
         RelativeDuration::from_raw(months, weeks, days)
             .expect("relative duration is invalid and exceeds bounds")
     }
 
+    /// Create a RelativeDuration from months, weeks and days
+    ///
+    /// Returns [None] if any of the components exceed 20 bits
+    pub fn try_from_mwd(months: i32, weeks: i32, days: i32) -> Option<RelativeDuration> {
+        RelativeDuration::from_raw(months, weeks, days)
+    }
+
     fn from_raw(months: i32, weeks: i32, days: i32) -> Option<RelativeDuration> {
-        Some(
-            RelativeDuration(RelativeImpl::default())
-                .with_months(months)
-                .with_weeks(weeks)
-                .with_days(days),
-        )
+        RelativeDuration::default()
+            .checked_with_months(months)?
+            .checked_with_weeks(weeks)?
+            .checked_with_days(days)
     }
 
     /// Create a RelativeDuration with the number of months
+    ///
+    /// # Panics
+    ///
+    /// If the months exceed 20 bits, see [RelativeDuration::try_months]. This is not available
+    /// with the `strict` feature.
+    #[cfg(not(feature = "strict"))]
     pub fn months(months: i32) -> RelativeDuration {
         RelativeDuration::default().with_months(months)
     }
 
+    #[cfg(feature = "strict")]
+    pub(crate) fn months(months: i32) -> RelativeDuration {
+        RelativeDuration::default().with_months(months)
+    }
+
+    /// Create a RelativeDuration with the number of months
+    ///
+    /// Returns [None] if the months exceed 20 bits
+    pub fn try_months(months: i32) -> Option<RelativeDuration> {
+        RelativeDuration::default().checked_with_months(months)
+    }
+
     /// Create a RelativeDuration with the numer of weeks
+    ///
+    /// # Panics
+    ///
+    /// If the weeks exceed 20 bits, see [RelativeDuration::try_weeks]. This is not available with
+    /// the `strict` feature.
+    #[cfg(not(feature = "strict"))]
     pub fn weeks(weeks: i32) -> RelativeDuration {
         RelativeDuration::default().with_weeks(weeks)
     }
 
+    #[cfg(feature = "strict")]
+    pub(crate) fn weeks(weeks: i32) -> RelativeDuration {
+        RelativeDuration::default().with_weeks(weeks)
+    }
+
+    /// Create a RelativeDuration with the number of weeks
+    ///
+    /// Returns [None] if the weeks exceed 20 bits
+    pub fn try_weeks(weeks: i32) -> Option<RelativeDuration> {
+        RelativeDuration::default().checked_with_weeks(weeks)
+    }
+
     /// Create a RelativeDuration with the number of days
+    ///
+    /// # Panics
+    ///
+    /// If the days exceed 20 bits, see [RelativeDuration::try_days]. This is not available with
+    /// the `strict` feature.
+    #[cfg(not(feature = "strict"))]
     #[inline]
     pub fn days(days: i32) -> RelativeDuration {
         RelativeDuration::zero().with_days(days)
     }
 
+    #[cfg(feature = "strict")]
+    #[inline]
+    pub(crate) fn days(days: i32) -> RelativeDuration {
+        RelativeDuration::zero().with_days(days)
+    }
+
+    /// Create a RelativeDuration with the number of days
+    ///
+    /// Returns [None] if the days exceed 20 bits
+    pub fn try_days(days: i32) -> Option<RelativeDuration> {
+        RelativeDuration::zero().checked_with_days(days)
+    }
+
     /// Set the number of months in the duration
+    ///
+    /// # Panics
+    ///
+    /// If the months exceed 20 bits, see [RelativeDuration::checked_with_months]. This is not
+    /// available with the `strict` feature.
+    #[cfg(not(feature = "strict"))]
     #[inline]
     pub fn with_months(&self, months: i32) -> RelativeDuration {
-        let RelativeDuration(mut ri) = self;
-        if months.is_negative() {
-            ri = ri.with_months(-months as u32);
-            ri = ri.with_months_negative(true);
-        } else {
-            ri = ri.with_months(months as u32);
-            ri = ri.with_months_negative(false);
-        }
-        RelativeDuration(ri)
+        self.checked_with_months(months)
+            .expect("relative duration is invalid and exceeds bounds")
+    }
+
+    #[cfg(feature = "strict")]
+    #[inline]
+    pub(crate) fn with_months(&self, months: i32) -> RelativeDuration {
+        self.checked_with_months(months)
+            .expect("relative duration is invalid and exceeds bounds")
+    }
+
+    /// Set the number of months in the duration, returning [None] if they exceed 20 bits
+    pub fn checked_with_months(&self, months: i32) -> Option<RelativeDuration> {
+        let RelativeDuration(ri) = self;
+        Some(RelativeDuration(
+            ri.with_months_checked(months.unsigned_abs())
+                .ok()?
+                .with_months_negative(months.is_negative()),
+        ))
     }
 
     /// Number of months in the duration
+    /// Number of months in the duration
+    ///
+    /// This is the months component rather than the months elapsed between two dates, see
+    /// [crate::duration::complete_months_between] for those.
     #[inline]
     pub fn num_months(&self) -> i32 {
         let months = self.0.months() as i32;
@@ -141,18 +293,34 @@ impl RelativeDuration {
         }
     }
 
-    /// Set the number of months in the duration
+    /// Set the number of weeks in the duration
+    ///
+    /// # Panics
+    ///
+    /// If the weeks exceed 20 bits, see [RelativeDuration::checked_with_weeks]. This is not
+    /// available with the `strict` feature.
+    #[cfg(not(feature = "strict"))]
     #[inline]
     pub fn with_weeks(&self, weeks: i32) -> RelativeDuration {
-        let RelativeDuration(mut ri) = self;
-        if weeks.is_negative() {
-            ri = ri.with_weeks(-weeks as u32);
-            ri = ri.with_weeks_negative(true);
-        } else {
-            ri = ri.with_weeks(weeks as u32);
-            ri = ri.with_weeks_negative(false);
-        }
-        RelativeDuration(ri)
+        self.checked_with_weeks(weeks)
+            .expect("relative duration is invalid and exceeds bounds")
+    }
+
+    #[cfg(feature = "strict")]
+    #[inline]
+    pub(crate) fn with_weeks(&self, weeks: i32) -> RelativeDuration {
+        self.checked_with_weeks(weeks)
+            .expect("relative duration is invalid and exceeds bounds")
+    }
+
+    /// Set the number of weeks in the duration, returning [None] if they exceed 20 bits
+    pub fn checked_with_weeks(&self, weeks: i32) -> Option<RelativeDuration> {
+        let RelativeDuration(ri) = self;
+        Some(RelativeDuration(
+            ri.with_weeks_checked(weeks.unsigned_abs())
+                .ok()?
+                .with_weeks_negative(weeks.is_negative()),
+        ))
     }
 
     /// Number of weeks in the duration
@@ -167,23 +335,39 @@ impl RelativeDuration {
     }
 
     /// Set the number of days in the duration
+    ///
+    /// # Panics
+    ///
+    /// If the days exceed 20 bits, see [RelativeDuration::checked_with_days]. This is not
+    /// available with the `strict` feature.
+    #[cfg(not(feature = "strict"))]
     #[inline]
     pub fn with_days(&self, days: i32) -> RelativeDuration {
-        let RelativeDuration(mut ri) = self;
-        if days.is_negative() {
-            ri = ri.with_days(-days as u32);
-            ri = ri.with_days_negative(true);
-        } else {
-            ri = ri.with_days(days as u32);
-            ri = ri.with_days_negative(false);
-        }
-        RelativeDuration(ri)
+        self.checked_with_days(days)
+            .expect("relative duration is invalid and exceeds bounds")
+    }
+
+    #[cfg(feature = "strict")]
+    #[inline]
+    pub(crate) fn with_days(&self, days: i32) -> RelativeDuration {
+        self.checked_with_days(days)
+            .expect("relative duration is invalid and exceeds bounds")
+    }
+
+    /// Set the number of days in the duration, returning [None] if they exceed 20 bits
+    pub fn checked_with_days(&self, days: i32) -> Option<RelativeDuration> {
+        let RelativeDuration(ri) = self;
+        Some(RelativeDuration(
+            ri.with_days_checked(days.unsigned_abs())
+                .ok()?
+                .with_days_negative(days.is_negative()),
+        ))
     }
 
     /// A `RelativeDuration` representing zero.
     #[inline]
     pub fn zero() -> RelativeDuration {
-        RelativeDuration::from_mwd(0, 0, 0)
+        RelativeDuration::default()
     }
 
     /// Returns true if the duration equals RelativeDuration::zero().
@@ -192,6 +376,116 @@ impl RelativeDuration {
         self.num_months() == 0 && self.num_weeks() == 0 && self.num_days() == 0
     }
 
+    /// Add two durations, returning [None] if the result exceeds the bounds of a duration
+    ///
+    /// The sum has the end of month policy of either duration other than the default
+    /// [EomPolicy::PreserveLastDay], that of `self` when both have one.
+    pub fn checked_add(&self, rhs: RelativeDuration) -> Option<RelativeDuration> {
+        let policy = match self.eom_policy() {
+            EomPolicy::PreserveLastDay => rhs.eom_policy(),
+            policy => policy,
+        };
+        RelativeDuration::from_raw(
+            self.num_months().checked_add(rhs.num_months())?,
+            self.num_weeks().checked_add(rhs.num_weeks())?,
+            self.num_days().checked_add(rhs.num_days())?,
+        )
+        .map(|rd| rd.with_eom_policy(policy))
+    }
+
+    /// Subtract two durations, returning [None] if the result exceeds the bounds of a duration
+    pub fn checked_sub(&self, rhs: RelativeDuration) -> Option<RelativeDuration> {
+        self.checked_add(-rhs)
+    }
+
+    /// Multiply a duration, returning [None] if the result exceeds the bounds of a duration
+    pub fn checked_mul(&self, rhs: i32) -> Option<RelativeDuration> {
+        RelativeDuration::from_raw(
+            self.num_months().checked_mul(rhs)?,
+            self.num_weeks().checked_mul(rhs)?,
+            self.num_days().checked_mul(rhs)?,
+        )
+        .map(|rd| rd.with_eom_policy(self.eom_policy()))
+    }
+
+    /// Divide a duration, returning [None] when dividing by zero
+    pub fn checked_div(&self, rhs: i32) -> Option<RelativeDuration> {
+        RelativeDuration::from_raw(
+            self.num_months().checked_div(rhs)?,
+            self.num_weeks().checked_div(rhs)?,
+            self.num_days().checked_div(rhs)?,
+        )
+        .map(|rd| rd.with_eom_policy(self.eom_policy()))
+    }
+
+    /// Set how the months of the duration treat days at the end of the month, see [EomPolicy]
+    ///
+    /// The policy is kept through arithmetic with other durations. Other than the default it is
+    /// written after the [iso8601](RelativeDuration::iso8601) form as `[eom=clamped]` or
+    /// `[eom=strict]`, a suffix in the style of RFC 9557 which the duration parsers accept.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// # use calends::{EomPolicy, RelativeDuration};
+    /// # use chrono::NaiveDate;
+    /// let date = NaiveDate::from_ymd_opt(2022, 2, 28).unwrap();
+    /// let month = RelativeDuration::months(1);
+    ///
+    /// assert_eq!(month.checked_add_to(date), NaiveDate::from_ymd_opt(2022, 3, 31));
+    /// assert_eq!(
+    ///     month
+    ///         .with_eom_policy(EomPolicy::PreserveDayClamped)
+    ///         .checked_add_to(date),
+    ///     NaiveDate::from_ymd_opt(2022, 3, 28)
+    /// );
+    /// assert_eq!(
+    ///     month.with_eom_policy(EomPolicy::Strict).iso8601(),
+    ///     "P1M[eom=strict]"
+    /// );
+    /// # }
+    /// ```
+    pub fn with_eom_policy(&self, policy: EomPolicy) -> RelativeDuration {
+        let bits = match policy {
+            EomPolicy::PreserveLastDay => 0,
+            EomPolicy::PreserveDayClamped => 1,
+            EomPolicy::Strict => 2,
+        };
+        RelativeDuration(self.0.with_eom_policy(bits))
+    }
+
+    /// The end of month policy applied when adding the months of the duration to a date
+    pub fn eom_policy(&self) -> EomPolicy {
+        match self.0.eom_policy() {
+            1 => EomPolicy::PreserveDayClamped,
+            2 => EomPolicy::Strict,
+            _ => EomPolicy::PreserveLastDay,
+        }
+    }
+
+    /// Add the duration to a date, returning [None] if the result is out of range
+    ///
+    /// Precendence for adding is from largest unit to smallest unit. With [EomPolicy::Strict] this
+    /// also returns [None] when the day doesn't exist in the month reached by adding the months.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// # use calends::RelativeDuration;
+    /// # use chrono::NaiveDate;
+    /// let rd = RelativeDuration::months(1).with_days(-2);
+    ///
+    /// assert_eq!(
+    ///     rd.checked_add_to(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()),
+    ///     NaiveDate::from_ymd_opt(2022, 1, 30)
+    /// );
+    /// assert_eq!(rd.checked_add_to(NaiveDate::MAX), None);
+    /// # }
+    /// ```
+    pub fn checked_add_to(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let date = shift::shift_months_with(date, self.num_months(), self.eom_policy()).ok()?;
+        let date = shift::checked_shift_weeks(date, self.num_weeks())?;
+        shift::checked_shift_days(date, self.num_days())
+    }
+
     /// Return an ISO8601-2:2019 formatted duration, notably we do not include offsets for time
     /// (hours, minutes or seconds etc.)
     ///
@@ -201,45 +495,71 @@ impl RelativeDuration {
     /// - 'P120M400D' is a duration of 120 months and 400 days
     /// - 'P4W3D' is a duration of 4 weeks and 3 days
     /// - 'P-4M3W' is a duration of negative 4 months and positive 3 weeks, the minus sign can be
-    /// applied to each of the components within the serialization format
+    ///   applied to each of the components within the serialization format
     ///
     pub fn iso8601(&self) -> String {
-        let build = vec![
+        self.iso8601_with(SignStyle::Component)
+    }
+
+    /// Return an ISO8601-2:2019 formatted duration with the sign written in the given style
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// # use calends::{duration::SignStyle, RelativeDuration};
+    /// let rd = RelativeDuration::months(-1).with_days(-2);
+    ///
+    /// assert_eq!(rd.iso8601(), "P-1M-2D");
+    /// assert_eq!(rd.iso8601_with(SignStyle::Leading), "-P1M2D");
+    /// # }
+    /// ```
+    pub fn iso8601_with(&self, style: SignStyle) -> String {
+        let build = [
             (self.num_months(), "M"),
             (self.num_weeks(), "W"),
             (self.num_days(), "D"),
         ];
+        let leading = style == SignStyle::Leading
+            && !self.is_zero()
+            && build.iter().all(|(count, _)| *count <= 0);
 
-        let mut result = String::from("P");
+        let mut result = String::from(if leading { "-P" } else { "P" });
 
         for (count, unit) in build.iter() {
             if *count != 0 {
+                let count = if leading { count.abs() } else { *count };
                 result.push_str(&count.to_string());
                 result.push_str(unit);
             }
         }
 
+        if self.eom_policy() != EomPolicy::default() {
+            result.push_str(&format!("[eom={}]", self.eom_policy().name()));
+        }
+
         result
     }
 }
 
 impl PartialOrd for RelativeDuration {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        (self.num_months(), self.num_weeks(), self.num_days()).partial_cmp(&(
-            other.num_months(),
-            other.num_weeks(),
-            other.num_days(),
-        ))
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for RelativeDuration {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        (self.num_months(), self.num_weeks(), self.num_days()).cmp(&(
-            other.num_months(),
-            other.num_weeks(),
-            other.num_days(),
-        ))
+        (
+            self.num_months(),
+            self.num_weeks(),
+            self.num_days(),
+            self.eom_policy(),
+        )
+            .cmp(&(
+                other.num_months(),
+                other.num_weeks(),
+                other.num_days(),
+                other.eom_policy(),
+            ))
     }
 }
 
@@ -256,19 +576,140 @@ impl Neg for RelativeDuration {
     }
 }
 
+/// Converting a [RelativeDuration] to or from the chrono units [Months] and [Days]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum UnitConversionError {
+    #[error("duration of {0:?} has weeks or days and is not a whole number of months")]
+    NotWholeMonths(RelativeDuration),
+    #[error("duration of {0:?} has months and is not a fixed number of days")]
+    NotWholeDays(RelativeDuration),
+    #[error("duration of {0:?} is negative")]
+    Negative(RelativeDuration),
+    #[error("more than 1048575 months or days does not fit in a duration")]
+    OutOfRange,
+}
+
+/// Number of days in a [Days], which has no accessor, counted from the earliest date
+fn num_days(days: Days) -> Option<i64> {
+    NaiveDate::MIN
+        .checked_add_days(days)
+        .map(|date| (date - NaiveDate::MIN).num_days())
+}
+
+fn try_from_months(months: Months) -> Result<RelativeDuration, UnitConversionError> {
+    i32::try_from(months.as_u32())
+        .ok()
+        .and_then(|months| RelativeDuration::try_from_mwd(months, 0, 0))
+        .ok_or(UnitConversionError::OutOfRange)
+}
+
+fn try_from_days(days: Days) -> Result<RelativeDuration, UnitConversionError> {
+    num_days(days)
+        .and_then(|days| i32::try_from(days).ok())
+        .and_then(|days| RelativeDuration::try_from_mwd(0, 0, days))
+        .ok_or(UnitConversionError::OutOfRange)
+}
+
+/// A duration of chrono [Months]
+///
+/// # Panics
+///
+/// If there are more than 1048575 months, the `strict` feature replaces this with a [TryFrom].
+///
+/// ```
+/// use calends::RelativeDuration;
+/// use chrono::Months;
+///
+/// assert_eq!(RelativeDuration::from(Months::new(3)), RelativeDuration::months(3));
+/// assert_eq!(Months::try_from(RelativeDuration::months(3)), Ok(Months::new(3)));
+/// ```
+#[cfg(not(feature = "strict"))]
+impl From<Months> for RelativeDuration {
+    fn from(months: Months) -> Self {
+        try_from_months(months).expect("relative duration is invalid and exceeds bounds")
+    }
+}
+
+/// A duration of chrono [Days]
+///
+/// # Panics
+///
+/// If there are more than 1048575 days, the `strict` feature replaces this with a [TryFrom].
+///
+/// ```
+/// use calends::RelativeDuration;
+/// use chrono::Days;
+///
+/// assert_eq!(RelativeDuration::from(Days::new(10)), RelativeDuration::days(10));
+/// assert_eq!(Days::try_from(RelativeDuration::weeks(2)), Ok(Days::new(14)));
+/// ```
+#[cfg(not(feature = "strict"))]
+impl From<Days> for RelativeDuration {
+    fn from(days: Days) -> Self {
+        try_from_days(days).expect("relative duration is invalid and exceeds bounds")
+    }
+}
+
+#[cfg(feature = "strict")]
+impl TryFrom<Months> for RelativeDuration {
+    type Error = UnitConversionError;
+
+    fn try_from(months: Months) -> Result<Self, Self::Error> {
+        try_from_months(months)
+    }
+}
+
+#[cfg(feature = "strict")]
+impl TryFrom<Days> for RelativeDuration {
+    type Error = UnitConversionError;
+
+    fn try_from(days: Days) -> Result<Self, Self::Error> {
+        try_from_days(days)
+    }
+}
+
+/// A duration of only months which are not negative
+impl TryFrom<RelativeDuration> for Months {
+    type Error = UnitConversionError;
+
+    fn try_from(duration: RelativeDuration) -> Result<Self, Self::Error> {
+        if duration.num_weeks() != 0 || duration.num_days() != 0 {
+            return Err(UnitConversionError::NotWholeMonths(duration));
+        }
+
+        u32::try_from(duration.num_months())
+            .map(Months::new)
+            .map_err(|_| UnitConversionError::Negative(duration))
+    }
+}
+
+/// A duration of weeks and days without months, where the total number of days is not negative
+impl TryFrom<RelativeDuration> for Days {
+    type Error = UnitConversionError;
+
+    fn try_from(duration: RelativeDuration) -> Result<Self, Self::Error> {
+        if duration.num_months() != 0 {
+            return Err(UnitConversionError::NotWholeDays(duration));
+        }
+
+        u64::try_from(duration.num_weeks() * 7 + duration.num_days())
+            .map(Days::new)
+            .map_err(|_| UnitConversionError::Negative(duration))
+    }
+}
+
+#[cfg(not(feature = "strict"))]
 impl Add<RelativeDuration> for RelativeDuration {
     type Output = RelativeDuration;
 
     #[inline]
     fn add(self, rhs: RelativeDuration) -> RelativeDuration {
-        RelativeDuration::from_mwd(
-            self.num_months() + rhs.num_months(),
-            self.num_weeks() + rhs.num_weeks(),
-            self.num_days() + rhs.num_days(),
-        )
+        self.checked_add(rhs)
+            .expect("relative duration is invalid and exceeds bounds")
     }
 }
 
+#[cfg(not(feature = "strict"))]
 impl Sub for RelativeDuration {
     type Output = RelativeDuration;
 
@@ -278,43 +719,39 @@ impl Sub for RelativeDuration {
     }
 }
 
+#[cfg(not(feature = "strict"))]
 impl Mul<i32> for RelativeDuration {
     type Output = RelativeDuration;
 
     #[inline]
     fn mul(self, rhs: i32) -> RelativeDuration {
-        RelativeDuration::from_mwd(
-            self.num_months() * rhs,
-            self.num_weeks() * rhs,
-            self.num_days() * rhs,
-        )
+        self.checked_mul(rhs)
+            .expect("relative duration is invalid and exceeds bounds")
     }
 }
 
+#[cfg(not(feature = "strict"))]
 impl Div<i32> for RelativeDuration {
     type Output = RelativeDuration;
 
     #[inline]
     fn div(self, rhs: i32) -> RelativeDuration {
-        RelativeDuration::from_mwd(
-            self.num_months() / rhs,
-            self.num_weeks() / rhs,
-            self.num_days() / rhs,
-        )
+        self.checked_div(rhs).expect("division by zero")
     }
 }
 
 /// Add a duration to a [NaiveDate]
 ///
-/// Precendence for adding is from largest unit to smallest unit
+/// Precendence for adding is from largest unit to smallest unit. This panics if the resulting
+/// date is out of range and is not available with the `strict` feature, see
+/// [RelativeDuration::checked_add_to].
+#[cfg(not(feature = "strict"))]
 impl Add<RelativeDuration> for NaiveDate {
     type Output = NaiveDate;
 
     #[inline]
     fn add(self, rhs: RelativeDuration) -> NaiveDate {
-        let date = shift::shift_months(self, rhs.num_months());
-        let date = shift::shift_weeks(date, rhs.num_weeks());
-        shift::shift_days(date, rhs.num_days())
+        rhs.checked_add_to(self).expect("date out of range")
     }
 }
 
@@ -323,6 +760,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(not(feature = "strict"))]
     fn test_from_duration_transits_year() {
         let duration = RelativeDuration::from_duration_between(
             NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
@@ -336,6 +774,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "strict"))]
     fn test_from_duration_transits_month() {
         let duration = RelativeDuration::from_duration_between(
             NaiveDate::from_ymd_opt(2023, 3, 1).unwrap(),
@@ -349,6 +788,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "strict"))]
     fn test_from_duration_transits_months_and_days() {
         let duration = RelativeDuration::from_duration_between(
             NaiveDate::from_ymd_opt(2023, 3, 1).unwrap(),
@@ -363,10 +803,11 @@ mod tests {
 
     #[test]
     fn test_from_duration_between_year() {
-        let duration = RelativeDuration::from_duration_between(
+        let duration = RelativeDuration::checked_from_duration_between(
             NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
             NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
-        );
+        )
+        .unwrap();
 
         assert_eq!(duration.num_months(), 12);
         assert_eq!(duration.num_weeks(), 0);
@@ -375,10 +816,11 @@ mod tests {
 
     #[test]
     fn test_from_duration_between_month() {
-        let duration = RelativeDuration::from_duration_between(
+        let duration = RelativeDuration::checked_from_duration_between(
             NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
             NaiveDate::from_ymd_opt(2022, 2, 1).unwrap(),
-        );
+        )
+        .unwrap();
 
         assert_eq!(duration.num_months(), 1);
         assert_eq!(duration.num_days(), 0);
@@ -431,6 +873,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_iso8601_leading_sign() {
+        let leading = |rd: RelativeDuration| rd.iso8601_with(SignStyle::Leading);
+
+        assert_eq!(leading(RelativeDuration::weeks(-3)), "-P3W");
+        assert_eq!(leading(RelativeDuration::days(3)), "P3D");
+        assert_eq!(
+            leading(RelativeDuration::months(-4).with_weeks(3)),
+            "P-4M3W"
+        );
+        assert_eq!(leading(RelativeDuration::zero()), "P");
+    }
+
+    #[test]
+    fn test_checked() {
+        let max = RelativeDuration::months((1 << 20) - 1);
+        assert_eq!(max.checked_add(RelativeDuration::months(1)), None);
+        assert_eq!(RelativeDuration::months(1).checked_div(0), None);
+        assert_eq!(RelativeDuration::try_from_mwd(i32::MIN, 0, 0), None);
+        assert_eq!(
+            RelativeDuration::months(2).checked_mul(-2),
+            Some(RelativeDuration::months(-4))
+        );
+    }
+
+    #[test]
+    fn test_chrono_units() {
+        assert_eq!(
+            try_from_months(Months::new(1 << 20)),
+            Err(UnitConversionError::OutOfRange)
+        );
+        assert_eq!(
+            try_from_days(Days::new(u64::MAX)),
+            Err(UnitConversionError::OutOfRange)
+        );
+        assert_eq!(
+            try_from_days(Days::new((1 << 20) - 1)),
+            Ok(RelativeDuration::days((1 << 20) - 1))
+        );
+
+        let mixed = RelativeDuration::months(1).with_days(1);
+        assert_eq!(
+            Months::try_from(mixed),
+            Err(UnitConversionError::NotWholeMonths(mixed))
+        );
+        assert_eq!(
+            Days::try_from(mixed),
+            Err(UnitConversionError::NotWholeDays(mixed))
+        );
+        assert_eq!(
+            Days::try_from(RelativeDuration::weeks(1).with_days(-8)),
+            Err(UnitConversionError::Negative(
+                RelativeDuration::weeks(1).with_days(-8)
+            ))
+        );
+        assert_eq!(
+            Days::try_from(RelativeDuration::weeks(1).with_days(-3)),
+            Ok(Days::new(4))
+        );
+    }
+
     #[test]
     fn test_zero() {
         assert!(RelativeDuration::zero().is_zero());
@@ -442,6 +945,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "strict"))]
     fn test_subtract() {
         assert_eq!(
             RelativeDuration::months(1).with_weeks(1).with_days(1)
@@ -451,6 +955,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "strict"))]
     fn test_mul() {
         assert_eq!(
             RelativeDuration::months(1).with_weeks(1).with_days(1) * 2,
@@ -459,6 +964,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "strict"))]
     fn test_div() {
         assert_eq!(
             RelativeDuration::months(2).with_weeks(2).with_days(2) / 2,
@@ -485,9 +991,124 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "strict"))]
     fn test_add_year() {
         let rd = RelativeDuration::months(12);
         let next = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap() + rd;
         assert_eq!(next, NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
     }
+
+    #[test]
+    fn test_eom_policy() {
+        let strict = RelativeDuration::months(1).with_eom_policy(EomPolicy::Strict);
+
+        assert_eq!(strict.eom_policy(), EomPolicy::Strict);
+        assert_eq!((-strict).eom_policy(), EomPolicy::Strict);
+        assert_eq!(
+            strict.checked_mul(3).unwrap().eom_policy(),
+            EomPolicy::Strict
+        );
+        assert_eq!(
+            strict
+                .checked_add(RelativeDuration::days(2))
+                .unwrap()
+                .eom_policy(),
+            EomPolicy::Strict
+        );
+        assert_ne!(strict, RelativeDuration::months(1));
+        assert_eq!(
+            strict.checked_add_to(NaiveDate::from_ymd_opt(2022, 1, 31).unwrap()),
+            None
+        );
+        assert_eq!(
+            RelativeDuration::months(1).eom_policy(),
+            EomPolicy::PreserveLastDay
+        );
+        assert_eq!(
+            RelativeDuration::days(2)
+                .checked_add(strict)
+                .unwrap()
+                .eom_policy(),
+            EomPolicy::Strict
+        );
+    }
+
+    #[test]
+    fn test_eom_policy_round_trip() {
+        use crate::duration::parse::parse_relative_duration;
+
+        for policy in [
+            EomPolicy::PreserveDayClamped,
+            EomPolicy::PreserveLastDay,
+            EomPolicy::Strict,
+        ] {
+            let rd = RelativeDuration::months(-2)
+                .with_days(3)
+                .with_eom_policy(policy);
+            for iso in [rd.iso8601(), rd.iso8601_with(SignStyle::Leading)] {
+                assert_eq!(parse_relative_duration(iso.as_bytes()), Ok((&b""[..], rd)));
+            }
+        }
+
+        let clamped = RelativeDuration::months(1).with_eom_policy(EomPolicy::PreserveDayClamped);
+        assert_eq!(clamped.iso8601(), "P1M[eom=clamped]");
+        assert_eq!(
+            clamped.to_string(),
+            "1 month (clamped at the end of the month)"
+        );
+        assert_eq!(
+            parse_relative_duration(b"-P1M[eom=last-day]"),
+            Ok((&b""[..], RelativeDuration::months(-1)))
+        );
+        assert!(parse_relative_duration(b"P1M[eom=nearest]").is_err());
+    }
+
+    #[test]
+    fn test_bounds() {
+        let max = (1 << 20) - 1;
+
+        assert_eq!(
+            RelativeDuration::try_from_mwd(max, max, max),
+            Some(RelativeDuration::MAX)
+        );
+        assert_eq!(
+            RelativeDuration::try_from_mwd(-max, -max, -max),
+            Some(RelativeDuration::MIN)
+        );
+        assert_eq!(-RelativeDuration::MAX, RelativeDuration::MIN);
+        assert_eq!(
+            RelativeDuration::MAX.checked_add(RelativeDuration::days(1)),
+            None
+        );
+        assert_eq!(
+            RelativeDuration::MAX.eom_policy(),
+            EomPolicy::PreserveLastDay
+        );
+        // Every duration can be added to dates of the common era
+        let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        assert!(RelativeDuration::MAX.checked_add_to(date).is_some());
+        assert!(RelativeDuration::MIN.checked_add_to(date).is_some());
+    }
+
+    #[test]
+    fn test_checked_components() {
+        let max = (1 << 20) - 1;
+
+        assert_eq!(
+            RelativeDuration::try_months(-max),
+            RelativeDuration::try_from_mwd(-max, 0, 0)
+        );
+        assert_eq!(RelativeDuration::try_months(max + 1), None);
+        assert_eq!(RelativeDuration::try_weeks(i32::MIN), None);
+        assert_eq!(RelativeDuration::try_days(-max - 1), None);
+        assert_eq!(
+            RelativeDuration::MAX.checked_with_days(-max),
+            RelativeDuration::try_from_mwd(max, max, -max)
+        );
+        assert_eq!(RelativeDuration::MAX.checked_with_weeks(max + 1), None);
+        assert_eq!(
+            RelativeDuration::checked_from_duration_between(NaiveDate::MAX, NaiveDate::MIN),
+            None
+        );
+    }
 }
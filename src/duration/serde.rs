@@ -1,17 +1,24 @@
 use serde::{ser::SerializeStruct, Serialize, Serializer};
 
-use crate::RelativeDuration;
+use crate::{EomPolicy, RelativeDuration};
 
 /// Serialize a `RelativeDuration` as a human readable struct
+///
+/// An end of month policy other than the default is written as an `eom_policy` field.
 impl Serialize for RelativeDuration {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("RelativeDuration", 3)?;
+        let policy = self.eom_policy();
+        let len = if policy == EomPolicy::default() { 3 } else { 4 };
+        let mut state = serializer.serialize_struct("RelativeDuration", len)?;
         state.serialize_field("months", &self.num_months())?;
         state.serialize_field("weeks", &self.num_weeks())?;
         state.serialize_field("days", &self.num_days())?;
+        if policy != EomPolicy::default() {
+            state.serialize_field("eom_policy", policy.name())?;
+        }
         state.end()
     }
 }
@@ -42,6 +49,7 @@ pub mod rd_iso8601 {
     ///
     /// # Example:
     /// ```rust
+    /// # #[cfg(not(feature = "strict"))] {
     /// # use calends::duration::RelativeDuration;
     /// # use serde_derive::{Deserialize, Serialize};
     /// use calends::duration::serde::rd_iso8601::serialize;
@@ -57,6 +65,7 @@ pub mod rd_iso8601 {
     /// };
     /// let as_string = serde_json::to_string(&s)?;
     /// assert_eq!(as_string, r#"{"duration":"P3M-3D"}"#);
+    /// # }
     /// # Ok::<(), serde_json::Error>(())
     /// ```
     pub fn serialize<S>(rd: &RelativeDuration, serializer: S) -> Result<S::Ok, S::Error>
@@ -93,6 +102,17 @@ pub mod rd_iso8601 {
         d.deserialize_string(DurationVisitor)
     }
 
+    /// Schema of the ISO8601-2:2019 string for a field using this module
+    ///
+    /// Intended for use with `schemars`s `schema_with` attribute, requires the `schemars` feature.
+    #[cfg(feature = "schemars")]
+    pub fn schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        crate::schema::string_schema(
+            "ISO8601-2:2019 duration of months, weeks and days",
+            &["P1M", "P2W", "P3M-3D"],
+        )
+    }
+
     pub struct DurationVisitor;
 
     impl<'de> de::Visitor<'de> for DurationVisitor {
@@ -131,6 +151,15 @@ mod tests {
         let rd = RelativeDuration::default().with_days(1).with_months(23);
         let s = S { rd };
         let parsed: S = serde_json::from_str(&serde_json::to_string(&s).unwrap()).unwrap();
-        assert_eq!(rd, parsed.rd)
+        assert_eq!(rd, parsed.rd);
+
+        let rd = rd.with_eom_policy(EomPolicy::PreserveDayClamped);
+        let json = serde_json::to_string(&S { rd }).unwrap();
+        assert_eq!(json, r#"{"rd":"P23M1D[eom=clamped]"}"#);
+        assert_eq!(serde_json::from_str::<S>(&json).unwrap().rd, rd);
+        assert_eq!(
+            serde_json::to_string(&rd).unwrap(),
+            r#"{"months":23,"weeks":0,"days":1,"eom_policy":"clamped"}"#
+        );
     }
 }
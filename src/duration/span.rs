@@ -0,0 +1,330 @@
+//! The duration between two dates
+use std::ops::RangeInclusive;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{shift::shift_months_with, EomPolicy, RelativeDuration};
+
+/// Units used for the duration between two dates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SpanStrategy {
+    /// Whole months, which includes years as 12 months, then the remaining days
+    #[default]
+    YearsMonthsDays,
+    /// Whole months then the remaining days as weeks and days
+    MonthsWeeksDays,
+    /// Weeks and days
+    WeeksDays,
+    /// Only days
+    Days,
+}
+
+impl RelativeDuration {
+    /// The duration which moves the start of the span to its end, in the units of the strategy
+    ///
+    /// Months are counted as whole calendar months with the day clamped to the end of shorter
+    /// months, so the duration has [EomPolicy::PreserveDayClamped] when it has months. Adding the
+    /// duration to the start always gives the end. A span which ends before it starts gives a
+    /// negative duration. Returns [None] when a component exceeds the bounds of a duration.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{duration::SpanStrategy, RelativeDuration};
+    /// use chrono::NaiveDate;
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2022, 3, 15).unwrap();
+    ///
+    /// let rd = RelativeDuration::checked_from_span(start..=end, SpanStrategy::YearsMonthsDays)
+    ///     .unwrap();
+    /// assert_eq!((rd.num_months(), rd.num_days()), (1, 15));
+    /// assert_eq!(rd.checked_add_to(start), Some(end));
+    ///
+    /// let rd = RelativeDuration::checked_from_span(start..=end, SpanStrategy::WeeksDays);
+    /// assert_eq!(rd, Some(RelativeDuration::weeks(6).with_days(1)));
+    /// # }
+    /// ```
+    pub fn checked_from_span(
+        span: RangeInclusive<NaiveDate>,
+        strategy: SpanStrategy,
+    ) -> Option<RelativeDuration> {
+        let (start, end) = span.into_inner();
+
+        let (months, rest) = match strategy {
+            SpanStrategy::YearsMonthsDays | SpanStrategy::MonthsWeeksDays => {
+                let months = whole_months(start, end);
+                let shifted = shift_months_with(start, months, EomPolicy::PreserveDayClamped);
+                (months, end - shifted.ok()?)
+            }
+            SpanStrategy::WeeksDays | SpanStrategy::Days => (0, end - start),
+        };
+
+        let days = i32::try_from(rest.num_days()).ok()?;
+        let (weeks, days) = match strategy {
+            SpanStrategy::MonthsWeeksDays | SpanStrategy::WeeksDays => (days / 7, days % 7),
+            SpanStrategy::YearsMonthsDays | SpanStrategy::Days => (0, days),
+        };
+
+        let duration = RelativeDuration::try_from_mwd(months, weeks, days)?;
+        Some(match months {
+            0 => duration,
+            _ => duration.with_eom_policy(EomPolicy::PreserveDayClamped),
+        })
+    }
+
+    /// The years, months and days from the start of the span to its end, see
+    /// [RelativeDuration::checked_from_span]
+    ///
+    /// # Panics
+    ///
+    /// If the span is longer than 1048575 months. This is not available with the `strict`
+    /// feature.
+    ///
+    /// ```
+    /// use calends::RelativeDuration;
+    /// use chrono::NaiveDate;
+    ///
+    /// let rd = RelativeDuration::from_span(
+    ///     NaiveDate::from_ymd_opt(2020, 2, 29).unwrap()..=NaiveDate::from_ymd_opt(2022, 3, 1).unwrap(),
+    /// );
+    /// assert_eq!((rd.num_months(), rd.num_days()), (24, 1));
+    /// ```
+    #[cfg(not(feature = "strict"))]
+    pub fn from_span(span: RangeInclusive<NaiveDate>) -> RelativeDuration {
+        RelativeDuration::checked_from_span(span, SpanStrategy::YearsMonthsDays)
+            .expect("relative duration is invalid and exceeds bounds")
+    }
+}
+
+/// The most whole months from the start which don't pass the end, in the direction of the end
+fn whole_months(start: NaiveDate, end: NaiveDate) -> i32 {
+    let months = (end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32;
+    let shifted = shift_months_with(start, months, EomPolicy::PreserveDayClamped)
+        .expect("the month of the end is in range");
+
+    // The shifted date is in the month of the end so at most one month too far
+    if end >= start && shifted > end {
+        months - 1
+    } else if end < start && shifted < end {
+        months + 1
+    } else {
+        months
+    }
+}
+
+/// Number of whole months elapsed from the start to the end
+///
+/// A month is complete on the same day of the following month, or the last day of a shorter
+/// month. Partial months are truncated and the count is negative when the end is before the start.
+///
+/// ```
+/// use calends::duration::complete_months_between;
+/// use chrono::NaiveDate;
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+///
+/// assert_eq!(complete_months_between(date(1, 15), date(3, 14)), 1);
+/// assert_eq!(complete_months_between(date(1, 15), date(3, 15)), 2);
+/// assert_eq!(complete_months_between(date(1, 31), date(2, 28)), 1);
+/// assert_eq!(complete_months_between(date(3, 15), date(1, 15)), -2);
+/// ```
+pub fn complete_months_between(start: NaiveDate, end: NaiveDate) -> i32 {
+    whole_months(start, end)
+}
+
+/// Number of whole quarters elapsed from the start to the end, every three complete months, see
+/// [complete_months_between]
+///
+/// ```
+/// use calends::duration::complete_quarters_between;
+/// use chrono::NaiveDate;
+///
+/// let start = NaiveDate::from_ymd_opt(2022, 2, 10).unwrap();
+///
+/// assert_eq!(complete_quarters_between(start, NaiveDate::from_ymd_opt(2022, 8, 9).unwrap()), 1);
+/// assert_eq!(complete_quarters_between(start, NaiveDate::from_ymd_opt(2022, 8, 10).unwrap()), 2);
+/// ```
+pub fn complete_quarters_between(start: NaiveDate, end: NaiveDate) -> i32 {
+    whole_months(start, end) / 3
+}
+
+/// Number of whole weeks elapsed from the start to the end
+///
+/// ```
+/// use calends::duration::complete_weeks_between;
+/// use chrono::NaiveDate;
+///
+/// let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+///
+/// assert_eq!(complete_weeks_between(start, NaiveDate::from_ymd_opt(2022, 1, 14).unwrap()), 1);
+/// assert_eq!(complete_weeks_between(start, NaiveDate::from_ymd_opt(2021, 12, 18).unwrap()), -2);
+/// ```
+pub fn complete_weeks_between(start: NaiveDate, end: NaiveDate) -> i32 {
+    ((end - start).num_days() / 7) as i32
+}
+
+/// Durations between [NaiveDate]s
+pub trait NaiveDateExt {
+    /// The duration from `other` to this date, see [RelativeDuration::checked_from_span]
+    fn checked_since(&self, other: NaiveDate, strategy: SpanStrategy) -> Option<RelativeDuration>;
+
+    /// The duration from `other` to this date
+    ///
+    /// # Panics
+    ///
+    /// If the duration exceeds the bounds of a [RelativeDuration]. This is not available with the
+    /// `strict` feature.
+    ///
+    /// ```
+    /// use calends::{duration::SpanStrategy, NaiveDateExt, RelativeDuration};
+    /// use chrono::NaiveDate;
+    ///
+    /// let hired = NaiveDate::from_ymd_opt(2019, 6, 3).unwrap();
+    /// let today = NaiveDate::from_ymd_opt(2022, 8, 17).unwrap();
+    ///
+    /// let tenure = today.since(hired, SpanStrategy::YearsMonthsDays);
+    /// assert_eq!((tenure.num_months() / 12, tenure.num_months() % 12), (3, 2));
+    /// assert_eq!(tenure.num_days(), 14);
+    /// ```
+    #[cfg(not(feature = "strict"))]
+    fn since(&self, other: NaiveDate, strategy: SpanStrategy) -> RelativeDuration {
+        self.checked_since(other, strategy)
+            .expect("relative duration is invalid and exceeds bounds")
+    }
+}
+
+impl NaiveDateExt for NaiveDate {
+    fn checked_since(&self, other: NaiveDate, strategy: SpanStrategy) -> Option<RelativeDuration> {
+        RelativeDuration::checked_from_span(other..=*self, strategy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn mwd(rd: RelativeDuration) -> (i32, i32, i32) {
+        (rd.num_months(), rd.num_weeks(), rd.num_days())
+    }
+
+    #[test]
+    fn test_span_reaches_end() {
+        let dates = [
+            date(2020, 1, 31),
+            date(2020, 2, 28),
+            date(2020, 2, 29),
+            date(2020, 3, 1),
+            date(2021, 2, 28),
+            date(2021, 3, 31),
+            date(2023, 12, 15),
+        ];
+        let strategies = [
+            SpanStrategy::YearsMonthsDays,
+            SpanStrategy::MonthsWeeksDays,
+            SpanStrategy::WeeksDays,
+            SpanStrategy::Days,
+        ];
+
+        for start in &dates {
+            for end in &dates {
+                for strategy in strategies {
+                    let rd = RelativeDuration::checked_from_span(*start..=*end, strategy).unwrap();
+                    assert_eq!(rd.checked_add_to(*start), Some(*end), "{start} {end}");
+                    // Every component has the direction of the span
+                    let (m, w, d) = mwd(rd);
+                    let direction = (*end - *start).num_days().signum() as i32;
+                    assert!([m, w, d].iter().all(|c| c * direction >= 0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_complete_periods() {
+        let extremes = (NaiveDate::MIN, NaiveDate::MAX);
+
+        assert_eq!(
+            complete_months_between(extremes.0, extremes.1),
+            -complete_months_between(extremes.1, extremes.0)
+        );
+        assert_eq!(
+            complete_months_between(date(2020, 2, 29), date(2021, 2, 28)),
+            12
+        );
+        assert_eq!(
+            complete_months_between(date(2021, 2, 28), date(2020, 2, 29)),
+            -11
+        );
+        assert_eq!(
+            complete_quarters_between(date(2022, 5, 1), date(2022, 1, 2)),
+            -1
+        );
+        assert_eq!(
+            complete_weeks_between(date(2022, 1, 1), date(2022, 1, 7)),
+            0
+        );
+        assert_eq!(
+            complete_weeks_between(extremes.0, extremes.1),
+            ((extremes.1 - extremes.0).num_days() / 7) as i32
+        );
+    }
+
+    #[test]
+    fn test_span_components() {
+        let span = |start, end, strategy| {
+            mwd(RelativeDuration::checked_from_span(start..=end, strategy).unwrap())
+        };
+
+        assert_eq!(
+            span(
+                date(2021, 2, 28),
+                date(2021, 3, 28),
+                SpanStrategy::YearsMonthsDays
+            ),
+            (1, 0, 0)
+        );
+        assert_eq!(
+            span(
+                date(2020, 1, 31),
+                date(2020, 2, 29),
+                SpanStrategy::YearsMonthsDays
+            ),
+            (1, 0, 0)
+        );
+        assert_eq!(
+            span(
+                date(2021, 3, 31),
+                date(2020, 2, 28),
+                SpanStrategy::MonthsWeeksDays
+            ),
+            (-13, 0, -1)
+        );
+        assert_eq!(
+            span(
+                date(2022, 1, 1),
+                date(2022, 3, 20),
+                SpanStrategy::MonthsWeeksDays
+            ),
+            (2, 2, 5)
+        );
+        assert_eq!(
+            span(date(2022, 1, 1), date(2022, 3, 20), SpanStrategy::Days),
+            (0, 0, 78)
+        );
+        assert_eq!(
+            RelativeDuration::checked_from_span(
+                NaiveDate::MIN..=NaiveDate::MAX,
+                SpanStrategy::YearsMonthsDays
+            ),
+            None
+        );
+        assert_eq!(
+            date(2022, 1, 1).checked_since(date(2022, 1, 8), SpanStrategy::WeeksDays),
+            Some(RelativeDuration::weeks(-1))
+        );
+    }
+}
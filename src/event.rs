@@ -0,0 +1,266 @@
+//! Events
+//!
+//! An event is something that happens on a day or a span of days, optionally repeating according
+//! to a recurrence rule. Each event carries a payload, and single occurences of a repeating event
+//! can be cancelled or changed. [Event::expand] produces the instances of an event within a window.
+//!
+//! ```
+//! use calends::{
+//!     event::{Event, Override},
+//!     interval::ClosedInterval,
+//!     recurrence::rrule::RRule,
+//!     Rule,
+//! };
+//! use chrono::NaiveDate;
+//!
+//! let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+//!
+//! let rent = Event::with_payload("Rent", date(1, 1), 1200)
+//!     .with_rrule(RRule::new(Rule::monthly()))
+//!     .with_cancellation(date(2, 1))
+//!     .with_override(
+//!         date(3, 1),
+//!         Override::Changed {
+//!             interval: ClosedInterval::exact(date(3, 4), date(3, 4)),
+//!             payload: 1250,
+//!         },
+//!     );
+//!
+//! let instances = rent.expand(&ClosedInterval::exact(date(1, 1), date(4, 30)));
+//! let days: Vec<(NaiveDate, i32)> = instances
+//!     .iter()
+//!     .map(|i| (i.start(), i.payload))
+//!     .collect();
+//!
+//! assert_eq!(
+//!     days,
+//!     vec![(date(1, 1), 1200), (date(3, 4), 1250), (date(4, 1), 1200)]
+//! );
+//! ```
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::{interval::ClosedInterval, recurrence::rrule::RRule, IntervalLike};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event<T = ()> {
+    /// Unique identifier of the event, used by calendar feeds to track changes
+    pub uid: Option<String>,
+    pub summary: String,
+    pub start: NaiveDate,
+    /// Last day of the event (inclusive), [None] for an event lasting a single day
+    pub end: Option<NaiveDate>,
+    /// How the event repeats, starting from the start date
+    pub rrule: Option<RRule>,
+    pub payload: T,
+    /// Changes to single occurences, keyed by the start date of the occurence in the series
+    pub overrides: BTreeMap<NaiveDate, Override<T>>,
+}
+
+/// Change to a single occurence of a repeating event, like `RECURRENCE-ID` in RFC 5545
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Override<T> {
+    /// The occurence doesn't happen, like `EXDATE`
+    Cancelled,
+    /// The occurence covers other days or carries another payload
+    Changed {
+        interval: ClosedInterval,
+        payload: T,
+    },
+}
+
+/// A single occurence of an event, see [Event::expand]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instance<T> {
+    /// Start date of the occurence in the series before any override
+    pub occurence: NaiveDate,
+    /// Days covered by the occurence
+    pub interval: ClosedInterval,
+    pub payload: T,
+}
+
+impl<T> Instance<T> {
+    /// First day of the occurence
+    pub fn start(&self) -> NaiveDate {
+        self.interval.start_opt().unwrap_or(self.occurence)
+    }
+}
+
+impl Event {
+    /// Create a single day event
+    pub fn new(summary: impl Into<String>, start: NaiveDate) -> Self {
+        Event::with_payload(summary, start, ())
+    }
+}
+
+impl<T> Event<T> {
+    /// Create a single day event carrying a payload
+    pub fn with_payload(summary: impl Into<String>, start: NaiveDate, payload: T) -> Self {
+        Event {
+            uid: None,
+            summary: summary.into(),
+            start,
+            end: None,
+            rrule: None,
+            payload,
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    /// Set the unique identifier of the event
+    pub fn with_uid(mut self, uid: impl Into<String>) -> Self {
+        self.uid = Some(uid.into());
+        self
+    }
+
+    /// Set the last day of the event (inclusive)
+    pub fn with_end(mut self, end: NaiveDate) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Repeat the event
+    pub fn with_rrule(mut self, rrule: RRule) -> Self {
+        self.rrule = Some(rrule);
+        self
+    }
+
+    /// Change a single occurence, identified by its start date in the series
+    pub fn with_override(mut self, occurence: NaiveDate, change: Override<T>) -> Self {
+        self.overrides.insert(occurence, change);
+        self
+    }
+
+    /// Cancel a single occurence, identified by its start date in the series
+    pub fn with_cancellation(self, occurence: NaiveDate) -> Self {
+        self.with_override(occurence, Override::Cancelled)
+    }
+
+    /// Span of days covered by the first occurence of the event
+    pub fn interval(&self) -> ClosedInterval {
+        ClosedInterval::exact(self.start, self.end.unwrap_or(self.start))
+    }
+
+    /// Start dates of every occurence of the event
+    ///
+    /// ```
+    /// use calends::{event::Event, recurrence::rrule::RRule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let rrule: RRule = "FREQ=WEEKLY;COUNT=3".parse().unwrap();
+    /// let event = Event::new("Standup", NaiveDate::from_ymd_opt(2022, 1, 3).unwrap()).with_rrule(rrule);
+    ///
+    /// assert_eq!(event.occurences().count(), 3);
+    /// ```
+    pub fn occurences(&self) -> Box<dyn Iterator<Item = NaiveDate> + '_> {
+        match &self.rrule {
+            Some(rrule) => Box::new(rrule.with_start(self.start)),
+            None => Box::new(std::iter::once(self.start)),
+        }
+    }
+    /// Occurences of the event overlapping the window, ordered by their first day
+    ///
+    /// Overrides are applied, so cancelled occurences are left out and changed occurences are
+    /// included when their new days overlap the window. Occurences are iterated from the start of
+    /// the event up to the end of the window or the last override, whichever is later.
+    pub fn expand(&self, window: &ClosedInterval) -> Vec<Instance<T>>
+    where
+        T: Clone,
+    {
+        let window_end = match window.end_opt() {
+            Some(end) => end,
+            None => return Vec::new(),
+        };
+        let last = match self.overrides.keys().next_back() {
+            Some(occurence) => std::cmp::max(*occurence, window_end),
+            None => window_end,
+        };
+        let length = Duration::days(self.end.map_or(0, |end| (end - self.start).num_days()));
+
+        let mut instances: Vec<Instance<T>> = self
+            .occurences()
+            .take_while(|occurence| *occurence <= last)
+            .filter_map(|occurence| {
+                let (interval, payload) = match self.overrides.get(&occurence) {
+                    Some(Override::Cancelled) => return None,
+                    Some(Override::Changed { interval, payload }) => {
+                        (interval.clone(), payload.clone())
+                    }
+                    None => {
+                        let end = occurence.checked_add_signed(length)?;
+                        (ClosedInterval::exact(occurence, end), self.payload.clone())
+                    }
+                };
+
+                interval.overlaps(window).then_some(Instance {
+                    occurence,
+                    interval,
+                    payload,
+                })
+            })
+            .collect();
+
+        instances.sort_by_key(|instance| (instance.start(), instance.occurence));
+        instances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rule;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_expand() {
+        // Three day event every two weeks
+        let event = Event::with_payload("Sprint review", date(1, 3), "review")
+            .with_end(date(1, 5))
+            .with_rrule(RRule::new(Rule::biweekly()))
+            .with_override(
+                date(3, 14),
+                Override::Changed {
+                    interval: ClosedInterval::exact(date(1, 20), date(1, 20)),
+                    payload: "moved",
+                },
+            )
+            .with_cancellation(date(1, 31));
+
+        let instances = event.expand(&ClosedInterval::exact(date(1, 4), date(2, 15)));
+        let found: Vec<(NaiveDate, Option<NaiveDate>, &str)> = instances
+            .iter()
+            .map(|i| (i.occurence, i.interval.end_opt(), i.payload))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                (date(1, 3), Some(date(1, 5)), "review"),
+                (date(1, 17), Some(date(1, 19)), "review"),
+                (date(3, 14), Some(date(1, 20)), "moved"),
+                (date(2, 14), Some(date(2, 16)), "review"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_single() {
+        let event = Event::new("Launch", date(6, 1));
+
+        assert_eq!(
+            event.expand(&ClosedInterval::exact(date(6, 1), date(6, 1))),
+            vec![Instance {
+                occurence: date(6, 1),
+                interval: ClosedInterval::exact(date(6, 1), date(6, 1)),
+                payload: (),
+            }]
+        );
+        assert!(event
+            .expand(&ClosedInterval::exact(date(6, 2), date(7, 1)))
+            .is_empty());
+    }
+}
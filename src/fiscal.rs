@@ -0,0 +1,338 @@
+//! Fiscal calendars whose year starts on a day other than January 1
+//!
+//! A fiscal year is split into halves, quarters and twelve fiscal months like a calendar year. Each
+//! fiscal month starts on the same day of the month as the year, so a year starting on April 15
+//! has months running from the 15th to the 14th.
+//!
+//! ```
+//! use calends::{CalendarUnit, FiscalCalendar, MonthDay};
+//! use chrono::NaiveDate;
+//!
+//! // US federal government, FY2023 runs from 2022-10-01 to 2023-09-30
+//! let federal = FiscalCalendar::new(MonthDay::new(10, 1).unwrap());
+//! let date = NaiveDate::from_ymd_opt(2022, 11, 15).unwrap();
+//!
+//! let quarter = federal.quarter_of(date);
+//! assert_eq!(quarter.unit(), CalendarUnit::Quarter(2023, 1));
+//! assert_eq!(quarter.to_string(), "FY2023-Q1");
+//! assert_eq!(quarter.end_date(), NaiveDate::from_ymd_opt(2022, 12, 31).unwrap());
+//! assert_eq!(quarter.succ().start_date(), NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+//! ```
+use std::fmt::Display;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    unit::CalendarUnitFormat, util::days_in_month, CalendarUnit, Interval, MonthDay, UnitKind,
+};
+
+/// A calendar whose years start on a fixed day of the year
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FiscalCalendar {
+    year_start: MonthDay,
+    named_by_start: bool,
+}
+
+impl FiscalCalendar {
+    /// A calendar whose years start on the month day
+    ///
+    /// Fiscal years are named after the calendar year they end in, see
+    /// [FiscalCalendar::named_by_start_year] for the other convention.
+    pub fn new(year_start: MonthDay) -> Self {
+        FiscalCalendar {
+            year_start,
+            named_by_start: false,
+        }
+    }
+
+    /// Name fiscal years after the calendar year they start in, e.g. FY2022 starting on
+    /// 2022-04-01 in Japan
+    ///
+    /// ```
+    /// use calends::{CalendarUnit, FiscalCalendar, MonthDay};
+    /// use chrono::NaiveDate;
+    ///
+    /// let japan = FiscalCalendar::new(MonthDay::new(4, 1).unwrap()).named_by_start_year();
+    /// let date = NaiveDate::from_ymd_opt(2023, 3, 31).unwrap();
+    ///
+    /// assert_eq!(japan.year_of(date).unit(), CalendarUnit::Year(2022));
+    /// ```
+    pub fn named_by_start_year(mut self) -> Self {
+        self.named_by_start = true;
+        self
+    }
+
+    /// Day of the year each fiscal year starts on
+    pub fn year_start(&self) -> MonthDay {
+        self.year_start
+    }
+
+    /// The fiscal unit with the fiscal numbering of the unit, e.g. `Quarter(2023, 1)` for the
+    /// first quarter of FY2023
+    ///
+    /// Returns [None] for kinds shorter than a month and for numbers out of range.
+    pub fn unit(&self, unit: CalendarUnit) -> Option<FiscalUnit> {
+        let valid = match unit {
+            CalendarUnit::Year(_) => true,
+            CalendarUnit::Half(_, half) => (1..=2).contains(&half),
+            CalendarUnit::Quarter(_, quarter) => (1..=4).contains(&quarter),
+            CalendarUnit::Month(_, month) => (1..=12).contains(&month),
+            CalendarUnit::BiWeek(_, _) | CalendarUnit::Week(_, _) | CalendarUnit::Day(_) => false,
+        };
+
+        valid.then_some(FiscalUnit {
+            calendar: *self,
+            unit,
+        })
+    }
+
+    /// The fiscal unit of the kind containing the date, [None] for kinds shorter than a month
+    pub fn containing(&self, date: NaiveDate, kind: UnitKind) -> Option<FiscalUnit> {
+        let (year, month) = self.fiscal_month_of(date);
+
+        let unit = match kind {
+            UnitKind::Year => CalendarUnit::Year(year),
+            UnitKind::Half => CalendarUnit::Half(year, (month - 1) / 6 + 1),
+            UnitKind::Quarter => CalendarUnit::Quarter(year, (month - 1) / 3 + 1),
+            UnitKind::Month => CalendarUnit::Month(year, month),
+            UnitKind::BiWeek | UnitKind::Week | UnitKind::Day => return None,
+        };
+
+        self.unit(unit)
+    }
+
+    /// Fiscal year containing the date
+    pub fn year_of(&self, date: NaiveDate) -> FiscalUnit {
+        self.containing(date, UnitKind::Year).unwrap()
+    }
+
+    /// Fiscal half containing the date
+    pub fn half_of(&self, date: NaiveDate) -> FiscalUnit {
+        self.containing(date, UnitKind::Half).unwrap()
+    }
+
+    /// Fiscal quarter containing the date
+    pub fn quarter_of(&self, date: NaiveDate) -> FiscalUnit {
+        self.containing(date, UnitKind::Quarter).unwrap()
+    }
+
+    /// Fiscal month containing the date
+    pub fn month_of(&self, date: NaiveDate) -> FiscalUnit {
+        self.containing(date, UnitKind::Month).unwrap()
+    }
+
+    /// Difference between the name of a fiscal year and the calendar year it starts in
+    fn year_offset(&self) -> i32 {
+        let starts_on_new_year = self.year_start == MonthDay::new(1, 1).unwrap();
+        i32::from(!self.named_by_start && !starts_on_new_year)
+    }
+
+    /// Calendar year the fiscal year starts in
+    fn start_year(&self, fiscal_year: i32) -> i32 {
+        fiscal_year - self.year_offset()
+    }
+
+    /// First date of the fiscal month, counting months from zero at the start of the year
+    /// starting in the calendar year
+    fn month_start(&self, start_year: i32, months: u32) -> NaiveDate {
+        let months = self.year_start.month() - 1 + months;
+        let year = start_year + (months / 12) as i32;
+        let month = months % 12 + 1;
+        let day = self.year_start.day().min(days_in_month(year, month));
+
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Fiscal year and fiscal month from 1 to 12 containing the date
+    fn fiscal_month_of(&self, date: NaiveDate) -> (i32, u8) {
+        let mut start_year = date.year();
+        if date < self.month_start(start_year, 0) {
+            start_year -= 1;
+        }
+
+        let months = (0..12)
+            .rev()
+            .find(|months| self.month_start(start_year, *months) <= date)
+            .unwrap();
+        (start_year + self.year_offset(), months as u8 + 1)
+    }
+}
+
+/// A year, half, quarter or month of a [FiscalCalendar]
+///
+/// The unit is numbered within the fiscal year, so `Month(2023, 1)` is the first month of FY2023.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FiscalUnit {
+    calendar: FiscalCalendar,
+    unit: CalendarUnit,
+}
+
+impl FiscalUnit {
+    /// Fiscal numbering of the unit
+    pub fn unit(&self) -> CalendarUnit {
+        self.unit
+    }
+
+    /// Calendar the unit belongs to
+    pub fn calendar(&self) -> FiscalCalendar {
+        self.calendar
+    }
+
+    /// First and one past the last fiscal month of the unit, counted from zero
+    fn month_range(&self) -> (i32, u32, u32) {
+        match self.unit {
+            CalendarUnit::Year(year) => (year, 0, 12),
+            CalendarUnit::Half(year, half) => (year, (half as u32 - 1) * 6, half as u32 * 6),
+            CalendarUnit::Quarter(year, quarter) => {
+                (year, (quarter as u32 - 1) * 3, quarter as u32 * 3)
+            }
+            CalendarUnit::Month(year, month) => (year, month as u32 - 1, month as u32),
+            _ => unreachable!("fiscal units are validated on construction"),
+        }
+    }
+
+    /// First date of the unit
+    pub fn start_date(&self) -> NaiveDate {
+        let (year, first, _) = self.month_range();
+        self.calendar
+            .month_start(self.calendar.start_year(year), first)
+    }
+
+    /// Last date of the unit
+    pub fn end_date(&self) -> NaiveDate {
+        let (year, _, last) = self.month_range();
+        self.calendar
+            .month_start(self.calendar.start_year(year), last)
+            .pred_opt()
+            .unwrap()
+    }
+
+    /// Whether the date falls within the unit
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start_date() <= date && date <= self.end_date()
+    }
+
+    pub fn into_interval(&self) -> Interval {
+        Interval::closed_exact(self.start_date(), self.end_date())
+    }
+
+    /// The following unit of the same kind
+    pub fn succ(&self) -> FiscalUnit {
+        FiscalUnit {
+            unit: self.unit.succ(),
+            ..*self
+        }
+    }
+
+    /// The preceding unit of the same kind
+    pub fn pred(&self) -> FiscalUnit {
+        FiscalUnit {
+            unit: self.unit.pred(),
+            ..*self
+        }
+    }
+
+    /// Iterate over this unit and the units of the same kind following it
+    pub fn onwards(&self) -> impl Iterator<Item = FiscalUnit> {
+        std::iter::successors(Some(*self), |unit| Some(unit.succ()))
+    }
+}
+
+/// Fiscal form of the unit e.g. `FY2023-Q1`
+impl Display for FiscalUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.unit.format_with(CalendarUnitFormat::Fiscal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_units_of_date() {
+        let federal = FiscalCalendar::new(MonthDay::new(10, 1).unwrap());
+
+        let year = federal.year_of(date(2022, 10, 1));
+        assert_eq!(year.unit(), CalendarUnit::Year(2023));
+        assert_eq!(year.start_date(), date(2022, 10, 1));
+        assert_eq!(year.end_date(), date(2023, 9, 30));
+        assert_eq!(
+            federal.year_of(date(2022, 9, 30)).unit(),
+            CalendarUnit::Year(2022)
+        );
+
+        assert_eq!(
+            federal.half_of(date(2023, 4, 1)).unit(),
+            CalendarUnit::Half(2023, 2)
+        );
+        assert_eq!(
+            federal.month_of(date(2023, 9, 30)).unit(),
+            CalendarUnit::Month(2023, 12)
+        );
+        assert!(federal
+            .containing(date(2023, 1, 1), UnitKind::Week)
+            .is_none());
+
+        // A calendar starting on January 1 matches the calendar units
+        let calendar = FiscalCalendar::new(MonthDay::new(1, 1).unwrap());
+        for day in date(2022, 1, 1).iter_days().take(400) {
+            let quarter = calendar.quarter_of(day);
+            assert_eq!(quarter.unit(), CalendarUnit::quarter_of(day));
+            assert_eq!(quarter.start_date(), quarter.unit().start_date());
+        }
+    }
+
+    #[test]
+    fn test_mid_month_start() {
+        let calendar = FiscalCalendar::new(MonthDay::new(4, 15).unwrap());
+
+        let month = calendar.month_of(date(2023, 5, 14));
+        assert_eq!(month.unit(), CalendarUnit::Month(2024, 1));
+        assert_eq!(
+            month.into_interval(),
+            Interval::closed_exact(date(2023, 4, 15), date(2023, 5, 14))
+        );
+
+        // Consecutive units leave no gaps
+        for unit in month.onwards().take(30) {
+            assert_eq!(unit.end_date().succ_opt(), Some(unit.succ().start_date()));
+            assert_eq!(calendar.month_of(unit.start_date()), unit);
+            assert_eq!(calendar.month_of(unit.end_date()), unit);
+        }
+    }
+
+    #[test]
+    fn test_month_end_start() {
+        // Months start on the last day of shorter months
+        let calendar = FiscalCalendar::new(MonthDay::new(1, 31).unwrap());
+
+        let february = calendar.month_of(date(2023, 2, 28));
+        assert_eq!(february.unit(), CalendarUnit::Month(2024, 2));
+        assert_eq!(february.end_date(), date(2023, 3, 30));
+        assert_eq!(february.pred().start_date(), date(2023, 1, 31));
+        assert_eq!(
+            calendar.month_of(date(2023, 1, 30)).unit(),
+            CalendarUnit::Month(2023, 12)
+        );
+    }
+
+    #[test]
+    fn test_unit_validation() {
+        let calendar = FiscalCalendar::new(MonthDay::new(7, 1).unwrap());
+
+        assert!(calendar.unit(CalendarUnit::Quarter(2023, 5)).is_none());
+        assert!(calendar.unit(CalendarUnit::Week(2023, 1)).is_none());
+        assert_eq!(
+            calendar
+                .unit(CalendarUnit::Half(2023, 1))
+                .unwrap()
+                .to_string(),
+            "FY2023-H1"
+        );
+    }
+}
@@ -1,5 +1,16 @@
-use crate::RelativeDuration;
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
 
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::{util, CalendarUnit, RelativeDuration, UnitKind};
+
+/// Size of the periods dates are bucketed into, ordered from finest to coarsest
+///
+/// Lustrums, decades and centuries start at years divisible by their length, so the decade of
+/// 1999 is 1990 to 1999.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
 pub enum Grain {
     Day,
     Week,
@@ -16,14 +27,375 @@ impl Grain {
     pub fn into_duration(&self) -> RelativeDuration {
         match self {
             Grain::Day => RelativeDuration::days(1),
-            Grain::Week => RelativeDuration::days(7),
+            Grain::Week => RelativeDuration::weeks(1),
             Grain::Month => RelativeDuration::months(1),
             Grain::Quarter => RelativeDuration::months(3),
             Grain::Half => RelativeDuration::months(6),
             Grain::Year => RelativeDuration::months(12),
-            Grain::Lustrum => todo!(),
-            Grain::Decade => todo!(),
-            Grain::Century => todo!(),
+            Grain::Lustrum => RelativeDuration::months(60),
+            Grain::Decade => RelativeDuration::months(120),
+            Grain::Century => RelativeDuration::months(1200),
+        }
+    }
+
+    /// The [CalendarUnit] of this grain which contains the date
+    ///
+    /// Returns [None] when the grain has no matching calendar unit
+    pub fn unit_of(&self, date: NaiveDate) -> Option<CalendarUnit> {
+        UnitKind::from_grain(*self).map(|kind| CalendarUnit::containing(date, kind))
+    }
+
+    /// First date of the period of this grain that contains the date
+    ///
+    /// Periods starting before [NaiveDate::MIN] are clamped to it.
+    ///
+    /// ```
+    /// use calends::grain::Grain;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 8, 17).unwrap();
+    ///
+    /// assert_eq!(Grain::Quarter.truncate(date), NaiveDate::from_ymd_opt(2022, 7, 1).unwrap());
+    /// assert_eq!(Grain::Decade.truncate(date), NaiveDate::from_ymd_opt(2020, 1, 1).unwrap());
+    /// ```
+    pub fn truncate(&self, date: NaiveDate) -> NaiveDate {
+        let start_of_years = |years: i32| {
+            let year = date.year().checked_sub(date.year().rem_euclid(years))?;
+            NaiveDate::from_ymd_opt(year, 1, 1)
+        };
+
+        match self {
+            Grain::Day => Some(date),
+            Grain::Week => date
+                .checked_sub_signed(Duration::days(date.weekday().num_days_from_monday().into())),
+            Grain::Month => Some(util::beginning_of_month(&date)),
+            Grain::Quarter => Some(util::beginning_of_quarter(&date)),
+            Grain::Half => Some(util::beginning_of_half(&date)),
+            Grain::Year => Some(util::beginning_of_year(&date)),
+            Grain::Lustrum => start_of_years(5),
+            Grain::Decade => start_of_years(10),
+            Grain::Century => start_of_years(100),
+        }
+        .unwrap_or(NaiveDate::MIN)
+    }
+
+    /// Start of the period closest to the date, rounding up when the date is halfway or later
+    ///
+    /// Dates in the last period before [NaiveDate::MAX] round down.
+    ///
+    /// ```
+    /// use calends::grain::Grain;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+    ///
+    /// assert_eq!(Grain::Month.round(date(2, 14)), date(2, 1));
+    /// assert_eq!(Grain::Month.round(date(2, 15)), date(3, 1));
+    /// assert_eq!(Grain::Year.round(date(7, 3)), NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+    /// ```
+    pub fn round(&self, date: NaiveDate) -> NaiveDate {
+        let start = self.truncate(date);
+
+        match self.succ(date) {
+            Some(next) if date - start >= next - date => next,
+            _ => start,
+        }
+    }
+
+    /// Start of the period after the one containing the date
+    ///
+    /// Returns [None] if the period would start after [NaiveDate::MAX].
+    ///
+    /// ```
+    /// use calends::grain::Grain;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 8, 17).unwrap();
+    ///
+    /// assert_eq!(Grain::Week.succ(date), NaiveDate::from_ymd_opt(2022, 8, 22));
+    /// assert_eq!(Grain::Century.succ(date), NaiveDate::from_ymd_opt(2100, 1, 1));
+    /// ```
+    pub fn succ(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.checked_end_of(date)?.succ_opt()
+    }
+
+    /// Last date of the period of this grain that contains the date, clamped to [NaiveDate::MAX]
+    pub(crate) fn end_of(&self, date: NaiveDate) -> NaiveDate {
+        self.checked_end_of(date).unwrap_or(NaiveDate::MAX)
+    }
+
+    /// Last date of the period of this grain that contains the date, [None] after [NaiveDate::MAX]
+    fn checked_end_of(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let end_of_years = |years: i32| {
+            let year = date
+                .year()
+                .checked_sub(date.year().rem_euclid(years))?
+                .checked_add(years - 1)?;
+            NaiveDate::from_ymd_opt(year, 12, 31)
+        };
+        let end_of_months = |months: u32| {
+            util::checked_month_end(date.year(), months * (date.month0() / months + 1))
+        };
+
+        match self {
+            Grain::Day => Some(date),
+            Grain::Week => date.checked_add_signed(Duration::days(
+                (6 - date.weekday().num_days_from_monday()).into(),
+            )),
+            Grain::Month => end_of_months(1),
+            Grain::Quarter => end_of_months(3),
+            Grain::Half => end_of_months(6),
+            Grain::Year => end_of_months(12),
+            Grain::Lustrum => end_of_years(5),
+            Grain::Decade => end_of_years(10),
+            Grain::Century => end_of_years(100),
+        }
+    }
+}
+
+/// Collect dates in any order into the calendar units of the kind that contain them
+///
+/// Dates keep their order within each unit.
+///
+/// ```
+/// use calends::{grain, CalendarUnit, UnitKind};
+/// use chrono::NaiveDate;
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// let buckets = grain::bucket([date(5, 2), date(1, 3), date(2, 14)], UnitKind::Quarter);
+///
+/// assert_eq!(buckets[&CalendarUnit::Quarter(2022, 1)], vec![date(1, 3), date(2, 14)]);
+/// assert_eq!(buckets[&CalendarUnit::Quarter(2022, 2)], vec![date(5, 2)]);
+/// ```
+pub fn bucket(
+    dates: impl IntoIterator<Item = NaiveDate>,
+    kind: UnitKind,
+) -> BTreeMap<CalendarUnit, Vec<NaiveDate>> {
+    bucket_by_key(dates, kind, |date| *date)
+}
+
+/// Collect records into the calendar units of the kind containing the date of each record
+///
+/// ```
+/// use calends::{grain, CalendarUnit, UnitKind};
+/// use chrono::NaiveDate;
+///
+/// struct Sale {
+///     date: NaiveDate,
+///     amount: u32,
+/// }
+///
+/// let sales = vec![
+///     Sale { date: NaiveDate::from_ymd_opt(2022, 1, 3).unwrap(), amount: 10 },
+///     Sale { date: NaiveDate::from_ymd_opt(2022, 1, 20).unwrap(), amount: 5 },
+///     Sale { date: NaiveDate::from_ymd_opt(2022, 3, 1).unwrap(), amount: 7 },
+/// ];
+///
+/// let totals: Vec<(CalendarUnit, u32)> = grain::bucket_by_key(sales, UnitKind::Month, |s| s.date)
+///     .into_iter()
+///     .map(|(month, sales)| (month, sales.iter().map(|s| s.amount).sum()))
+///     .collect();
+///
+/// assert_eq!(
+///     totals,
+///     vec![(CalendarUnit::Month(2022, 1), 15), (CalendarUnit::Month(2022, 3), 7)]
+/// );
+/// ```
+pub fn bucket_by_key<T, F>(
+    items: impl IntoIterator<Item = T>,
+    kind: UnitKind,
+    key: F,
+) -> BTreeMap<CalendarUnit, Vec<T>>
+where
+    F: Fn(&T) -> NaiveDate,
+{
+    let mut buckets: BTreeMap<CalendarUnit, Vec<T>> = BTreeMap::new();
+    for item in items {
+        let unit = CalendarUnit::containing(key(&item), kind);
+        buckets.entry(unit).or_default().push(item);
+    }
+    buckets
+}
+
+impl Display for Grain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Grain::Day => "day",
+            Grain::Week => "week",
+            Grain::Month => "month",
+            Grain::Quarter => "quarter",
+            Grain::Half => "half",
+            Grain::Year => "year",
+            Grain::Lustrum => "lustrum",
+            Grain::Decade => "decade",
+            Grain::Century => "century",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GrainParseError {
+    #[error("unknown grain: {0}")]
+    UnknownGrain(String),
+}
+
+/// Parse the lowercase name of a grain, ignoring case
+///
+/// ```
+/// use calends::grain::Grain;
+///
+/// assert_eq!("quarter".parse(), Ok(Grain::Quarter));
+/// assert_eq!("Decade".parse(), Ok(Grain::Decade));
+/// assert_eq!(Grain::Half.to_string(), "half");
+/// assert!("fortnight".parse::<Grain>().is_err());
+/// ```
+impl FromStr for Grain {
+    type Err = GrainParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "day" => Ok(Grain::Day),
+            "week" => Ok(Grain::Week),
+            "month" => Ok(Grain::Month),
+            "quarter" => Ok(Grain::Quarter),
+            "half" => Ok(Grain::Half),
+            "year" => Ok(Grain::Year),
+            "lustrum" => Ok(Grain::Lustrum),
+            "decade" => Ok(Grain::Decade),
+            "century" => Ok(Grain::Century),
+            _ => Err(GrainParseError::UnknownGrain(s.to_string())),
+        }
+    }
+}
+
+/// The grain of the same length, failing with the kind for biweeks
+impl TryFrom<UnitKind> for Grain {
+    type Error = UnitKind;
+
+    fn try_from(kind: UnitKind) -> Result<Self, Self::Error> {
+        kind.grain().ok_or(kind)
+    }
+}
+
+/// The kind of unit of the same length, failing with the grain for grains longer than a year
+impl TryFrom<Grain> for UnitKind {
+    type Error = Grain;
+
+    fn try_from(grain: Grain) -> Result<Self, Self::Error> {
+        UnitKind::from_grain(grain).ok_or(grain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRAINS: [Grain; 9] = [
+        Grain::Day,
+        Grain::Week,
+        Grain::Month,
+        Grain::Quarter,
+        Grain::Half,
+        Grain::Year,
+        Grain::Lustrum,
+        Grain::Decade,
+        Grain::Century,
+    ];
+
+    #[test]
+    fn test_snapping() {
+        let start = NaiveDate::from_ymd_opt(1995, 1, 1).unwrap();
+
+        for date in start.iter_days().step_by(11).take(1000) {
+            for grain in GRAINS {
+                let truncated = grain.truncate(date);
+                let next = grain.succ(date).unwrap();
+
+                assert!(truncated <= date && date < next);
+                assert_eq!(grain.truncate(truncated), truncated);
+                assert_eq!(grain.truncate(next), next);
+                assert_eq!(
+                    grain.into_duration().checked_add_to(truncated),
+                    Some(next),
+                    "{:?}",
+                    grain
+                );
+                assert!([truncated, next].contains(&grain.round(date)));
+            }
         }
+
+        assert_eq!(
+            Grain::Decade.truncate(NaiveDate::from_ymd_opt(-5, 6, 1).unwrap()),
+            NaiveDate::from_ymd_opt(-10, 1, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_range_edges() {
+        for grain in GRAINS {
+            assert_eq!(grain.succ(NaiveDate::MAX), None, "{:?}", grain);
+            assert_eq!(grain.end_of(NaiveDate::MAX), NaiveDate::MAX);
+            assert_eq!(
+                grain.round(NaiveDate::MAX),
+                grain.truncate(NaiveDate::MAX),
+                "{:?}",
+                grain
+            );
+            assert!(grain.truncate(NaiveDate::MAX) <= NaiveDate::MAX);
+            assert_eq!(util::days_remaining_in(grain, NaiveDate::MAX), 0);
+
+            assert_eq!(
+                grain.truncate(NaiveDate::MIN),
+                NaiveDate::MIN,
+                "{:?}",
+                grain
+            );
+            assert_eq!(grain.round(NaiveDate::MIN), NaiveDate::MIN);
+            assert!(grain.succ(NaiveDate::MIN).is_some());
+        }
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for grain in GRAINS {
+            assert_eq!(grain.to_string().parse(), Ok(grain));
+            assert_eq!(grain.to_string().to_uppercase().parse(), Ok(grain));
+
+            if let Ok(kind) = UnitKind::try_from(grain) {
+                assert_eq!(Grain::try_from(kind), Ok(grain));
+            }
+        }
+
+        assert_eq!(
+            "".parse::<Grain>(),
+            Err(GrainParseError::UnknownGrain(String::new()))
+        );
+        assert_eq!(Grain::try_from(UnitKind::BiWeek), Err(UnitKind::BiWeek));
+        assert_eq!(UnitKind::try_from(Grain::Century), Err(Grain::Century));
+    }
+
+    #[test]
+    fn test_bucket() {
+        let start = NaiveDate::from_ymd_opt(2021, 12, 20).unwrap();
+        let dates: Vec<NaiveDate> = start.iter_days().take(60).collect();
+
+        let weeks = bucket(dates.iter().rev().copied(), UnitKind::Week);
+        assert_eq!(weeks.len(), 9);
+        assert!(weeks.values().all(|days| days.len() <= 7));
+        assert_eq!(weeks.values().map(Vec::len).sum::<usize>(), 60);
+        assert_eq!(weeks.keys().next(), Some(&CalendarUnit::Week(2021, 51)));
+
+        let years = bucket_by_key(dates.iter().enumerate(), UnitKind::Year, |(_, d)| **d);
+        assert_eq!(years[&CalendarUnit::Year(2021)].len(), 12);
+        assert_eq!(years[&CalendarUnit::Year(2022)][0].0, 12);
+
+        let biweeks = bucket([NaiveDate::MIN, NaiveDate::MAX], UnitKind::BiWeek);
+        assert_eq!(biweeks.len(), 2);
+    }
+
+    #[test]
+    fn test_order() {
+        assert!(GRAINS.windows(2).all(|w| w[0] < w[1]));
     }
 }
@@ -0,0 +1,141 @@
+//! Holidays of common markets, enabled with the `holidays` feature
+//!
+//! Each calendar is a [HolidaySet] built from [HolidayRule]s, so it can be extended with one-off
+//! holidays such as a royal jubilee using [HolidaySet::with_date], or used as an example for
+//! defining other calendars. The rules are the current ones and are applied to every year, past
+//! changes such as new holidays being introduced are not tracked.
+//!
+//! ```
+//! use calends::{calendar::HolidayCalendar, holidays};
+//! use chrono::NaiveDate;
+//!
+//! let us = holidays::us_federal();
+//! // Independence Day 2021 is a Sunday
+//! assert!(us.is_holiday(NaiveDate::from_ymd_opt(2021, 7, 5).unwrap()));
+//! ```
+use chrono::Weekday;
+
+use crate::{
+    calendar::{HolidayRule, HolidaySet, Observance},
+    MonthDay,
+};
+
+fn fixed(month: u32, day: u32) -> HolidayRule {
+    HolidayRule::Fixed(MonthDay::new(month, day).unwrap())
+}
+
+/// US federal holidays, holidays on a Saturday are observed on the Friday before and holidays on
+/// a Sunday on the Monday after
+pub fn us_federal() -> HolidaySet {
+    let observed = Observance::NearestWeekday;
+
+    HolidaySet::new()
+        // New Year's Day
+        .with_observed_rule(fixed(1, 1), observed)
+        // Birthday of Martin Luther King, Jr.
+        .with_rule(HolidayRule::Weekday(1, 3, Weekday::Mon))
+        // Washington's Birthday
+        .with_rule(HolidayRule::Weekday(2, 3, Weekday::Mon))
+        // Memorial Day
+        .with_rule(HolidayRule::Weekday(5, -1, Weekday::Mon))
+        // Juneteenth National Independence Day
+        .with_observed_rule(fixed(6, 19), observed)
+        // Independence Day
+        .with_observed_rule(fixed(7, 4), observed)
+        // Labor Day
+        .with_rule(HolidayRule::Weekday(9, 1, Weekday::Mon))
+        // Columbus Day
+        .with_rule(HolidayRule::Weekday(10, 2, Weekday::Mon))
+        // Veterans Day
+        .with_observed_rule(fixed(11, 11), observed)
+        // Thanksgiving Day
+        .with_rule(HolidayRule::Weekday(11, 4, Weekday::Thu))
+        // Christmas Day
+        .with_observed_rule(fixed(12, 25), observed)
+}
+
+/// Bank holidays in England and Wales, holidays on a weekend are substituted by the next weekday
+pub fn uk_england_and_wales() -> HolidaySet {
+    let substitute = Observance::NextWeekday;
+
+    HolidaySet::new()
+        // New Year's Day
+        .with_observed_rule(fixed(1, 1), substitute)
+        // Good Friday
+        .with_rule(HolidayRule::Easter(-2))
+        // Easter Monday
+        .with_rule(HolidayRule::Easter(1))
+        // Early May bank holiday
+        .with_rule(HolidayRule::Weekday(5, 1, Weekday::Mon))
+        // Spring bank holiday
+        .with_rule(HolidayRule::Weekday(5, -1, Weekday::Mon))
+        // Summer bank holiday
+        .with_rule(HolidayRule::Weekday(8, -1, Weekday::Mon))
+        // Christmas Day
+        .with_observed_rule(fixed(12, 25), substitute)
+        // Boxing Day
+        .with_observed_rule(fixed(12, 26), substitute)
+}
+
+/// Closing days of the TARGET2 payment system of the euro area, holidays on a weekend are not
+/// moved
+pub fn target2() -> HolidaySet {
+    HolidaySet::new()
+        // New Year's Day
+        .with_rule(fixed(1, 1))
+        // Good Friday
+        .with_rule(HolidayRule::Easter(-2))
+        // Easter Monday
+        .with_rule(HolidayRule::Easter(1))
+        // Labour Day
+        .with_rule(fixed(5, 1))
+        // Christmas Day
+        .with_rule(fixed(12, 25))
+        // Christmas Holiday
+        .with_rule(fixed(12, 26))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn dates(holidays: Vec<NaiveDate>) -> Vec<String> {
+        holidays
+            .into_iter()
+            .map(|d| d.format("%m-%d").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_us_federal() {
+        assert_eq!(
+            dates(us_federal().holidays_in_year(2022)),
+            vec![
+                "01-17", "02-21", "05-30", "06-20", "07-04", "09-05", "10-10", "11-11", "11-24",
+                "12-26"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uk_england_and_wales() {
+        assert_eq!(
+            dates(uk_england_and_wales().holidays_in_year(2022)),
+            vec!["01-03", "04-15", "04-18", "05-02", "05-30", "08-29", "12-26", "12-27"]
+        );
+        assert_eq!(
+            dates(uk_england_and_wales().holidays_in_year(2021)),
+            vec!["01-01", "04-02", "04-05", "05-03", "05-31", "08-30", "12-27", "12-28"]
+        );
+    }
+
+    #[test]
+    fn test_target2() {
+        assert_eq!(
+            dates(target2().holidays_in_year(2022)),
+            vec!["01-01", "04-15", "04-18", "05-01", "12-25", "12-26"]
+        );
+    }
+}
@@ -0,0 +1,409 @@
+//! iCalendar (RFC 5545) import and export
+//!
+//! Available with the `ical` feature. Events are written as all day `VEVENT` components where
+//! `DTEND` is the day after the last day of the event, as required for `DATE` values.
+//!
+//! ```
+//! use calends::{event::Event, ical, recurrence::rrule::RRule};
+//! use chrono::NaiveDate;
+//!
+//! let event = Event::new("Rent", NaiveDate::from_ymd_opt(2022, 1, 1).unwrap())
+//!     .with_uid("rent@example.com")
+//!     .with_rrule("FREQ=MONTHLY;COUNT=12".parse().unwrap());
+//!
+//! let stamp = NaiveDate::from_ymd_opt(2021, 12, 1).unwrap().and_hms_opt(9, 30, 0).unwrap();
+//! let vevent = ical::to_vevent(&event, stamp).unwrap();
+//! assert_eq!(
+//!     vevent,
+//!     "BEGIN:VEVENT\r\n\
+//!      UID:rent@example.com\r\n\
+//!      DTSTAMP:20211201T093000Z\r\n\
+//!      SUMMARY:Rent\r\n\
+//!      DTSTART;VALUE=DATE:20220101\r\n\
+//!      DTEND;VALUE=DATE:20220102\r\n\
+//!      RRULE:FREQ=MONTHLY;COUNT=12\r\n\
+//!      END:VEVENT\r\n"
+//! );
+//!
+//! assert_eq!(ical::parse_events(&vevent).unwrap(), vec![event]);
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+use crate::{
+    event::{Event, Override},
+    interval::bound::Bound,
+    recurrence::rrule::{RRule, RRuleError},
+    IntervalLike,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IcalError {
+    #[error("the event is missing {0}")]
+    MissingProperty(&'static str),
+
+    #[error("invalid date {0:?}")]
+    InvalidDate(String),
+
+    #[error("intervals without a start or an end can't be represented")]
+    Unbounded,
+
+    #[error("BEGIN:VEVENT without a matching END:VEVENT")]
+    Unterminated,
+
+    #[error(transparent)]
+    RRule(#[from] RRuleError),
+}
+
+/// `DTSTART` and `DTEND` lines for a bounded interval
+///
+/// ```
+/// use calends::{ical, CalendarUnit};
+///
+/// assert_eq!(
+///     ical::interval_to_ical(&CalendarUnit::Month(2022, 2).into_interval()).unwrap(),
+///     "DTSTART;VALUE=DATE:20220201\r\nDTEND;VALUE=DATE:20220301\r\n"
+/// );
+/// ```
+pub fn interval_to_ical<I: IntervalLike + ?Sized>(interval: &I) -> Result<String, IcalError> {
+    match (interval.bound_start(), interval.bound_end()) {
+        (Bound::Included(start), Bound::Included(end)) => {
+            let mut out = String::new();
+            write_dates(&mut out, start, end)?;
+            Ok(out)
+        }
+        _ => Err(IcalError::Unbounded),
+    }
+}
+
+/// Write an event as a `VEVENT` component
+///
+/// `UID` and `DTSTAMP` are required by RFC 5545, so the event must have a uid and `dtstamp` is
+/// written as the UTC time the component was created. Cancelled occurences are written as
+/// `EXDATE`, the payload and changed occurences are not written.
+pub fn to_vevent<T>(event: &Event<T>, dtstamp: NaiveDateTime) -> Result<String, IcalError> {
+    let uid = event
+        .uid
+        .as_ref()
+        .ok_or(IcalError::MissingProperty("UID"))?;
+
+    let mut out = String::new();
+    write_line(&mut out, "BEGIN:VEVENT");
+    write_line(&mut out, &format!("UID:{}", escape(uid)));
+    write_line(
+        &mut out,
+        &format!("DTSTAMP:{}", dtstamp.format("%Y%m%dT%H%M%SZ")),
+    );
+    write_line(&mut out, &format!("SUMMARY:{}", escape(&event.summary)));
+    write_dates(&mut out, event.start, event.end.unwrap_or(event.start))?;
+
+    if let Some(rrule) = &event.rrule {
+        write_line(
+            &mut out,
+            &format!("RRULE:{}", rrule.to_rrule_with_start(event.start)?),
+        );
+    }
+    for (occurence, change) in &event.overrides {
+        if let Override::Cancelled = change {
+            write_line(
+                &mut out,
+                &format!("EXDATE;VALUE=DATE:{}", occurence.format("%Y%m%d")),
+            );
+        }
+    }
+
+    write_line(&mut out, "END:VEVENT");
+    Ok(out)
+}
+
+/// Write events as a `VCALENDAR`, see [to_vevent]
+pub fn to_vcalendar<T>(events: &[Event<T>], dtstamp: NaiveDateTime) -> Result<String, IcalError> {
+    let mut out = String::new();
+    write_line(&mut out, "BEGIN:VCALENDAR");
+    write_line(&mut out, "VERSION:2.0");
+    write_line(&mut out, "PRODID:-//calends//calends//EN");
+
+    for event in events {
+        out.push_str(&to_vevent(event, dtstamp)?);
+    }
+
+    write_line(&mut out, "END:VCALENDAR");
+    Ok(out)
+}
+
+/// Parse the `VEVENT` components found in an iCalendar document
+///
+/// Only the summary, uid, dates, recurrence rule and exception dates of each event are read,
+/// other properties are ignored. Times are dropped from `DATE-TIME` values.
+pub fn parse_events(s: &str) -> Result<Vec<Event>, IcalError> {
+    let mut events = Vec::new();
+    let mut current: Option<Vec<(String, String)>> = None;
+
+    for line in unfold(s) {
+        let (name, value) = match line.split_once(':') {
+            Some(property) => property,
+            None => continue,
+        };
+        // Parameters such as VALUE=DATE are not needed to read the value
+        let name = name
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+
+        match (name.as_str(), value) {
+            ("BEGIN", "VEVENT") => current = Some(Vec::new()),
+            ("END", "VEVENT") => {
+                let properties = current.take().ok_or(IcalError::Unterminated)?;
+                events.push(parse_event(properties)?);
+            }
+            _ => {
+                if let Some(properties) = current.as_mut() {
+                    properties.push((name, value.to_string()));
+                }
+            }
+        }
+    }
+
+    match current {
+        Some(_) => Err(IcalError::Unterminated),
+        None => Ok(events),
+    }
+}
+
+fn parse_event(properties: Vec<(String, String)>) -> Result<Event, IcalError> {
+    let mut uid = None;
+    let mut summary = None;
+    let mut start = None;
+    let mut end = None;
+    let mut rrule = None;
+    let mut overrides = BTreeMap::new();
+
+    for (name, value) in properties {
+        match name.as_str() {
+            "UID" => uid = Some(unescape(&value)),
+            "SUMMARY" => summary = Some(unescape(&value)),
+            "DTSTART" => start = Some(parse_date(&value)?),
+            "DTEND" => end = Some((parse_date(&value)?, value.len() == 8)),
+            "RRULE" => rrule = Some(value.parse::<RRule>()?),
+            "EXDATE" => {
+                for date in value.split(',') {
+                    overrides.insert(parse_date(date)?, Override::Cancelled);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let start = start.ok_or(IcalError::MissingProperty("DTSTART"))?;
+    // DATE values end on the day after the event, DATE-TIME values end during the last day
+    let end = end
+        .map(|(end, is_date)| {
+            if is_date {
+                end.pred_opt().unwrap_or(end)
+            } else {
+                end
+            }
+        })
+        .filter(|end| *end > start);
+
+    Ok(Event {
+        uid,
+        summary: summary.unwrap_or_default(),
+        start,
+        end,
+        rrule,
+        payload: (),
+        overrides,
+    })
+}
+
+fn write_dates(out: &mut String, start: NaiveDate, end: NaiveDate) -> Result<(), IcalError> {
+    let exclusive_end = end
+        .checked_add_signed(Duration::days(1))
+        .ok_or_else(|| IcalError::InvalidDate(end.to_string()))?;
+
+    write_line(
+        out,
+        &format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")),
+    );
+    write_line(
+        out,
+        &format!("DTEND;VALUE=DATE:{}", exclusive_end.format("%Y%m%d")),
+    );
+    Ok(())
+}
+
+/// Write a content line, folding it at 75 octets
+fn write_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// Join folded content lines back together
+fn unfold(s: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in s.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, IcalError> {
+    let invalid = || IcalError::InvalidDate(value.to_string());
+    let time = value.get(8..).ok_or_else(invalid)?;
+    if !(time.is_empty() || time.starts_with('T')) {
+        return Err(invalid());
+    }
+
+    NaiveDate::parse_from_str(value.get(..8).ok_or_else(invalid)?, "%Y%m%d").map_err(|_| invalid())
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n') | Some('N') => out.push('\n'),
+                Some(c) => out.push(c),
+                None => out.push('\\'),
+            },
+            (c, false) => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stamp() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2022, 1, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let calendar = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:1@example.com\r\n\
+            SUMMARY:Planning\\, quarterly\r\n\
+            DTSTART:20220103T090000Z\r\n\
+            DTEND:20220105T170000Z\r\n\
+            RRULE:FREQ=MONTHLY;INTERVAL=3;BY\r\n \
+             DAY=1MO\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            SUMMARY:Holiday\r\n\
+            DTSTART;VALUE=DATE:20221226\r\n\
+            DTEND;VALUE=DATE:20221227\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = parse_events(calendar).unwrap();
+        assert_eq!(events.len(), 2);
+
+        assert_eq!(events[0].uid.as_deref(), Some("1@example.com"));
+        assert_eq!(events[0].summary, "Planning, quarterly");
+        assert_eq!(events[0].end, NaiveDate::from_ymd_opt(2022, 1, 5));
+        assert_eq!(
+            events[0].rrule.as_ref().unwrap().to_rrule().unwrap(),
+            "FREQ=MONTHLY;INTERVAL=3;BYDAY=1MO"
+        );
+
+        assert_eq!(
+            events[1].start,
+            NaiveDate::from_ymd_opt(2022, 12, 26).unwrap()
+        );
+        assert_eq!(events[1].end, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_events("BEGIN:VEVENT\r\nSUMMARY:x\r\nEND:VEVENT\r\n"),
+            Err(IcalError::MissingProperty("DTSTART"))
+        );
+        assert_eq!(
+            parse_events("BEGIN:VEVENT\r\nDTSTART:20220101\r\n"),
+            Err(IcalError::Unterminated)
+        );
+    }
+
+    #[test]
+    fn test_fold_round_trip() {
+        let event = Event::new(
+            "A very long summary; with characters that need escaping, and more than seventy five octets",
+            NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+        )
+        .with_uid("1@example.com")
+        .with_end(NaiveDate::from_ymd_opt(2022, 1, 3).unwrap())
+        .with_rrule("FREQ=WEEKLY".parse().unwrap())
+        .with_cancellation(NaiveDate::from_ymd_opt(2022, 1, 8).unwrap());
+
+        let vevent = to_vevent(&event, stamp()).unwrap();
+        assert!(vevent.lines().all(|line| line.len() <= 75));
+        assert_eq!(parse_events(&vevent).unwrap(), vec![event]);
+    }
+
+    #[test]
+    fn test_monthly_rule() {
+        let event = Event::new("Invoice", NaiveDate::from_ymd_opt(2022, 1, 15).unwrap())
+            .with_uid("invoice@example.com")
+            .with_rrule(RRule::new(crate::Rule::monthly()));
+
+        let vevent = to_vevent(&event, stamp()).unwrap();
+        assert!(vevent.contains("RRULE:FREQ=MONTHLY;BYMONTHDAY=15\r\n"));
+    }
+
+    #[test]
+    fn test_required_properties() {
+        let event = Event::new("Rent", NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+        assert_eq!(
+            to_vevent(&event, stamp()),
+            Err(IcalError::MissingProperty("UID"))
+        );
+
+        let vcalendar = to_vcalendar(&[event.with_uid("rent@example.com")], stamp()).unwrap();
+        assert!(vcalendar.contains("\r\nUID:rent@example.com\r\nDTSTAMP:20220101T120000Z\r\n"));
+    }
+
+    #[test]
+    fn test_unbounded_interval() {
+        let interval = crate::Interval::open_end(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+        assert_eq!(interval_to_ical(&interval), Err(IcalError::Unbounded));
+    }
+}
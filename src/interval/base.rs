@@ -1,11 +1,13 @@
+use std::fmt::Display;
+
 use chrono::NaiveDate;
 
-use crate::{IntervalLike, RelativeDuration};
+use crate::{grain::Grain, IntervalLike, RelativeDuration};
 
 use super::bound::Bound;
 use super::closed::ClosedInterval;
-use super::iter::UntilAfter;
-use super::marker;
+use super::iter::{ChunksAligned, UntilAfter};
+use super::marker::{self, Start};
 use super::open::{OpenEndInterval, OpenStartInterval};
 
 #[derive(Debug, thiserror::Error)]
@@ -30,14 +32,14 @@ pub enum IntervalError {
 /// These rules have been adapted from ISO 8601-2:2019 7.14 Time Intervals.
 ///
 /// - **Start and end:** A given start and end, we will calculate a duration based on the difference of
-/// these two time sets and assign that as the duration. This may not produce the correct results
-/// in the case of months.
+///   these two time sets and assign that as the duration. This may not produce the correct results
+///   in the case of months.
 ///
 /// - **Start and duration:** The start time plus the duration creates the end of the interval.
-/// Intervals are then iterated on with the given duration. e.g. if the duration is 1 month, then
-/// the next call to the iterator would give you a month in the future.
+///   Intervals are then iterated on with the given duration. e.g. if the duration is 1 month, then
+///   the next call to the iterator would give you a month in the future.
 /// - **End and duration:** The end time minus the duration creates the beginning of the interval.
-/// Intervals are then iterated on with the given duration.
+///   Intervals are then iterated on with the given duration.
 ///
 /// ## Other notes
 ///
@@ -49,6 +51,7 @@ pub enum IntervalError {
 /// decision that it adds too much cognitive load / API cruft so we do not include it.
 ///
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Interval {
     /// A closed interval that will always have a start and end
@@ -63,6 +66,7 @@ impl Interval {
     /// # Example
     ///
     /// ```
+    /// # #[cfg(not(feature = "strict"))] {
     /// use chrono::NaiveDate;
     /// use calends::{Interval, IntervalLike, RelativeDuration};
     /// use calends::interval::marker::{End, Start};
@@ -74,6 +78,7 @@ impl Interval {
     ///
     /// assert_eq!(interval.start_opt().unwrap(), start);
     /// assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd_opt(2022, 1, 31).unwrap());
+    /// # }
     /// ```
     pub fn closed_from_start(date: NaiveDate, duration: RelativeDuration) -> Self {
         Interval::Closed(ClosedInterval::from_start(date, duration))
@@ -81,12 +86,18 @@ impl Interval {
 
     /// Create an interval from an end and a duration
     ///
+    /// # Panics
+    ///
+    /// If the start is out of range, see [Interval::checked_closed_from_end]. This is not
+    /// available with the `strict` feature.
+    ///
     /// # Example
     ///
     /// ```
     /// use chrono::NaiveDate;
     /// use calends::{Interval, IntervalLike, RelativeDuration};
     /// use calends::interval::marker::{End, Start};
+    /// # #[cfg(not(feature = "strict"))] {
     ///
     /// let interval = Interval::closed_from_end(
     ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
@@ -95,11 +106,30 @@ impl Interval {
     ///
     /// assert_eq!(interval.start_opt().unwrap(), NaiveDate::from_ymd_opt(2021, 12, 13).unwrap());
     /// assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    /// # }
     /// ```
+    #[cfg(not(feature = "strict"))]
     pub fn closed_from_end(end: NaiveDate, duration: RelativeDuration) -> Self {
         Interval::Closed(ClosedInterval::from_end(end, duration))
     }
 
+    /// Create an interval from an end and a duration, returning [None] if the start is out of
+    /// range
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use calends::{Interval, IntervalLike, RelativeDuration};
+    ///
+    /// let month = RelativeDuration::try_months(1).unwrap();
+    /// let interval = Interval::checked_closed_from_end(NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(), month);
+    ///
+    /// assert_eq!(interval.unwrap().start_opt(), NaiveDate::from_ymd_opt(2021, 12, 31));
+    /// assert_eq!(Interval::checked_closed_from_end(NaiveDate::MIN, month), None);
+    /// ```
+    pub fn checked_closed_from_end(end: NaiveDate, duration: RelativeDuration) -> Option<Self> {
+        ClosedInterval::checked_from_end(end, duration).map(Interval::Closed)
+    }
+
     /// Create an interval from an end and a duration
     ///
     /// # Example
@@ -121,6 +151,25 @@ impl Interval {
         Interval::Closed(ClosedInterval::with_dates(start, end))
     }
 
+    /// Create an interval that keeps both the start and end dates exactly
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use calends::{Interval, IntervalLike};
+    ///
+    /// let interval = Interval::closed_exact(
+    ///     NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(),
+    /// );
+    ///
+    /// assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd_opt(2022, 2, 28).unwrap());
+    /// ```
+    pub fn closed_exact(start: NaiveDate, end: NaiveDate) -> Self {
+        Interval::Closed(ClosedInterval::exact(start, end))
+    }
+
     pub fn open_start(end: NaiveDate) -> Self {
         Interval::OpenStart(OpenStartInterval::new(end))
     }
@@ -134,11 +183,40 @@ impl Interval {
         until: NaiveDate,
     ) -> Result<UntilAfter<ClosedInterval>, IntervalError> {
         match self {
-            Interval::Closed(closed) => Ok(UntilAfter::new(closed, until)),
+            Interval::Closed(closed) => Ok(closed.until_after(until)),
             Interval::OpenStart(_) => Err(IntervalError::NotIterable),
             Interval::OpenEnd(_) => Err(IntervalError::NotIterable),
         }
     }
+
+    /// Split the interval into sub-intervals cut at the calendar boundaries of the grain
+    ///
+    /// Intervals with an open end are split indefinitely, intervals with an open start can not be
+    /// split.
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use calends::{Interval, IntervalLike};
+    /// use calends::grain::Grain;
+    ///
+    /// let interval = Interval::closed_exact(
+    ///     NaiveDate::from_ymd_opt(2022, 2, 15).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2022, 7, 10).unwrap(),
+    /// );
+    ///
+    /// let mut chunks = interval.chunks_aligned(Grain::Quarter).unwrap();
+    /// assert_eq!(chunks.next().unwrap().end_opt(), NaiveDate::from_ymd_opt(2022, 3, 31));
+    /// assert_eq!(chunks.next().unwrap().end_opt(), NaiveDate::from_ymd_opt(2022, 6, 30));
+    /// assert_eq!(chunks.next().unwrap().end_opt(), NaiveDate::from_ymd_opt(2022, 7, 10));
+    /// assert!(chunks.next().is_none());
+    /// ```
+    pub fn chunks_aligned(&self, grain: Grain) -> Result<ChunksAligned, IntervalError> {
+        match self {
+            Interval::Closed(closed) => Ok(closed.chunks_aligned(grain)),
+            Interval::OpenStart(_) => Err(IntervalError::NotIterable),
+            Interval::OpenEnd(open) => Ok(ChunksAligned::new(open.start(), None, grain)),
+        }
+    }
 }
 
 impl IntervalLike for Interval {
@@ -167,6 +245,19 @@ impl IntervalLike for Interval {
     }
 }
 
+/// Display an `Interval` in a human readable form e.g. "2022-01-01 to 2022-03-31"
+///
+/// The alternate form (`{:#}`) displays the ISO8601-2:2019 representation
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Interval::Closed(i) => i.fmt(f),
+            Interval::OpenStart(i) => i.fmt(f),
+            Interval::OpenEnd(i) => i.fmt(f),
+        }
+    }
+}
+
 impl From<IntervalWithStart> for Interval {
     fn from(i: IntervalWithStart) -> Self {
         match i {
@@ -187,6 +278,7 @@ impl From<IntervalWithEnd> for Interval {
 
 /// An interval that has a guaranteed start but deos not guarantee and end
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum IntervalWithStart {
     Closed(ClosedInterval),
@@ -220,10 +312,41 @@ impl IntervalWithStart {
         until: NaiveDate,
     ) -> Result<UntilAfter<ClosedInterval>, IntervalError> {
         match self {
-            IntervalWithStart::Closed(closed) => Ok(UntilAfter::new(closed, until)),
+            IntervalWithStart::Closed(closed) => Ok(closed.until_after(until)),
             IntervalWithStart::OpenEnd(_) => Err(IntervalError::NotIterable),
         }
     }
+
+    /// Iterate from the start of the interval in steps of the supplied duration
+    ///
+    /// This allows intervals without an end to be expanded, the resulting iterator is unbounded
+    /// so it is usually combined with [ClosedInterval::until_after].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use chrono::NaiveDate;
+    /// use calends::{IntervalWithStart, IntervalLike, RelativeDuration};
+    /// use calends::interval::OpenEndInterval;
+    ///
+    /// let interval = IntervalWithStart::OpenEnd(OpenEndInterval::new(
+    ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+    /// ));
+    ///
+    /// let mut iter = interval
+    ///     .iterate_with(RelativeDuration::months(1))
+    ///     .until_after(NaiveDate::from_ymd_opt(2022, 5, 1).unwrap());
+    ///
+    /// assert_eq!(iter.next().unwrap().start_opt(), NaiveDate::from_ymd_opt(2022, 1, 1));
+    /// assert_eq!(iter.next().unwrap().start_opt(), NaiveDate::from_ymd_opt(2022, 2, 1));
+    /// assert_eq!(iter.next().unwrap().start_opt(), NaiveDate::from_ymd_opt(2022, 3, 1));
+    /// assert!(iter.next().is_none());
+    /// # }
+    /// ```
+    pub fn iterate_with(&self, step: RelativeDuration) -> ClosedInterval {
+        ClosedInterval::from_start(self.start(), step)
+    }
 }
 
 impl IntervalLike for IntervalWithStart {
@@ -264,6 +387,7 @@ impl TryFrom<Interval> for IntervalWithStart {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum IntervalWithEnd {
     Closed(ClosedInterval),
@@ -370,6 +494,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "strict"))]
     fn test_interval_closed_from_end() {
         let interval = Interval::closed_from_end(
             NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
@@ -404,4 +529,60 @@ mod tests {
             Some(NaiveDate::from_ymd_opt(2023, 1, 1).unwrap())
         );
     }
+
+    #[test]
+    fn test_display() {
+        let closed = Interval::closed_with_dates(
+            NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2022, 3, 31).unwrap(),
+        );
+        assert_eq!(closed.to_string(), "2022-01-01 to 2022-03-31");
+        assert_eq!(format!("{:#}", closed), "2022-01-01/2022-03-31");
+
+        let open_start = Interval::open_start(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+        assert_eq!(open_start.to_string(), "until 2022-01-01");
+        assert_eq!(format!("{:#}", open_start), "../2022-01-01");
+
+        let open_end = Interval::open_end(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+        assert_eq!(open_end.to_string(), "from 2022-01-01");
+        assert_eq!(format!("{:#}", open_end), "2022-01-01/..");
+    }
+
+    #[test]
+    fn test_with_start_iterate_with() {
+        let start = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+        let interval = IntervalWithStart::try_from(Interval::open_end(start)).unwrap();
+
+        let starts: Vec<_> = interval
+            .iterate_with(RelativeDuration::weeks(1))
+            .until_after(NaiveDate::from_ymd_opt(2022, 2, 21).unwrap())
+            .map(|i| i.start())
+            .collect();
+
+        assert_eq!(
+            starts,
+            vec![
+                NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+                NaiveDate::from_ymd_opt(2022, 2, 7).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chunks_aligned_open_end() {
+        let interval = Interval::open_end(NaiveDate::from_ymd_opt(2022, 12, 30).unwrap());
+        let chunks: Vec<_> = interval
+            .chunks_aligned(Grain::Week)
+            .unwrap()
+            .take(2)
+            .collect();
+
+        assert_eq!(chunks[0].end_opt(), NaiveDate::from_ymd_opt(2023, 1, 1));
+        assert_eq!(chunks[1].start_opt(), NaiveDate::from_ymd_opt(2023, 1, 2));
+        assert!(
+            Interval::open_start(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap())
+                .chunks_aligned(Grain::Week)
+                .is_err()
+        );
+    }
 }
@@ -42,16 +42,31 @@ pub fn within<Q>(item: Q, start: &Bound<Q>, end: &Bound<Q>) -> bool
 where
     Q: Ord,
 {
-    let item_bound = Bound::Included(item);
-    match cmp_bound(&item_bound, start) {
-        Ordering::Less => false,
-        Ordering::Equal => true,
-        Ordering::Greater => match cmp_bound(&item_bound, end) {
-            Ordering::Less => true,
-            Ordering::Equal => true,
-            Ordering::Greater => false,
-        },
+    let after_start = match start {
+        Bound::Included(s) => &item >= s,
+        Bound::Unbounded => true,
+    };
+    let before_end = match end {
+        Bound::Included(e) => &item <= e,
+        Bound::Unbounded => true,
+    };
+
+    after_start && before_end
+}
+
+/// Whether two ranges share at least one point
+pub fn overlaps<Q>(e1: (&Bound<Q>, &Bound<Q>), e2: (&Bound<Q>, &Bound<Q>)) -> bool
+where
+    Q: Ord,
+{
+    fn start_before_end<Q: Ord>(start: &Bound<Q>, end: &Bound<Q>) -> bool {
+        match (start, end) {
+            (Bound::Included(s), Bound::Included(e)) => s <= e,
+            _ => true,
+        }
     }
+
+    start_before_end(e1.0, e2.1) && start_before_end(e2.0, e1.1)
 }
 
 pub fn to_opt<Q>(b: Bound<Q>) -> Option<Q> {
@@ -67,7 +82,21 @@ mod tests {
 
     #[test]
     fn test_within() {
-        assert!(within(3, &Bound::Included(1), &Bound::Unbounded))
+        assert!(within(3, &Bound::Included(1), &Bound::Unbounded));
+        assert!(within(3, &Bound::Unbounded, &Bound::Included(4)));
+        assert!(!within(5, &Bound::Unbounded, &Bound::Included(4)));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(overlaps(
+            (&Bound::Included(1), &Bound::Included(3)),
+            (&Bound::Included(3), &Bound::Unbounded)
+        ));
+        assert!(!overlaps(
+            (&Bound::Unbounded, &Bound::Included(2)),
+            (&Bound::Included(3), &Bound::Included(4))
+        ));
     }
 
     #[test]
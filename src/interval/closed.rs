@@ -1,51 +1,121 @@
-use crate::{duration::RelativeDuration, IntervalLike};
+use std::{fmt::Display, iter::FusedIterator};
 
-use super::{bound::Bound, iter::UntilAfter, marker, parse::parse_interval};
-use chrono::NaiveDate;
+use crate::{duration::RelativeDuration, grain::Grain, IntervalLike};
+
+use super::{
+    bound::Bound,
+    iter::{ChunksAligned, UntilAfter},
+    marker,
+    parse::parse_interval,
+};
+use chrono::{Datelike, NaiveDate, Weekday};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 /// An interval that is constructed off of the idea of the standard calendar (Gregorian Proleptic
 /// calendar).
 ///
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Range<diesel::sql_types::Date>)
+)]
 pub struct ClosedInterval {
     /// Indicating up to OR on in the direction of the interval
     ///
     /// e.g. if the direction is "forwards" and the end is inclusive then it will include the
     /// specified end date
     date: NaiveDate,
-    pub(crate) duration: RelativeDuration,
+    /// Only [None] for an exact interval whose dates are too far apart for a duration
+    pub(crate) duration: Option<RelativeDuration>,
+    /// When set the interval is exact: the end is stored rather than derived from the duration
+    /// and iteration steps by the true span between the two dates
+    end: Option<NaiveDate>,
 }
 
 impl ClosedInterval {
     /// Create an interval from a start and a duration
+    ///
+    /// An end which would be out of range is clamped to [NaiveDate::MIN] or [NaiveDate::MAX].
     pub fn from_start(date: NaiveDate, duration: RelativeDuration) -> Self {
-        ClosedInterval { date, duration }
+        ClosedInterval {
+            date,
+            duration: Some(duration),
+            end: None,
+        }
     }
 
     /// Create an interval from an end and a duration
+    ///
+    /// # Panics
+    ///
+    /// If the start is out of range, see [ClosedInterval::checked_from_end]. This is not
+    /// available with the `strict` feature.
+    #[cfg(not(feature = "strict"))]
     pub fn from_end(end: NaiveDate, duration: RelativeDuration) -> Self {
-        ClosedInterval {
-            date: end + -duration,
-            duration,
-        }
+        ClosedInterval::checked_from_end(end, duration).expect("interval date out of range")
+    }
+
+    /// Create an interval from an end and a duration, returning [None] if the start is out of
+    /// range
+    pub fn checked_from_end(end: NaiveDate, duration: RelativeDuration) -> Option<Self> {
+        Some(ClosedInterval {
+            date: (-duration).checked_add_to(end)?,
+            duration: Some(duration),
+            end: None,
+        })
     }
 
     /// Create an interval with a specified set of dates
+    ///
+    /// The end date is derived from a [RelativeDuration] which may not reproduce the given end in
+    /// the case of months, see [ClosedInterval::exact] for an interval that keeps both dates.
+    /// Dates too far apart for a duration give an exact interval.
     pub fn with_dates(start: NaiveDate, end: NaiveDate) -> Self {
+        match RelativeDuration::checked_from_duration_between(start, end) {
+            Some(duration) => ClosedInterval {
+                date: start,
+                duration: Some(duration),
+                end: None,
+            },
+            None => ClosedInterval::exact(start, end),
+        }
+    }
+
+    /// Create an interval which keeps both the start and the end date
+    ///
+    /// Unlike [ClosedInterval::with_dates] the end is always exactly the date supplied and
+    /// iterating the interval steps by the number of days between the two dates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use calends::IntervalLike;
+    /// use calends::interval::ClosedInterval;
+    ///
+    /// let mut interval = ClosedInterval::exact(
+    ///     NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(),
+    /// );
+    /// assert_eq!(interval.end_opt(), NaiveDate::from_ymd_opt(2022, 2, 28));
+    ///
+    /// interval.next();
+    /// let next = interval.next().unwrap();
+    /// assert_eq!(next.start_opt(), NaiveDate::from_ymd_opt(2022, 2, 28));
+    /// assert_eq!(next.end_opt(), NaiveDate::from_ymd_opt(2022, 3, 28));
+    /// ```
+    pub fn exact(start: NaiveDate, end: NaiveDate) -> Self {
         ClosedInterval {
             date: start,
-            duration: RelativeDuration::from_duration_between(start, end),
+            duration: RelativeDuration::checked_from_duration_between(start, end),
+            end: Some(end),
         }
     }
 
-    #[allow(dead_code)]
-    fn adjust_duration(duration: RelativeDuration) -> RelativeDuration {
-        match duration.cmp(&RelativeDuration::zero()) {
-            std::cmp::Ordering::Less => duration + RelativeDuration::default().with_days(1),
-            std::cmp::Ordering::Equal => duration,
-            std::cmp::Ordering::Greater => duration - RelativeDuration::default().with_days(1),
-        }
+    /// Whether the interval keeps an exact end date, see [ClosedInterval::exact]
+    pub fn is_exact(&self) -> bool {
+        self.end.is_some()
     }
 
     /// Start date of the interval
@@ -53,13 +123,147 @@ impl ClosedInterval {
         self.date
     }
 
-    /// End date of the interval
+    /// End date of the interval, clamped to the range of dates
     fn computed_end_date(&self) -> NaiveDate {
-        self.date + self.duration
+        self.end
+            .or_else(|| self.duration?.checked_add_to(self.date))
+            // A duration can't span half the range, so it overflows on the side of the start
+            .unwrap_or(match self.date > NaiveDate::default() {
+                true => NaiveDate::MAX,
+                false => NaiveDate::MIN,
+            })
     }
 
     pub fn until_after(self, until: NaiveDate) -> UntilAfter<ClosedInterval> {
-        UntilAfter::new(self, until)
+        let hint = self.until_after_hint(until);
+        UntilAfter::new(self, until).with_hint(hint)
+    }
+
+    /// Bounds on the number of intervals ending before `until`
+    ///
+    /// Exact when stepping by days and weeks, months only bound the length of each step.
+    fn until_after_hint(&self, until: NaiveDate) -> (usize, Option<usize>) {
+        // Number of steps of a fixed length from the date which end before the bound
+        let steps = |from: NaiveDate, bound: i64, step: i64| {
+            ((bound - i64::from(from.num_days_from_ce()) - 1).max(0) / step) as usize
+        };
+        let until = i64::from(until.num_days_from_ce());
+
+        let duration = match (self.end, self.duration) {
+            (Some(end), _) => {
+                let step = (end - self.date).num_days();
+                if step <= 0 {
+                    return (0, Some(usize::from(step < 0)));
+                }
+                // The following interval has to be in range for an interval to be produced
+                let bound = until.min(i64::from(NaiveDate::MAX.num_days_from_ce()) - step + 1);
+                let count = steps(end, bound + step, step);
+                return (count, Some(count));
+            }
+            (None, Some(duration)) => duration,
+            (None, None) => return (0, Some(0)),
+        };
+
+        let (months, days) = (
+            duration.num_months() as i64,
+            7 * duration.num_weeks() as i64 + duration.num_days() as i64,
+        );
+        let (shortest, longest) = (28 * months + days, 31 * months + days);
+        if months < 0 || shortest <= 0 {
+            return (0, None);
+        }
+
+        (
+            steps(self.date, until, longest),
+            Some(steps(self.date, until, shortest)),
+        )
+    }
+
+    /// Split the interval into sub-intervals cut at the calendar boundaries of the grain
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use calends::IntervalLike;
+    /// use calends::grain::Grain;
+    /// use calends::interval::ClosedInterval;
+    ///
+    /// let interval = ClosedInterval::exact(
+    ///     NaiveDate::from_ymd_opt(2022, 1, 15).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2022, 4, 10).unwrap(),
+    /// );
+    ///
+    /// let chunks: Vec<String> = interval
+    ///     .chunks_aligned(Grain::Month)
+    ///     .map(|c| c.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     chunks,
+    ///     vec![
+    ///         "2022-01-15 to 2022-01-31",
+    ///         "2022-02-01 to 2022-02-28",
+    ///         "2022-03-01 to 2022-03-31",
+    ///         "2022-04-01 to 2022-04-10",
+    ///     ]
+    /// );
+    /// ```
+    pub fn chunks_aligned(&self, grain: Grain) -> ChunksAligned {
+        ChunksAligned::new(
+            self.computed_start_date(),
+            Some(self.computed_end_date()),
+            grain,
+        )
+    }
+
+    /// ISO year and week pairs touched by the interval, in order
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use calends::interval::ClosedInterval;
+    ///
+    /// let interval = ClosedInterval::exact(
+    ///     NaiveDate::from_ymd_opt(2021, 12, 29).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2022, 1, 10).unwrap(),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     interval.iso_weeks().collect::<Vec<_>>(),
+    ///     vec![(2021, 52), (2022, 1), (2022, 2)]
+    /// );
+    /// ```
+    pub fn iso_weeks(&self) -> impl Iterator<Item = (i32, u32)> {
+        let end = self.computed_end_date();
+        let start = self.computed_start_date();
+        // The week of NaiveDate::MIN starts before it, so it is stepped from the start instead
+        let monday = start
+            .checked_sub_signed(chrono::Duration::days(
+                start.weekday().num_days_from_monday().into(),
+            ))
+            .unwrap_or(start);
+
+        std::iter::successors(Some(monday), |d| {
+            d.checked_add_signed(chrono::Duration::weeks(1))
+        })
+        .take_while(move |d| *d <= end)
+        .map(|d| {
+            let week = d.iso_week();
+            (week.year(), week.week())
+        })
+    }
+
+    /// Whether the interval shares at least one day with the given ISO week
+    ///
+    /// Returns false for weeks which do not exist in the ISO year
+    pub fn spans_week(&self, year: i32, week: u32) -> bool {
+        match NaiveDate::from_isoywd_opt(year, week, Weekday::Mon) {
+            Some(monday) => {
+                let sunday = monday
+                    .checked_add_signed(chrono::Duration::days(6))
+                    .unwrap_or(NaiveDate::MAX);
+                monday <= self.computed_end_date() && sunday >= self.computed_start_date()
+            }
+            None => false,
+        }
     }
 }
 
@@ -73,13 +277,31 @@ impl IntervalLike for ClosedInterval {
     }
 
     fn duration(&self) -> Option<RelativeDuration> {
-        Some(self.duration)
+        self.duration
     }
 }
 
 impl marker::Start for ClosedInterval {}
 impl marker::End for ClosedInterval {}
 
+/// Display a `ClosedInterval` as "2022-01-01 to 2022-03-31"
+///
+/// The alternate form (`{:#}`) displays the ISO8601-2:2019 representation
+impl Display for ClosedInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            f.write_str(&self.iso8601())
+        } else {
+            write!(
+                f,
+                "{} to {}",
+                self.computed_start_date(),
+                self.computed_end_date()
+            )
+        }
+    }
+}
+
 /// Serialize a `Interval` as a ISO8601-2:2019 compatible format
 impl Serialize for ClosedInterval {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -122,9 +344,188 @@ impl Iterator for ClosedInterval {
     type Item = ClosedInterval;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let interval = ClosedInterval::from_start(self.date, self.duration);
-        // to prevent overlapping dates we add one day
-        self.date = self.date + self.duration;
+        let interval = self.clone();
+        let date = match self.end {
+            Some(end) => end,
+            None => self.duration?.checked_add_to(self.date)?,
+        };
+
+        // An interval which doesn't move would repeat forever
+        if date == self.date {
+            return None;
+        }
+
+        if let Some(end) = self.end {
+            self.end = Some(end.checked_add_signed(end - self.date)?);
+        }
+        self.date = date;
+
         Some(interval)
     }
 }
+
+impl FusedIterator for ClosedInterval {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_end_of_month() {
+        let start = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+        let end = NaiveDate::from_ymd_opt(2022, 2, 28).unwrap();
+
+        assert_ne!(ClosedInterval::with_dates(start, end).end_opt(), Some(end));
+        assert_eq!(ClosedInterval::exact(start, end).end_opt(), Some(end));
+    }
+
+    #[test]
+    fn test_exact_iteration() {
+        let mut iter = ClosedInterval::exact(
+            NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2022, 1, 11).unwrap(),
+        );
+
+        let first = iter.next().unwrap();
+        assert!(first.is_exact());
+        assert_eq!(first.start_opt(), NaiveDate::from_ymd_opt(2022, 1, 1));
+        assert_eq!(first.end_opt(), NaiveDate::from_ymd_opt(2022, 1, 11));
+
+        let second = iter.next().unwrap();
+        assert_eq!(second.start_opt(), NaiveDate::from_ymd_opt(2022, 1, 11));
+        assert_eq!(second.end_opt(), NaiveDate::from_ymd_opt(2022, 1, 21));
+    }
+
+    #[test]
+    fn test_iteration_stops_without_progress() {
+        let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let until = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+
+        for duration in [
+            RelativeDuration::zero(),
+            RelativeDuration::months(1).with_days(-31),
+        ] {
+            let mut iter = ClosedInterval::from_start(date, duration).until_after(until);
+            assert_eq!(iter.next(), None);
+        }
+
+        // Moving backwards never reaches the end
+        let backwards = ClosedInterval::from_start(date, RelativeDuration::days(-1));
+        assert_eq!(backwards.until_after(until).count(), 1);
+
+        assert_eq!(ClosedInterval::exact(date, date).next(), None);
+
+        let last = ClosedInterval::from_start(NaiveDate::MAX, RelativeDuration::days(1));
+        assert_eq!(last.count(), 0);
+    }
+
+    #[test]
+    fn test_until_after_size_hint() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let until = date(2023, 1, 1);
+
+        let mut weekly = ClosedInterval::from_start(date(2022, 1, 1), RelativeDuration::weeks(1))
+            .until_after(until);
+        assert_eq!(weekly.size_hint(), (52, Some(52)));
+        weekly.next();
+        assert_eq!(weekly.size_hint(), (51, Some(51)));
+        assert_eq!(weekly.count(), 51);
+
+        let exact = ClosedInterval::exact(date(2022, 1, 1), date(2022, 1, 11)).until_after(until);
+        assert_eq!(exact.size_hint(), (36, Some(36)));
+        assert_eq!(exact.count(), 36);
+
+        let monthly = ClosedInterval::from_start(date(2022, 1, 31), RelativeDuration::months(1))
+            .until_after(until);
+        let (lower, upper) = monthly.size_hint();
+        assert!(lower <= 11 && upper >= Some(11));
+        assert_eq!(monthly.count(), 11);
+
+        for duration in [
+            RelativeDuration::days(3),
+            RelativeDuration::months(1).with_days(-1),
+            RelativeDuration::months(3).with_weeks(1),
+        ] {
+            for start in [date(2022, 1, 31), date(2022, 2, 28), date(2022, 3, 15)] {
+                let iter = ClosedInterval::from_start(start, duration).until_after(until);
+                let (lower, upper) = iter.size_hint();
+                let count = iter.count();
+                assert!(lower <= count && upper >= Some(count));
+            }
+        }
+
+        let backwards = ClosedInterval::from_start(date(2022, 1, 1), RelativeDuration::days(-1));
+        assert_eq!(backwards.until_after(until).size_hint(), (0, None));
+
+        // The interval after the last one is out of range
+        let last = NaiveDate::MAX - chrono::Duration::days(10);
+        let end_of_time = ClosedInterval::exact(last, last + chrono::Duration::days(4));
+        assert_eq!(
+            end_of_time.clone().until_after(NaiveDate::MAX).size_hint(),
+            (1, Some(1))
+        );
+        assert_eq!(end_of_time.until_after(NaiveDate::MAX).count(), 1);
+    }
+
+    #[test]
+    fn test_iso_weeks() {
+        let interval = ClosedInterval::exact(
+            NaiveDate::from_ymd_opt(2022, 1, 3).unwrap(),
+            NaiveDate::from_ymd_opt(2022, 1, 9).unwrap(),
+        );
+        assert_eq!(interval.iso_weeks().collect::<Vec<_>>(), vec![(2022, 1)]);
+
+        assert!(interval.spans_week(2022, 1));
+        assert!(!interval.spans_week(2022, 2));
+        assert!(!interval.spans_week(2021, 52));
+        assert!(!interval.spans_week(2022, 53));
+
+        // The weeks at the edges of the range start before or end after them
+        let first = ClosedInterval::exact(NaiveDate::MIN, NaiveDate::MIN);
+        let week = NaiveDate::MIN.iso_week();
+        assert_eq!(
+            first.iso_weeks().collect::<Vec<_>>(),
+            vec![(week.year(), week.week())]
+        );
+        let last = ClosedInterval::exact(NaiveDate::MAX, NaiveDate::MAX);
+        let week = NaiveDate::MAX.iso_week();
+        assert_eq!(
+            last.iso_weeks().collect::<Vec<_>>(),
+            vec![(week.year(), week.week())]
+        );
+        assert!(last.spans_week(week.year(), week.week()));
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let month = RelativeDuration::months(1);
+
+        assert_eq!(
+            ClosedInterval::checked_from_end(NaiveDate::MIN, month),
+            None
+        );
+        assert_eq!(
+            ClosedInterval::from_start(NaiveDate::MAX, month).end_opt(),
+            Some(NaiveDate::MAX)
+        );
+        assert_eq!(
+            ClosedInterval::from_start(NaiveDate::MIN, -month).to_string(),
+            format!("{} to {}", NaiveDate::MIN, NaiveDate::MIN)
+        );
+
+        // Too far apart for a duration, so both dates are kept
+        let interval = ClosedInterval::with_dates(NaiveDate::MIN, NaiveDate::MAX);
+        assert!(interval.is_exact());
+        assert_eq!(interval.duration(), None);
+        assert_eq!(interval.end_opt(), Some(NaiveDate::MAX));
+    }
+
+    #[test]
+    fn test_parse_keeps_end() {
+        let interval: ClosedInterval = serde_json::from_str(r#""2022-01-31/2022-02-28""#).unwrap();
+        assert_eq!(
+            interval.end_opt(),
+            Some(NaiveDate::from_ymd_opt(2022, 2, 28).unwrap())
+        );
+    }
+}
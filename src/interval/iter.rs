@@ -1,5 +1,9 @@
+use std::iter::FusedIterator;
+
 use chrono::NaiveDate;
 
+use crate::grain::Grain;
+
 use super::{marker::End, ClosedInterval};
 
 #[derive(Debug, Clone)]
@@ -9,6 +13,11 @@ where
 {
     iter: T,
     until: NaiveDate,
+    /// End of the last interval produced
+    last: Option<NaiveDate>,
+    /// Bounds on the number of remaining intervals when known upfront
+    hint: Option<(usize, Option<usize>)>,
+    done: bool,
 }
 
 impl<T> UntilAfter<T>
@@ -16,7 +25,19 @@ where
     T: Iterator<Item = ClosedInterval>,
 {
     pub fn new(iter: T, until: NaiveDate) -> Self {
-        UntilAfter { iter, until }
+        UntilAfter {
+            iter,
+            until,
+            last: None,
+            hint: None,
+            done: false,
+        }
+    }
+
+    /// Provide bounds on the number of intervals that will be produced, used by `size_hint`
+    pub(crate) fn with_hint(mut self, hint: (usize, Option<usize>)) -> Self {
+        self.hint = Some(hint);
+        self
     }
 }
 
@@ -27,15 +48,83 @@ where
     type Item = ClosedInterval;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
         match self.iter.next() {
-            Some(item) => {
-                if item.end() >= self.until {
-                    None
-                } else {
-                    Some(item)
+            // Intervals which don't move forwards would never reach the end
+            Some(item) if item.end() < self.until && self.last.is_none_or(|l| l < item.end()) => {
+                self.last = Some(item.end());
+                if let Some((lower, upper)) = &mut self.hint {
+                    *lower = lower.saturating_sub(1);
+                    *upper = upper.map(|upper| upper.saturating_sub(1));
                 }
+                Some(item)
+            }
+            _ => {
+                self.done = true;
+                None
             }
-            None => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        match self.hint {
+            Some(hint) => hint,
+            None => (0, self.iter.size_hint().1),
         }
     }
 }
+
+impl<T> FusedIterator for UntilAfter<T> where T: Iterator<Item = ClosedInterval> {}
+
+/// Splits a span of time into intervals cut at calendar boundaries
+///
+/// The first and last intervals may be partial e.g. splitting 2022-01-15 to 2022-04-10 by month
+/// yields Jan 15–31, Feb, Mar and Apr 1–10. All intervals produced are exact, see
+/// [ClosedInterval::exact].
+#[derive(Debug, Clone)]
+pub struct ChunksAligned {
+    next: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    grain: Grain,
+}
+
+impl ChunksAligned {
+    /// Chunk from the start date up to and including the end date, or forever without an end
+    pub fn new(start: NaiveDate, end: Option<NaiveDate>, grain: Grain) -> Self {
+        ChunksAligned {
+            next: Some(start),
+            end,
+            grain,
+        }
+    }
+}
+
+impl Iterator for ChunksAligned {
+    type Item = ClosedInterval;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.next?;
+        if self.end.is_some_and(|end| start > end) {
+            self.next = None;
+            return None;
+        }
+
+        let boundary = self.grain.end_of(start);
+        let chunk_end = match self.end {
+            Some(end) if end < boundary => end,
+            _ => boundary,
+        };
+
+        self.next = chunk_end.succ_opt();
+        Some(ClosedInterval::exact(start, chunk_end))
+    }
+}
+
+impl FusedIterator for ChunksAligned {}
@@ -1,14 +1,25 @@
+//! Interval
+//!
+//! Used to coalesce both recurring and non-recurring intervals into one interface.
 use crate::RelativeDuration;
 
-///! Interval
-///!
-///! Used to coalesce both recurring and non-recurring intervals into one interface.
 use super::{
     bound::{self, Bound},
     marker::{End, Start},
 };
 use chrono::NaiveDate;
 
+/// Where an interval lies relative to a given date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntervalStatus {
+    /// The interval ended before the date
+    Past,
+    /// The date falls within the interval
+    Current,
+    /// The interval starts after the date
+    Upcoming,
+}
+
 pub trait IntervalLike {
     fn bound_start(&self) -> Bound<NaiveDate>;
     fn bound_end(&self) -> Bound<NaiveDate>;
@@ -41,6 +52,54 @@ pub trait IntervalLike {
         bound::within(date, &self.bound_start(), &self.bound_end())
     }
 
+    /// Determine whether two intervals share at least one date
+    ///
+    fn overlaps(&self, other: &dyn IntervalLike) -> bool {
+        bound::overlaps(
+            (&self.bound_start(), &self.bound_end()),
+            (&other.bound_start(), &other.bound_end()),
+        )
+    }
+
+    /// Status of the interval relative to today
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use calends::{Interval, IntervalLike};
+    /// use calends::interval::IntervalStatus;
+    ///
+    /// let interval = Interval::closed_exact(
+    ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2022, 3, 31).unwrap(),
+    /// );
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2022, 2, 1).unwrap();
+    /// assert_eq!(interval.status(today), IntervalStatus::Current);
+    /// assert_eq!(interval.days_until_start(today), Some(-31));
+    /// assert_eq!(interval.days_until_end(today), Some(58));
+    /// ```
+    fn status(&self, today: NaiveDate) -> IntervalStatus {
+        match (self.start_opt(), self.end_opt()) {
+            (Some(start), _) if today < start => IntervalStatus::Upcoming,
+            (_, Some(end)) if today > end => IntervalStatus::Past,
+            _ => IntervalStatus::Current,
+        }
+    }
+
+    /// Number of days from today until the start of the interval
+    ///
+    /// Negative when the interval has already started and [None] when the start is unbounded
+    fn days_until_start(&self, today: NaiveDate) -> Option<i64> {
+        self.start_opt().map(|start| (start - today).num_days())
+    }
+
+    /// Number of days from today until the end of the interval
+    ///
+    /// Negative when the interval has already ended and [None] when the end is unbounded
+    fn days_until_end(&self, today: NaiveDate) -> Option<i64> {
+        self.end_opt().map(|end| (end - today).num_days())
+    }
+
     /// ISO8601-2:2019 Formatting of intervals
     ///
     /// The standard allows for:
@@ -66,6 +125,40 @@ pub trait IntervalLike {
     }
 }
 
+/// Intervals can be boxed to allow for heterogeneous collections e.g. `Vec<Box<dyn IntervalLike>>`
+impl<T: IntervalLike + ?Sized> IntervalLike for Box<T> {
+    fn bound_start(&self) -> Bound<NaiveDate> {
+        (**self).bound_start()
+    }
+
+    fn bound_end(&self) -> Bound<NaiveDate> {
+        (**self).bound_end()
+    }
+
+    fn duration(&self) -> Option<RelativeDuration> {
+        (**self).duration()
+    }
+}
+
+impl<T: IntervalLike + ?Sized> IntervalLike for &T {
+    fn bound_start(&self) -> Bound<NaiveDate> {
+        (**self).bound_start()
+    }
+
+    fn bound_end(&self) -> Bound<NaiveDate> {
+        (**self).bound_end()
+    }
+
+    fn duration(&self) -> Option<RelativeDuration> {
+        (**self).duration()
+    }
+}
+
+impl<T: Start + ?Sized> Start for Box<T> {}
+impl<T: End + ?Sized> End for Box<T> {}
+impl<T: Start + ?Sized> Start for &T {}
+impl<T: End + ?Sized> End for &T {}
+
 pub trait IntervalLikeWithStart: IntervalLike + Start {}
 pub trait IntervalLikeWithEnd: IntervalLike + End {}
 pub trait IntervalLikeWithStartAndEnd: IntervalLike + Start + End {}
@@ -125,6 +218,49 @@ mod tests {
         assert_eq!(i1.end_opt(), NaiveDate::from_ymd_opt(2022, 12, 31));
     }
 
+    #[test]
+    fn test_status() {
+        let i = Int {
+            start: NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2022, 12, 31).unwrap(),
+        };
+
+        let before = NaiveDate::from_ymd_opt(2021, 12, 31).unwrap();
+        let after = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+
+        assert_eq!(i.status(before), IntervalStatus::Upcoming);
+        assert_eq!(i.status(i.start), IntervalStatus::Current);
+        assert_eq!(i.status(i.end), IntervalStatus::Current);
+        assert_eq!(i.status(after), IntervalStatus::Past);
+
+        assert_eq!(i.days_until_start(before), Some(1));
+        assert_eq!(i.days_until_end(after), Some(-1));
+    }
+
+    #[test]
+    fn test_boxed_collection() {
+        use crate::{interval::OpenStartInterval, CalendarUnit, Interval};
+
+        let date = NaiveDate::from_ymd_opt(2022, 5, 18).unwrap();
+        let intervals: Vec<Box<dyn IntervalLike>> = vec![
+            Box::new(Int {
+                start: NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+                end: NaiveDate::from_ymd_opt(2022, 12, 31).unwrap(),
+            }),
+            Box::new(OpenStartInterval::new(date)),
+            Box::new(CalendarUnit::Month(2022, 5).into_interval()),
+            Box::new(Interval::open_end(date)),
+        ];
+
+        assert!(intervals.iter().all(|i| i.within(date)));
+        assert!(intervals[0].overlaps(&intervals[1]));
+
+        fn starts<I: IntervalLike>(intervals: &[I]) -> Vec<Option<NaiveDate>> {
+            intervals.iter().map(|i| i.start_opt()).collect()
+        }
+        assert_eq!(starts(&intervals)[1], None);
+    }
+
     #[test]
     fn test_iso8601() {
         let i = Int {
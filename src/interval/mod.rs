@@ -5,9 +5,13 @@ pub mod iter;
 pub mod like;
 pub mod marker;
 pub mod open;
-mod parse;
+pub mod parse;
+pub mod periods;
+pub mod set;
 
 pub use base::{Interval, IntervalWithEnd, IntervalWithStart};
 pub use closed::ClosedInterval;
-pub use like::IntervalLike;
+pub use like::{IntervalLike, IntervalStatus};
 pub use open::{OpenEndInterval, OpenStartInterval};
+pub use periods::{covers, repair, validate_periods, CoverageReport, PeriodsError, RepairStrategy};
+pub use set::{find_slot, free_busy, IntervalSet, SlotConstraints};
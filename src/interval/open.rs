@@ -1,12 +1,15 @@
+use std::fmt::Display;
+
 use chrono::NaiveDate;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::IntervalLike;
+use crate::{IntervalLike, RelativeDuration};
 
 use super::{
     bound::Bound,
     marker,
     parse::{parse_open_end_interval, parse_open_start_interval},
+    ClosedInterval,
 };
 
 /// Indicating that the preceeding direction is unbounded, this is the time leading up to the
@@ -31,13 +34,26 @@ impl IntervalLike for OpenStartInterval {
         Bound::Included(self.end)
     }
 
-    fn duration(&self) -> Option<crate::RelativeDuration> {
+    fn duration(&self) -> Option<RelativeDuration> {
         None
     }
 }
 
 impl marker::End for OpenStartInterval {}
 
+/// Display an `OpenStartInterval` as "until 2022-01-01"
+///
+/// The alternate form (`{:#}`) displays the ISO8601-2:2019 representation
+impl Display for OpenStartInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            f.write_str(&self.iso8601())
+        } else {
+            write!(f, "until {}", self.end)
+        }
+    }
+}
+
 impl Serialize for OpenStartInterval {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -86,6 +102,11 @@ impl OpenEndInterval {
     pub fn new(start: NaiveDate) -> Self {
         Self { start }
     }
+
+    /// Iterate from the start of the interval in steps of the supplied duration
+    pub fn iterate_with(&self, step: RelativeDuration) -> ClosedInterval {
+        ClosedInterval::from_start(self.start, step)
+    }
 }
 
 impl IntervalLike for OpenEndInterval {
@@ -97,13 +118,26 @@ impl IntervalLike for OpenEndInterval {
         Bound::Unbounded
     }
 
-    fn duration(&self) -> Option<crate::RelativeDuration> {
+    fn duration(&self) -> Option<RelativeDuration> {
         None
     }
 }
 
 impl marker::Start for OpenEndInterval {}
 
+/// Display an `OpenEndInterval` as "from 2022-01-01"
+///
+/// The alternate form (`{:#}`) displays the ISO8601-2:2019 representation
+impl Display for OpenEndInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            f.write_str(&self.iso8601())
+        } else {
+            write!(f, "from {}", self.start)
+        }
+    }
+}
+
 impl Serialize for OpenEndInterval {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -2,54 +2,163 @@ use chrono::NaiveDate;
 use nom::{
     branch::alt,
     bytes::complete::tag,
+    combinator::map,
+    error::{Error, ErrorKind},
     sequence::{preceded, terminated},
-    IResult,
+    Err, IResult,
 };
 
-use crate::{duration::parse::parse_relative_duration, parser::take_n_digits};
+use crate::{
+    duration::parse::{
+        parse_relative_duration_with, parse_with_options, ParseError, ParseMode, ParseOptions,
+    },
+    parser::take_n_digits,
+    CalendarUnit,
+};
 
 use super::{ClosedInterval, OpenEndInterval, OpenStartInterval};
 
 pub fn parse_date(i: &[u8]) -> IResult<&[u8], NaiveDate> {
-    let (i, year) = take_n_digits(i, 4)?;
-    let (i, _) = tag(b"-")(i)?;
-    let (i, month) = take_n_digits(i, 2)?;
-    let (i, _) = tag(b"-")(i)?;
-    let (i, day) = take_n_digits(i, 2)?;
+    parse_date_with(i, ParseMode::Strict)
+}
+
+/// Parse a full date, [ParseMode::Lenient] also accepts the basic format `YYYYMMDD`
+pub fn parse_date_with(i: &[u8], mode: ParseMode) -> IResult<&[u8], NaiveDate> {
+    let extended = parse_ymd(i, b"-");
+    let (rest, (year, month, day)) = match mode {
+        ParseMode::Strict => extended?,
+        ParseMode::Lenient => extended.or_else(|_| parse_ymd(i, b""))?,
+    };
+
+    match NaiveDate::from_ymd_opt(year as i32, month, day) {
+        Some(date) => Ok((rest, date)),
+        None => Err(Err::Error(Error::new(i, ErrorKind::Verify))),
+    }
+}
+
+fn parse_ymd<'a>(i: &'a [u8], separator: &[u8]) -> IResult<&'a [u8], (u32, u32, u32)> {
+    let (rest, year) = take_n_digits(i, 4)?;
+    let (rest, _) = tag(separator)(rest)?;
+    let (rest, month) = take_n_digits(rest, 2)?;
+    let (rest, _) = tag(separator)(rest)?;
+    let (rest, day) = take_n_digits(rest, 2)?;
+
+    Ok((rest, (year, month, day)))
+}
+
+/// Parse a date of reduced precision, `2022` or `2022-04`, as well as a full date
+///
+/// The [CalendarUnit] is the span of dates the endpoint covers, so the start of an interval is the
+/// first date of the unit and the end is its last date.
+pub fn parse_reduced_date(i: &[u8]) -> IResult<&[u8], CalendarUnit> {
+    parse_reduced_date_with(i, ParseMode::Strict)
+}
 
-    Ok((
-        i,
-        NaiveDate::from_ymd_opt(year.try_into().unwrap(), month, day).unwrap(),
-    ))
+pub fn parse_reduced_date_with(i: &[u8], mode: ParseMode) -> IResult<&[u8], CalendarUnit> {
+    if let Ok((rest, date)) = parse_date_with(i, mode) {
+        return Ok((rest, CalendarUnit::Day(date)));
+    }
+
+    let (rest, year) = take_n_digits(i, 4)?;
+    let year = year as i32;
+    let month: IResult<&[u8], u32> = preceded(tag(b"-"), |i| take_n_digits(i, 2))(rest);
+
+    match month {
+        Ok((rest, month @ 1..=12)) => Ok((rest, CalendarUnit::Month(year, month as u8))),
+        Ok(_) => Err(Err::Error(Error::new(i, ErrorKind::Verify))),
+        Err(_) => Ok((rest, CalendarUnit::Year(year))),
+    }
+}
+
+fn parse_start(i: &[u8], mode: ParseMode) -> IResult<&[u8], NaiveDate> {
+    map(
+        |i| parse_reduced_date_with(i, mode),
+        |unit| unit.start_date(),
+    )(i)
 }
 
-fn parse_start_and_duration(i: &[u8]) -> IResult<&[u8], ClosedInterval> {
-    let (i, date) = parse_date(i)?;
+fn parse_end(i: &[u8], mode: ParseMode) -> IResult<&[u8], NaiveDate> {
+    map(|i| parse_reduced_date_with(i, mode), |unit| unit.end_date())(i)
+}
+
+fn parse_start_and_duration(i: &[u8], mode: ParseMode) -> IResult<&[u8], ClosedInterval> {
+    let (i, date) = parse_start(i, mode)?;
     let (i, _) = tag(b"/")(i)?;
-    let (i, duration) = parse_relative_duration(i)?;
+    let (i, duration) = parse_relative_duration_with(i, mode)?;
 
     Ok((i, ClosedInterval::from_start(date, duration)))
 }
 
-fn parse_start_and_end(i: &[u8]) -> IResult<&[u8], ClosedInterval> {
-    let (i, start) = parse_date(i)?;
+fn parse_start_and_end(i: &[u8], mode: ParseMode) -> IResult<&[u8], ClosedInterval> {
+    let (i, start) = parse_start(i, mode)?;
     let (i, _) = tag(b"/")(i)?;
-    let (i, end) = parse_date(i)?;
+    let (i, end) = parse_end(i, mode)?;
 
-    Ok((i, ClosedInterval::with_dates(start, end)))
+    Ok((i, ClosedInterval::exact(start, end)))
 }
 
+/// Parse a closed interval of a start and an end, or a start and a duration
+///
+/// Either date can have reduced precision, `2022-04/2022-06` is April 1 to June 30.
 pub fn parse_interval(i: &[u8]) -> IResult<&[u8], ClosedInterval> {
-    alt((parse_start_and_end, parse_start_and_duration))(i)
+    parse_interval_with(i, ParseMode::Strict)
+}
+
+/// Parse a closed interval, [ParseMode::Lenient] also accepts basic format dates and lowercase
+/// duration designators
+///
+/// ```
+/// use calends::{duration::parse::ParseMode, interval::parse::parse_interval_with, IntervalLike};
+/// use chrono::NaiveDate;
+///
+/// let (_, interval) = parse_interval_with(b"20220101/p1m", ParseMode::Lenient).unwrap();
+/// assert_eq!(interval.end_opt(), NaiveDate::from_ymd_opt(2022, 2, 1));
+/// ```
+pub fn parse_interval_with(i: &[u8], mode: ParseMode) -> IResult<&[u8], ClosedInterval> {
+    alt((
+        |i| parse_start_and_end(i, mode),
+        |i| parse_start_and_duration(i, mode),
+    ))(i)
+}
+
+/// Parse the whole of the input as a closed interval, checking any duration against the options
+///
+/// ```
+/// use calends::{duration::parse::{ParseError, ParseOptions}, interval::parse::parse_interval_with_options};
+///
+/// let options = ParseOptions::new().lenient();
+/// assert!(parse_interval_with_options("2022-01-01 / p3m", &options).is_ok());
+/// assert_eq!(
+///     parse_interval_with_options("2022-01-01/P1M", &options.max_len(12)),
+///     Err(ParseError::TooLong { len: 14, max_len: 12 })
+/// );
+/// ```
+pub fn parse_interval_with_options(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<ClosedInterval, ParseError> {
+    parse_with_options(input, options, parse_interval_with)
 }
 
 pub fn parse_open_start_interval(i: &[u8]) -> IResult<&[u8], OpenStartInterval> {
-    let (i, date) = preceded(tag("../"), parse_date)(i)?;
+    parse_open_start_interval_with(i, ParseMode::Strict)
+}
+
+pub fn parse_open_start_interval_with(
+    i: &[u8],
+    mode: ParseMode,
+) -> IResult<&[u8], OpenStartInterval> {
+    let (i, date) = preceded(tag("../"), |i| parse_end(i, mode))(i)?;
     Ok((i, OpenStartInterval::new(date)))
 }
 
 pub fn parse_open_end_interval(i: &[u8]) -> IResult<&[u8], OpenEndInterval> {
-    let (i, date) = terminated(parse_date, tag("../"))(i)?;
+    parse_open_end_interval_with(i, ParseMode::Strict)
+}
+
+pub fn parse_open_end_interval_with(i: &[u8], mode: ParseMode) -> IResult<&[u8], OpenEndInterval> {
+    // `2022-01-01/..` as in ISO8601-2:2019, `2022-01-01../` is still accepted
+    let (i, date) = terminated(|i| parse_start(i, mode), alt((tag("/.."), tag("../"))))(i)?;
     Ok((i, OpenEndInterval::new(date)))
 }
 
@@ -67,4 +176,51 @@ mod tests {
             NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()
         )
     }
+
+    #[test]
+    fn test_parse_reduced_precision() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        let (_, months) = parse_interval(b"2022-04/2022-06").unwrap();
+        assert_eq!(months.start_opt(), Some(date(2022, 4, 1)));
+        assert_eq!(months.end_opt(), Some(date(2022, 6, 30)));
+
+        let (_, years) = parse_interval(b"2021/2022-02-14").unwrap();
+        assert_eq!(years.start_opt(), Some(date(2021, 1, 1)));
+        assert_eq!(years.end_opt(), Some(date(2022, 2, 14)));
+
+        let (_, duration) = parse_interval(b"2022/P1Y").unwrap();
+        assert_eq!(duration.start_opt(), Some(date(2022, 1, 1)));
+        assert_eq!(duration.end_opt(), Some(date(2023, 1, 1)));
+
+        let (_, open) = parse_open_start_interval(b"../2022-02").unwrap();
+        assert_eq!(open.end_opt(), Some(date(2022, 2, 28)));
+    }
+
+    #[test]
+    fn test_parse_invalid_date() {
+        assert!(parse_date(b"2022-02-30").is_err());
+        assert!(parse_interval(b"2022-13/2023").is_err());
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        let (_, basic) = parse_interval_with(b"20220101/20220331", ParseMode::Lenient).unwrap();
+        assert_eq!(basic.start_opt(), Some(date(2022, 1, 1)));
+        assert_eq!(basic.end_opt(), Some(date(2022, 3, 31)));
+
+        let (_, mixed) = parse_interval_with(b"2022-01-01/p3m", ParseMode::Lenient).unwrap();
+        assert_eq!(mixed.end_opt(), Some(date(2022, 4, 1)));
+
+        let (_, open) = parse_open_end_interval_with(b"20220101../", ParseMode::Lenient).unwrap();
+        assert_eq!(open.start_opt(), Some(date(2022, 1, 1)));
+        let (_, open) = parse_open_end_interval_with(b"20220101/..", ParseMode::Lenient).unwrap();
+        assert_eq!(open.start_opt(), Some(date(2022, 1, 1)));
+
+        assert!(parse_interval(b"20220101/20220331").is_err());
+        assert!(parse_interval(b"2022-01-01/p3m").is_err());
+        assert!(parse_date_with(b"20220230", ParseMode::Lenient).is_err());
+    }
 }
@@ -0,0 +1,380 @@
+//! Validation of user supplied lists of periods
+//!
+//! Lists of periods such as fiscal calendars are expected to be ordered, non-overlapping and
+//! without gaps, i.e. every period starts on the day after the previous one ends.
+use chrono::NaiveDate;
+
+use super::{marker::End, marker::Start, ClosedInterval};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PeriodsError {
+    #[error("period {index} ends on {end} before it starts on {start}")]
+    Inverted {
+        index: usize,
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+
+    #[error("period {index} starts before the period preceding it")]
+    Unordered { index: usize },
+
+    #[error("period {first} overlaps period {second} from {start} to {end}")]
+    Overlap {
+        first: usize,
+        second: usize,
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+
+    #[error("there is a gap between period {first} and period {second} from {start} to {end}")]
+    Gap {
+        first: usize,
+        second: usize,
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+}
+
+/// How [repair] should fix a list of periods
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairStrategy {
+    /// Overlapping periods are trimmed to start the day after the preceding period ends
+    Trim,
+    /// Overlapping periods are merged into a single period
+    Merge,
+}
+
+/// Result of checking whether a list of periods covers a target window, see [covers]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CoverageReport {
+    /// Parts of the target not covered by any period
+    pub gaps: Vec<ClosedInterval>,
+    /// Parts of the target covered by more than one period
+    pub overlaps: Vec<ClosedInterval>,
+}
+
+impl CoverageReport {
+    /// Whether every day of the target is covered by at least one period
+    pub fn is_covered(&self) -> bool {
+        self.gaps.is_empty()
+    }
+
+    /// Whether every day of the target is covered by exactly one period
+    pub fn is_exact(&self) -> bool {
+        self.gaps.is_empty() && self.overlaps.is_empty()
+    }
+}
+
+/// Check how a list of periods covers a target window
+///
+/// Periods may be supplied in any order, only the parts of them falling within the target are
+/// considered. Adjacent or overlapping gaps and overlaps are reported as a single interval.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use calends::interval::{periods::covers, ClosedInterval};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// let periods = vec![
+///     ClosedInterval::exact(date(1, 1), date(1, 31)),
+///     ClosedInterval::exact(date(1, 20), date(2, 10)),
+/// ];
+///
+/// let report = covers(&periods, &ClosedInterval::exact(date(1, 1), date(2, 28)));
+///
+/// assert!(!report.is_covered());
+/// assert_eq!(report.gaps, vec![ClosedInterval::exact(date(2, 11), date(2, 28))]);
+/// assert_eq!(report.overlaps, vec![ClosedInterval::exact(date(1, 20), date(1, 31))]);
+/// ```
+pub fn covers(periods: &[ClosedInterval], target: &ClosedInterval) -> CoverageReport {
+    let (target_start, target_end) = (target.start(), target.end());
+
+    let mut spans: Vec<(NaiveDate, NaiveDate)> = periods
+        .iter()
+        .map(|p| {
+            let (start, end) = (p.start(), p.end());
+            (std::cmp::min(start, end), std::cmp::max(start, end))
+        })
+        .filter(|(start, end)| *start <= target_end && *end >= target_start)
+        .map(|(start, end)| {
+            (
+                std::cmp::max(start, target_start),
+                std::cmp::min(end, target_end),
+            )
+        })
+        .collect();
+    spans.sort();
+
+    fn push(list: &mut Vec<(NaiveDate, NaiveDate)>, start: NaiveDate, end: NaiveDate) {
+        match list.last_mut() {
+            Some(last) if last.1.succ_opt().is_some_and(|d| d >= start) => {
+                last.1 = std::cmp::max(last.1, end)
+            }
+            _ => list.push((start, end)),
+        }
+    }
+
+    let mut gaps = Vec::new();
+    let mut overlaps = Vec::new();
+    let mut covered_until: Option<NaiveDate> = None;
+
+    for (start, end) in spans {
+        match covered_until {
+            None if start > target_start => {
+                push(&mut gaps, target_start, start.pred_opt().unwrap())
+            }
+            Some(until) if start > until => {
+                let day_after = until.succ_opt().unwrap();
+                if start > day_after {
+                    push(&mut gaps, day_after, start.pred_opt().unwrap());
+                }
+            }
+            Some(until) => push(&mut overlaps, start, std::cmp::min(until, end)),
+            None => {}
+        }
+
+        covered_until = Some(covered_until.map_or(end, |until| std::cmp::max(until, end)));
+    }
+
+    match covered_until {
+        None => push(&mut gaps, target_start, target_end),
+        Some(until) if until < target_end => push(&mut gaps, until.succ_opt().unwrap(), target_end),
+        Some(_) => {}
+    }
+
+    let to_intervals = |list: Vec<(NaiveDate, NaiveDate)>| {
+        list.into_iter()
+            .map(|(start, end)| ClosedInterval::exact(start, end))
+            .collect()
+    };
+
+    CoverageReport {
+        gaps: to_intervals(gaps),
+        overlaps: to_intervals(overlaps),
+    }
+}
+
+/// Check that periods are ordered and that they neither overlap nor leave gaps
+///
+/// The first problem found is returned with the indices of the offending periods.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use calends::interval::{periods::{validate_periods, PeriodsError}, ClosedInterval};
+///
+/// let periods = vec![
+///     ClosedInterval::exact(
+///         NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+///         NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+///     ),
+///     ClosedInterval::exact(
+///         NaiveDate::from_ymd_opt(2022, 2, 3).unwrap(),
+///         NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(),
+///     ),
+/// ];
+///
+/// assert_eq!(
+///     validate_periods(&periods),
+///     Err(PeriodsError::Gap {
+///         first: 0,
+///         second: 1,
+///         start: NaiveDate::from_ymd_opt(2022, 2, 1).unwrap(),
+///         end: NaiveDate::from_ymd_opt(2022, 2, 2).unwrap(),
+///     })
+/// );
+/// ```
+pub fn validate_periods(periods: &[ClosedInterval]) -> Result<(), PeriodsError> {
+    for (index, period) in periods.iter().enumerate() {
+        if period.end() < period.start() {
+            return Err(PeriodsError::Inverted {
+                index,
+                start: period.start(),
+                end: period.end(),
+            });
+        }
+    }
+
+    for (first, pair) in periods.windows(2).enumerate() {
+        let second = first + 1;
+        let (prev, next) = (&pair[0], &pair[1]);
+
+        if next.start() < prev.start() {
+            return Err(PeriodsError::Unordered { index: second });
+        }
+
+        if next.start() <= prev.end() {
+            return Err(PeriodsError::Overlap {
+                first,
+                second,
+                start: next.start(),
+                end: std::cmp::min(prev.end(), next.end()),
+            });
+        }
+
+        let day_after = prev.end().succ_opt().unwrap();
+        if next.start() > day_after {
+            return Err(PeriodsError::Gap {
+                first,
+                second,
+                start: day_after,
+                end: next.start().pred_opt().unwrap(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Repair a list of periods so that it passes [validate_periods]
+///
+/// Periods are sorted by their start, inverted periods are flipped, overlaps are resolved using
+/// the [RepairStrategy] and gaps are closed by extending the preceding period.
+pub fn repair(periods: &[ClosedInterval], strategy: RepairStrategy) -> Vec<ClosedInterval> {
+    let mut spans: Vec<(NaiveDate, NaiveDate)> = periods
+        .iter()
+        .map(|p| {
+            let (start, end) = (p.start(), p.end());
+            (std::cmp::min(start, end), std::cmp::max(start, end))
+        })
+        .collect();
+    spans.sort();
+
+    let mut repaired: Vec<(NaiveDate, NaiveDate)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        let prev = match repaired.last_mut() {
+            Some(prev) => prev,
+            None => {
+                repaired.push((start, end));
+                continue;
+            }
+        };
+
+        let day_after = prev.1.succ_opt().unwrap();
+        if start > day_after {
+            prev.1 = start.pred_opt().unwrap();
+            repaired.push((start, end));
+        } else if start == day_after {
+            repaired.push((start, end));
+        } else {
+            match strategy {
+                RepairStrategy::Merge => prev.1 = std::cmp::max(prev.1, end),
+                RepairStrategy::Trim => {
+                    if end >= day_after {
+                        repaired.push((day_after, end));
+                    }
+                }
+            }
+        }
+    }
+
+    repaired
+        .into_iter()
+        .map(|(start, end)| ClosedInterval::exact(start, end))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period(start: (u32, u32), end: (u32, u32)) -> ClosedInterval {
+        ClosedInterval::exact(
+            NaiveDate::from_ymd_opt(2022, start.0, start.1).unwrap(),
+            NaiveDate::from_ymd_opt(2022, end.0, end.1).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_validate_contiguous() {
+        let periods = vec![period((1, 1), (1, 31)), period((2, 1), (2, 28))];
+        assert_eq!(validate_periods(&periods), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_errors() {
+        assert_eq!(
+            validate_periods(&[period((2, 1), (2, 28)), period((1, 1), (1, 31))]),
+            Err(PeriodsError::Unordered { index: 1 })
+        );
+
+        assert_eq!(
+            validate_periods(&[period((1, 1), (1, 31)), period((1, 30), (2, 28))]),
+            Err(PeriodsError::Overlap {
+                first: 0,
+                second: 1,
+                start: NaiveDate::from_ymd_opt(2022, 1, 30).unwrap(),
+                end: NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+            })
+        );
+
+        assert!(matches!(
+            validate_periods(&[period((1, 31), (1, 1))]),
+            Err(PeriodsError::Inverted { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_covers() {
+        let target = period((1, 1), (3, 31));
+
+        let report = covers(&[period((1, 1), (3, 31))], &target);
+        assert!(report.is_exact());
+
+        let report = covers(&[], &target);
+        assert_eq!(report.gaps, vec![target.clone()]);
+
+        let report = covers(
+            &[
+                period((3, 1), (4, 30)),
+                period((1, 10), (1, 31)),
+                period((1, 20), (2, 5)),
+                period((1, 25), (1, 28)),
+            ],
+            &target,
+        );
+        assert!(!report.is_covered());
+        assert_eq!(
+            report.gaps,
+            vec![period((1, 1), (1, 9)), period((2, 6), (2, 28))]
+        );
+        assert_eq!(report.overlaps, vec![period((1, 20), (1, 31))]);
+    }
+
+    #[test]
+    fn test_repair_trim() {
+        let periods = vec![
+            period((2, 1), (2, 28)),
+            period((1, 1), (1, 31)),
+            period((2, 20), (3, 31)),
+            period((4, 5), (4, 30)),
+        ];
+
+        let repaired = repair(&periods, RepairStrategy::Trim);
+        assert_eq!(validate_periods(&repaired), Ok(()));
+        assert_eq!(
+            repaired,
+            vec![
+                period((1, 1), (1, 31)),
+                period((2, 1), (2, 28)),
+                period((3, 1), (4, 4)),
+                period((4, 5), (4, 30)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repair_merge() {
+        let periods = vec![
+            period((1, 1), (1, 31)),
+            period((1, 15), (2, 15)),
+            period((2, 16), (2, 28)),
+        ];
+
+        let repaired = repair(&periods, RepairStrategy::Merge);
+        assert_eq!(validate_periods(&repaired), Ok(()));
+        assert_eq!(
+            repaired,
+            vec![period((1, 1), (2, 15)), period((2, 16), (2, 28))]
+        );
+    }
+}
@@ -0,0 +1,454 @@
+//! Sets of days made up of intervals, free/busy computation and slot search over them
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{grain::Grain, RelativeDuration};
+
+use super::{
+    bound::Bound,
+    marker::{End, Start},
+    ClosedInterval, Interval, IntervalLike,
+};
+
+/// A set of days stored as ordered intervals which neither overlap nor touch
+///
+/// Intervals inserted into the set are merged with any interval they overlap or are adjacent to,
+/// so the set always has a single canonical form.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use calends::interval::{ClosedInterval, IntervalSet};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+///
+/// let set: IntervalSet = vec![
+///     ClosedInterval::exact(date(1, 10), date(1, 20)),
+///     ClosedInterval::exact(date(1, 1), date(1, 9)),
+///     ClosedInterval::exact(date(2, 1), date(2, 5)),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(
+///     set.intervals(),
+///     vec![
+///         ClosedInterval::exact(date(1, 1), date(1, 20)),
+///         ClosedInterval::exact(date(2, 1), date(2, 5)),
+///     ]
+/// );
+/// assert!(set.contains(date(1, 15)));
+/// assert!(!set.contains(date(1, 25)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    spans: Vec<(NaiveDate, NaiveDate)>,
+}
+
+impl IntervalSet {
+    /// An empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the days of an interval to the set
+    pub fn insert(&mut self, interval: &ClosedInterval) {
+        let (start, end) = (interval.start(), interval.end());
+        self.insert_span(std::cmp::min(start, end), std::cmp::max(start, end));
+    }
+
+    fn insert_span(&mut self, start: NaiveDate, end: NaiveDate) {
+        // Spans ending before the day preceding the start are unaffected
+        let first = self
+            .spans
+            .partition_point(|(_, e)| e.succ_opt().is_some_and(|d| d < start));
+        let last = self
+            .spans
+            .partition_point(|(s, _)| s.pred_opt().is_none_or(|d| d <= end));
+
+        let touched = &self.spans[first..last];
+        let merged = match (touched.first(), touched.last()) {
+            (Some(first_span), Some(last_span)) => (
+                std::cmp::min(start, first_span.0),
+                std::cmp::max(end, last_span.1),
+            ),
+            _ => (start, end),
+        };
+
+        self.spans.splice(first..last, [merged]);
+    }
+
+    /// Intervals of the set in order
+    pub fn intervals(&self) -> Vec<ClosedInterval> {
+        self.iter().collect()
+    }
+
+    /// Iterate over the intervals of the set in order
+    pub fn iter(&self) -> impl Iterator<Item = ClosedInterval> + '_ {
+        self.spans
+            .iter()
+            .map(|(start, end)| ClosedInterval::exact(*start, *end))
+    }
+
+    /// Whether the set has no days
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Whether the date is in the set
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        let index = self.spans.partition_point(|(_, end)| *end < date);
+        self.spans
+            .get(index)
+            .is_some_and(|(start, _)| *start <= date)
+    }
+
+    /// First interval of the set sharing a day with the span
+    fn first_overlap(&self, start: NaiveDate, end: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let index = self.spans.partition_point(|(_, e)| *e < start);
+        self.spans.get(index).filter(|(s, _)| *s <= end).copied()
+    }
+
+    /// Days of the window which are not in the set
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use calends::interval::{ClosedInterval, IntervalSet};
+    ///
+    /// let date = |d| NaiveDate::from_ymd_opt(2022, 1, d).unwrap();
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert(&ClosedInterval::exact(date(5), date(10)));
+    ///
+    /// assert_eq!(
+    ///     set.complement(&ClosedInterval::exact(date(1), date(7))).intervals(),
+    ///     vec![ClosedInterval::exact(date(1), date(4))]
+    /// );
+    /// ```
+    pub fn complement(&self, window: &ClosedInterval) -> IntervalSet {
+        let (window_start, window_end) = (window.start(), window.end());
+        let mut gaps = IntervalSet::new();
+        let mut from = Some(window_start);
+
+        for (start, end) in &self.spans {
+            let gap_start = match from {
+                Some(gap_start) if gap_start <= window_end => gap_start,
+                _ => break,
+            };
+            if *start > gap_start {
+                let gap_end = std::cmp::min(start.pred_opt().unwrap(), window_end);
+                gaps.spans.push((gap_start, gap_end));
+            }
+            if *end >= gap_start {
+                from = end.succ_opt();
+            }
+        }
+
+        if let Some(gap_start) = from.filter(|d| *d <= window_end) {
+            gaps.spans.push((gap_start, window_end));
+        }
+        gaps
+    }
+
+    /// Only the days of the set falling on one of the weekdays
+    ///
+    /// ```
+    /// use chrono::{NaiveDate, Weekday};
+    /// use calends::interval::{ClosedInterval, IntervalSet};
+    ///
+    /// let date = |d| NaiveDate::from_ymd_opt(2022, 1, d).unwrap();
+    ///
+    /// // 2022-01-07 is a Friday
+    /// let mut set = IntervalSet::new();
+    /// set.insert(&ClosedInterval::exact(date(7), date(12)));
+    ///
+    /// let weekdays = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+    /// assert_eq!(
+    ///     set.on_weekdays(&weekdays).intervals(),
+    ///     vec![
+    ///         ClosedInterval::exact(date(7), date(7)),
+    ///         ClosedInterval::exact(date(10), date(12)),
+    ///     ]
+    /// );
+    /// ```
+    pub fn on_weekdays(&self, weekdays: &[Weekday]) -> IntervalSet {
+        let mut kept = IntervalSet::new();
+
+        for (start, end) in &self.spans {
+            let days = start.iter_days().take_while(|d| d <= end);
+            for day in days.filter(|d| weekdays.contains(&d.weekday())) {
+                match kept.spans.last_mut() {
+                    Some(last) if last.1.succ_opt() == Some(day) => last.1 = day,
+                    _ => kept.spans.push((day, day)),
+                }
+            }
+        }
+        kept
+    }
+}
+
+impl FromIterator<ClosedInterval> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = ClosedInterval>>(iter: T) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(&interval);
+        }
+        set
+    }
+}
+
+/// Days of the window not covered by any of the busy intervals
+///
+/// Busy intervals may be unbounded, they are clipped to the window. Use
+/// [IntervalSet::on_weekdays] on the result to only keep working days.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use calends::{interval::{free_busy, ClosedInterval}, Interval};
+///
+/// let date = |d| NaiveDate::from_ymd_opt(2022, 1, d).unwrap();
+/// let busy = vec![
+///     Interval::closed_exact(date(3), date(4)),
+///     Interval::closed_exact(date(6), date(6)),
+///     Interval::open_end(date(12)),
+/// ];
+///
+/// let free = free_busy(&ClosedInterval::exact(date(1), date(31)), &busy);
+/// assert_eq!(
+///     free.intervals(),
+///     vec![
+///         ClosedInterval::exact(date(1), date(2)),
+///         ClosedInterval::exact(date(5), date(5)),
+///         ClosedInterval::exact(date(7), date(11)),
+///     ]
+/// );
+/// ```
+pub fn free_busy<I: IntervalLike>(window: &ClosedInterval, busy: &[I]) -> IntervalSet {
+    let (window_start, window_end) = (window.start(), window.end());
+    let mut taken = IntervalSet::new();
+
+    for interval in busy {
+        let start = match interval.bound_start() {
+            Bound::Included(start) => std::cmp::max(start, window_start),
+            Bound::Unbounded => window_start,
+        };
+        let end = match interval.bound_end() {
+            Bound::Included(end) => std::cmp::min(end, window_end),
+            Bound::Unbounded => window_end,
+        };
+        if start <= end {
+            taken.insert_span(start, end);
+        }
+    }
+
+    taken.complement(window)
+}
+
+/// Restrictions on the slots found by [find_slot]
+#[derive(Debug, Clone, Default)]
+pub struct SlotConstraints {
+    weekdays: Vec<Weekday>,
+    grain: Option<Grain>,
+}
+
+impl SlotConstraints {
+    /// No restrictions, any free days can form a slot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only allow slots made up of days falling on the weekdays
+    pub fn on_weekdays(mut self, weekdays: &[Weekday]) -> Self {
+        self.weekdays = weekdays.to_vec();
+        self
+    }
+
+    /// Only allow slots starting on the first day of a period of the grain, e.g. a Monday for
+    /// [Grain::Week]
+    pub fn aligned_to(mut self, grain: Grain) -> Self {
+        self.grain = Some(grain);
+        self
+    }
+
+    fn allows(&self, date: NaiveDate) -> bool {
+        self.weekdays.is_empty() || self.weekdays.contains(&date.weekday())
+    }
+}
+
+/// Earliest slot of free days within an interval
+///
+/// A slot starting on a date covers the days up to the date the duration later (exclusive), so a
+/// duration of one month starting on Feb 1 covers February. A slot can't include any busy day or
+/// extend past the end of the interval. Intervals without a start have no earliest slot. When
+/// the interval has no end the search gives up four years after the last busy day.
+///
+/// ```
+/// # #[cfg(not(feature = "strict"))] {
+/// use chrono::{NaiveDate, Weekday};
+/// use calends::{
+///     grain::Grain,
+///     interval::{set::{find_slot, SlotConstraints}, ClosedInterval, IntervalSet},
+///     Interval, RelativeDuration,
+/// };
+///
+/// let date = |d| NaiveDate::from_ymd_opt(2022, 1, d).unwrap();
+/// let busy: IntervalSet = vec![ClosedInterval::exact(date(4), date(5))].into_iter().collect();
+/// let within = Interval::open_end(date(1));
+///
+/// let slot = find_slot(RelativeDuration::days(3), &within, &busy, &SlotConstraints::new());
+/// assert_eq!(slot, Some(ClosedInterval::exact(date(1), date(3))));
+///
+/// // A full working week, 2022-01-03 is a Monday
+/// let workweek = SlotConstraints::new()
+///     .aligned_to(Grain::Week)
+///     .on_weekdays(&[Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri]);
+/// let slot = find_slot(RelativeDuration::days(5), &within, &busy, &workweek);
+/// assert_eq!(slot, Some(ClosedInterval::exact(date(10), date(14))));
+/// # }
+/// ```
+pub fn find_slot(
+    duration: RelativeDuration,
+    within: &Interval,
+    busy: &IntervalSet,
+    constraints: &SlotConstraints,
+) -> Option<ClosedInterval> {
+    let mut candidate = within.start_opt()?;
+    let limit = match within.end_opt() {
+        Some(end) => end,
+        None => {
+            let last_busy = busy.spans.last().map_or(candidate, |(_, end)| *end);
+            RelativeDuration::months(48).checked_add_to(std::cmp::max(last_busy, candidate))?
+        }
+    };
+
+    loop {
+        if let Some(grain) = constraints.grain {
+            if grain.truncate(candidate) != candidate {
+                candidate = grain.end_of(candidate).succ_opt()?;
+            }
+        }
+
+        let end = duration.checked_add_to(candidate)?.pred_opt()?;
+        if end < candidate || end > limit {
+            return None;
+        }
+
+        let not_allowed = candidate
+            .iter_days()
+            .take_while(|d| *d <= end)
+            .find(|d| !constraints.allows(*d));
+        if let Some(day) = not_allowed {
+            candidate = day.succ_opt()?;
+            continue;
+        }
+
+        match busy.first_overlap(candidate, end) {
+            Some((_, busy_end)) => candidate = busy_end.succ_opt()?,
+            None => return Some(ClosedInterval::exact(candidate, end)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: u32, end: u32) -> ClosedInterval {
+        ClosedInterval::exact(
+            NaiveDate::from_ymd_opt(2022, 1, start).unwrap(),
+            NaiveDate::from_ymd_opt(2022, 1, end).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut set: IntervalSet = vec![span(1, 2), span(10, 12), span(20, 25)]
+            .into_iter()
+            .collect();
+
+        set.insert(&span(4, 4));
+        assert_eq!(
+            set.intervals(),
+            vec![span(1, 2), span(4, 4), span(10, 12), span(20, 25)]
+        );
+
+        // Touching the neighbours on both sides merges all three
+        set.insert(&span(3, 9));
+        assert_eq!(set.intervals(), vec![span(1, 12), span(20, 25)]);
+
+        set.insert(&span(15, 30));
+        assert_eq!(set.intervals(), vec![span(1, 12), span(15, 30)]);
+
+        set.insert(&span(5, 6));
+        assert_eq!(set.intervals(), vec![span(1, 12), span(15, 30)]);
+    }
+
+    #[test]
+    fn test_complement() {
+        let set: IntervalSet = vec![span(3, 5), span(10, 12)].into_iter().collect();
+
+        assert_eq!(
+            set.complement(&span(1, 31)).intervals(),
+            vec![span(1, 2), span(6, 9), span(13, 31)]
+        );
+        assert_eq!(set.complement(&span(4, 11)).intervals(), vec![span(6, 9)]);
+        assert!(set.complement(&span(10, 12)).is_empty());
+        assert_eq!(
+            IntervalSet::new().complement(&span(1, 31)).intervals(),
+            vec![span(1, 31)]
+        );
+    }
+    #[test]
+    fn test_find_slot() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+        let busy: IntervalSet = vec![span(3, 5), span(8, 8)].into_iter().collect();
+        let any = SlotConstraints::new();
+
+        let within = Interval::closed_exact(date(1, 1), date(1, 10));
+        assert_eq!(
+            find_slot(RelativeDuration::days(2), &within, &busy, &any),
+            Some(span(1, 2))
+        );
+        assert_eq!(
+            find_slot(RelativeDuration::days(3), &within, &busy, &any),
+            None
+        );
+
+        let within = Interval::open_end(date(1, 1));
+        assert_eq!(
+            find_slot(RelativeDuration::days(3), &within, &busy, &any),
+            Some(span(9, 11))
+        );
+        assert_eq!(
+            find_slot(
+                RelativeDuration::months(1),
+                &within,
+                &busy,
+                &SlotConstraints::new().aligned_to(Grain::Month)
+            ),
+            Some(ClosedInterval::exact(date(2, 1), date(2, 28)))
+        );
+
+        // Seven days can't avoid Sundays
+        let no_sundays = SlotConstraints::new().on_weekdays(&[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+        ]);
+        assert_eq!(
+            find_slot(RelativeDuration::weeks(1), &within, &busy, &no_sundays),
+            None
+        );
+        assert_eq!(
+            find_slot(RelativeDuration::days(3), &within, &busy, &no_sundays),
+            Some(span(10, 12))
+        );
+
+        let within = Interval::open_start(date(1, 31));
+        assert_eq!(
+            find_slot(RelativeDuration::days(1), &within, &busy, &any),
+            None
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Laws that the types in calends are expected to uphold
+//!
+//! Each law is a plain function returning whether it holds for the given inputs. They are used by
+//! the property tests of this crate and can be run by downstream crates against their own
+//! wrappers or [IntervalLike] implementations with the property testing library of their choice.
+//!
+//! ```
+//! # #[cfg(not(feature = "strict"))] {
+//! use calends::invariants;
+//! use calends::RelativeDuration;
+//!
+//! assert!(invariants::duration_iso8601_round_trip(
+//!     RelativeDuration::months(3).with_days(-2)
+//! ));
+//! # }
+//! ```
+use chrono::NaiveDate;
+
+use crate::{
+    duration::parse::parse_relative_duration,
+    interval::{parse::parse_interval, ClosedInterval, OpenEndInterval, OpenStartInterval},
+    CalendarUnit, IntervalLike, RelativeDuration,
+};
+
+/// Adding a duration and then its negation returns the original date
+///
+/// This only holds for durations that consist of months or of weeks and days but not both, and for
+/// months only when the day of the month exists in every month and is never the last day of a
+/// month (day 27 or before) as the last day of a month is pinned to the end of the month. Mixed
+/// durations are always applied largest unit first, so they cannot be undone by negation.
+pub fn add_sub_round_trip(date: NaiveDate, duration: RelativeDuration) -> bool {
+    duration
+        .checked_add_to(date)
+        .and_then(|shifted| (-duration).checked_add_to(shifted))
+        == Some(date)
+}
+
+/// Formatting a duration as ISO8601-2:2019 and parsing it again produces the same duration
+pub fn duration_iso8601_round_trip(duration: RelativeDuration) -> bool {
+    match parse_relative_duration(duration.iso8601().as_bytes()) {
+        Ok((leftover, parsed)) => leftover.is_empty() && parsed == duration,
+        Err(_) => false,
+    }
+}
+
+/// Formatting a closed interval as ISO8601-2:2019 and parsing it again keeps the start and end
+pub fn interval_iso8601_round_trip(interval: &ClosedInterval) -> bool {
+    match parse_interval(interval.iso8601().as_bytes()) {
+        Ok((leftover, parsed)) => {
+            leftover.is_empty()
+                && parsed.start_opt() == interval.start_opt()
+                && parsed.end_opt() == interval.end_opt()
+        }
+        Err(_) => false,
+    }
+}
+
+/// The interval of the successor of a unit begins the day after the unit ends
+pub fn unit_succ_contiguity(unit: CalendarUnit) -> bool {
+    let current = unit.into_interval();
+    let next = unit.succ().into_interval();
+
+    match (current.end_opt(), next.start_opt()) {
+        (Some(end), Some(start)) => end.succ_opt() == Some(start),
+        _ => false,
+    }
+}
+
+/// Iterating a closed interval produces intervals that share their boundary dates
+pub fn interval_iteration_contiguity(interval: ClosedInterval) -> bool {
+    let mut iter = interval;
+    match (iter.next(), iter.next()) {
+        (Some(first), Some(second)) => first.end_opt() == second.start_opt(),
+        _ => false,
+    }
+}
+
+/// A date is within an interval exactly when it falls between the bounds and exactly when the
+/// single day interval on that date overlaps it
+pub fn within_overlap_consistency<I: IntervalLike>(interval: &I, date: NaiveDate) -> bool {
+    let between = interval.start_opt().is_none_or(|start| start <= date)
+        && interval.end_opt().is_none_or(|end| date <= end);
+    let day = ClosedInterval::exact(date, date);
+
+    interval.within(date) == between && interval.overlaps(&day) == between
+}
+
+/// Open intervals contain every date on their unbounded side
+pub fn open_interval_unbounded(date: NaiveDate, days: u32) -> bool {
+    let before = date - chrono::Duration::days(days.into());
+    let after = date + chrono::Duration::days(days.into());
+
+    OpenStartInterval::new(date).within(before) && OpenEndInterval::new(date).within(after)
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::{Arbitrary, Gen, TestResult};
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    struct Date(NaiveDate);
+
+    impl Arbitrary for Date {
+        fn arbitrary(g: &mut Gen) -> Date {
+            let year = 1584 + (u32::arbitrary(g) % 1216) as i32;
+            let ordinal = 1 + u32::arbitrary(g) % 365;
+            Date(NaiveDate::from_yo_opt(year, ordinal).unwrap())
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct Duration(RelativeDuration);
+
+    impl Arbitrary for Duration {
+        fn arbitrary(g: &mut Gen) -> Duration {
+            let small = |g: &mut Gen| i32::arbitrary(g) % 1000;
+            Duration(RelativeDuration::try_from_mwd(small(g), small(g), small(g)).unwrap())
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct Unit(CalendarUnit);
+
+    impl Arbitrary for Unit {
+        fn arbitrary(g: &mut Gen) -> Unit {
+            let year = 1584 + (u32::arbitrary(g) % 1216) as i32;
+            let unit = match u8::arbitrary(g) % 4 {
+                0 => CalendarUnit::Year(year),
+                1 => CalendarUnit::Half(year, 1 + u8::arbitrary(g) % 2),
+                2 => CalendarUnit::Quarter(year, 1 + u8::arbitrary(g) % 4),
+                _ => CalendarUnit::Month(year, 1 + u8::arbitrary(g) % 12),
+            };
+            Unit(unit)
+        }
+    }
+
+    #[quickcheck]
+    fn test_add_sub_round_trip_months(d: Date, months: i16) -> TestResult {
+        use chrono::Datelike;
+
+        if d.0.day() > 27 {
+            return TestResult::discard();
+        }
+
+        let duration = RelativeDuration::months((months % 1000).into());
+        TestResult::from_bool(add_sub_round_trip(d.0, duration))
+    }
+
+    #[quickcheck]
+    fn test_add_sub_round_trip_days(d: Date, weeks: i16, days: i16) -> bool {
+        add_sub_round_trip(
+            d.0,
+            RelativeDuration::weeks((weeks % 1000).into()).with_days(days.into()),
+        )
+    }
+
+    #[quickcheck]
+    fn test_duration_iso8601_round_trip(duration: Duration) -> bool {
+        duration_iso8601_round_trip(duration.0)
+    }
+
+    #[quickcheck]
+    fn test_interval_iso8601_round_trip(start: Date, days: u16) -> bool {
+        let end = start.0 + chrono::Duration::days(days.into());
+        interval_iso8601_round_trip(&ClosedInterval::exact(start.0, end))
+            && interval_iso8601_round_trip(&ClosedInterval::with_dates(start.0, end))
+    }
+
+    #[quickcheck]
+    fn test_unit_succ_contiguity(unit: Unit) -> bool {
+        unit_succ_contiguity(unit.0)
+    }
+
+    #[quickcheck]
+    fn test_interval_iteration_contiguity(start: Date, duration: Duration) -> TestResult {
+        // Intervals which don't move end straight away
+        if duration.0.checked_add_to(start.0) == Some(start.0) {
+            return TestResult::discard();
+        }
+
+        TestResult::from_bool(interval_iteration_contiguity(ClosedInterval::from_start(
+            start.0, duration.0,
+        )))
+    }
+
+    #[quickcheck]
+    fn test_within_overlap_consistency(start: Date, days: u16, date: Date) -> bool {
+        let end = start.0 + chrono::Duration::days(days.into());
+
+        within_overlap_consistency(&ClosedInterval::exact(start.0, end), date.0)
+            && within_overlap_consistency(&OpenStartInterval::new(start.0), date.0)
+            && within_overlap_consistency(&OpenEndInterval::new(start.0), date.0)
+    }
+
+    #[quickcheck]
+    fn test_open_interval_unbounded(date: Date, days: u16) -> bool {
+        open_interval_unbounded(date.0, days.into())
+    }
+}
@@ -22,6 +22,7 @@
 //! date.
 //!
 //! ```
+//! # #[cfg(not(feature = "strict"))] {
 //! use calends::RelativeDuration;
 //! use chrono::NaiveDate;
 //!
@@ -33,20 +34,30 @@
 //!     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap() + rd,
 //!     NaiveDate::from_ymd_opt(2022, 1, 30).unwrap()
 //! );
+//! # }
 //! ```
 //!
 //! When applying durations to dates, it will apply in order if the largest units first e.g.
 //! months will come before weeks. Therefore when you construct durations such as 1 month, -1 day
 //! it will then move forward 1 month and then go backwards one day.
 //!
+//! ## Strict mode
+//!
+//! Enabling the `strict` feature removes the convenience APIs that panic when the result is out of
+//! range, such as `RelativeDuration::from_mwd`, `RelativeDuration::months` and the arithmetic
+//! operators on [RelativeDuration] and [chrono::NaiveDate]. Their checked equivalents such as
+//! [RelativeDuration::try_from_mwd], [RelativeDuration::try_months] and
+//! [RelativeDuration::checked_add_to] are always available.
+//!
 //! ## Serialization
 //!
 //! There are two ways to serialize a RelativeDuration:
 //! - The first one serializes it as an object.
 //! - The second way is an ISO8601-2:2019 compatible serializer. Because the format is not
-//! widely used yet we do not set it as the default (de)serializer.
+//!   widely used yet we do not set it as the default (de)serializer.
 //!
 //! ```
+//! # #[cfg(not(feature = "strict"))] {
 //! use calends::RelativeDuration;
 //! use calends::rd_iso8601;
 //!
@@ -67,6 +78,7 @@
 //!
 //! let parsed: S = serde_json::from_str(&rd_string).unwrap();
 //! assert_eq!(rd, parsed.rd)
+//! # }
 //! ```
 //!
 //! # Recurrence & Rules
@@ -101,6 +113,7 @@
 //! of the month.
 //!
 //! ```
+//! # #[cfg(not(feature = "strict"))] {
 //! use calends::{Interval, RelativeDuration};
 //! use chrono::NaiveDate;
 //!
@@ -108,6 +121,7 @@
 //! let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
 //!
 //! let mut interval = Interval::closed_from_start(start, duration);
+//! # }
 //! ```
 //!
 //! ## Serialization
@@ -115,9 +129,10 @@
 //! There are two ways to serialize a Interval:
 //! - The first one serializes it as an object.
 //! - The second way is an ISO8601-2:2019 compatible serializer. Because the format is not
-//! widely used yet we do not set it as the default (de)serializer.
+//!   widely used yet we do not set it as the default (de)serializer.
 //!
 //! ```
+//! # #[cfg(not(feature = "strict"))] {
 //! use chrono::NaiveDate;
 //! use calends::{Interval, RelativeDuration, IntervalLike};
 //! use calends::interval::marker::Start;
@@ -136,20 +151,45 @@
 //!
 //! let parsed: S = serde_json::from_str(&int_string).unwrap();
 //! assert_eq!(parsed.i.start_opt().unwrap(), int.start_opt().unwrap())
+//! # }
 //! ```
 
+pub mod calendar;
 pub mod duration;
+pub mod event;
+pub mod fiscal;
 pub mod grain;
+#[cfg(feature = "holidays")]
+pub mod holidays;
+#[cfg(feature = "ical")]
+pub mod ical;
 pub mod interval;
-mod parser;
+pub mod invariants;
+pub mod month_day;
+pub mod parser;
 pub mod recurrence;
+pub mod retail;
+pub mod schedule;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(any(feature = "diesel", feature = "sqlx"))]
+pub mod sql;
+#[cfg(feature = "time")]
+pub mod time_date;
 pub mod unit;
 pub mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod year_month;
 
 pub use crate::duration::serde::rd_iso8601;
-pub use crate::duration::RelativeDuration;
+pub use crate::duration::{NaiveDateExt, RelativeDuration};
+pub use crate::event::Event;
+pub use crate::fiscal::{FiscalCalendar, FiscalUnit};
 pub use crate::interval::{Interval, IntervalWithEnd, IntervalWithStart};
-pub use crate::recurrence::Rule;
-pub use crate::unit::CalendarUnit;
+pub use crate::month_day::MonthDay;
+pub use crate::recurrence::{AnchorPolicy, OccurencesExt, Rule, RuleSet};
+pub use crate::unit::{CalendarUnit, UnitKind};
 pub use crate::util::*;
+pub use crate::year_month::YearMonth;
 pub use crate::{interval::IntervalLike, recurrence::Recurrence};
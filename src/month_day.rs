@@ -0,0 +1,209 @@
+//! A day of the year without a year
+use std::{fmt::Display, str::FromStr};
+
+use chrono::NaiveDate;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{recurrence::AnchorPolicy, util::days_in_month};
+
+/// A month and day such as June 30, which recurs every year
+///
+/// February 29 is a valid month day, [MonthDay::resolve] decides where it falls in other years.
+/// The [Display] form is the ISO 8601 `--MM-DD`.
+///
+/// ```
+/// use calends::{month_day::LeapPolicy, MonthDay};
+/// use chrono::NaiveDate;
+///
+/// let leap_day = MonthDay::new(2, 29).unwrap();
+/// assert_eq!(leap_day.resolve(2023, LeapPolicy::Feb28), NaiveDate::from_ymd_opt(2023, 2, 28));
+/// assert_eq!(leap_day.to_string(), "--02-29");
+/// assert_eq!("--02-29".parse(), Ok(leap_day));
+/// assert!(MonthDay::new(4, 31).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MonthDay {
+    month: u8,
+    day: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MonthDayError {
+    #[error("month {0} is out of range, expected 1 to 12")]
+    MonthOutOfRange(u32),
+    #[error("day {1} is out of range for month {0}")]
+    DayOutOfRange(u32, u32),
+    #[error("expected a month and day such as --03-15, found `{0}`")]
+    InvalidFormat(String),
+}
+
+/// Where February 29 falls in years without it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LeapPolicy {
+    /// Move to February 28
+    #[default]
+    Feb28,
+    /// Move to March 1
+    Mar1,
+    /// The date doesn't occur
+    Skip,
+}
+
+impl From<LeapPolicy> for AnchorPolicy {
+    fn from(policy: LeapPolicy) -> Self {
+        match policy {
+            LeapPolicy::Feb28 => AnchorPolicy::ClampDay,
+            LeapPolicy::Mar1 => AnchorPolicy::RollForward,
+            LeapPolicy::Skip => AnchorPolicy::SkipMissing,
+        }
+    }
+}
+
+impl MonthDay {
+    /// A month day, failing when the day doesn't exist in the month even in a leap year
+    pub fn new(month: u32, day: u32) -> Result<MonthDay, MonthDayError> {
+        if !(1..=12).contains(&month) {
+            return Err(MonthDayError::MonthOutOfRange(month));
+        }
+        if NaiveDate::from_ymd_opt(2000, month, day).is_none() {
+            return Err(MonthDayError::DayOutOfRange(month, day));
+        }
+
+        Ok(MonthDay {
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+
+    /// Month from 1 to 12
+    pub fn month(&self) -> u32 {
+        self.month.into()
+    }
+
+    /// Day of the month from 1 to 31
+    pub fn day(&self) -> u32 {
+        self.day.into()
+    }
+
+    /// The date in the year, clamping February 29 to February 28 outside leap years
+    pub fn in_year(&self, year: i32) -> Option<NaiveDate> {
+        self.resolve(year, LeapPolicy::Feb28)
+    }
+
+    /// The date in the year, February 29 follows the policy outside leap years
+    ///
+    /// Returns [None] when the year is out of range or the policy skips the year.
+    pub fn resolve(&self, year: i32, policy: LeapPolicy) -> Option<NaiveDate> {
+        let month = self.month();
+        NaiveDate::from_ymd_opt(year, month, 1)?;
+
+        match NaiveDate::from_ymd_opt(year, month, self.day()) {
+            Some(date) => Some(date),
+            None => match policy {
+                LeapPolicy::Feb28 => {
+                    NaiveDate::from_ymd_opt(year, month, days_in_month(year, month))
+                }
+                LeapPolicy::Mar1 => NaiveDate::from_ymd_opt(year, month + 1, 1),
+                LeapPolicy::Skip => None,
+            },
+        }
+    }
+}
+
+impl Display for MonthDay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "--{:0>2}-{:0>2}", self.month, self.day)
+    }
+}
+
+/// Parse the ISO 8601 `--MM-DD` form of a month day
+impl FromStr for MonthDay {
+    type Err = MonthDayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || MonthDayError::InvalidFormat(s.to_string());
+
+        let (month, day) = s
+            .strip_prefix("--")
+            .and_then(|rest| rest.split_once('-'))
+            .ok_or_else(invalid)?;
+        if month.len() != 2 || day.len() != 2 {
+            return Err(invalid());
+        }
+        let number = |value: &str| match value.bytes().all(|b| b.is_ascii_digit()) {
+            true => value.parse::<u32>().map_err(|_| invalid()),
+            false => Err(invalid()),
+        };
+
+        MonthDay::new(number(month)?, number(day)?)
+    }
+}
+
+impl Serialize for MonthDay {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for MonthDay {
+    fn deserialize<D>(deserializer: D) -> Result<MonthDay, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let leap_day = MonthDay::new(2, 29).unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+
+        assert_eq!(leap_day.resolve(2024, LeapPolicy::Skip), date(2024, 2, 29));
+        assert_eq!(leap_day.resolve(2023, LeapPolicy::Feb28), date(2023, 2, 28));
+        assert_eq!(leap_day.resolve(2023, LeapPolicy::Mar1), date(2023, 3, 1));
+        assert_eq!(leap_day.resolve(2023, LeapPolicy::Skip), None);
+        assert_eq!(leap_day.resolve(i32::MAX, LeapPolicy::Feb28), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        for (month, day) in [(1, 1), (2, 29), (12, 31)] {
+            let month_day = MonthDay::new(month, day).unwrap();
+            assert_eq!(month_day.to_string().parse(), Ok(month_day));
+        }
+
+        assert_eq!(
+            "--13-01".parse::<MonthDay>(),
+            Err(MonthDayError::MonthOutOfRange(13))
+        );
+        assert_eq!(
+            "--02-30".parse::<MonthDay>(),
+            Err(MonthDayError::DayOutOfRange(2, 30))
+        );
+        assert_eq!(
+            "02-03".parse::<MonthDay>(),
+            Err(MonthDayError::InvalidFormat("02-03".to_string()))
+        );
+        assert!("--2-3".parse::<MonthDay>().is_err());
+        assert!("--+2-03".parse::<MonthDay>().is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let month_day = MonthDay::new(6, 30).unwrap();
+        let json = serde_json::to_string(&month_day).unwrap();
+
+        assert_eq!(json, r#""--06-30""#);
+        assert_eq!(serde_json::from_str::<MonthDay>(&json).unwrap(), month_day);
+        assert!(serde_json::from_str::<MonthDay>(r#""--06-31""#).is_err());
+    }
+}
@@ -1,37 +1,181 @@
+//! nom parsers over `&str` for embedding calends values in other grammars
+//!
+//! Each parser returns the remaining input and fails with an error rather than panicking, so they
+//! can be combined with other nom parsers. The functions taking a [ParseMode] return a parser.
+//!
+//! ```
+//! # #[cfg(not(feature = "strict"))] {
+//! use calends::{parser::relative_duration, RelativeDuration};
+//! use nom::{bytes::complete::tag, sequence::preceded};
+//!
+//! let (rest, duration) = preceded(tag("every "), relative_duration)("every P2W, on Monday").unwrap();
+//!
+//! assert_eq!(duration, RelativeDuration::weeks(2));
+//! assert_eq!(rest, ", on Monday");
+//! # }
+//! ```
+use chrono::NaiveDate;
 use nom::{
     bytes::complete::{take_while, take_while_m_n},
     character::{complete::char, is_digit},
     combinator::opt,
-    error::Error,
+    error::{Error, ErrorKind},
     Err, IResult,
 };
 
-pub fn take_signed_digits(i: &[u8]) -> IResult<&[u8], i32> {
-    let (i, negative) = opt(char('-'))(i)?;
-    let (i, digits) = take_while(is_digit)(i)?;
+pub use crate::duration::parse::ParseMode;
+use crate::{
+    duration::parse::parse_relative_duration_with,
+    interval::{
+        parse::{
+            parse_date_with, parse_interval_with, parse_open_end_interval_with,
+            parse_open_start_interval_with, parse_reduced_date_with,
+        },
+        ClosedInterval, OpenEndInterval, OpenStartInterval,
+    },
+    CalendarUnit, RelativeDuration,
+};
+
+pub(crate) fn take_signed_digits(i: &[u8]) -> IResult<&[u8], i32> {
+    let (rest, negative) = opt(char('-'))(i)?;
+    let (rest, digits) = take_while(is_digit)(rest)?;
 
     if digits.is_empty() {
-        return Err(Err::Error(Error::new(i, nom::error::ErrorKind::Eof)));
+        return Err(Err::Error(Error::new(rest, ErrorKind::Eof)));
     }
 
-    let s = std::str::from_utf8(digits).expect("Invalid data, expected UTF-8 string");
-    let res: i32 = s
-        .parse()
-        .expect("Invalid string, expected ASCII representation of a number");
+    let res = digits
+        .iter()
+        .try_fold(0i32, |acc, digit| {
+            acc.checked_mul(10)?.checked_add(i32::from(digit - b'0'))
+        })
+        .ok_or(Err::Error(Error::new(i, ErrorKind::TooLarge)))?;
 
     match negative {
-        Some(_) => Ok((i, -res)),
-        None => Ok((i, res)),
+        Some(_) => Ok((rest, -res)),
+        None => Ok((rest, res)),
+    }
+}
+
+pub(crate) fn take_n_digits(i: &[u8], n: usize) -> IResult<&[u8], u32> {
+    let (rest, digits) = take_while_m_n(n, n, is_digit)(i)?;
+
+    let res = digits
+        .iter()
+        .try_fold(0u32, |acc, digit| {
+            acc.checked_mul(10)?.checked_add(u32::from(digit - b'0'))
+        })
+        .ok_or(Err::Error(Error::new(i, ErrorKind::TooLarge)))?;
+
+    Ok((rest, res))
+}
+
+/// Run a parser over the bytes of the input, mapping the remaining input back onto the `&str`
+///
+/// The parsers only consume ASCII so the remainder always starts on a character boundary.
+fn on_str<'a, T>(
+    input: &'a str,
+    parser: impl FnOnce(&'a [u8]) -> IResult<&'a [u8], T>,
+) -> IResult<&'a str, T> {
+    let rest = |remaining: &[u8]| &input[input.len() - remaining.len()..];
+
+    match parser(input.as_bytes()) {
+        Ok((remaining, value)) => Ok((rest(remaining), value)),
+        Err(Err::Error(e)) => Err(Err::Error(Error::new(rest(e.input), e.code))),
+        Err(Err::Failure(e)) => Err(Err::Failure(Error::new(rest(e.input), e.code))),
+        Err(Err::Incomplete(needed)) => Err(Err::Incomplete(needed)),
     }
 }
 
-pub fn take_n_digits(i: &[u8], n: usize) -> IResult<&[u8], u32> {
-    let (i, digits) = take_while_m_n(n, n, is_digit)(i)?;
+/// Parse an ISO8601-2:2019 duration such as `P1M2D`
+pub fn relative_duration(input: &str) -> IResult<&str, RelativeDuration> {
+    relative_duration_with(ParseMode::Strict)(input)
+}
+
+pub fn relative_duration_with(mode: ParseMode) -> impl Fn(&str) -> IResult<&str, RelativeDuration> {
+    move |input| on_str(input, |i| parse_relative_duration_with(i, mode))
+}
+
+/// Parse a full date such as `2022-01-31`
+pub fn date(input: &str) -> IResult<&str, NaiveDate> {
+    date_with(ParseMode::Strict)(input)
+}
 
-    let s = std::str::from_utf8(digits).expect("Invalid data, expected UTF-8 string");
-    let res = s
-        .parse()
-        .expect("Invalid string, expected ASCII representation of a number");
+pub fn date_with(mode: ParseMode) -> impl Fn(&str) -> IResult<&str, NaiveDate> {
+    move |input| on_str(input, |i| parse_date_with(i, mode))
+}
+
+/// Parse a date of year, year-month or full precision as the [CalendarUnit] it covers
+pub fn reduced_date(input: &str) -> IResult<&str, CalendarUnit> {
+    reduced_date_with(ParseMode::Strict)(input)
+}
+
+pub fn reduced_date_with(mode: ParseMode) -> impl Fn(&str) -> IResult<&str, CalendarUnit> {
+    move |input| on_str(input, |i| parse_reduced_date_with(i, mode))
+}
 
-    Ok((i, res))
+/// Parse a closed interval such as `2022-01-01/P1M` or `2022-01/2022-03`
+pub fn closed_interval(input: &str) -> IResult<&str, ClosedInterval> {
+    closed_interval_with(ParseMode::Strict)(input)
+}
+
+pub fn closed_interval_with(mode: ParseMode) -> impl Fn(&str) -> IResult<&str, ClosedInterval> {
+    move |input| on_str(input, |i| parse_interval_with(i, mode))
+}
+
+/// Parse an interval without a start such as `../2022-01-01`
+pub fn open_start_interval(input: &str) -> IResult<&str, OpenStartInterval> {
+    open_start_interval_with(ParseMode::Strict)(input)
+}
+
+pub fn open_start_interval_with(
+    mode: ParseMode,
+) -> impl Fn(&str) -> IResult<&str, OpenStartInterval> {
+    move |input| on_str(input, |i| parse_open_start_interval_with(i, mode))
+}
+
+/// Parse an interval without an end such as `2022-01-01/..` or `2022-01-01../`
+pub fn open_end_interval(input: &str) -> IResult<&str, OpenEndInterval> {
+    open_end_interval_with(ParseMode::Strict)(input)
+}
+
+pub fn open_end_interval_with(mode: ParseMode) -> impl Fn(&str) -> IResult<&str, OpenEndInterval> {
+    move |input| on_str(input, |i| parse_open_end_interval_with(i, mode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IntervalLike;
+
+    #[test]
+    fn test_digits_do_not_panic() {
+        assert!(take_signed_digits(b"99999999999D").is_err());
+        assert_eq!(take_signed_digits(b"-12D"), Ok((&b"D"[..], -12)));
+        // Components that don't fit are left unparsed
+        let (rest, _) = relative_duration("P99999999999M").unwrap();
+        assert_eq!(rest, "99999999999M");
+        let (rest, _) = closed_interval("2022-01-01/P99999999999D").unwrap();
+        assert_eq!(rest, "99999999999D");
+        assert!(relative_duration("P2000000M").is_err());
+    }
+
+    #[test]
+    fn test_remaining_input() {
+        let (rest, interval) = closed_interval("2022-01/2022-03 and more").unwrap();
+        assert_eq!(rest, " and more");
+        assert_eq!(interval.end_opt(), NaiveDate::from_ymd_opt(2022, 3, 31));
+
+        let (rest, unit) = reduced_date("2022-04é").unwrap();
+        assert_eq!(rest, "é");
+        assert_eq!(unit, CalendarUnit::Month(2022, 4));
+
+        let (rest, _) = open_end_interval_with(ParseMode::Lenient)("20220101../;").unwrap();
+        assert_eq!(rest, ";");
+
+        assert_eq!(
+            date("2022-02-30"),
+            Err(Err::Error(Error::new("2022-02-30", ErrorKind::Verify)))
+        );
+    }
 }
@@ -0,0 +1,59 @@
+//! Moving occurences which fall on weekends or holidays
+use std::iter::FusedIterator;
+
+use chrono::NaiveDate;
+
+use crate::calendar::{AdjustRule, HolidayCalendar};
+
+/// A series with each occurence moved to a business day, see [OccurencesExt::adjusted]
+///
+/// [OccurencesExt::adjusted]: super::OccurencesExt::adjusted
+#[derive(Debug, Clone)]
+pub struct Adjusted<'a, I, C>
+where
+    I: Iterator<Item = NaiveDate>,
+    C: HolidayCalendar + ?Sized,
+{
+    iter: I,
+    rule: AdjustRule,
+    calendar: &'a C,
+}
+
+impl<'a, I, C> Adjusted<'a, I, C>
+where
+    I: Iterator<Item = NaiveDate>,
+    C: HolidayCalendar + ?Sized,
+{
+    pub fn new(iter: I, rule: AdjustRule, calendar: &'a C) -> Self {
+        Adjusted {
+            iter,
+            rule,
+            calendar,
+        }
+    }
+}
+
+impl<I, C> Iterator for Adjusted<'_, I, C>
+where
+    I: Iterator<Item = NaiveDate>,
+    C: HolidayCalendar + ?Sized,
+{
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .by_ref()
+            .find_map(|date| self.rule.adjust(date, self.calendar))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I, C> FusedIterator for Adjusted<'_, I, C>
+where
+    I: FusedIterator<Item = NaiveDate>,
+    C: HolidayCalendar + ?Sized,
+{
+}
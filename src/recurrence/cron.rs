@@ -0,0 +1,541 @@
+//! Standard 5-field cron expressions evaluated at the level of dates
+//!
+//! An expression such as `0 9 1,15 * *` is made up of the minute, hour, day of the month, month and
+//! day of the week. The minute and hour are checked but otherwise ignored, a date matches when its
+//! month, day of the month and day of the week match, however many times it would run on the day.
+//!
+//! As in cron, when both the day of the month and the day of the week are restricted a date matches
+//! either of them, e.g. `0 0 1 * MON` is the first of the month and every Monday.
+//!
+//! | Cron            | Rule                                              |
+//! |-----------------|---------------------------------------------------|
+//! | `0 0 * * *`     | [Rule::Offset] of a day with an offset of `0`     |
+//! | `0 0 * * 1,3`   | [Rule::Weekdays]                                  |
+//! | `0 0 1,15 * *`  | [Rule::MonthDays]                                 |
+//! | `0 0 29 2 *`    | [Rule::Annual] with [AnchorPolicy::SkipMissing]   |
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::duration::RelativeDuration;
+
+use super::{AnchorPolicy, Rule};
+
+/// The longest run of days without a match before a series ends, the gap between two February
+/// 29ths around a century year which isn't a leap year
+const MAX_GAP: u32 = 8 * 366;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CronError {
+    #[error("expected 5 fields, found {0}")]
+    FieldCount(usize),
+
+    #[error("invalid value {value:?} for the {field} field")]
+    InvalidField { field: &'static str, value: String },
+
+    #[error("{0} is not supported")]
+    Unsupported(String),
+}
+
+/// The values allowed by one field of a cron expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Field {
+    bits: u64,
+    /// The field was written as `*` or `*/step`
+    star: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FieldKind {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    names: &'static [&'static str],
+}
+
+const MINUTE: FieldKind = FieldKind {
+    name: "minute",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const HOUR: FieldKind = FieldKind {
+    name: "hour",
+    min: 0,
+    max: 23,
+    names: &[],
+};
+const DAY_OF_MONTH: FieldKind = FieldKind {
+    name: "day of month",
+    min: 1,
+    max: 31,
+    names: &[],
+};
+const MONTH: FieldKind = FieldKind {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: &[
+        "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+    ],
+};
+const DAY_OF_WEEK: FieldKind = FieldKind {
+    name: "day of week",
+    min: 0,
+    max: 7,
+    names: &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"],
+};
+
+impl FieldKind {
+    fn all(&self) -> Field {
+        Field {
+            bits: (self.min..=self.max).fold(0, |bits, n| bits | 1 << n),
+            star: true,
+        }
+    }
+
+    fn value(&self, s: &str) -> Option<u32> {
+        let n = match self
+            .names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(s))
+        {
+            Some(index) => index as u32 + self.min,
+            None => s.parse().ok()?,
+        };
+        (self.min..=self.max).contains(&n).then_some(n)
+    }
+
+    fn parse(&self, s: &str) -> Result<Field, CronError> {
+        let invalid = || CronError::InvalidField {
+            field: self.name,
+            value: s.to_string(),
+        };
+        let mut bits = 0u64;
+
+        for part in s.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => {
+                    let step = step.parse::<u32>().ok().filter(|s| *s > 0);
+                    (range, step.ok_or_else(invalid)?)
+                }
+                None => (part, 1),
+            };
+            let (start, end) = match range.split_once('-') {
+                _ if range == "*" => (self.min, self.max),
+                Some((start, end)) => (
+                    self.value(start).ok_or_else(invalid)?,
+                    self.value(end).ok_or_else(invalid)?,
+                ),
+                // `5/10` runs from 5 to the end of the range
+                None if step > 1 => (self.value(range).ok_or_else(invalid)?, self.max),
+                None => {
+                    let n = self.value(range).ok_or_else(invalid)?;
+                    (n, n)
+                }
+            };
+            if start > end {
+                return Err(invalid());
+            }
+
+            for n in (start..=end).step_by(step as usize) {
+                bits |= 1 << n;
+            }
+        }
+
+        let star = s.starts_with('*');
+        if star && s.contains(',') {
+            return Err(invalid());
+        }
+
+        Ok(Field { bits, star })
+    }
+
+    fn format(&self, field: &Field) -> String {
+        let values: Vec<u32> = (self.min..=self.max)
+            .filter(|n| field.contains(*n))
+            .collect();
+
+        // A star field holds every value or those a step apart from the minimum
+        if field.star {
+            return match values.as_slice() {
+                _ if *field == self.all() => "*".to_string(),
+                [first, second, ..] => format!("*/{}", second - first),
+                _ => format!("*/{}", self.max - self.min + 1),
+            };
+        }
+
+        // Collapse runs of consecutive values into ranges
+        let mut parts: Vec<String> = vec![];
+        let mut iter = values.iter().peekable();
+        while let Some(&start) = iter.next() {
+            let mut end = start;
+            while iter.peek() == Some(&&(end + 1)) {
+                end += 1;
+                iter.next();
+            }
+            parts.push(match end > start {
+                true => format!("{}-{}", start, end),
+                false => start.to_string(),
+            });
+        }
+
+        parts.join(",")
+    }
+}
+
+impl Field {
+    fn contains(&self, n: u32) -> bool {
+        self.bits & (1 << n) != 0
+    }
+
+    fn values(&self, kind: &FieldKind) -> Vec<u32> {
+        (kind.min..=kind.max)
+            .filter(|n| self.contains(*n))
+            .collect()
+    }
+}
+
+/// A cron expression such as `30 6 * * MON-FRI`
+///
+/// ```
+/// use calends::recurrence::cron::Cron;
+/// use chrono::NaiveDate;
+///
+/// let cron: Cron = "0 9 1,15 * *".parse().unwrap();
+/// let dates: Vec<NaiveDate> = cron
+///     .with_start(NaiveDate::from_ymd_opt(2022, 1, 10).unwrap())
+///     .take(3)
+///     .collect();
+///
+/// assert_eq!(
+///     dates,
+///     vec![
+///         NaiveDate::from_ymd_opt(2022, 1, 15).unwrap(),
+///         NaiveDate::from_ymd_opt(2022, 2, 1).unwrap(),
+///         NaiveDate::from_ymd_opt(2022, 2, 15).unwrap(),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    minute: Field,
+    hour: Field,
+    day_of_month: Field,
+    month: Field,
+    day_of_week: Field,
+}
+
+impl Cron {
+    /// Whether the expression runs on the date
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        if !self.month.contains(date.month()) {
+            return false;
+        }
+
+        let day_of_month = self.day_of_month.contains(date.day());
+        let weekday = date.weekday().num_days_from_sunday();
+        // Sunday is both 0 and 7
+        let day_of_week =
+            self.day_of_week.contains(weekday) || (weekday == 0 && self.day_of_week.contains(7));
+
+        match (self.day_of_month.star, self.day_of_week.star) {
+            (false, false) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+
+    /// Iterate the dates the expression runs on from a start date (inclusive)
+    ///
+    /// The series ends when there is no date for 8 years, such as `0 0 30 2 *`.
+    pub fn with_start(&self, start: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+        let cron = self.clone();
+        let mut next = Some(start);
+
+        std::iter::from_fn(move || {
+            for _ in 0..=MAX_GAP {
+                let date = next?;
+                next = date.succ_opt();
+
+                if cron.matches(date) {
+                    return Some(date);
+                }
+            }
+
+            next = None;
+            None
+        })
+    }
+
+    /// The equivalent [Rule], for expressions which have one
+    ///
+    /// The series of a rule starts from the start given to [super::Recurrence], the days of a
+    /// [Rule::Weekdays] or [Rule::MonthDays] before the start are skipped as in cron.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{recurrence::cron::Cron, RelativeDuration, Rule};
+    /// use chrono::Weekday;
+    ///
+    /// let cron: Cron = "0 9 * * MON,FRI".parse().unwrap();
+    /// assert_eq!(
+    ///     cron.to_rule().unwrap(),
+    ///     Rule::Weekdays(RelativeDuration::weeks(1), vec![Weekday::Mon, Weekday::Fri])
+    /// );
+    /// # }
+    /// ```
+    pub fn to_rule(&self) -> Result<Rule, CronError> {
+        let every_month = self.month.star && self.month.bits == MONTH.all().bits;
+        let every_day = self.day_of_month.star && self.day_of_month.bits == DAY_OF_MONTH.all().bits;
+        let every_weekday =
+            self.day_of_week.star && self.day_of_week.bits == DAY_OF_WEEK.all().bits;
+
+        let days = self.day_of_month.values(&DAY_OF_MONTH);
+        let months = self.month.values(&MONTH);
+
+        let rule = match (every_month, every_day, every_weekday) {
+            (true, true, true) => Rule::Offset(RelativeDuration::days(1), 0),
+            (true, true, false) => {
+                let mut weekdays: Vec<Weekday> = self
+                    .day_of_week
+                    .values(&DAY_OF_WEEK)
+                    .into_iter()
+                    .map(|n| weekday(n % 7))
+                    .collect();
+                weekdays.sort_by_key(|d| d.num_days_from_monday());
+                weekdays.dedup();
+                Rule::Weekdays(RelativeDuration::weeks(1), weekdays)
+            }
+            (true, false, true) => Rule::MonthDays(
+                RelativeDuration::months(1),
+                days.into_iter().map(|d| d as i32).collect(),
+            ),
+            (false, false, true) if months.len() == 1 && days.len() == 1 => {
+                Rule::Annual(months[0], days[0], AnchorPolicy::SkipMissing)
+            }
+            _ => return Err(CronError::Unsupported(self.to_string())),
+        };
+
+        Ok(rule)
+    }
+
+    /// A cron expression running at midnight on the dates of the rule, for rules which have one
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{recurrence::cron::Cron, RelativeDuration, Rule};
+    ///
+    /// let rule = Rule::MonthDays(RelativeDuration::months(1), vec![1, 15]);
+    /// assert_eq!(Cron::from_rule(&rule).unwrap().to_string(), "0 0 1,15 * *");
+    /// assert!(Cron::from_rule(&Rule::Offset(RelativeDuration::days(2), 0)).is_err());
+    /// # }
+    /// ```
+    pub fn from_rule(rule: &Rule) -> Result<Cron, CronError> {
+        let unsupported = |reason: &str| Err(CronError::Unsupported(reason.to_string()));
+        let values = |values: &[u32]| Field {
+            bits: values.iter().fold(0, |bits, n| bits | 1 << n),
+            star: false,
+        };
+        let mut cron = Cron {
+            minute: values(&[0]),
+            hour: values(&[0]),
+            day_of_month: DAY_OF_MONTH.all(),
+            month: MONTH.all(),
+            day_of_week: DAY_OF_WEEK.all(),
+        };
+
+        match rule {
+            Rule::Offset(duration, 0) if *duration == RelativeDuration::days(1) => {}
+            Rule::Weekdays(duration, weekdays)
+                if *duration == RelativeDuration::weeks(1) && !weekdays.is_empty() =>
+            {
+                let days: Vec<u32> = weekdays.iter().map(|d| d.num_days_from_sunday()).collect();
+                cron.day_of_week = values(&days);
+            }
+            Rule::MonthDays(duration, days)
+                if *duration == RelativeDuration::months(1) && !days.is_empty() =>
+            {
+                let days = days
+                    .iter()
+                    .map(|d| u32::try_from(*d).ok().filter(|d| (1..=31).contains(d)))
+                    .collect::<Option<Vec<u32>>>();
+                match days {
+                    Some(days) => cron.day_of_month = values(&days),
+                    None => return unsupported("days counted from the end of the month"),
+                }
+            }
+            Rule::Annual(month, day, AnchorPolicy::SkipMissing)
+                if (1..=12).contains(month) && (1..=31).contains(day) =>
+            {
+                cron.month = values(&[*month]);
+                cron.day_of_month = values(&[*day]);
+            }
+            _ => return unsupported("a rule repeating other than every day, week, month or year"),
+        }
+
+        Ok(cron)
+    }
+}
+
+fn weekday(days_from_sunday: u32) -> Weekday {
+    match days_from_sunday {
+        0 => Weekday::Sun,
+        1 => Weekday::Mon,
+        2 => Weekday::Tue,
+        3 => Weekday::Wed,
+        4 => Weekday::Thu,
+        5 => Weekday::Fri,
+        _ => Weekday::Sat,
+    }
+}
+
+/// Parse a 5-field cron expression, or one of `@yearly`, `@annually`, `@monthly`, `@weekly`,
+/// `@daily` and `@midnight`
+impl FromStr for Cron {
+    type Err = CronError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = match s.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            macro_ if macro_.starts_with('@') => {
+                return Err(CronError::Unsupported(macro_.to_string()))
+            }
+            expression => expression,
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            return Err(CronError::FieldCount(fields.len()));
+        };
+
+        Ok(Cron {
+            minute: MINUTE.parse(minute)?,
+            hour: HOUR.parse(hour)?,
+            day_of_month: DAY_OF_MONTH.parse(day_of_month)?,
+            month: MONTH.parse(month)?,
+            day_of_week: DAY_OF_WEEK.parse(day_of_week)?,
+        })
+    }
+}
+
+impl Display for Cron {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}",
+            MINUTE.format(&self.minute),
+            HOUR.format(&self.hour),
+            DAY_OF_MONTH.format(&self.day_of_month),
+            MONTH.format(&self.month),
+            DAY_OF_WEEK.format(&self.day_of_week)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Recurrence;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn dates(cron: &str, start: NaiveDate, n: usize) -> Vec<NaiveDate> {
+        cron.parse::<Cron>()
+            .unwrap()
+            .with_start(start)
+            .take(n)
+            .collect()
+    }
+
+    #[test]
+    fn test_matches() {
+        assert_eq!(
+            dates("30 6 * * MON-FRI", date(2022, 1, 7), 3),
+            vec![date(2022, 1, 7), date(2022, 1, 10), date(2022, 1, 11)]
+        );
+        // Either the day of the month or the day of the week
+        assert_eq!(
+            dates("0 0 1 * 7", date(2022, 1, 25), 3),
+            vec![date(2022, 1, 30), date(2022, 2, 1), date(2022, 2, 6)]
+        );
+        // Both when one of them is a star
+        assert_eq!(
+            dates("0 0 */10 * 2", date(2022, 1, 1), 2),
+            vec![date(2022, 1, 11), date(2022, 2, 1)]
+        );
+        assert_eq!(
+            dates("0 0 29 feb *", date(2022, 1, 1), 2),
+            vec![date(2024, 2, 29), date(2028, 2, 29)]
+        );
+        assert_eq!(dates("0 0 30 2 *", date(2022, 1, 1), 1), vec![]);
+        assert_eq!(
+            dates("@yearly", date(2022, 6, 1), 1),
+            vec![date(2023, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("0 0 * *".parse::<Cron>(), Err(CronError::FieldCount(4)));
+        assert_eq!(
+            "0 0 32 * *".parse::<Cron>(),
+            Err(CronError::InvalidField {
+                field: "day of month",
+                value: "32".to_string()
+            })
+        );
+        assert!("0 0 5-1 * *".parse::<Cron>().is_err());
+        assert!("0 0 */0 * *".parse::<Cron>().is_err());
+        assert!("0 0 * * FOO".parse::<Cron>().is_err());
+        assert_eq!(
+            "@reboot".parse::<Cron>(),
+            Err(CronError::Unsupported("@reboot".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        for s in [
+            "30 6 * * 1-5",
+            "0 0 */10 * 2",
+            "0 */4 1,15 1-3,12 *",
+            "0 0 1 */12 *",
+            "*/45 0 * * *",
+        ] {
+            assert_eq!(s.parse::<Cron>().unwrap().to_string(), s);
+        }
+        assert_eq!(
+            "0 0 * JAN-MAR SUN,SAT".parse::<Cron>().unwrap().to_string(),
+            "0 0 * 1-3 0,6"
+        );
+    }
+
+    #[test]
+    fn test_rules() {
+        let start = date(2022, 1, 10);
+
+        for s in ["0 0 * * *", "0 0 * * 0,3", "0 0 1,15 * *", "0 0 29 2 *"] {
+            let cron: Cron = s.parse().unwrap();
+            let rule = cron.to_rule().unwrap();
+
+            let expected: Vec<NaiveDate> = cron.with_start(start).take(5).collect();
+            let actual: Vec<NaiveDate> = Recurrence::with_start(rule.clone(), start)
+                .skip_while(|d| *d < start)
+                .take(5)
+                .collect();
+            assert_eq!(actual, expected, "{s}");
+            assert_eq!(Cron::from_rule(&rule).unwrap(), cron, "{s}");
+        }
+
+        assert!("0 0 1 * 1".parse::<Cron>().unwrap().to_rule().is_err());
+        assert!("0 0 1 1,7 *".parse::<Cron>().unwrap().to_rule().is_err());
+        assert!(Cron::from_rule(&Rule::MonthDays(RelativeDuration::months(1), vec![-1])).is_err());
+        // Offsets count from the start of the recurrence rather than the month
+        assert!(Cron::from_rule(&Rule::Offset(RelativeDuration::months(1), 4)).is_err());
+    }
+}
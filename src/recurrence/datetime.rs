@@ -0,0 +1,181 @@
+//! Recurrences at a time of day, optionally in a time zone
+use std::iter::FusedIterator;
+
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, NaiveTime, Offset, TimeZone};
+
+use super::Recurrence;
+
+/// A series of dates at a time of day, see [Recurrence::at]
+#[derive(Debug, Clone)]
+pub struct DateTimeRecurrence {
+    recur: Recurrence,
+    time: NaiveTime,
+}
+
+impl DateTimeRecurrence {
+    pub fn new(recur: Recurrence, time: NaiveTime) -> Self {
+        DateTimeRecurrence { recur, time }
+    }
+
+    /// Time of day of every occurence
+    pub fn time(&self) -> NaiveTime {
+        self.time
+    }
+
+    /// Resolve each occurence to a local time in the time zone
+    ///
+    /// Times which occur twice as clocks go back use the first of the two. Times which are
+    /// skipped as clocks go forward are moved forward by the length of the gap, like RFC 5545.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::{NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let mut recur = Recurrence::with_start(Rule::weekly_on(&[Weekday::Tue]), start)
+    ///     .at(NaiveTime::from_hms_opt(9, 30, 0).unwrap())
+    ///     .in_timezone(Utc);
+    ///
+    /// assert_eq!(
+    ///     recur.next(),
+    ///     Some(Utc.with_ymd_and_hms(2022, 1, 4, 9, 30, 0).unwrap())
+    /// );
+    /// ```
+    pub fn in_timezone<Tz: TimeZone>(self, timezone: Tz) -> ZonedRecurrence<Tz> {
+        ZonedRecurrence {
+            recur: self,
+            timezone,
+        }
+    }
+}
+
+impl Iterator for DateTimeRecurrence {
+    type Item = NaiveDateTime;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.recur.next().map(|date| date.and_time(self.time))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.recur.size_hint()
+    }
+}
+
+impl FusedIterator for DateTimeRecurrence {}
+
+/// A series of local times in a time zone, see [DateTimeRecurrence::in_timezone]
+#[derive(Debug, Clone)]
+pub struct ZonedRecurrence<Tz: TimeZone> {
+    recur: DateTimeRecurrence,
+    timezone: Tz,
+}
+
+impl<Tz: TimeZone> Iterator for ZonedRecurrence<Tz> {
+    type Item = DateTime<Tz>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let local = self.recur.next()?;
+            if let Some(found) = resolve_local(&self.timezone, local) {
+                return Some(found);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.recur.size_hint().1)
+    }
+}
+
+impl<Tz: TimeZone> FusedIterator for ZonedRecurrence<Tz> {}
+
+/// Local time in the time zone, moving times in a gap forward by the length of the gap
+fn resolve_local<Tz: TimeZone>(timezone: &Tz, local: NaiveDateTime) -> Option<DateTime<Tz>> {
+    match timezone.from_local_datetime(&local) {
+        LocalResult::Single(found) => Some(found),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest),
+        LocalResult::None => {
+            // Interpret the time with the offset in effect before the gap
+            let before = local.checked_sub_signed(Duration::days(1))?;
+            let offset = timezone
+                .offset_from_local_datetime(&before)
+                .earliest()?
+                .fix();
+            let utc =
+                local.checked_sub_signed(Duration::seconds(offset.local_minus_utc().into()))?;
+
+            Some(timezone.from_utc_datetime(&utc))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, Weekday};
+    use chrono_tz::Europe::Berlin;
+
+    use super::*;
+    use crate::Rule;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_across_daylight_saving() {
+        // Clocks go forward on 2022-03-27 and back on 2022-10-30, both Sundays
+        let start = NaiveDate::from_ymd_opt(2022, 3, 20).unwrap();
+        let weekly = Recurrence::with_start(Rule::weekly_on(&[Weekday::Sun]), start);
+
+        let offsets: Vec<String> = weekly
+            .clone()
+            .at(time(9, 30))
+            .in_timezone(Berlin)
+            .take(2)
+            .map(|d| d.to_rfc3339())
+            .collect();
+        assert_eq!(
+            offsets,
+            vec!["2022-03-20T09:30:00+01:00", "2022-03-27T09:30:00+02:00"]
+        );
+
+        let gap: Vec<String> = weekly
+            .clone()
+            .at(time(2, 30))
+            .in_timezone(Berlin)
+            .take(2)
+            .map(|d| d.to_rfc3339())
+            .collect();
+        assert_eq!(
+            gap,
+            vec!["2022-03-20T02:30:00+01:00", "2022-03-27T03:30:00+02:00"]
+        );
+
+        let start = NaiveDate::from_ymd_opt(2022, 10, 30).unwrap();
+        let ambiguous = Recurrence::with_start(Rule::daily(), start)
+            .at(time(2, 30))
+            .in_timezone(Berlin)
+            .next()
+            .unwrap();
+        assert_eq!(ambiguous.to_rfc3339(), "2022-10-30T02:30:00+02:00");
+    }
+
+    #[test]
+    fn test_naive() {
+        let start = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+        let dates: Vec<NaiveDateTime> = Recurrence::with_start(Rule::monthly(), start)
+            .at(time(17, 0))
+            .take(2)
+            .collect();
+
+        assert_eq!(
+            dates,
+            vec![
+                start.and_time(time(17, 0)),
+                NaiveDate::from_ymd_opt(2022, 2, 28)
+                    .unwrap()
+                    .and_time(time(17, 0))
+            ]
+        );
+    }
+}
@@ -0,0 +1,394 @@
+//! Human readable descriptions of rules and recurrences
+//!
+//! [Rule::describe] gives text such as "Every 2 weeks on Friday", the words come from a [Locale]
+//! so descriptions in other languages implement the trait, [English] is the default.
+use chrono::{NaiveDate, Weekday};
+
+use crate::{duration::RelativeDuration, recurrence::until::Until};
+
+use super::{Recurrence, Rule};
+
+/// The words and phrases a description is built from
+pub trait Locale {
+    /// How often the rule repeats, "Every 2 weeks"
+    fn every(&self, duration: &RelativeDuration) -> String;
+
+    /// When the rule occurs within each repetition, "Every month" and "the 15th"
+    fn on(&self, every: String, when: String) -> String;
+
+    fn weekday(&self, weekday: Weekday) -> String;
+
+    /// Day of the month where `-1` is the last day, "the 15th"
+    fn day(&self, day: i32) -> String;
+
+    /// Day of the year where `-1` is the last day, "the 100th day"
+    fn day_of_year(&self, day: i32) -> String;
+
+    /// Occurence of a weekday where `-1` is the last one, "the first Friday"
+    fn nth_weekday(&self, n: i32, weekday: Weekday) -> String;
+
+    /// "March 15"
+    fn month_day(&self, month: u32, day: u32) -> String;
+
+    /// Weekday of an ISO week, "Monday of week 26"
+    fn week_weekday(&self, week: u32, weekday: Weekday) -> String;
+
+    /// The day of the month the series started on
+    fn same_day(&self) -> String;
+
+    /// Join items into a list, "Monday, Wednesday and Friday"
+    fn list(&self, items: Vec<String>) -> String;
+
+    fn starting(&self, description: String, date: NaiveDate) -> String;
+
+    fn until(&self, description: String, date: NaiveDate, inclusive: bool) -> String;
+
+    /// A series ending after a number of occurences
+    fn times(&self, description: String, n: usize) -> String;
+}
+
+/// Descriptions in English
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct English;
+
+impl English {
+    fn ordinal(n: i32) -> String {
+        let suffix = match (n % 10, n % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        format!("{}{}", n, suffix)
+    }
+
+    /// Count from the end where `-1` is the last, "last" or "2nd to last"
+    fn from_end(n: i32) -> String {
+        match n {
+            -1 => "last".to_string(),
+            n => format!("{} to last", English::ordinal(-n)),
+        }
+    }
+}
+
+impl Locale for English {
+    fn every(&self, duration: &RelativeDuration) -> String {
+        let units = [
+            (duration.num_months(), "month"),
+            (duration.num_weeks(), "week"),
+            (duration.num_days(), "day"),
+        ];
+
+        match units {
+            [(months, _), (0, _), (0, _)] if months > 0 && months % 12 == 0 => match months / 12 {
+                1 => "Every year".to_string(),
+                years => format!("Every {} years", years),
+            },
+            [(3, _), (0, _), (0, _)] => "Every quarter".to_string(),
+            _ => {
+                let parts: Vec<String> = units
+                    .iter()
+                    .filter(|(n, _)| *n != 0)
+                    .map(|(n, unit)| match n {
+                        1 => unit.to_string(),
+                        n => format!("{} {}s", n, unit),
+                    })
+                    .collect();
+                format!("Every {}", self.list(parts))
+            }
+        }
+    }
+
+    fn on(&self, every: String, when: String) -> String {
+        format!("{} on {}", every, when)
+    }
+
+    fn weekday(&self, weekday: Weekday) -> String {
+        let name = match weekday {
+            Weekday::Mon => "Monday",
+            Weekday::Tue => "Tuesday",
+            Weekday::Wed => "Wednesday",
+            Weekday::Thu => "Thursday",
+            Weekday::Fri => "Friday",
+            Weekday::Sat => "Saturday",
+            Weekday::Sun => "Sunday",
+        };
+        name.to_string()
+    }
+
+    fn day(&self, day: i32) -> String {
+        match day {
+            day if day > 0 => format!("the {}", English::ordinal(day)),
+            day => format!("the {} day", English::from_end(day)),
+        }
+    }
+
+    fn day_of_year(&self, day: i32) -> String {
+        match day {
+            day if day > 0 => format!("the {} day", English::ordinal(day)),
+            day => format!("the {} day", English::from_end(day)),
+        }
+    }
+
+    fn nth_weekday(&self, n: i32, weekday: Weekday) -> String {
+        let n = match n {
+            1 => "first".to_string(),
+            2 => "second".to_string(),
+            3 => "third".to_string(),
+            4 => "fourth".to_string(),
+            n if n > 0 => English::ordinal(n),
+            n => English::from_end(n),
+        };
+        format!("the {} {}", n, self.weekday(weekday))
+    }
+
+    fn month_day(&self, month: u32, day: u32) -> String {
+        match u8::try_from(month)
+            .ok()
+            .and_then(|m| chrono::Month::try_from(m).ok())
+        {
+            Some(month) => format!("{} {}", month.name(), day),
+            None => format!("month {} day {}", month, day),
+        }
+    }
+
+    fn week_weekday(&self, week: u32, weekday: Weekday) -> String {
+        format!("{} of week {}", self.weekday(weekday), week)
+    }
+
+    fn same_day(&self) -> String {
+        "the same day".to_string()
+    }
+
+    fn list(&self, mut items: Vec<String>) -> String {
+        match items.pop() {
+            None => String::new(),
+            Some(last) if items.is_empty() => last,
+            Some(last) => format!("{} and {}", items.join(", "), last),
+        }
+    }
+
+    fn starting(&self, description: String, date: NaiveDate) -> String {
+        format!("{}, starting {}", description, date)
+    }
+
+    fn until(&self, description: String, date: NaiveDate, inclusive: bool) -> String {
+        match inclusive {
+            true => format!("{}, until {}", description, date),
+            false => format!("{}, before {}", description, date),
+        }
+    }
+
+    fn times(&self, description: String, n: usize) -> String {
+        match n {
+            1 => format!("{}, once", description),
+            n => format!("{}, {} times", description, n),
+        }
+    }
+}
+
+impl Rule {
+    /// Describe the rule in English
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{RelativeDuration, Rule};
+    /// use chrono::Weekday;
+    ///
+    /// assert_eq!(
+    ///     Rule::Weekdays(RelativeDuration::weeks(2), vec![Weekday::Fri]).describe(),
+    ///     "Every 2 weeks on Friday"
+    /// );
+    /// assert_eq!(
+    ///     Rule::Occurence(RelativeDuration::months(1), -1, Weekday::Fri).describe(),
+    ///     "Every month on the last Friday"
+    /// );
+    /// assert_eq!(Rule::semimonthly().describe(), "Every month on the 1st and the 15th");
+    /// # }
+    /// ```
+    pub fn describe(&self) -> String {
+        self.describe_in(&English)
+    }
+
+    /// Describe the rule with the words of the locale
+    pub fn describe_in(&self, locale: &impl Locale) -> String {
+        let year = RelativeDuration::months(12);
+
+        match self {
+            Rule::Offset(duration, 0) => locale.every(duration),
+            Rule::Offset(duration, offset) => {
+                let day = if *offset >= 0 { offset + 1 } else { *offset };
+                let when = match duration.num_months() {
+                    12 => locale.day_of_year(day),
+                    _ => locale.day(day),
+                };
+                locale.on(locale.every(duration), when)
+            }
+            Rule::Occurence(duration, n, weekday) => {
+                locale.on(locale.every(duration), locale.nth_weekday(*n, *weekday))
+            }
+            Rule::Anchored(duration, _) => locale.on(locale.every(duration), locale.same_day()),
+            Rule::Weekdays(duration, weekdays) => {
+                let days = weekdays.iter().map(|d| locale.weekday(*d)).collect();
+                locale.on(locale.every(duration), locale.list(days))
+            }
+            Rule::MonthDays(duration, days) => {
+                let days = days.iter().map(|d| locale.day(*d)).collect();
+                locale.on(locale.every(duration), locale.list(days))
+            }
+            Rule::Annual(month, day, _) => {
+                locale.on(locale.every(&year), locale.month_day(*month, *day))
+            }
+            Rule::YearWeekday(week, weekday) => {
+                locale.on(locale.every(&year), locale.week_weekday(*week, *weekday))
+            }
+            Rule::YearDay(day) => locale.on(locale.every(&year), locale.day_of_year(*day)),
+        }
+    }
+}
+
+impl Recurrence {
+    /// Describe the series in English, including its start and how it ends
+    ///
+    /// A series which has been iterated describes the occurences it has left.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+    /// let recur = Recurrence::with_start(Rule::monthly(), start).with_max_occurrences(6);
+    ///
+    /// assert_eq!(recur.describe(), "Every month, starting 2024-01-05, 6 times");
+    /// ```
+    pub fn describe(&self) -> String {
+        self.describe_in(&English)
+    }
+
+    pub fn describe_in(&self, locale: &impl Locale) -> String {
+        let mut description = locale.starting(self.rule().describe_in(locale), self.start());
+
+        if let Some(horizon) = self.horizon() {
+            description = locale.until(description, horizon, true);
+        }
+        if let Some(n) = self.remaining() {
+            description = locale.times(description, n);
+        }
+
+        description
+    }
+}
+
+impl Until<Recurrence> {
+    /// Describe the series in English, including when it ends
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{RelativeDuration, Recurrence, Rule};
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let rule = Rule::Weekdays(RelativeDuration::weeks(2), vec![Weekday::Fri]);
+    /// let start = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+    /// let recur = Recurrence::with_start(rule, start)
+    ///     .until_and_including(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+    ///
+    /// assert_eq!(
+    ///     recur.describe(),
+    ///     "Every 2 weeks on Friday, starting 2024-01-05, until 2025-01-01"
+    /// );
+    /// # }
+    /// ```
+    pub fn describe(&self) -> String {
+        self.describe_in(&English)
+    }
+
+    pub fn describe_in(&self, locale: &impl Locale) -> String {
+        let description = self.iter.describe_in(locale);
+
+        match self.until {
+            std::ops::Bound::Included(date) => locale.until(description, date, true),
+            std::ops::Bound::Excluded(date) => locale.until(description, date, false),
+            std::ops::Bound::Unbounded => description,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnchorPolicy;
+
+    #[test]
+    fn test_describe_rules() {
+        let cases = [
+            (Rule::daily(), "Every day"),
+            (Rule::quarterly(), "Every quarter"),
+            (
+                Rule::Offset(RelativeDuration::months(24), 0),
+                "Every 2 years",
+            ),
+            (
+                Rule::Offset(RelativeDuration::try_from_mwd(1, 0, 2).unwrap(), 0),
+                "Every month and 2 days",
+            ),
+            (
+                Rule::Offset(RelativeDuration::months(1), -2),
+                "Every month on the 2nd to last day",
+            ),
+            (
+                Rule::Offset(RelativeDuration::months(1), 10),
+                "Every month on the 11th",
+            ),
+            (
+                Rule::weekly_on(&[Weekday::Mon, Weekday::Wed, Weekday::Fri]),
+                "Every week on Monday, Wednesday and Friday",
+            ),
+            (
+                Rule::Occurence(RelativeDuration::months(3), 2, Weekday::Tue),
+                "Every quarter on the second Tuesday",
+            ),
+            (
+                Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::ClampDay),
+                "Every month on the same day",
+            ),
+            (
+                Rule::MonthDays(RelativeDuration::months(2), vec![-1]),
+                "Every 2 months on the last day",
+            ),
+            (
+                Rule::yearly_on(2, 29, AnchorPolicy::SkipMissing),
+                "Every year on February 29",
+            ),
+            (
+                Rule::yearly_week(26, Weekday::Mon),
+                "Every year on Monday of week 26",
+            ),
+            (Rule::yearly_ordinal(100), "Every year on the 100th day"),
+            (
+                Rule::Offset(RelativeDuration::months(12), 111),
+                "Every year on the 112th day",
+            ),
+        ];
+
+        for (rule, description) in cases {
+            assert_eq!(rule.describe(), description);
+        }
+    }
+
+    #[test]
+    fn test_ordinals() {
+        let ordinals: Vec<String> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 101, 111]
+            .into_iter()
+            .map(English::ordinal)
+            .collect();
+
+        assert_eq!(
+            ordinals,
+            [
+                "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "23rd",
+                "101st", "111th"
+            ]
+        );
+    }
+}
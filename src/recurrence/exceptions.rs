@@ -0,0 +1,196 @@
+//! Exception dates (EXDATE) and additional dates (RDATE) for a series of occurences
+//!
+//! [OccurencesExt] also provides the other adaptors for series of dates.
+use std::{
+    collections::BTreeSet,
+    iter::{FusedIterator, Peekable},
+};
+
+use chrono::NaiveDate;
+
+use crate::calendar::{AdjustRule, HolidayCalendar};
+
+use super::adjust::Adjusted;
+
+/// A series with some of its occurences removed, see [OccurencesExt::except]
+#[derive(Debug, Clone)]
+pub struct Except<I>
+where
+    I: Iterator<Item = NaiveDate>,
+{
+    iter: I,
+    dates: BTreeSet<NaiveDate>,
+}
+
+impl<I> Iterator for Except<I>
+where
+    I: Iterator<Item = NaiveDate>,
+{
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let dates = &self.dates;
+        self.iter.find(|d| !dates.contains(d))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I> FusedIterator for Except<I> where I: FusedIterator<Item = NaiveDate> {}
+
+/// A series with additional occurences spliced in, see [OccurencesExt::including]
+#[derive(Debug, Clone)]
+pub struct Including<I>
+where
+    I: Iterator<Item = NaiveDate>,
+{
+    iter: Peekable<I>,
+    dates: Peekable<std::vec::IntoIter<NaiveDate>>,
+}
+
+impl<I> Iterator for Including<I>
+where
+    I: Iterator<Item = NaiveDate>,
+{
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.iter.peek(), self.dates.peek()) {
+            (Some(a), Some(b)) if a == b => {
+                self.dates.next();
+                self.iter.next()
+            }
+            (Some(a), Some(b)) if b < a => self.dates.next(),
+            (Some(_), _) => self.iter.next(),
+            (None, _) => self.dates.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let extra = self.dates.len();
+
+        (
+            std::cmp::max(lower, extra),
+            upper.and_then(|upper| upper.checked_add(extra)),
+        )
+    }
+}
+
+impl<I> FusedIterator for Including<I> where I: FusedIterator<Item = NaiveDate> {}
+
+/// Exception and additional dates for any ascending series of dates such as a [Recurrence]
+///
+/// [Recurrence]: super::Recurrence
+pub trait OccurencesExt: Iterator<Item = NaiveDate> + Sized {
+    /// Remove occurences falling on any of the dates, like `EXDATE` in RFC 5545
+    ///
+    /// ```
+    /// use calends::{OccurencesExt, Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+    ///
+    /// let dates: Vec<NaiveDate> = Recurrence::with_start(Rule::monthly(), date(1, 1))
+    ///     .except([date(2, 1)])
+    ///     .including([date(2, 2)])
+    ///     .take(3)
+    ///     .collect();
+    ///
+    /// assert_eq!(dates, vec![date(1, 1), date(2, 2), date(3, 1)]);
+    /// ```
+    fn except<D>(self, dates: D) -> Except<Self>
+    where
+        D: IntoIterator<Item = NaiveDate>,
+    {
+        Except {
+            iter: self,
+            dates: dates.into_iter().collect(),
+        }
+    }
+
+    /// Add occurences on each of the dates, like `RDATE` in RFC 5545
+    ///
+    /// The series is expected to be ascending, the dates are merged in so that the result stays
+    /// sorted. Dates which are already part of the series are not repeated.
+    fn including<D>(self, dates: D) -> Including<Self>
+    where
+        D: IntoIterator<Item = NaiveDate>,
+    {
+        let mut dates: Vec<NaiveDate> = dates.into_iter().collect();
+        dates.sort();
+        dates.dedup();
+
+        Including {
+            iter: self.peekable(),
+            dates: dates.into_iter().peekable(),
+        }
+    }
+
+    /// Move occurences falling on weekends or holidays to a business day
+    ///
+    /// Each occurence is adjusted independently, so two occurences can be moved onto the same day
+    /// and [AdjustRule::PreviousBusinessDay] can move an occurence before the previous one.
+    /// Occurences without a business day within a year are dropped.
+    ///
+    /// ```
+    /// use calends::{calendar::AdjustRule, OccurencesExt, Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+    /// let holidays = vec![date(1, 3)];
+    ///
+    /// let dates: Vec<NaiveDate> = Recurrence::with_start(Rule::monthly(), date(1, 1))
+    ///     .adjusted(AdjustRule::NextBusinessDay, &holidays)
+    ///     .take(3)
+    ///     .collect();
+    ///
+    /// assert_eq!(dates, vec![date(1, 4), date(2, 1), date(3, 1)]);
+    /// ```
+    fn adjusted<C>(self, rule: AdjustRule, calendar: &C) -> Adjusted<'_, Self, C>
+    where
+        C: HolidayCalendar + ?Sized,
+    {
+        Adjusted::new(self, rule, calendar)
+    }
+}
+
+impl<I> OccurencesExt for I where I: Iterator<Item = NaiveDate> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Recurrence, Rule};
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_except_and_including() {
+        let end = date(4, 30);
+        let series = Recurrence::with_start(Rule::monthly(), date(1, 1)).until_and_including(end);
+
+        let dates: Vec<NaiveDate> = series
+            .except([date(3, 1), date(3, 2)])
+            .including([date(4, 1), date(3, 3), date(1, 1), date(3, 3)])
+            .collect();
+
+        assert_eq!(dates, vec![date(1, 1), date(2, 1), date(3, 3), date(4, 1)]);
+    }
+
+    #[test]
+    fn test_including_after_end() {
+        let series = Recurrence::with_start(Rule::monthly(), date(1, 1)).count(2);
+        let mut dates = series.including([date(12, 25), date(1, 15)]);
+
+        assert_eq!(dates.size_hint(), (2, Some(4)));
+        assert_eq!(
+            dates.by_ref().collect::<Vec<_>>(),
+            vec![date(1, 1), date(1, 15), date(2, 1), date(12, 25)]
+        );
+        assert_eq!(dates.next(), None);
+    }
+}
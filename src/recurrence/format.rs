@@ -0,0 +1,308 @@
+//! Compact string form of rules and recurrences
+//!
+//! A rule is written as its variant followed by its arguments e.g. `Offset(P1M,-1)`,
+//! `Weekdays(P1W,Mon,Fri)` or `Annual(2,29,RollForward)`, durations use the ISO8601-2:2019 format.
+//! A recurrence is written as `R/<start>/<rule>` e.g. `R/2022-01-01/MonthDays(P1M,1,15)`.
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{NaiveDate, Weekday};
+
+use crate::duration::{parse::parse_relative_duration, RelativeDuration};
+
+use super::{AnchorPolicy, Recurrence, Rule};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RuleParseError {
+    #[error("unknown rule `{0}`")]
+    UnknownRule(String),
+    #[error("expected a rule of the form `Name(arguments)`")]
+    InvalidSyntax,
+    #[error("invalid argument `{0}`")]
+    InvalidArgument(String),
+    #[error("wrong number of arguments for `{0}`")]
+    ArgumentCount(String),
+    #[error("expected a recurrence of the form `R/<start>/<rule>`")]
+    InvalidRecurrence,
+}
+
+impl Display for AnchorPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AnchorPolicy::EndOfMonth => "EndOfMonth",
+            AnchorPolicy::ClampDay => "ClampDay",
+            AnchorPolicy::SkipMissing => "SkipMissing",
+            AnchorPolicy::RollForward => "RollForward",
+        };
+
+        f.write_str(name)
+    }
+}
+
+impl FromStr for AnchorPolicy {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "EndOfMonth" => Ok(AnchorPolicy::EndOfMonth),
+            "ClampDay" => Ok(AnchorPolicy::ClampDay),
+            "SkipMissing" => Ok(AnchorPolicy::SkipMissing),
+            "RollForward" => Ok(AnchorPolicy::RollForward),
+            _ => Err(RuleParseError::InvalidArgument(s.to_string())),
+        }
+    }
+}
+
+/// Canonical string form of a rule, see the [module documentation](self)
+///
+/// ```
+/// # #[cfg(not(feature = "strict"))] {
+/// use calends::{RelativeDuration, Rule};
+/// use chrono::Weekday;
+///
+/// let rule = Rule::Occurence(RelativeDuration::months(1), -1, Weekday::Fri);
+/// assert_eq!(rule.to_string(), "Occurence(P1M,-1,Fri)");
+/// assert_eq!("Occurence(P1M,-1,Fri)".parse::<Rule>().unwrap(), rule);
+/// # }
+/// ```
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rule::Offset(duration, offset) => {
+                write!(f, "Offset({},{})", duration.iso8601(), offset)
+            }
+            Rule::Occurence(duration, occurence, weekday) => {
+                write!(
+                    f,
+                    "Occurence({},{},{})",
+                    duration.iso8601(),
+                    occurence,
+                    weekday
+                )
+            }
+            Rule::Anchored(duration, policy) => {
+                write!(f, "Anchored({},{})", duration.iso8601(), policy)
+            }
+            Rule::Weekdays(duration, weekdays) => {
+                write!(f, "Weekdays({}", duration.iso8601())?;
+                for weekday in weekdays {
+                    write!(f, ",{}", weekday)?;
+                }
+                f.write_str(")")
+            }
+            Rule::MonthDays(duration, days) => {
+                write!(f, "MonthDays({}", duration.iso8601())?;
+                for day in days {
+                    write!(f, ",{}", day)?;
+                }
+                f.write_str(")")
+            }
+            Rule::Annual(month, day, policy) => write!(f, "Annual({},{},{})", month, day, policy),
+            Rule::YearWeekday(week, weekday) => write!(f, "YearWeekday({},{})", week, weekday),
+            Rule::YearDay(day) => write!(f, "YearDay({})", day),
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, args) = s
+            .trim()
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .ok_or(RuleParseError::InvalidSyntax)?;
+        let args: Vec<&str> = args.split(',').map(str::trim).collect();
+
+        let count = |n: usize| {
+            if args.len() == n {
+                Ok(())
+            } else {
+                Err(RuleParseError::ArgumentCount(name.to_string()))
+            }
+        };
+
+        let rule = match name {
+            "Offset" => {
+                count(2)?;
+                Rule::Offset(duration(args[0])?, parse(args[1])?)
+            }
+            "Occurence" => {
+                count(3)?;
+                Rule::Occurence(duration(args[0])?, parse(args[1])?, weekday(args[2])?)
+            }
+            "Anchored" => {
+                count(2)?;
+                Rule::Anchored(duration(args[0])?, args[1].parse()?)
+            }
+            "Weekdays" => Rule::Weekdays(
+                duration(args[0])?,
+                args[1..]
+                    .iter()
+                    .map(|d| weekday(d))
+                    .collect::<Result<_, _>>()?,
+            ),
+            "MonthDays" => Rule::MonthDays(
+                duration(args[0])?,
+                args[1..]
+                    .iter()
+                    .map(|d| parse(d))
+                    .collect::<Result<_, _>>()?,
+            ),
+            "Annual" => {
+                count(3)?;
+                Rule::Annual(parse(args[0])?, parse(args[1])?, args[2].parse()?)
+            }
+            "YearWeekday" => {
+                count(2)?;
+                Rule::YearWeekday(parse(args[0])?, weekday(args[1])?)
+            }
+            "YearDay" => {
+                count(1)?;
+                Rule::YearDay(parse(args[0])?)
+            }
+            _ => return Err(RuleParseError::UnknownRule(name.to_string())),
+        };
+
+        Ok(rule)
+    }
+}
+
+/// String form of a recurrence from its start, see the [module documentation](self)
+///
+/// The position of the iterator is not part of the string form.
+///
+/// ```
+/// use calends::{Recurrence, Rule};
+/// use chrono::NaiveDate;
+///
+/// let recur: Recurrence = "R/2022-01-01/MonthDays(P1M,1,15)".parse().unwrap();
+/// assert_eq!(recur.to_string(), "R/2022-01-01/MonthDays(P1M,1,15)");
+/// assert_eq!(
+///     recur.take(2).collect::<Vec<_>>(),
+///     Recurrence::with_start(Rule::semimonthly(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap())
+///         .take(2)
+///         .collect::<Vec<_>>()
+/// );
+/// ```
+impl Display for Recurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "R/{}/{}", self.start(), self.rule())
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, rule) = s
+            .trim()
+            .strip_prefix("R/")
+            .and_then(|s| s.split_once('/'))
+            .ok_or(RuleParseError::InvalidRecurrence)?;
+        let start = NaiveDate::parse_from_str(start, "%Y-%m-%d")
+            .map_err(|_| RuleParseError::InvalidArgument(start.to_string()))?;
+
+        Ok(Recurrence::with_start(rule.parse()?, start))
+    }
+}
+
+fn duration(value: &str) -> Result<RelativeDuration, RuleParseError> {
+    match parse_relative_duration(value.as_bytes()) {
+        Ok((b"", duration)) => Ok(duration),
+        _ => Err(RuleParseError::InvalidArgument(value.to_string())),
+    }
+}
+
+fn parse<T: FromStr>(value: &str) -> Result<T, RuleParseError> {
+    value
+        .parse()
+        .map_err(|_| RuleParseError::InvalidArgument(value.to_string()))
+}
+
+fn weekday(value: &str) -> Result<Weekday, RuleParseError> {
+    parse(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let rules = vec![
+            Rule::monthly(),
+            Rule::Offset(RelativeDuration::months(1).with_days(-2), -1),
+            Rule::Occurence(RelativeDuration::months(3), 2, Weekday::Tue),
+            Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::EndOfMonth),
+            Rule::weekly_on(&[Weekday::Mon, Weekday::Fri]),
+            Rule::Weekdays(RelativeDuration::weeks(2), vec![]),
+            Rule::semimonthly(),
+            Rule::yearly_on(2, 29, AnchorPolicy::RollForward),
+            Rule::yearly_week(26, Weekday::Sun),
+            Rule::yearly_ordinal(-1),
+        ];
+
+        for rule in rules {
+            let s = rule.to_string();
+            assert_eq!(s.parse::<Rule>(), Ok(rule.clone()), "{}", s);
+
+            let json = serde_json::to_string(&rule).unwrap();
+            assert_eq!(
+                serde_json::from_str::<Rule>(&json).unwrap(),
+                rule,
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn test_serde() {
+        let rule = Rule::Occurence(RelativeDuration::months(1), -1, Weekday::Fri);
+        assert_eq!(
+            serde_json::to_string(&rule).unwrap(),
+            r#"{"Occurence":["P1M",-1,"Fri"]}"#
+        );
+
+        // A deserialized recurrence resumes where it left off
+        let mut recur = Recurrence::with_start(rule, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+        recur.next();
+        let json = serde_json::to_string(&recur).unwrap();
+        let resumed: Recurrence = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            resumed.take(3).collect::<Vec<_>>(),
+            recur.take(3).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "Monthly".parse::<Rule>(),
+            Err(RuleParseError::InvalidSyntax)
+        );
+        assert_eq!(
+            "Monthly(P1M)".parse::<Rule>(),
+            Err(RuleParseError::UnknownRule("Monthly".to_string()))
+        );
+        assert_eq!(
+            "Offset(P1M)".parse::<Rule>(),
+            Err(RuleParseError::ArgumentCount("Offset".to_string()))
+        );
+        assert_eq!(
+            "Offset(1M,0)".parse::<Rule>(),
+            Err(RuleParseError::InvalidArgument("1M".to_string()))
+        );
+        assert_eq!(
+            "Weekdays(P1W,Mon,Funday)".parse::<Rule>(),
+            Err(RuleParseError::InvalidArgument("Funday".to_string()))
+        );
+        assert_eq!(
+            "2022-01-01/Offset(P1M,0)"
+                .parse::<Recurrence>()
+                .unwrap_err(),
+            RuleParseError::InvalidRecurrence
+        );
+    }
+}
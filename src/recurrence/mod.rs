@@ -1,4 +1,19 @@
+pub mod adjust;
+pub mod cron;
+pub mod datetime;
+pub mod describe;
+pub mod exceptions;
+pub mod format;
+#[cfg(feature = "natural")]
+pub mod natural;
 pub mod recur;
+pub mod rrule;
+pub mod set;
 pub mod until;
 
+pub use adjust::Adjusted;
+pub use datetime::{DateTimeRecurrence, ZonedRecurrence};
+pub use exceptions::{Except, Including, OccurencesExt};
+pub use format::RuleParseError;
 pub use recur::*;
+pub use set::{RuleSet, RuleSetRecurrence};
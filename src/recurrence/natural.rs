@@ -0,0 +1,488 @@
+//! English schedule phrases such as "every other week" or "first Monday of each month"
+//!
+//! Phrases are parsed into a [Rule] along with when the series starts, words are matched without
+//! regard to case and filler words such as "on", "the" and "and" are ignored.
+//!
+//! | Phrase                                  | Rule                                       |
+//! |-----------------------------------------|--------------------------------------------|
+//! | `daily`, `every 3 days`                 | [Rule::Offset] with an offset of `0`       |
+//! | `every other week`, `quarterly`         | [Rule::Offset] with an offset of `0`       |
+//! | `every Monday and Wednesday`, `every weekday` | [Rule::Weekdays]                     |
+//! | `every 2nd Friday`, `every 2 weeks on Friday` | [Rule::Weekdays] every 2 weeks       |
+//! | `first Monday of each month`, `last Friday of the quarter` | [Rule::Occurence]       |
+//! | `the 1st and 15th of every month`, `monthly on the 15th` | [Rule::MonthDays]         |
+//! | `last day of the year`                  | [Rule::Offset] within the year             |
+//! | `every March 15th`                      | [Rule::Annual] with [AnchorPolicy::SkipMissing] |
+//!
+//! A phrase can end with when it starts, `starting March`, `from 2025-01-06` or
+//! `beginning June 2025`. "every 2nd Friday" is every other Friday, the second Friday of each month
+//! is `2nd Friday of each month`.
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{
+    duration::RelativeDuration,
+    util::{beginning_of_month, beginning_of_quarter, beginning_of_year},
+};
+
+use super::{AnchorPolicy, Recurrence, Rule};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NaturalError {
+    #[error("unrecognized schedule {0:?}")]
+    Unrecognized(String),
+
+    #[error("unrecognized start {0:?}, expected a month or a date such as 2025-01-06")]
+    InvalidStart(String),
+}
+
+/// When a parsed schedule starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Starting {
+    /// The first day of the next occurence of the month, `starting March`
+    Month(u32),
+    /// `from 2025-01-06` or `starting June 2025`
+    Date(NaiveDate),
+}
+
+/// A [Rule] parsed from a phrase along with when it starts
+///
+/// ```
+/// # #[cfg(not(feature = "strict"))] {
+/// use calends::{recurrence::natural::NaturalRule, RelativeDuration, Rule};
+/// use chrono::{NaiveDate, Weekday};
+///
+/// let schedule: NaturalRule = "first Monday of each month".parse().unwrap();
+/// assert_eq!(
+///     schedule.rule,
+///     Rule::Occurence(RelativeDuration::months(1), 1, Weekday::Mon)
+/// );
+///
+/// let today = NaiveDate::from_ymd_opt(2022, 1, 12).unwrap();
+/// let dates: Vec<NaiveDate> = schedule.with_start(today).take(2).collect();
+/// assert_eq!(
+///     dates,
+///     vec![
+///         NaiveDate::from_ymd_opt(2022, 2, 7).unwrap(),
+///         NaiveDate::from_ymd_opt(2022, 3, 7).unwrap(),
+///     ]
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NaturalRule {
+    pub rule: Rule,
+    pub starting: Option<Starting>,
+}
+
+impl NaturalRule {
+    /// First day of the series, with `today` as the start when the phrase doesn't give one
+    pub fn start(&self, today: NaiveDate) -> NaiveDate {
+        match self.starting {
+            None => today,
+            Some(Starting::Date(date)) => date,
+            Some(Starting::Month(month)) => {
+                let year = match month < today.month() {
+                    true => today.year() + 1,
+                    false => today.year(),
+                };
+                NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(today)
+            }
+        }
+    }
+
+    /// Iterate the series from its start, skipping dates before it
+    ///
+    /// Rules which pick a day within a month, quarter or year are evaluated within calendar
+    /// periods, so "last Friday of the month" is the last Friday of each calendar month.
+    pub fn with_start(&self, today: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+        let start = self.start(today);
+        let aligned = match &self.rule {
+            Rule::Offset(duration, offset) if *offset != 0 => align(duration, start),
+            Rule::Occurence(duration, ..) => align(duration, start),
+            _ => start,
+        };
+
+        Recurrence::with_start(self.rule.clone(), aligned).skip_while(move |d| *d < start)
+    }
+}
+
+/// Align the start of a series to the calendar month, quarter or year of its duration
+fn align(duration: &RelativeDuration, start: NaiveDate) -> NaiveDate {
+    match duration.num_months() {
+        12 => beginning_of_year(&start),
+        3 => beginning_of_quarter(&start),
+        _ => beginning_of_month(&start),
+    }
+}
+
+impl FromStr for NaturalRule {
+    type Err = NaturalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_lowercase().replace(',', " ");
+        let words: Vec<&str> = lower
+            .split_whitespace()
+            .filter(|w| !matches!(*w, "on" | "the" | "and" | "at"))
+            .collect();
+
+        let split = words
+            .iter()
+            .position(|w| matches!(*w, "starting" | "from" | "beginning"));
+        let (phrase, starting) = match split {
+            Some(index) => {
+                let start = &words[index + 1..];
+                let starting = parse_start(start)
+                    .ok_or_else(|| NaturalError::InvalidStart(start.join(" ")))?;
+                (&words[..index], Some(starting))
+            }
+            None => (&words[..], None),
+        };
+
+        let rule = parse_rule(phrase).ok_or_else(|| NaturalError::Unrecognized(s.to_string()))?;
+
+        Ok(NaturalRule { rule, starting })
+    }
+}
+
+fn parse_rule(words: &[&str]) -> Option<Rule> {
+    if let Some(index) = words.iter().position(|w| *w == "of") {
+        let period = match &words[index + 1..] {
+            [period] | ["each" | "every", period] => period_duration(period)?,
+            _ => return None,
+        };
+        return parse_within(&words[..index], period);
+    }
+
+    match words {
+        ["daily"] => Some(Rule::daily()),
+        ["weekly"] => Some(Rule::weekly()),
+        ["biweekly" | "fortnightly"] => Some(Rule::biweekly()),
+        ["monthly"] => Some(Rule::monthly()),
+        ["quarterly"] => Some(Rule::quarterly()),
+        ["yearly" | "annually"] => Some(Rule::yearly()),
+        ["semimonthly"] | ["twice", "a", "month"] => Some(Rule::semimonthly()),
+        ["weekly", days @ ..] => weekdays(days, RelativeDuration::weeks(1)),
+        ["monthly", days @ ..] => month_days(days),
+        ["every", rest @ ..] => parse_every(rest),
+        _ => None,
+    }
+}
+
+/// The rest of a phrase starting with "every"
+fn parse_every(words: &[&str]) -> Option<Rule> {
+    match words {
+        ["weekday" | "weekdays"] => Some(Rule::weekly_on(&[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ])),
+        ["weekend" | "weekends"] => Some(Rule::weekly_on(&[Weekday::Sat, Weekday::Sun])),
+        ["other", rest @ ..] => every_n(2, rest),
+        [month, day] if month_number(month).is_some() => {
+            let day = ordinal(day).or_else(|| day.parse().ok())?;
+            Some(Rule::Annual(
+                month_number(month)?,
+                u32::try_from(day).ok().filter(|d| (1..=31).contains(d))?,
+                AnchorPolicy::SkipMissing,
+            ))
+        }
+        [n, rest @ ..] if number(n).is_some() => every_n(number(n)?, rest),
+        [n, day, ..] if ordinal(n).is_some_and(|n| n > 0) && weekday(day).is_some() => {
+            weekdays(&words[1..], weeks(ordinal(n)?)?)
+        }
+        rest => every_n(1, rest),
+    }
+}
+
+/// Every `n` of a unit, or every `n` weeks on some weekdays
+fn every_n(n: i32, words: &[&str]) -> Option<Rule> {
+    let (unit, days) = words.split_first()?;
+    if weekday(unit).is_some() {
+        return weekdays(words, weeks(n)?);
+    }
+    let unit = unit.strip_suffix('s').unwrap_or(unit);
+
+    let duration = match unit {
+        "day" => RelativeDuration::try_from_mwd(0, 0, n)?,
+        "week" => weeks(n)?,
+        "month" => RelativeDuration::try_from_mwd(n, 0, 0)?,
+        "quarter" => RelativeDuration::try_from_mwd(n.checked_mul(3)?, 0, 0)?,
+        "year" => RelativeDuration::try_from_mwd(n.checked_mul(12)?, 0, 0)?,
+        _ => return None,
+    };
+
+    match (unit, days) {
+        (_, []) => Some(Rule::Offset(duration, 0)),
+        ("week", days) => weekdays(days, duration),
+        _ => None,
+    }
+}
+
+/// Weekdays or days within each period, the words before "of"
+fn parse_within(words: &[&str], period: RelativeDuration) -> Option<Rule> {
+    if let [n, day] = words {
+        if let Some(day) = weekday(day) {
+            let n = ordinal(n).filter(|n| *n <= 5)?;
+            return Some(Rule::Occurence(period, n, day));
+        }
+    }
+
+    let days = words
+        .iter()
+        .filter(|w| !matches!(**w, "day" | "days"))
+        .map(|w| ordinal(w))
+        .collect::<Option<Vec<i32>>>()
+        .filter(|days| !days.is_empty())?;
+
+    match days.as_slice() {
+        _ if period == RelativeDuration::months(1) => Some(Rule::MonthDays(period, days)),
+        [day] => Some(Rule::Offset(period, if *day > 0 { day - 1 } else { *day })),
+        _ => None,
+    }
+}
+
+fn weekdays(words: &[&str], duration: RelativeDuration) -> Option<Rule> {
+    let mut days = words
+        .iter()
+        .map(|w| weekday(w))
+        .collect::<Option<Vec<Weekday>>>()
+        .filter(|days| !days.is_empty())?;
+    days.sort_by_key(|d| d.num_days_from_monday());
+    days.dedup();
+
+    Some(Rule::Weekdays(duration, days))
+}
+
+fn month_days(words: &[&str]) -> Option<Rule> {
+    let days = words
+        .iter()
+        .map(|w| ordinal(w))
+        .collect::<Option<Vec<i32>>>()
+        .filter(|days| !days.is_empty())?;
+
+    Some(Rule::MonthDays(RelativeDuration::months(1), days))
+}
+
+fn weeks(n: i32) -> Option<RelativeDuration> {
+    RelativeDuration::try_from_mwd(0, n, 0)
+}
+
+fn period_duration(word: &str) -> Option<RelativeDuration> {
+    match word {
+        "month" => Some(RelativeDuration::months(1)),
+        "quarter" => Some(RelativeDuration::months(3)),
+        "year" => Some(RelativeDuration::months(12)),
+        _ => None,
+    }
+}
+
+fn parse_start(words: &[&str]) -> Option<Starting> {
+    match words {
+        [date] if month_number(date).is_none() => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .ok()
+            .map(Starting::Date),
+        [month] => month_number(month).map(Starting::Month),
+        [month, year] => {
+            let date = NaiveDate::from_ymd_opt(year.parse().ok()?, month_number(month)?, 1)?;
+            Some(Starting::Date(date))
+        }
+        _ => None,
+    }
+}
+
+/// A count such as `3` or `three`
+fn number(word: &str) -> Option<i32> {
+    const NAMES: [&str; 12] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+        "twelve",
+    ];
+
+    match NAMES.iter().position(|name| *name == word) {
+        Some(index) => Some(index as i32 + 1),
+        None => word.parse().ok().filter(|n| *n > 0),
+    }
+}
+
+/// An ordinal such as `1st`, `second` or `last`, which is `-1`
+fn ordinal(word: &str) -> Option<i32> {
+    const NAMES: [&str; 5] = ["first", "second", "third", "fourth", "fifth"];
+
+    if word == "last" {
+        return Some(-1);
+    }
+    if let Some(index) = NAMES.iter().position(|name| *name == word) {
+        return Some(index as i32 + 1);
+    }
+
+    let digits = word
+        .strip_suffix("st")
+        .or_else(|| word.strip_suffix("nd"))
+        .or_else(|| word.strip_suffix("rd"))
+        .or_else(|| word.strip_suffix("th"))?;
+    digits.parse().ok().filter(|n| (1..=31).contains(n))
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    let word = word.strip_suffix('s').unwrap_or(word);
+    match word.get(..3)? {
+        _ if word.len() > 3 && !is_weekday_name(word) => None,
+        "mon" => Some(Weekday::Mon),
+        "tue" => Some(Weekday::Tue),
+        "wed" => Some(Weekday::Wed),
+        "thu" => Some(Weekday::Thu),
+        "fri" => Some(Weekday::Fri),
+        "sat" => Some(Weekday::Sat),
+        "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn is_weekday_name(word: &str) -> bool {
+    [
+        "monday",
+        "tuesday",
+        "wednesday",
+        "thursday",
+        "friday",
+        "saturday",
+        "sunday",
+    ]
+    .contains(&word)
+}
+
+fn month_number(word: &str) -> Option<u32> {
+    const NAMES: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+
+    NAMES
+        .iter()
+        .position(|name| *name == word || (word.len() == 3 && name.starts_with(word)))
+        .map(|index| index as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(s: &str) -> Rule {
+        s.parse::<NaturalRule>().unwrap().rule
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        assert_eq!(rule("Daily"), Rule::daily());
+        assert_eq!(
+            rule("every 3 days"),
+            Rule::Offset(RelativeDuration::days(3), 0)
+        );
+        assert_eq!(rule("every other week"), Rule::biweekly());
+        assert_eq!(
+            rule("every two months"),
+            Rule::Offset(RelativeDuration::months(2), 0)
+        );
+        assert_eq!(rule("every quarter"), Rule::quarterly());
+        assert_eq!(
+            rule("every Monday and Wednesday"),
+            Rule::weekly_on(&[Weekday::Mon, Weekday::Wed])
+        );
+        assert_eq!(
+            rule("every weekday"),
+            Rule::Weekdays(
+                RelativeDuration::weeks(1),
+                vec![
+                    Weekday::Mon,
+                    Weekday::Tue,
+                    Weekday::Wed,
+                    Weekday::Thu,
+                    Weekday::Fri
+                ]
+            )
+        );
+        assert_eq!(
+            rule("every 2nd Friday"),
+            Rule::Weekdays(RelativeDuration::weeks(2), vec![Weekday::Fri])
+        );
+        assert_eq!(
+            rule("every 3 weeks on Tuesday, Thursday"),
+            Rule::Weekdays(RelativeDuration::weeks(3), vec![Weekday::Tue, Weekday::Thu])
+        );
+        assert_eq!(
+            rule("last Friday of the quarter"),
+            Rule::Occurence(RelativeDuration::months(3), -1, Weekday::Fri)
+        );
+        assert_eq!(
+            rule("the 1st and 15th of every month"),
+            Rule::MonthDays(RelativeDuration::months(1), vec![1, 15])
+        );
+        assert_eq!(
+            rule("last day of the month"),
+            Rule::MonthDays(RelativeDuration::months(1), vec![-1])
+        );
+        assert_eq!(
+            rule("monthly on the 10th"),
+            Rule::MonthDays(RelativeDuration::months(1), vec![10])
+        );
+        assert_eq!(
+            rule("last day of the year"),
+            Rule::Offset(RelativeDuration::months(12), -1)
+        );
+        assert_eq!(
+            rule("every Feb 29th"),
+            Rule::Annual(2, 29, AnchorPolicy::SkipMissing)
+        );
+        assert_eq!(rule("twice a month"), Rule::semimonthly());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "every blue moon".parse::<NaturalRule>(),
+            Err(NaturalError::Unrecognized("every blue moon".to_string()))
+        );
+        assert!("sixth Monday of each month".parse::<NaturalRule>().is_err());
+        assert!("every Monday of each week".parse::<NaturalRule>().is_err());
+        assert_eq!(
+            "weekly starting soon".parse::<NaturalRule>(),
+            Err(NaturalError::InvalidStart("soon".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_starting() {
+        let today = date(2022, 10, 12);
+
+        let quarterly: NaturalRule = "quarterly starting March".parse().unwrap();
+        assert_eq!(quarterly.starting, Some(Starting::Month(3)));
+        let dates: Vec<NaiveDate> = quarterly.with_start(today).take(2).collect();
+        assert_eq!(dates, vec![date(2023, 3, 1), date(2023, 6, 1)]);
+
+        let weekly: NaturalRule = "every Friday from 2022-11-01".parse().unwrap();
+        assert_eq!(weekly.with_start(today).next(), Some(date(2022, 11, 4)));
+
+        let yearly: NaturalRule = "last day of the year beginning June 2023".parse().unwrap();
+        assert_eq!(yearly.with_start(today).next(), Some(date(2023, 12, 31)));
+
+        let monthly: NaturalRule = "last Friday of each month".parse().unwrap();
+        assert_eq!(monthly.with_start(today).next(), Some(date(2022, 10, 28)));
+    }
+}
@@ -1,8 +1,20 @@
-use chrono::NaiveDate;
+use std::iter::{FusedIterator, Take};
 
-use crate::duration::RelativeDuration;
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
 
-use super::until::Until;
+use crate::{
+    duration::{rd_iso8601, RelativeDuration},
+    grain::Grain,
+    interval::ClosedInterval,
+    month_day::{LeapPolicy, MonthDay},
+    util::{
+        beginning_of_month, beginning_of_week_starting, checked_month_end, checked_shift_months,
+        find_weekday_between, shift_months, WeekStart,
+    },
+};
+
+use super::{datetime::DateTimeRecurrence, until::Until};
 
 /// Structure for how an interval of time gets repeated
 ///
@@ -23,16 +35,41 @@ use super::until::Until;
 ///
 /// - Until a point in time (inclusive or exclusive)
 /// - Count of recurrences (end after a count of occurences) (inclusive)
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Rule {
     /// An offset within an interval
     ///
     /// - Duration ([RelativeDuration]): A duration of time
     /// - Offset ([i32]): the offset in days with positive starting at the beginning of the cycle and
-    /// negative being referenced from the end of the interval.
+    ///   negative being referenced from the end of the interval.
     ///
     /// This covers cases 1.1 and 1.2 in the rules of recurrence
-    Offset(RelativeDuration, i32),
+    ///
+    /// With an offset of `0` each date is the duration after the previous one, a duration which
+    /// doesn't move forwards such as [RelativeDuration::zero] ends the series after its start.
+    ///
+    /// An offset of `0` is the first day of each period and `-1` is the last day. Periods which are
+    /// too short for the offset are skipped e.g. the 31st day of a month.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{RelativeDuration, Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let rule = Rule::Offset(RelativeDuration::months(1), -1);
+    /// let mut recur = Recurrence::with_start(rule, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    ///
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 1, 31));
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 2, 28));
+    /// # }
+    /// ```
+    Offset(
+        #[serde(with = "rd_iso8601")]
+        #[cfg_attr(feature = "schemars", schemars(schema_with = "rd_iso8601::schema"))]
+        RelativeDuration,
+        i32,
+    ),
 
     /// An occurence within an interval
     ///
@@ -42,12 +79,167 @@ pub enum Rule {
     ///
     /// This covers cases 2.1 and 2.2
     ///
-    /// TODO: Describe the ruleset for finding a day of the week
-    Occurence(RelativeDuration, i32, chrono::Weekday),
+    /// Each period starts at the start of the series and lasts for the duration. Positive offsets
+    /// count from the start of the period and negative offsets from the end, so `-1` is the last
+    /// weekday in the period. Periods which don't contain the occurence are skipped e.g. the 5th
+    /// wednesday of a month.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{RelativeDuration, Recurrence, Rule};
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let rule = Rule::Occurence(RelativeDuration::months(1), -1, Weekday::Fri);
+    /// let mut recur = Recurrence::with_start(rule, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    ///
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 1, 28));
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 2, 25));
+    /// # }
+    /// ```
+    Occurence(
+        #[serde(with = "rd_iso8601")]
+        #[cfg_attr(feature = "schemars", schemars(schema_with = "rd_iso8601::schema"))]
+        RelativeDuration,
+        i32,
+        chrono::Weekday,
+    ),
+
+    /// Repeat on the day of the month the series started on
+    ///
+    /// - Duration ([RelativeDuration]): A duration of time
+    /// - Policy ([AnchorPolicy]): how months without the anchored day are handled
+    ///
+    /// Every occurence is computed from the start of the series rather than the previous
+    /// occurence, so a short month does not move the day of later occurences.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{AnchorPolicy, RelativeDuration, Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let rule = Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::SkipMissing);
+    /// let mut recur = Recurrence::with_start(rule, NaiveDate::from_ymd_opt(2022, 1, 31).unwrap());
+    ///
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 1, 31));
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 3, 31));
+    /// # }
+    /// ```
+    Anchored(
+        #[serde(with = "rd_iso8601")]
+        #[cfg_attr(feature = "schemars", schemars(schema_with = "rd_iso8601::schema"))]
+        RelativeDuration,
+        AnchorPolicy,
+    ),
+
+    /// Days of the week inside a week
+    ///
+    /// - Duration ([RelativeDuration]): the duration between the weeks the event happens in
+    /// - Weekdays ([chrono::Weekday]): the days of the week that this happens on
+    ///
+    /// This covers case 3.1. Weeks start on a Monday unless the series starts them on another day,
+    /// see [Recurrence::with_week_start]. Dates within a week are produced in order from the first
+    /// day of the week. Dates before the start of the series are skipped.
+    Weekdays(
+        #[serde(with = "rd_iso8601")]
+        #[cfg_attr(feature = "schemars", schemars(schema_with = "rd_iso8601::schema"))]
+        RelativeDuration,
+        Vec<chrono::Weekday>,
+    ),
+
+    /// Days of the month inside a month
+    ///
+    /// - Duration ([RelativeDuration]): the duration between the months the event happens in
+    /// - Days ([i32]): the days of the month with `1` being the first day and `-1` the last day
+    ///
+    /// Months start on the first and dates within a month are produced in order. Months without
+    /// one of the days skip that day, and dates before the start of the series are skipped. The
+    /// duration must be a whole number of months.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{RelativeDuration, Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let rule = Rule::MonthDays(RelativeDuration::months(1), vec![-1, 15]);
+    /// let mut recur = Recurrence::with_start(rule, NaiveDate::from_ymd_opt(2022, 1, 20).unwrap());
+    ///
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 1, 31));
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 2, 15));
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 2, 28));
+    /// # }
+    /// ```
+    MonthDays(
+        #[serde(with = "rd_iso8601")]
+        #[cfg_attr(feature = "schemars", schemars(schema_with = "rd_iso8601::schema"))]
+        RelativeDuration,
+        Vec<i32>,
+    ),
+
+    /// A day of the year given by its month and day
+    ///
+    /// - Month ([u32]): the month of the year starting from `1`
+    /// - Day ([u32]): the day of the month starting from `1`
+    /// - Policy ([AnchorPolicy]): how years without the day (Feb 29) are handled
+    ///
+    /// Occurs once in each calendar year, dates before the start of the series are skipped. A
+    /// month and day which don't exist in any year such as Feb 30 end the series.
+    Annual(u32, u32, AnchorPolicy),
+
+    /// A day of the week inside a week of the ISO 8601 week-numbering year
+    ///
+    /// - Week ([u32]): the ISO week starting from `1`
+    /// - Weekday ([chrono::Weekday]): the day of the week
+    ///
+    /// Occurs once in each ISO year, years without a 53rd week are skipped. The date can fall in
+    /// the neighbouring calendar year e.g. the Monday of week 1 can be in December.
+    YearWeekday(u32, chrono::Weekday),
+
+    /// A day of the year
+    ///
+    /// - Day ([i32]): the ordinal day with `1` being Jan 1 and `-1` being Dec 31
+    ///
+    /// Occurs once in each calendar year, years too short for the day (the 366th) are skipped.
+    YearDay(i32),
+}
+
+/// How an anchored recurrence handles months that are too short for the anchored day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum AnchorPolicy {
+    /// A series anchored on the last day of a month stays on the last day of the month, other
+    /// days are clamped e.g. Apr 30 -> May 31 but Jan 30 -> Feb 28 -> Mar 30
+    EndOfMonth,
+    /// Use the last day of the month when the day is missing e.g. Jan 31 -> Feb 28 -> Mar 31
+    ClampDay,
+    /// Skip months without the day e.g. Jan 31 -> Mar 31
+    SkipMissing,
+    /// Move to the first day of the following month e.g. Jan 31 -> Mar 1 -> Mar 31
+    RollForward,
+}
+
+impl AnchorPolicy {
+    /// Resolve the anchored date within a month
+    ///
+    /// Returns [None] when the month is skipped or out of range
+    pub fn resolve(&self, anchor: NaiveDate, year: i32, month: u32) -> Option<NaiveDate> {
+        let month_end = checked_month_end(year, month)?;
+        let day = anchor.day();
+
+        match self {
+            AnchorPolicy::EndOfMonth if anchor.succ_opt().is_none_or(|d| d.day() == 1) => {
+                Some(month_end)
+            }
+            AnchorPolicy::EndOfMonth | AnchorPolicy::ClampDay => {
+                month_end.with_day(std::cmp::min(day, month_end.day()))
+            }
+            AnchorPolicy::SkipMissing => month_end.with_day(day),
+            AnchorPolicy::RollForward => month_end.with_day(day).or_else(|| month_end.succ_opt()),
+        }
+    }
 }
 
 impl Rule {
-    /// Create a recurrence that occurs on a quarterly basis
+    /// Create a recurrence that occurs on a yearly basis
     pub fn yearly() -> Rule {
         Rule::Offset(RelativeDuration::months(12), 0)
     }
@@ -76,15 +268,343 @@ impl Rule {
     pub fn daily() -> Rule {
         Rule::Offset(RelativeDuration::days(1), 0)
     }
+
+    /// Create a recurrence that occurs twice a month on the 1st and the 15th
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+    /// let dates: Vec<NaiveDate> = Recurrence::with_start(Rule::semimonthly(), date(1, 10))
+    ///     .take(3)
+    ///     .collect();
+    ///
+    /// assert_eq!(dates, vec![date(1, 15), date(2, 1), date(2, 15)]);
+    /// ```
+    pub fn semimonthly() -> Rule {
+        Rule::MonthDays(RelativeDuration::months(1), vec![1, 15])
+    }
+
+    /// Create a recurrence that occurs every month on each of the days of the month
+    ///
+    /// Months which are too short for a day skip that day, use [Rule::MonthDays] with a negative
+    /// day to count from the end of the month instead.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+    /// let dates: Vec<NaiveDate> = Recurrence::with_start(Rule::monthly_on_days(&[16, 1]), date(1, 1))
+    ///     .take(3)
+    ///     .collect();
+    ///
+    /// assert_eq!(dates, vec![date(1, 1), date(1, 16), date(2, 1)]);
+    /// ```
+    pub fn monthly_on_days(days: &[u8]) -> Rule {
+        let mut days: Vec<i32> = days.iter().map(|d| i32::from(*d)).collect();
+        days.sort();
+        days.dedup();
+
+        Rule::MonthDays(RelativeDuration::months(1), days)
+    }
+
+    /// Create a recurrence that occurs every year on the month and day
+    ///
+    /// The policy decides what happens to Feb 29 outside of leap years, see [Rule::Annual].
+    ///
+    /// ```
+    /// use calends::{AnchorPolicy, Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let rule = Rule::yearly_on(2, 29, AnchorPolicy::RollForward);
+    /// let mut recur = Recurrence::with_start(rule, NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+    ///
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2023, 3, 1));
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2024, 2, 29));
+    /// ```
+    pub fn yearly_on(month: u32, day: u32, policy: AnchorPolicy) -> Rule {
+        Rule::Annual(month, day, policy)
+    }
+
+    /// Create a recurrence that occurs every year on the month day, such as every June 30
+    ///
+    /// ```
+    /// use calends::{month_day::LeapPolicy, MonthDay, Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let leap_day = MonthDay::new(2, 29).unwrap();
+    /// let rule = Rule::annually_on(leap_day, LeapPolicy::Skip);
+    /// let mut recur = Recurrence::with_start(rule, NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+    ///
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2024, 2, 29));
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2028, 2, 29));
+    /// ```
+    pub fn annually_on(month_day: MonthDay, policy: LeapPolicy) -> Rule {
+        Rule::Annual(month_day.month(), month_day.day(), policy.into())
+    }
+
+    /// Create a recurrence that occurs every year on a weekday of an ISO week
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let rule = Rule::yearly_week(26, Weekday::Mon);
+    /// let mut recur = Recurrence::with_start(rule, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    ///
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 6, 27));
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2023, 6, 26));
+    /// ```
+    pub fn yearly_week(week: u32, weekday: chrono::Weekday) -> Rule {
+        Rule::YearWeekday(week, weekday)
+    }
+
+    /// Create a recurrence that occurs every year on the nth day of the year
+    ///
+    /// Negative days count from the end of the year, see [Rule::YearDay].
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut recur =
+    ///     Recurrence::with_start(Rule::yearly_ordinal(100), NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+    ///
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2023, 4, 10));
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2024, 4, 9));
+    /// ```
+    pub fn yearly_ordinal(day: i32) -> Rule {
+        Rule::YearDay(day)
+    }
+
+    /// Create a recurrence that occurs every `n` of the grain e.g. every 3 weeks
+    ///
+    /// Returns [None] when `n` is zero or the duration is out of range
+    ///
+    /// ```
+    /// use calends::{grain::Grain, Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let rule = Rule::try_every(2, Grain::Month).unwrap();
+    /// let mut recur = Recurrence::with_start(rule, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    ///
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 1, 1));
+    /// assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 3, 1));
+    /// assert!(Rule::try_every(0, Grain::Week).is_none());
+    /// ```
+    pub fn try_every(n: u32, grain: Grain) -> Option<Rule> {
+        let n = i32::try_from(n).ok().filter(|n| *n > 0)?;
+        let duration = grain.into_duration().checked_mul(n)?;
+
+        Some(Rule::Offset(duration, 0))
+    }
+
+    /// Create a recurrence that occurs every `n` of the grain e.g. every 2 months
+    ///
+    /// # Panics
+    ///
+    /// Panics when `n` is zero or the duration is out of range, see [Rule::try_every]
+    #[cfg(not(feature = "strict"))]
+    pub fn every(n: u32, grain: Grain) -> Rule {
+        Rule::try_every(n, grain).expect("invalid interval for a recurrence")
+    }
+
+    /// Create a recurrence that occurs every week on each of the weekdays
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let rule = Rule::weekly_on(&[Weekday::Fri, Weekday::Mon, Weekday::Wed]);
+    /// let dates: Vec<NaiveDate> =
+    ///     Recurrence::with_start(rule, NaiveDate::from_ymd_opt(2022, 1, 5).unwrap())
+    ///         .take(4)
+    ///         .collect();
+    ///
+    /// assert_eq!(
+    ///     dates,
+    ///     vec![
+    ///         NaiveDate::from_ymd_opt(2022, 1, 5).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2022, 1, 7).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2022, 1, 10).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2022, 1, 12).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn weekly_on(weekdays: &[chrono::Weekday]) -> Rule {
+        let mut weekdays = weekdays.to_vec();
+        weekdays.sort_by_key(|d| d.num_days_from_monday());
+        weekdays.dedup();
+
+        Rule::Weekdays(RelativeDuration::weeks(1), weekdays)
+    }
+
+    /// Check that the rule describes a series which can produce dates
+    ///
+    /// Rules which fail validation produce no dates or end early when iterated, this reports why.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{recurrence::RuleError, RelativeDuration, Rule};
+    /// use chrono::Weekday;
+    ///
+    /// assert_eq!(Rule::monthly().validate(), Ok(()));
+    /// assert_eq!(
+    ///     Rule::Occurence(RelativeDuration::months(1), 6, Weekday::Mon).validate(),
+    ///     Err(RuleError::OccurenceOutOfRange(6))
+    /// );
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Result<(), RuleError> {
+        let forwards = |duration: &RelativeDuration| {
+            if is_forwards(duration) {
+                Ok(())
+            } else {
+                Err(RuleError::NotForwards(*duration))
+            }
+        };
+        let longest_period = |duration: &RelativeDuration| {
+            31 * duration.num_months() + 7 * duration.num_weeks() + duration.num_days()
+        };
+        let months_only = |duration: &RelativeDuration| {
+            if duration.num_weeks() == 0 && duration.num_days() == 0 {
+                Ok(())
+            } else {
+                Err(RuleError::UnsupportedDuration(*duration))
+            }
+        };
+
+        match self {
+            // Stepping from date to date only needs each step to move forwards
+            Rule::Offset(duration, 0) => {
+                let shortest = 28 * duration.num_months() as i64
+                    + 7 * duration.num_weeks() as i64
+                    + duration.num_days() as i64;
+                if duration.num_months() < 0 || shortest <= 0 {
+                    return Err(RuleError::NotForwards(*duration));
+                }
+            }
+            Rule::Offset(duration, offset) => {
+                forwards(duration)?;
+                // An offset of -1 is the last day, so both directions need a period of |offset| + 1
+                let needed = offset.unsigned_abs() as i64 + i64::from(*offset > 0);
+                if needed > longest_period(duration) as i64 {
+                    return Err(RuleError::OffsetOutOfRange(*offset));
+                }
+            }
+            Rule::Occurence(duration, occurence, _) => {
+                forwards(duration)?;
+                let longest = longest_period(duration);
+                if duration.num_months() == 0 && longest < 14 {
+                    return Err(RuleError::UnsupportedDuration(*duration));
+                }
+                if *occurence == 0 || occurence.unsigned_abs() > (longest as u32).div_ceil(7) {
+                    return Err(RuleError::OccurenceOutOfRange(*occurence));
+                }
+            }
+            Rule::Anchored(duration, _) => forwards(duration)?,
+            Rule::Weekdays(duration, weekdays) => {
+                forwards(duration)?;
+                if weekdays.is_empty() {
+                    return Err(RuleError::Empty);
+                }
+            }
+            Rule::MonthDays(duration, days) => {
+                forwards(duration)?;
+                months_only(duration)?;
+                if days.is_empty() {
+                    return Err(RuleError::Empty);
+                }
+                if let Some(day) = days.iter().find(|d| **d == 0 || d.abs() > 31) {
+                    return Err(RuleError::DayOutOfRange(*day));
+                }
+            }
+            Rule::Annual(month, day, _) => {
+                if NaiveDate::from_ymd_opt(2000, *month, *day).is_none() {
+                    return Err(RuleError::DayOutOfRange(*day as i32));
+                }
+            }
+            Rule::YearWeekday(week, _) => {
+                if !(1..=53).contains(week) {
+                    return Err(RuleError::WeekOutOfRange(*week));
+                }
+            }
+            Rule::YearDay(day) => {
+                if *day == 0 || day.abs() > 366 {
+                    return Err(RuleError::DayOutOfRange(*day));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the date is an occurence of the rule in a series starting on the anchor
+    ///
+    /// ```
+    /// use calends::Rule;
+    /// use chrono::NaiveDate;
+    ///
+    /// let anchor = NaiveDate::from_ymd_opt(2000, 1, 31).unwrap();
+    ///
+    /// assert!(Rule::monthly().matches(anchor, NaiveDate::from_ymd_opt(2030, 4, 30).unwrap()));
+    /// assert!(!Rule::monthly().matches(anchor, NaiveDate::from_ymd_opt(2030, 4, 29).unwrap()));
+    /// ```
+    pub fn matches(&self, anchor: NaiveDate, date: NaiveDate) -> bool {
+        Recurrence::with_start(self.clone(), anchor).contains(date)
+    }
+}
+
+/// Reasons a [Rule] can't produce a series, see [Rule::validate]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RuleError {
+    #[error("the duration {0} does not move forwards in time")]
+    NotForwards(RelativeDuration),
+    #[error("the duration {0} is not supported by this rule")]
+    UnsupportedDuration(RelativeDuration),
+    #[error("the offset {0} does not fit in any period")]
+    OffsetOutOfRange(i32),
+    #[error("the occurence {0} does not fit in any period")]
+    OccurenceOutOfRange(i32),
+    #[error("the day {0} does not exist")]
+    DayOutOfRange(i32),
+    #[error("the week {0} does not exist")]
+    WeekOutOfRange(u32),
+    #[error("the rule has no days to occur on")]
+    Empty,
 }
 
+/// First and last day of a period of a series
+type Period = (NaiveDate, NaiveDate);
+
 /// Evaluate an existing rule
-#[derive(Debug, Clone)]
+///
+/// Serializing a recurrence keeps its position so that the deserialized series resumes where it
+/// left off. The string form `R/<start>/<rule>` describes the whole series instead, see
+/// [format](super::format).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Recurrence {
     rule: Rule,
-    #[allow(dead_code)]
-    occurence_count: i32,
-    date: NaiveDate,
+    /// Next date in the series, [None] once the series has run past the supported range of dates
+    date: Option<NaiveDate>,
+    /// First date of the series
+    start: NaiveDate,
+    /// Number of periods since the start of the series
+    index: i64,
+    /// Last date the series can produce, see [Recurrence::with_horizon]
+    #[serde(default)]
+    horizon: Option<NaiveDate>,
+    /// Number of occurences left before the series ends, see [Recurrence::with_max_occurrences]
+    #[serde(default)]
+    remaining: Option<usize>,
+    /// First day of the weeks of weekday rules, see [Recurrence::with_week_start]
+    #[serde(default)]
+    week_start: WeekStart,
+    /// A date in the first week of the cycle of weekday rules, see [Recurrence::with_week_anchor]
+    #[serde(default)]
+    week_anchor: Option<NaiveDate>,
 }
 
 impl Recurrence {
@@ -104,11 +624,193 @@ impl Recurrence {
     pub fn with_start(rule: Rule, date: NaiveDate) -> Self {
         Self {
             rule,
-            occurence_count: 0,
-            date,
+            date: Some(date),
+            start: date,
+            index: 0,
+            horizon: None,
+            remaining: None,
+            week_start: WeekStart::default(),
+            week_anchor: None,
+        }
+    }
+
+    /// Starting point for the recurring series, checking the rule first
+    ///
+    /// See [Rule::validate] for the rules which are rejected.
+    pub fn try_with_start(rule: Rule, date: NaiveDate) -> Result<Self, RuleError> {
+        rule.validate()?;
+        Ok(Self::with_start(rule, date))
+    }
+
+    /// Yearly anniversaries of a date, from the first anniversary a year after the date
+    ///
+    /// The policy decides when anniversaries of February 29 fall outside leap years.
+    ///
+    /// ```
+    /// use calends::{month_day::LeapPolicy, Recurrence};
+    /// use chrono::NaiveDate;
+    ///
+    /// let hired = NaiveDate::from_ymd_opt(2020, 2, 29).unwrap();
+    /// let mut anniversaries = Recurrence::anniversaries_of(hired, LeapPolicy::Mar1);
+    ///
+    /// assert_eq!(anniversaries.next(), NaiveDate::from_ymd_opt(2021, 3, 1));
+    /// assert_eq!(anniversaries.nth(2), NaiveDate::from_ymd_opt(2024, 2, 29));
+    /// ```
+    pub fn anniversaries_of(date: NaiveDate, policy: LeapPolicy) -> Recurrence {
+        let rule = Rule::Annual(date.month(), date.day(), policy.into());
+
+        match date.succ_opt() {
+            Some(next) => Recurrence::with_start(rule, next),
+            None => Recurrence::with_start(rule, date).with_max_occurrences(0),
         }
     }
 
+    /// The `n`th yearly anniversary of a date, the date itself being the 0th
+    ///
+    /// Returns [None] when the year is out of range or the policy skips the year.
+    ///
+    /// ```
+    /// use calends::{month_day::LeapPolicy, Recurrence};
+    /// use chrono::NaiveDate;
+    ///
+    /// let hired = NaiveDate::from_ymd_opt(2020, 2, 29).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Recurrence::nth_anniversary(hired, 5, LeapPolicy::Feb28),
+    ///     NaiveDate::from_ymd_opt(2025, 2, 28)
+    /// );
+    /// assert_eq!(Recurrence::nth_anniversary(hired, 5, LeapPolicy::Skip), None);
+    /// ```
+    pub fn nth_anniversary(date: NaiveDate, n: u32, policy: LeapPolicy) -> Option<NaiveDate> {
+        let year = i32::try_from(n)
+            .ok()
+            .and_then(|n| date.year().checked_add(n))?;
+
+        AnchorPolicy::from(policy).resolve(date, year, date.month())
+    }
+
+    /// Occur at a time of day on each date of the series
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::{NaiveDate, NaiveTime};
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let time = NaiveTime::from_hms_opt(9, 30, 0).unwrap();
+    ///
+    /// let mut recur = Recurrence::with_start(Rule::monthly(), date).at(time);
+    /// assert_eq!(recur.next(), Some(date.and_time(time)));
+    /// ```
+    pub fn at(self, time: NaiveTime) -> DateTimeRecurrence {
+        DateTimeRecurrence::new(self, time)
+    }
+
+    /// Start the weeks of [Rule::Weekdays] on another day than Monday, like `WKST` in RFC 5545
+    ///
+    /// The week start decides which days share a week, which matters for rules repeating every
+    /// other week or more.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{RelativeDuration, Recurrence, Rule, WeekStart};
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let rule = Rule::Weekdays(RelativeDuration::weeks(2), vec![Weekday::Mon, Weekday::Sun]);
+    /// // 2022-01-03 is a Monday
+    /// let start = NaiveDate::from_ymd_opt(2022, 1, 3).unwrap();
+    /// let date = |d| NaiveDate::from_ymd_opt(2022, 1, d).unwrap();
+    ///
+    /// let monday_weeks: Vec<_> = Recurrence::with_start(rule.clone(), start).take(3).collect();
+    /// assert_eq!(monday_weeks, vec![date(3), date(9), date(17)]);
+    ///
+    /// let sunday_weeks: Vec<_> = Recurrence::with_start(rule, start)
+    ///     .with_week_start(WeekStart::SUNDAY)
+    ///     .take(3)
+    ///     .collect();
+    /// assert_eq!(sunday_weeks, vec![date(3), date(16), date(17)]);
+    /// # }
+    /// ```
+    pub fn with_week_start(mut self, week_start: WeekStart) -> Self {
+        self.week_start = week_start;
+        self
+    }
+
+    /// Count the weeks of [Rule::Weekdays] from the week containing the anchor instead of the
+    /// week the series starts in
+    ///
+    /// A biweekly rule then keeps to a cycle such as a payroll, whichever date the series starts
+    /// on. The anchor can be before or after the start of the series. Rules stepping by months are
+    /// not affected.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{RelativeDuration, Recurrence, Rule};
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+    /// let paydays = Rule::Weekdays(RelativeDuration::weeks(2), vec![Weekday::Fri]);
+    ///
+    /// // The first payday was 2022-01-07, the series starts the week after
+    /// let mut recur = Recurrence::with_start(paydays, date(1, 10)).with_week_anchor(date(1, 7));
+    /// assert_eq!(recur.next(), Some(date(1, 21)));
+    /// assert_eq!(recur.next(), Some(date(2, 4)));
+    /// # }
+    /// ```
+    pub fn with_week_anchor(mut self, anchor: NaiveDate) -> Self {
+        self.week_anchor = Some(anchor);
+        self
+    }
+
+    /// End the series after the date, no matter what the rule is
+    ///
+    /// Unlike [Recurrence::until] the horizon stays with the series through adaptors such as
+    /// [Recurrence::between], which makes it suitable as a safety limit on rules loaded from
+    /// elsewhere.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let horizon = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+    ///
+    /// let recur = Recurrence::with_start(Rule::monthly(), date).with_horizon(horizon);
+    /// assert_eq!(recur.last(), NaiveDate::from_ymd_opt(2022, 12, 1));
+    /// ```
+    pub fn with_horizon(mut self, date: NaiveDate) -> Self {
+        self.horizon = Some(date);
+        self
+    }
+
+    /// End the series after a number of occurences, no matter what the rule is
+    ///
+    /// Like [Recurrence::with_horizon] the limit is a safety net which stays with the series, use
+    /// [Recurrence::count] to take a number of occurences.
+    pub fn with_max_occurrences(mut self, n: usize) -> Self {
+        self.remaining = Some(n);
+        self
+    }
+
+    /// The rule of the series
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    /// First date of the series
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    /// Last date the series can produce, see [Recurrence::with_horizon]
+    pub fn horizon(&self) -> Option<NaiveDate> {
+        self.horizon
+    }
+
+    /// Number of occurences left before the series ends, see [Recurrence::with_max_occurrences]
+    pub fn remaining(&self) -> Option<usize> {
+        self.remaining
+    }
+
     /// Iterate up to a date
     ///
     /// ```
@@ -124,7 +826,8 @@ impl Recurrence {
     /// assert_eq!(recur.next(), None);
     /// ```
     pub fn until(&self, date: NaiveDate) -> Until<Recurrence> {
-        Until::exclusive(date, self.clone())
+        let hint = self.count_hint(date.pred_opt());
+        Until::exclusive(date, self.clone()).with_hint(hint)
     }
 
     /// Iterate up to and including the date
@@ -142,36 +845,1830 @@ impl Recurrence {
     /// assert_eq!(recur.next(), None);
     /// ```
     pub fn until_and_including(&self, date: NaiveDate) -> Until<Recurrence> {
-        Until::inclusive(date, self.clone())
+        let hint = self.count_hint(Some(date));
+        Until::inclusive(date, self.clone()).with_hint(hint)
     }
-}
 
-impl Iterator for Recurrence {
-    type Item = NaiveDate;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let date = self.date;
+    /// Iterate a fixed number of occurences
+    ///
+    /// This takes precedence over [Iterator::count] on a `Recurrence`, which would never finish
+    /// for most rules anyway.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+    ///
+    /// let payments: Vec<NaiveDate> = Recurrence::with_start(Rule::monthly(), date).count(12).collect();
+    /// assert_eq!(payments.len(), 12);
+    /// assert_eq!(payments.last(), NaiveDate::from_ymd_opt(2022, 12, 31).as_ref());
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn count(self, n: usize) -> Take<Recurrence> {
+        self.take(n)
+    }
+
+    /// Iterate up to and including a date or a number of occurences, whichever comes first
+    ///
+    /// Mirrors the behaviour of `UNTIL` and `COUNT` in RFC 5545
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+    ///
+    /// let recur = Recurrence::with_start(Rule::monthly(), date);
+    /// assert_eq!(recur.until_or_count(end, 12).count(), 3);
+    /// assert_eq!(recur.until_or_count(end, 2).count(), 2);
+    /// ```
+    pub fn until_or_count(&self, date: NaiveDate, n: usize) -> Take<Until<Recurrence>> {
+        self.until_and_including(date).take(n)
+    }
+
+    /// Iterate the occurences along with their number and the period of the rule they fall in
+    ///
+    /// Occurences are numbered from zero. The period runs from the start of the cycle of the rule
+    /// to the day before the next cycle starts.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{IntervalLike, Recurrence, RelativeDuration, Rule};
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let rule = Rule::Occurence(RelativeDuration::months(1), 1, Weekday::Mon);
+    /// let mut recur = Recurrence::with_start(rule, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap())
+    ///     .enumerated();
+    ///
+    /// recur.next();
+    /// let occurence = recur.next().unwrap();
+    ///
+    /// assert_eq!(occurence.index, 1);
+    /// assert_eq!(occurence.date, NaiveDate::from_ymd_opt(2022, 2, 7).unwrap());
+    /// assert_eq!(occurence.period.start_opt(), NaiveDate::from_ymd_opt(2022, 2, 1));
+    /// assert_eq!(occurence.period.end_opt(), NaiveDate::from_ymd_opt(2022, 2, 28));
+    /// # }
+    /// ```
+    pub fn enumerated(self) -> Enumerated {
+        Enumerated {
+            recur: self,
+            index: 0,
+        }
+    }
+
+    /// Occurences within a window (inclusive) without iterating from the start of the series
+    ///
+    /// The series jumps straight to the window where the rule allows it, which is every rule
+    /// except those stepping by months and days together or by months from late in the month.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let recur = Recurrence::with_start(Rule::weekly(), NaiveDate::from_ymd_opt(2010, 1, 4).unwrap());
+    /// let dates: Vec<NaiveDate> = recur
+    ///     .between(
+    ///         NaiveDate::from_ymd_opt(2030, 3, 1).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2030, 3, 14).unwrap(),
+    ///     )
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     dates,
+    ///     vec![
+    ///         NaiveDate::from_ymd_opt(2030, 3, 4).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2030, 3, 11).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn between(&self, window_start: NaiveDate, window_end: NaiveDate) -> Until<Recurrence> {
+        let mut recur = self.clone();
+        recur.skip_before(window_start);
+        recur.until_and_including(window_end)
+    }
+
+    /// Whether the date is one of the remaining occurences
+    ///
+    /// The series jumps straight to the date in the same way as [Recurrence::between] so the
+    /// check doesn't depend on how far the date is from the start of the series. A series with a
+    /// maximum number of occurences is iterated up to the date.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let recur = Recurrence::with_start(
+    ///     Rule::weekly_on(&[Weekday::Mon, Weekday::Thu]),
+    ///     NaiveDate::from_ymd_opt(2010, 1, 4).unwrap(),
+    /// );
+    ///
+    /// assert!(recur.contains(NaiveDate::from_ymd_opt(2030, 3, 7).unwrap()));
+    /// assert!(!recur.contains(NaiveDate::from_ymd_opt(2030, 3, 8).unwrap()));
+    /// ```
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        if self.horizon.is_some_and(|horizon| date > horizon) {
+            return false;
+        }
+
+        let mut recur = self.clone();
+        recur.skip_before(date);
+        recur.next() == Some(date)
+    }
+
+    /// A page of the remaining occurences, counting pages from zero
+    ///
+    /// The series jumps to the first occurence of the page in the same way as [Recurrence::nth].
+    /// Pages past the end of the series are empty.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let recur = Recurrence::with_start(Rule::monthly(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    ///
+    /// assert_eq!(
+    ///     recur.page(2, 3),
+    ///     vec![
+    ///         NaiveDate::from_ymd_opt(2022, 7, 1).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2022, 8, 1).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn page(&self, page_size: usize, page_index: usize) -> Vec<NaiveDate> {
+        let first = match page_size.checked_mul(page_index).and_then(|n| self.nth(n)) {
+            Some(first) => first,
+            None => return Vec::new(),
+        };
+
+        let mut recur = self.clone();
+        recur.skip_before(first);
+        recur.take(page_size).collect()
+    }
+
+    /// The series continuing after the date, for resuming from the last occurence served
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let recur = Recurrence::with_start(Rule::monthly(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    /// let first_page: Vec<NaiveDate> = recur.clone().take(3).collect();
+    ///
+    /// let mut resumed = recur.resume_from(*first_page.last().unwrap());
+    /// assert_eq!(resumed.next(), NaiveDate::from_ymd_opt(2022, 4, 1));
+    /// ```
+    pub fn resume_from(&self, date: NaiveDate) -> Recurrence {
+        let mut recur = self.clone();
+        match date.succ_opt() {
+            Some(next) => recur.skip_before(next),
+            None => recur.date = None,
+        }
+        recur
+    }
+
+    /// The nth remaining occurence counting from zero, without advancing the series
+    ///
+    /// Gives the same date as [Iterator::nth] but is computed directly for rules with a fixed
+    /// step, such as daily or weekly rules or anchored monthly rules. Unlike [Iterator::nth] the
+    /// series is not advanced, use `Iterator::nth(&mut recur, n)` to skip occurences.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let recur = Recurrence::with_start(Rule::daily(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    /// assert_eq!(recur.nth(1_000_000), NaiveDate::from_ymd_opt(4759, 11, 29));
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn nth(&self, n: usize) -> Option<NaiveDate> {
+        if self.remaining.is_some_and(|remaining| n >= remaining) {
+            return None;
+        }
+
+        let found = self.nth_of_rule(n)?;
+        self.horizon
+            .is_none_or(|horizon| found <= horizon)
+            .then_some(found)
+    }
+
+    /// The nth remaining occurence of the rule ignoring the horizon and maximum occurences
+    fn nth_of_rule(&self, n: usize) -> Option<NaiveDate> {
+        let date = self.date?;
+        let n = i64::try_from(n).ok()?;
+
+        match &self.rule {
+            Rule::Offset(duration, 0) if is_forwards(duration) => {
+                let months = duration.num_months() as i64;
+                let step = 7 * duration.num_weeks() as i64 + duration.num_days() as i64;
+
+                if months == 0 {
+                    return date.checked_add_signed(Duration::days(n.checked_mul(step)?));
+                } else if step == 0 && keeps_day_of_month(date) {
+                    return checked_shift_months(date, i32::try_from(n.checked_mul(months)?).ok()?);
+                }
+            }
+            // Every period of these policies has exactly one date
+            Rule::Anchored(duration, policy) if *policy != AnchorPolicy::SkipMissing => {
+                return self.anchored(duration, *policy, self.index.checked_add(n)?);
+            }
+            _ => {}
+        }
+
+        Iterator::nth(&mut self.clone(), n as usize)
+    }
+
+    /// The first remaining occurence after the date
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let recur = Recurrence::with_start(Rule::monthly(), NaiveDate::from_ymd_opt(2010, 1, 15).unwrap());
+    /// let now = NaiveDate::from_ymd_opt(2030, 3, 15).unwrap();
+    ///
+    /// assert_eq!(recur.next_after(now), NaiveDate::from_ymd_opt(2030, 4, 15));
+    /// assert_eq!(recur.previous_before(now), NaiveDate::from_ymd_opt(2030, 2, 15));
+    /// ```
+    pub fn next_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let mut recur = self.clone();
+        recur.fast_forward(date.succ_opt()?);
+        recur.find(|d| *d > date)
+    }
+
+    /// The last remaining occurence before the date
+    ///
+    /// Searches windows before the date which double in size until an occurence is found.
+    pub fn previous_before(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let first = self.date?;
+        let last = date.pred_opt()?;
+        if last < first {
+            return None;
+        }
+
+        let mut lookback = 32;
+        loop {
+            let window_start = last
+                .checked_sub_signed(Duration::days(lookback))
+                .filter(|d| *d > first)
+                .unwrap_or(first);
+
+            if let Some(found) = self.between(window_start, last).last() {
+                return Some(found);
+            }
+            if window_start == first {
+                return None;
+            }
+            lookback = lookback.saturating_mul(2);
+        }
+    }
+
+    /// Iterate the remaining occurences on or before the date backwards in time
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let recur = Recurrence::with_start(Rule::monthly(), NaiveDate::from_ymd_opt(2010, 1, 15).unwrap());
+    /// let closed = NaiveDate::from_ymd_opt(2030, 3, 15).unwrap();
+    ///
+    /// let dates: Vec<NaiveDate> = recur.rev_from(closed).take(3).collect();
+    /// assert_eq!(
+    ///     dates,
+    ///     vec![
+    ///         NaiveDate::from_ymd_opt(2030, 3, 15).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2030, 2, 15).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2030, 1, 15).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn rev_from(&self, date: NaiveDate) -> RevRecurrence {
+        RevRecurrence {
+            recur: self.clone(),
+            end: Some(date),
+            lookback: 32,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Snap the series to the calendar periods of its rule
+    ///
+    /// A rule stepping by months starts on the first of the month, by quarters on the first of the
+    /// quarter and so on, by weeks on a Monday. Occurences before the start of the series are
+    /// skipped, so a monthly series started on Jan 15 occurs on Feb 1, Mar 1 and so on. A series
+    /// which has already been iterated continues from its next occurence. See
+    /// [Recurrence::aligned_to_period_keeping_offset] to keep the day within the period instead.
+    ///
+    /// Rules which are already fixed to the calendar, and rules stepping by days, are unchanged.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let recur = Recurrence::with_start(Rule::monthly(), NaiveDate::from_ymd_opt(2022, 1, 15).unwrap());
+    /// let mut aligned = recur.aligned_to_period();
+    ///
+    /// assert_eq!(aligned.next(), NaiveDate::from_ymd_opt(2022, 2, 1));
+    /// assert_eq!(aligned.next(), NaiveDate::from_ymd_opt(2022, 3, 1));
+    /// ```
+    pub fn aligned_to_period(&self) -> Recurrence {
+        self.align(false)
+    }
+
+    /// Snap the series to the calendar periods of its rule keeping the offset of the start
+    ///
+    /// A monthly series started on Jan 31 occurs on the 31st of every month which has one, rather
+    /// than moving to the end of shorter months.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let recur = Recurrence::with_start(Rule::monthly(), NaiveDate::from_ymd_opt(2022, 1, 31).unwrap());
+    /// let mut aligned = recur.aligned_to_period_keeping_offset();
+    ///
+    /// assert_eq!(aligned.next(), NaiveDate::from_ymd_opt(2022, 1, 31));
+    /// assert_eq!(aligned.next(), NaiveDate::from_ymd_opt(2022, 3, 31));
+    /// ```
+    pub fn aligned_to_period_keeping_offset(&self) -> Recurrence {
+        self.align(true)
+    }
+
+    fn align(&self, keep_offset: bool) -> Recurrence {
+        let (duration, offset) = match &self.rule {
+            Rule::Offset(duration, offset) => (duration, Some(*offset)),
+            Rule::Occurence(duration, ..) | Rule::Anchored(duration, _) => (duration, None),
+            _ => return self.clone(),
+        };
+        let Some(grain) = grain_of(duration) else {
+            return self.clone();
+        };
+
+        let start = grain.truncate(self.start);
+        let rule = match (&self.rule, offset) {
+            (_, Some(0)) if keep_offset => Rule::Offset(
+                *duration,
+                i32::try_from((self.start - start).num_days()).unwrap_or(i32::MAX),
+            ),
+            (Rule::Anchored(..), _) if keep_offset => return self.clone(),
+            (rule, _) => rule.clone(),
+        };
+
+        let mut recur = Recurrence {
+            rule,
+            date: Some(start),
+            start,
+            index: 0,
+            ..self.clone()
+        };
+        if let Some(date) = self.date {
+            recur.skip_before(date);
+        }
+        recur
+    }
+
+    /// Move past every occurence before the date
+    ///
+    /// Occurences are counted one by one when the series has a maximum number of occurences.
+    fn skip_before(&mut self, date: NaiveDate) {
+        if self.remaining.is_none() {
+            self.fast_forward(date);
+        }
+
+        // The jump lands on the period containing the date, skip what comes before it
+        loop {
+            let mut probe = self.clone();
+            match probe.next() {
+                Some(found) if found < date => *self = probe,
+                _ => break,
+            }
+        }
+    }
+
+    /// Move the series as close to the target as possible without passing any occurence on or
+    /// after the target
+    fn fast_forward(&mut self, target: NaiveDate) {
+        let date = match self.date {
+            Some(date) if date < target => date,
+            _ => return,
+        };
+
+        let month_index = |d: NaiveDate| d.year() as i64 * 12 + d.month0() as i64;
+
+        match &self.rule {
+            Rule::Offset(duration, _) | Rule::Occurence(duration, ..) if is_forwards(duration) => {
+                let months = duration.num_months() as i64;
+                let step = 7 * duration.num_weeks() as i64 + duration.num_days() as i64;
+
+                let jumped = if months == 0 {
+                    let periods = (target - date).num_days() / step;
+                    date.checked_add_signed(Duration::days(periods * step))
+                } else if step == 0 && keeps_day_of_month(date) {
+                    let periods = (month_index(target) - month_index(date)) / months;
+                    i32::try_from(periods * months)
+                        .ok()
+                        .and_then(|months| checked_shift_months(date, months))
+                        .map(|jumped| {
+                            if jumped > target {
+                                shift_months(jumped, -duration.num_months())
+                            } else {
+                                jumped
+                            }
+                        })
+                } else {
+                    None
+                };
+
+                if let Some(jumped) = jumped {
+                    self.date = Some(jumped);
+                }
+            }
+            Rule::Anchored(duration, _) if is_forwards(duration) => {
+                let months = duration.num_months() as i64;
+                if months == 0 || duration.num_weeks() != 0 || duration.num_days() != 0 {
+                    return;
+                }
+
+                // One period early as rolling forward can move a date into the next month
+                let index = (month_index(target) - month_index(self.start)) / months - 1;
+                if index > self.index {
+                    self.index = index;
+                    self.date = self.anchored(duration, AnchorPolicy::ClampDay, index);
+                }
+            }
+            Rule::Weekdays(duration, _) if is_forwards(duration) => {
+                if duration.num_months() != 0 {
+                    return;
+                }
+
+                let step = 7 * duration.num_weeks() as i64 + duration.num_days() as i64;
+                let first_week = self.first_week(duration);
+                let index = (target - first_week).num_days() / step;
+                if index > self.index {
+                    self.index = index;
+                    self.date = first_week.checked_add_signed(Duration::days(index * step));
+                }
+            }
+            Rule::MonthDays(duration, _) if is_forwards(duration) => {
+                let months = duration.num_months() as i64;
+                if months == 0 || duration.num_weeks() != 0 || duration.num_days() != 0 {
+                    return;
+                }
+
+                let index = (month_index(target) - month_index(self.start)) / months;
+                if index > self.index {
+                    self.index = index;
+                    self.date = i32::try_from(index * months).ok().and_then(|months| {
+                        checked_shift_months(beginning_of_month(&self.start), months)
+                    });
+                }
+            }
+            Rule::Annual(..) | Rule::YearWeekday(..) | Rule::YearDay(_) => {
+                let jumped = match self.rule {
+                    Rule::YearWeekday(..) => {
+                        NaiveDate::from_isoywd_opt(target.iso_week().year(), 1, Weekday::Mon)
+                    }
+                    _ => NaiveDate::from_ymd_opt(target.year(), 1, 1),
+                };
+                if let Some(jumped) = jumped {
+                    if jumped > date {
+                        self.date = Some(jumped);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Next date of the series along with the first and last day of the period it falls in
+    fn next_with_period(&mut self) -> Option<(NaiveDate, Period)> {
+        if self.remaining == Some(0) {
+            self.date = None;
+            return None;
+        }
+
+        match self.next_of_rule() {
+            Some((date, _)) if self.horizon.is_some_and(|horizon| date > horizon) => {
+                self.date = None;
+                None
+            }
+            Some(next) => {
+                if let Some(remaining) = &mut self.remaining {
+                    *remaining -= 1;
+                }
+                Some(next)
+            }
+            None => None,
+        }
+    }
+
+    /// Next date of the rule ignoring the horizon and the maximum number of occurences
+    fn next_of_rule(&mut self) -> Option<(NaiveDate, Period)> {
+        let date = self.date?;
+
+        // Weekday and day of the month rules are not cloned to avoid allocating on every step
+        match self.rule {
+            Rule::Weekdays(..) => return self.next_weekday(),
+            Rule::MonthDays(..) => return self.next_month_day(),
+            _ => {}
+        }
+
+        match self.rule.clone() {
+            Rule::Offset(duration, 0) => {
+                // A step which doesn't move forwards would repeat dates forever
+                self.date = duration.checked_add_to(date).filter(|next| *next > date);
+                Some((date, (date, period_end(self.date))))
+            }
+            Rule::Offset(duration, offset) => {
+                // An offset of -1 is the last day, so both directions need a period of |offset| + 1
+                let needed = offset.unsigned_abs() as i64 + i64::from(offset > 0);
+                self.next_in_period(duration, needed, |start, end| {
+                    let found = match offset {
+                        1.. => start.checked_add_signed(Duration::days(offset.into()))?,
+                        _ => end.checked_add_signed(Duration::days((offset + 1).into()))?,
+                    };
+                    (start <= found && found <= end).then_some(found)
+                })
+            }
+            Rule::Anchored(duration, policy) => loop {
+                let index = self.index;
+                self.index += 1;
+                self.date = self.anchored(&duration, AnchorPolicy::ClampDay, index + 1);
+
+                if let Some(date) = self.anchored(&duration, policy, index) {
+                    // Periods run between the clamped anchors so rolled forward dates stay inside
+                    let start = self
+                        .anchored(&duration, AnchorPolicy::ClampDay, index)
+                        .map_or(date, |start| start.min(date));
+                    return Some((date, (start, period_end(self.date))));
+                }
+                self.date?;
+            },
+            Rule::Weekdays(..) => self.next_weekday(),
+            Rule::MonthDays(..) => self.next_month_day(),
+            Rule::Annual(..) | Rule::YearWeekday(..) | Rule::YearDay(_) => self.next_yearly(),
+            Rule::Occurence(duration, occurence, weekday) => {
+                let needed = 7 * (occurence.unsigned_abs() as i64 - 1) + 1;
+                if occurence == 0 {
+                    self.date = None;
+                    return None;
+                }
+                self.next_in_period(duration, needed, |start, end| {
+                    find_weekday_between(weekday, start, end, occurence)
+                })
+            }
+        }
+    }
+
+    /// Next date of a weekday series
+    fn next_weekday(&mut self) -> Option<(NaiveDate, Period)> {
+        let Rule::Weekdays(duration, weekdays) = &self.rule else {
+            return None;
+        };
+
+        if !is_forwards(duration) || weekdays.is_empty() {
+            self.date = None;
+            return None;
+        }
+
+        let week_start = self.week_start;
+        let first_week = self.first_week(duration);
+        let week = |index: i64| {
+            let months = i32::try_from(index * duration.num_months() as i64).ok()?;
+            let days = index * (7 * duration.num_weeks() as i64 + duration.num_days() as i64);
+            checked_shift_months(first_week, months)?.checked_add_signed(Duration::days(days))
+        };
+
+        loop {
+            let cursor = self.date?;
+            let start = week(self.index)?;
+
+            let found = weekdays
+                .iter()
+                .filter_map(|d| {
+                    start.checked_add_signed(Duration::days(week_start.days_from_start(*d).into()))
+                })
+                .filter(|d| *d >= cursor)
+                .min();
+
+            match found {
+                Some(found) => {
+                    self.date = found.succ_opt();
+                    return Some((found, (start, period_end(week(self.index + 1)))));
+                }
+                None => self.index += 1,
+            }
+        }
+    }
+
+    /// First day of the first week of a weekday series, which may be before the start when the
+    /// weeks are anchored elsewhere
+    fn first_week(&self, duration: &RelativeDuration) -> NaiveDate {
+        let week = beginning_of_week_starting(&self.start, self.week_start);
+        let step = 7 * duration.num_weeks() as i64 + duration.num_days() as i64;
+
+        match self.week_anchor {
+            Some(anchor) if duration.num_months() == 0 && step > 0 => {
+                let anchor = beginning_of_week_starting(&anchor, self.week_start);
+                week - Duration::days((week - anchor).num_days().rem_euclid(step))
+            }
+            _ => week,
+        }
+    }
+
+    /// Next date of a days of the month series
+    fn next_month_day(&mut self) -> Option<(NaiveDate, Period)> {
+        let Rule::MonthDays(duration, days) = &self.rule else {
+            return None;
+        };
+
+        let months = duration.num_months() as i64;
+        let valid = |d: &i32| *d != 0 && d.abs() <= 31;
+        if !is_forwards(duration)
+            || duration.num_weeks() != 0
+            || duration.num_days() != 0
+            || !days.iter().any(valid)
+        {
+            self.date = None;
+            return None;
+        }
+
+        let first = beginning_of_month(&self.start);
+        let month_start =
+            |index: i64| checked_shift_months(first, i32::try_from(index * months).ok()?);
+
+        loop {
+            let cursor = self.date?;
+
+            let start = month_start(self.index)?;
+            let length = checked_month_end(start.year(), start.month())?.day() as i32;
+
+            let found = days
+                .iter()
+                .filter(|d| valid(d))
+                .map(|d| if *d > 0 { *d } else { length + d + 1 })
+                .filter(|d| 1 <= *d && *d <= length)
+                .filter_map(|d| start.with_day(d as u32))
+                .filter(|d| *d >= cursor)
+                .min();
+
+            match found {
+                Some(found) => {
+                    self.date = found.succ_opt();
+                    return Some((found, (start, period_end(month_start(self.index + 1)))));
+                }
+                None => self.index += 1,
+            }
+        }
+    }
+
+    /// Next date of a rule which occurs once in each year
+    ///
+    /// Years are calendar years except for ISO week rules which use ISO years.
+    fn next_yearly(&mut self) -> Option<(NaiveDate, Period)> {
+        let valid = match self.rule {
+            Rule::Annual(month, day, _) => NaiveDate::from_ymd_opt(2000, month, day).is_some(),
+            Rule::YearWeekday(week, _) => (1..=53).contains(&week),
+            Rule::YearDay(day) => day != 0 && day.abs() <= 366,
+            _ => false,
+        };
+        if !valid {
+            self.date = None;
+            return None;
+        }
+
+        let calendar_year = |year: i32| {
+            Some((
+                NaiveDate::from_ymd_opt(year, 1, 1)?,
+                NaiveDate::from_ymd_opt(year, 12, 31)?,
+            ))
+        };
+
+        loop {
+            let cursor = self.date?;
+
+            let (found, (start, end)) = match self.rule {
+                Rule::Annual(month, day, policy) => {
+                    let anchor = NaiveDate::from_ymd_opt(2000, month, day)?;
+                    let year = cursor.year();
+                    (policy.resolve(anchor, year, month), calendar_year(year)?)
+                }
+                Rule::YearWeekday(week, weekday) => {
+                    let year = cursor.iso_week().year();
+                    let start = NaiveDate::from_isoywd_opt(year, 1, Weekday::Mon)?;
+                    let end = year
+                        .checked_add(1)
+                        .and_then(|next| NaiveDate::from_isoywd_opt(next, 1, Weekday::Mon))
+                        .and_then(|next| next.pred_opt())
+                        .unwrap_or(NaiveDate::MAX);
+                    (
+                        NaiveDate::from_isoywd_opt(year, week, weekday),
+                        (start, end),
+                    )
+                }
+                Rule::YearDay(day) => {
+                    let (start, end) = calendar_year(cursor.year())?;
+                    let length = end.ordinal() as i32;
+                    let day = if day > 0 { day } else { length + day + 1 };
+                    let found = (1 <= day && day <= length)
+                        .then(|| start.with_ordinal(day as u32))
+                        .flatten();
+                    (found, (start, end))
+                }
+                _ => return None,
+            };
+
+            match found.filter(|d| *d >= cursor) {
+                Some(found) => {
+                    self.date = found.succ_opt();
+                    return Some((found, (start, end)));
+                }
+                None => self.date = end.succ_opt(),
+            }
+        }
+    }
+
+    /// Date of the nth period of an anchored series
+    fn anchored(
+        &self,
+        duration: &RelativeDuration,
+        policy: AnchorPolicy,
+        index: i64,
+    ) -> Option<NaiveDate> {
+        let months = self.start.year() as i64 * 12
+            + self.start.month0() as i64
+            + index * duration.num_months() as i64;
+        let days = index * (7 * duration.num_weeks() as i64 + duration.num_days() as i64);
+
+        let year = i32::try_from(months.div_euclid(12)).ok()?;
+        let month = months.rem_euclid(12) as u32 + 1;
+
+        policy
+            .resolve(self.start, year, month)?
+            .checked_add_signed(Duration::days(days))
+    }
+
+    /// Walk the periods of the series until one of them contains a date
+    ///
+    /// Periods need to be at least `needed` days long to contain a date, rules where no period
+    /// can ever be that long end the series.
+    fn next_in_period<F>(
+        &mut self,
+        duration: RelativeDuration,
+        needed: i64,
+        find: F,
+    ) -> Option<(NaiveDate, Period)>
+    where
+        F: Fn(NaiveDate, NaiveDate) -> Option<NaiveDate>,
+    {
+        let longest_period = 31 * duration.num_months() as i64
+            + 7 * duration.num_weeks() as i64
+            + duration.num_days() as i64;
+        if !is_forwards(&duration) || needed > longest_period {
+            self.date = None;
+            return None;
+        }
+
+        loop {
+            let start = self.date?;
+            let next = duration.checked_add_to(start);
+            self.date = next;
+
+            let end = period_end(next);
+            if let Some(found) = find(start, end) {
+                return Some((found, (start, end)));
+            }
+        }
+    }
+
+    /// Bounds on the number of occurences on or before the last date
+    ///
+    /// Exact for most rules, stepping by months from late in the month is only bounded as the day
+    /// of the month can be clamped towards the end of the month.
+    fn count_hint(&self, last: Option<NaiveDate>) -> (usize, Option<usize>) {
+        let last = match (last, self.horizon) {
+            (Some(last), Some(horizon)) => Some(last.min(horizon)),
+            (last, None) => last,
+            (None, horizon) => horizon,
+        };
+        let (lower, upper) = self.rule_count_hint(last);
+
+        match self.remaining {
+            Some(remaining) => (
+                lower.min(remaining),
+                Some(upper.map_or(remaining, |upper| upper.min(remaining))),
+            ),
+            None => (lower, upper),
+        }
+    }
+
+    /// Bounds on the number of occurences of the rule on or before the last date
+    fn rule_count_hint(&self, last: Option<NaiveDate>) -> (usize, Option<usize>) {
+        let (duration, exact) = match &self.rule {
+            // Every date between the next date and the last could be produced
+            Rule::Weekdays(..)
+            | Rule::MonthDays(..)
+            | Rule::Annual(..)
+            | Rule::YearWeekday(..)
+            | Rule::YearDay(_) => {
+                return match (self.date, last) {
+                    (Some(date), Some(last)) if last >= date => {
+                        (0, Some((last - date).num_days() as usize + 1))
+                    }
+                    _ => (0, Some(0)),
+                };
+            }
+            Rule::Offset(duration, offset) => (duration, *offset == 0),
+            Rule::Occurence(duration, ..) => (duration, false),
+            Rule::Anchored(duration, _) => (duration, keeps_day_of_month(self.start)),
+        };
+
+        if !is_forwards(duration) {
+            return (0, None);
+        }
+
+        // Dates within a period can be skipped, so only the number of periods is known
+        if !exact {
+            let periods = Recurrence {
+                rule: Rule::Offset(*duration, 0),
+                ..self.clone()
+            };
+            return (0, periods.rule_count_hint(last).1);
+        }
+
+        let (months, weeks, days) = (
+            duration.num_months(),
+            duration.num_weeks(),
+            duration.num_days(),
+        );
+
+        let (date, last) = match (self.date, last) {
+            (Some(date), Some(last)) if last >= date => (date, last),
+            _ => return (0, Some(0)),
+        };
+
+        let step = 7 * weeks as i64 + days as i64;
+        if months == 0 {
+            let count = ((last - date).num_days() / step + 1) as usize;
+            return (count, Some(count));
+        }
+
+        let months = months as i64;
+        let month_index = |d: NaiveDate| d.year() as i64 * 12 + d.month0() as i64;
+        let diff = month_index(last) - month_index(date);
+        let upper = (diff / months + 1) as usize;
+
+        if step == 0 && keeps_day_of_month(date) {
+            let count = if diff % months == 0 && date.day() > last.day() {
+                upper - 1
+            } else {
+                upper
+            };
+            return (count, Some(count));
+        }
+
+        let lower = if step > 0 || diff == 0 {
+            1
+        } else {
+            ((diff - 1) / months + 1) as usize
+        };
+
+        (lower, Some(upper))
+    }
+}
+
+/// Calendar period a duration steps by, [None] for days or durations mixing units
+fn grain_of(duration: &RelativeDuration) -> Option<Grain> {
+    match (
+        duration.num_months(),
+        duration.num_weeks(),
+        duration.num_days(),
+    ) {
+        (1.., 0, 0) if duration.num_months() % 12 == 0 => Some(Grain::Year),
+        (1.., 0, 0) if duration.num_months() % 6 == 0 => Some(Grain::Half),
+        (1.., 0, 0) if duration.num_months() % 3 == 0 => Some(Grain::Quarter),
+        (1.., 0, 0) => Some(Grain::Month),
+        (0, 1.., 0) => Some(Grain::Week),
+        _ => None,
+    }
+}
+
+/// Last day of a period given the start of the next period, which is out of range when [None]
+fn period_end(next: Option<NaiveDate>) -> NaiveDate {
+    next.and_then(|next| next.pred_opt())
+        .unwrap_or(NaiveDate::MAX)
+}
+
+/// Whether stepping by months keeps the day of the month
+///
+/// Days up to the 27th are never clamped, while the 28th can be the last day of February and is
+/// then pinned to the end of the following months.
+fn keeps_day_of_month(date: NaiveDate) -> bool {
+    date.day() <= 27
+}
+
+/// Whether the duration moves strictly forwards in time
+fn is_forwards(duration: &RelativeDuration) -> bool {
+    duration.num_months() >= 0
+        && duration.num_weeks() >= 0
+        && duration.num_days() >= 0
+        && !duration.is_zero()
+}
+
+impl Iterator for Recurrence {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_period().map(|(date, _)| date)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.count_hint(Some(NaiveDate::MAX))
+    }
+}
+
+impl FusedIterator for Recurrence {}
+
+/// A series going backwards in time, see [Recurrence::rev_from]
+///
+/// Occurences are found by evaluating windows before the last occurence which double in size
+/// until they contain an occurence.
+#[derive(Debug, Clone)]
+pub struct RevRecurrence {
+    recur: Recurrence,
+    /// Last date (inclusive) which has not been searched yet
+    end: Option<NaiveDate>,
+    /// Number of days in the next window
+    lookback: i64,
+    /// Occurences of the last window which are yet to be produced, in ascending order
+    buffer: Vec<NaiveDate>,
+}
+
+impl Iterator for RevRecurrence {
+    type Item = NaiveDate;
 
-        match &self.rule {
-            Rule::Offset(duration, _) => {
-                self.date = date + *duration;
-                Some(date)
-            }
-            Rule::Occurence(duration, count, _) => {
-                if count < &self.occurence_count {
-                    self.date = date + *duration;
-                    Some(date)
-                } else {
-                    None
-                }
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(date) = self.buffer.pop() {
+                return Some(date);
+            }
+
+            let first = self.recur.date?;
+            let end = self.end.filter(|end| *end >= first)?;
+            let window_start = end
+                .checked_sub_signed(Duration::days(self.lookback - 1))
+                .filter(|d| *d > first)
+                .unwrap_or(first);
+
+            self.buffer = self.recur.between(window_start, end).collect();
+            self.end = window_start.pred_opt();
+            if self.buffer.is_empty() {
+                self.lookback = self.lookback.saturating_mul(2);
             }
         }
     }
 }
 
+impl FusedIterator for RevRecurrence {}
+
+/// A date of a series along with its position, see [Recurrence::enumerated]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrence {
+    /// Number of occurences before this one
+    pub index: u32,
+    /// Date of the occurence
+    pub date: NaiveDate,
+    /// Period of the rule the occurence falls in
+    pub period: ClosedInterval,
+}
+
+/// A series of [Occurrence], see [Recurrence::enumerated]
+#[derive(Debug, Clone)]
+pub struct Enumerated {
+    recur: Recurrence,
+    index: u32,
+}
+
+impl Iterator for Enumerated {
+    type Item = Occurrence;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (date, (start, end)) = self.recur.next_with_period()?;
+        let index = self.index;
+        self.index = self.index.saturating_add(1);
+
+        Some(Occurrence {
+            index,
+            date,
+            period: ClosedInterval::exact(start, end),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.recur.size_hint()
+    }
+}
+
+impl FusedIterator for Enumerated {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::IntervalLike;
+    use chrono::Weekday;
+
+    #[test]
+    fn test_occurence() {
+        let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+
+        let third_wednesday = Rule::Occurence(RelativeDuration::months(1), 3, chrono::Weekday::Wed);
+        let dates: Vec<NaiveDate> = Recurrence::with_start(third_wednesday, date)
+            .take(3)
+            .collect();
+        assert_eq!(
+            dates,
+            vec![
+                NaiveDate::from_ymd_opt(2022, 1, 19).unwrap(),
+                NaiveDate::from_ymd_opt(2022, 2, 16).unwrap(),
+                NaiveDate::from_ymd_opt(2022, 3, 16).unwrap(),
+            ]
+        );
+
+        // Only March, June and August have a fifth wednesday in 2022
+        let fifth_wednesday = Rule::Occurence(RelativeDuration::months(1), 5, chrono::Weekday::Wed);
+        let mut recur = Recurrence::with_start(fifth_wednesday, date);
+        assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 3, 30));
+        assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 6, 29));
+        assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 8, 31));
+
+        let impossible = Rule::Occurence(RelativeDuration::weeks(1), 2, chrono::Weekday::Wed);
+        assert_eq!(Recurrence::with_start(impossible, date).next(), None);
+    }
+
+    #[test]
+    fn test_offset() {
+        let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+
+        let penultimate = Rule::Offset(RelativeDuration::months(1), -2);
+        let mut recur = Recurrence::with_start(penultimate, date);
+        assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 1, 30));
+        assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 2, 27));
+
+        let fifteenth = Rule::Offset(RelativeDuration::months(1), 14);
+        let mut recur = Recurrence::with_start(fifteenth, date);
+        assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 1, 15));
+        assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 2, 15));
+
+        // February is too short for a 30th day
+        let thirtieth = Rule::Offset(RelativeDuration::months(1), 29);
+        let mut recur = Recurrence::with_start(thirtieth, date);
+        assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 1, 30));
+        assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 3, 30));
+
+        let end = NaiveDate::from_ymd_opt(2022, 6, 30).unwrap();
+        let last_days = Recurrence::with_start(Rule::Offset(RelativeDuration::months(1), -1), date)
+            .until_and_including(end);
+        assert_eq!(last_days.size_hint(), (0, Some(6)));
+        assert_eq!(last_days.count(), 6);
+
+        let impossible = Rule::Offset(RelativeDuration::weeks(1), 7);
+        assert_eq!(Recurrence::with_start(impossible, date).next(), None);
+    }
+
+    #[test]
+    fn test_anniversaries() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let leap_day = date(2000, 2, 29);
+
+        for policy in [LeapPolicy::Feb28, LeapPolicy::Mar1, LeapPolicy::Skip] {
+            let series: Vec<_> = Recurrence::anniversaries_of(leap_day, policy)
+                .take(8)
+                .collect();
+            let nth: Vec<_> = (1..=40)
+                .filter_map(|n| Recurrence::nth_anniversary(leap_day, n, policy))
+                .take(8)
+                .collect();
+            assert_eq!(series, nth);
+        }
+
+        let skipped: Vec<_> = Recurrence::anniversaries_of(leap_day, LeapPolicy::Skip)
+            .take(2)
+            .collect();
+        assert_eq!(skipped, [date(2004, 2, 29), date(2008, 2, 29)]);
+
+        let start = date(2021, 6, 30);
+        assert_eq!(
+            Recurrence::anniversaries_of(start, LeapPolicy::Feb28).next(),
+            Some(date(2022, 6, 30))
+        );
+        assert_eq!(
+            Recurrence::nth_anniversary(start, 0, LeapPolicy::Feb28),
+            Some(start)
+        );
+        assert_eq!(
+            Recurrence::anniversaries_of(NaiveDate::MAX, LeapPolicy::Feb28).next(),
+            None
+        );
+        assert_eq!(
+            Recurrence::nth_anniversary(start, u32::MAX, LeapPolicy::Feb28),
+            None
+        );
+    }
+
+    #[test]
+    fn test_anchored() {
+        let series = |start: (i32, u32, u32), policy| {
+            let start = NaiveDate::from_ymd_opt(start.0, start.1, start.2).unwrap();
+            Recurrence::with_start(Rule::Anchored(RelativeDuration::months(1), policy), start)
+                .take(4)
+                .map(|d| (d.month(), d.day()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            series((2022, 1, 31), AnchorPolicy::ClampDay),
+            vec![(1, 31), (2, 28), (3, 31), (4, 30)]
+        );
+        assert_eq!(
+            series((2022, 1, 30), AnchorPolicy::EndOfMonth),
+            vec![(1, 30), (2, 28), (3, 30), (4, 30)]
+        );
+        assert_eq!(
+            series((2022, 4, 30), AnchorPolicy::EndOfMonth),
+            vec![(4, 30), (5, 31), (6, 30), (7, 31)]
+        );
+        assert_eq!(
+            series((2022, 1, 31), AnchorPolicy::SkipMissing),
+            vec![(1, 31), (3, 31), (5, 31), (7, 31)]
+        );
+        assert_eq!(
+            series((2022, 1, 31), AnchorPolicy::RollForward),
+            vec![(1, 31), (3, 1), (3, 31), (5, 1)]
+        );
+
+        let end = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+        let skipped = Recurrence::with_start(
+            Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::SkipMissing),
+            NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+        )
+        .until_and_including(end);
+        assert_eq!(skipped.size_hint(), (0, Some(12)));
+        assert_eq!(skipped.count(), 7);
+    }
+
+    #[test]
+    fn test_weekly_on() {
+        let rule = Rule::weekly_on(&[Weekday::Sun, Weekday::Tue, Weekday::Tue]);
+        assert_eq!(
+            rule,
+            Rule::Weekdays(RelativeDuration::weeks(1), vec![Weekday::Tue, Weekday::Sun])
+        );
+
+        // 2022-01-01 is a Saturday
+        let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+        let dates: Vec<NaiveDate> = Recurrence::with_start(rule, date)
+            .until_and_including(end)
+            .collect();
+
+        assert_eq!(dates.len(), 9);
+        assert_eq!(dates[0], NaiveDate::from_ymd_opt(2022, 1, 2).unwrap());
+        assert_eq!(dates[1], NaiveDate::from_ymd_opt(2022, 1, 4).unwrap());
+        assert!(dates
+            .iter()
+            .all(|d| [Weekday::Tue, Weekday::Sun].contains(&d.weekday())));
+
+        let biweekly = Rule::Weekdays(RelativeDuration::weeks(2), vec![Weekday::Mon]);
+        let mut recur = Recurrence::with_start(biweekly, date);
+        assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 1, 10));
+        assert_eq!(recur.next(), NaiveDate::from_ymd_opt(2022, 1, 24));
+    }
+
+    #[test]
+    fn test_month_days() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+
+        let dates: Vec<NaiveDate> = Recurrence::with_start(Rule::semimonthly(), date(1, 1))
+            .until_and_including(date(3, 1))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![date(1, 1), date(1, 15), date(2, 1), date(2, 15), date(3, 1)]
+        );
+
+        // The 30th is missing in February and coincides with the last day in April
+        let rule = Rule::MonthDays(RelativeDuration::months(1), vec![30, -1]);
+        let dates: Vec<NaiveDate> = Recurrence::with_start(rule, date(1, 31)).take(5).collect();
+        assert_eq!(
+            dates,
+            vec![
+                date(1, 31),
+                date(2, 28),
+                date(3, 30),
+                date(3, 31),
+                date(4, 30)
+            ]
+        );
+
+        let weeks = Rule::MonthDays(RelativeDuration::weeks(1), vec![1]);
+        assert_eq!(Recurrence::with_start(weeks, date(1, 1)).next(), None);
+    }
+
+    #[test]
+    fn test_enumerated() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+        let periods = |rule: Rule, start: NaiveDate| {
+            Recurrence::with_start(rule, start)
+                .enumerated()
+                .take(3)
+                .map(|o| {
+                    let (start, end) = (o.period.start_opt(), o.period.end_opt());
+                    assert!(start <= Some(o.date) && Some(o.date) <= end);
+                    (o.index, o.date, start.unwrap(), end.unwrap())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            periods(Rule::monthly(), date(1, 15)),
+            vec![
+                (0, date(1, 15), date(1, 15), date(2, 14)),
+                (1, date(2, 15), date(2, 15), date(3, 14)),
+                (2, date(3, 15), date(3, 15), date(4, 14)),
+            ]
+        );
+        assert_eq!(
+            periods(Rule::semimonthly(), date(1, 10)),
+            vec![
+                (0, date(1, 15), date(1, 1), date(1, 31)),
+                (1, date(2, 1), date(2, 1), date(2, 28)),
+                (2, date(2, 15), date(2, 1), date(2, 28)),
+            ]
+        );
+        assert_eq!(
+            periods(Rule::weekly_on(&[Weekday::Mon, Weekday::Fri]), date(1, 5)),
+            vec![
+                (0, date(1, 7), date(1, 3), date(1, 9)),
+                (1, date(1, 10), date(1, 10), date(1, 16)),
+                (2, date(1, 14), date(1, 10), date(1, 16)),
+            ]
+        );
+
+        let rule = Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::RollForward);
+        assert_eq!(
+            periods(rule, date(1, 31)),
+            vec![
+                (0, date(1, 31), date(1, 31), date(2, 27)),
+                (1, date(3, 1), date(2, 28), date(3, 30)),
+                (2, date(3, 31), date(3, 31), date(4, 29)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rev_from() {
+        let start = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let rules = vec![
+            Rule::daily(),
+            Rule::yearly(),
+            Rule::Offset(RelativeDuration::months(1), -1),
+            Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::SkipMissing),
+            Rule::Occurence(RelativeDuration::months(1), 5, Weekday::Fri),
+            Rule::semimonthly(),
+        ];
+
+        for rule in rules {
+            let recur = Recurrence::with_start(rule, start);
+            let mut expected: Vec<NaiveDate> = recur.until_and_including(end).collect();
+            expected.reverse();
+
+            assert_eq!(recur.rev_from(end).collect::<Vec<_>>(), expected);
+        }
+
+        let recur = Recurrence::with_start(Rule::monthly(), start);
+        let before = start.pred_opt().unwrap();
+        assert_eq!(recur.rev_from(before).next(), None);
+        assert_eq!(recur.rev_from(start).collect::<Vec<_>>(), vec![start]);
+    }
+
+    #[test]
+    fn test_yearly_on() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let dates = |policy| {
+            Recurrence::with_start(Rule::yearly_on(2, 29, policy), date(2023, 3, 1))
+                .take(3)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            dates(AnchorPolicy::SkipMissing),
+            vec![date(2024, 2, 29), date(2028, 2, 29), date(2032, 2, 29)]
+        );
+        assert_eq!(
+            dates(AnchorPolicy::ClampDay),
+            vec![date(2024, 2, 29), date(2025, 2, 28), date(2026, 2, 28)]
+        );
+        assert_eq!(
+            dates(AnchorPolicy::RollForward),
+            vec![date(2023, 3, 1), date(2024, 2, 29), date(2025, 3, 1)]
+        );
+
+        let invalid = Rule::yearly_on(2, 30, AnchorPolicy::ClampDay);
+        assert_eq!(
+            Recurrence::with_start(invalid, date(2023, 1, 1)).next(),
+            None
+        );
+
+        assert_eq!(
+            Rule::monthly_on_days(&[16, 1, 16]),
+            Rule::MonthDays(RelativeDuration::months(1), vec![1, 16])
+        );
+    }
+
+    #[test]
+    fn test_yearly_week_and_ordinal() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        // 2020 has 53 ISO weeks and week 1 of 2025 starts on 2024-12-30
+        let dates: Vec<NaiveDate> =
+            Recurrence::with_start(Rule::yearly_week(53, Weekday::Thu), date(2019, 1, 1))
+                .take(2)
+                .collect();
+        assert_eq!(dates, vec![date(2020, 12, 31), date(2026, 12, 31)]);
+
+        let dates: Vec<NaiveDate> =
+            Recurrence::with_start(Rule::yearly_week(1, Weekday::Mon), date(2024, 6, 1))
+                .take(2)
+                .collect();
+        assert_eq!(dates, vec![date(2024, 12, 30), date(2025, 12, 29)]);
+
+        let dates: Vec<NaiveDate> =
+            Recurrence::with_start(Rule::yearly_ordinal(366), date(2023, 1, 1))
+                .take(2)
+                .collect();
+        assert_eq!(dates, vec![date(2024, 12, 31), date(2028, 12, 31)]);
+
+        let dates: Vec<NaiveDate> =
+            Recurrence::with_start(Rule::yearly_ordinal(-1), date(2023, 1, 1))
+                .take(2)
+                .collect();
+        assert_eq!(dates, vec![date(2023, 12, 31), date(2024, 12, 31)]);
+
+        for rule in [Rule::yearly_week(54, Weekday::Mon), Rule::yearly_ordinal(0)] {
+            assert_eq!(Recurrence::with_start(rule, date(2023, 1, 1)).next(), None);
+        }
+    }
+
+    #[test]
+    fn test_guards() {
+        let date = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+        let end = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+
+        for duration in [
+            RelativeDuration::zero(),
+            RelativeDuration::months(1).with_days(-31),
+            RelativeDuration::weeks(-1),
+        ] {
+            let recur = Recurrence::with_start(Rule::Offset(duration, 0), date);
+            assert_eq!(recur.until(end).collect::<Vec<_>>(), vec![date]);
+        }
+
+        let recur = Recurrence::with_start(Rule::daily(), date).with_max_occurrences(3);
+        assert_eq!(recur.size_hint(), (3, Some(3)));
+        assert_eq!(recur.until(end).count(), 3);
+
+        let recur = Recurrence::with_start(Rule::weekly(), date).with_horizon(end);
+        assert_eq!(recur.size_hint(), (48, Some(48)));
+        assert_eq!(recur.clone().last(), NaiveDate::from_ymd_opt(2022, 12, 26));
+        assert_eq!(recur.nth(47), NaiveDate::from_ymd_opt(2022, 12, 26));
+        assert_eq!(recur.nth(48), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        let month = RelativeDuration::months(1);
+        let invalid = vec![
+            (
+                Rule::Offset(RelativeDuration::zero(), 0),
+                RuleError::NotForwards(RelativeDuration::zero()),
+            ),
+            (Rule::Offset(month, 31), RuleError::OffsetOutOfRange(31)),
+            (Rule::Offset(month, -32), RuleError::OffsetOutOfRange(-32)),
+            (
+                Rule::Offset(month.with_days(-28), 0),
+                RuleError::NotForwards(month.with_days(-28)),
+            ),
+            (
+                Rule::Occurence(RelativeDuration::weeks(1), 1, Weekday::Mon),
+                RuleError::UnsupportedDuration(RelativeDuration::weeks(1)),
+            ),
+            (
+                Rule::Occurence(month, 0, Weekday::Mon),
+                RuleError::OccurenceOutOfRange(0),
+            ),
+            (
+                Rule::Occurence(month, -6, Weekday::Mon),
+                RuleError::OccurenceOutOfRange(-6),
+            ),
+            (
+                Rule::Weekdays(RelativeDuration::weeks(1), vec![]),
+                RuleError::Empty,
+            ),
+            (
+                Rule::MonthDays(RelativeDuration::weeks(4), vec![1]),
+                RuleError::UnsupportedDuration(RelativeDuration::weeks(4)),
+            ),
+            (
+                Rule::MonthDays(month, vec![1, 32]),
+                RuleError::DayOutOfRange(32),
+            ),
+            (
+                Rule::yearly_on(2, 30, AnchorPolicy::ClampDay),
+                RuleError::DayOutOfRange(30),
+            ),
+            (
+                Rule::yearly_week(0, Weekday::Mon),
+                RuleError::WeekOutOfRange(0),
+            ),
+            (Rule::yearly_ordinal(367), RuleError::DayOutOfRange(367)),
+        ];
+
+        let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        for (rule, error) in invalid {
+            assert_eq!(rule.validate(), Err(error.clone()), "{:?}", rule);
+            assert_eq!(Recurrence::try_with_start(rule, date).unwrap_err(), error);
+        }
+
+        let valid = vec![
+            Rule::Offset(month, 30),
+            Rule::Offset(month.with_days(-2), 0),
+            Rule::Occurence(RelativeDuration::weeks(2), 2, Weekday::Fri),
+            Rule::Occurence(month, -5, Weekday::Mon),
+            Rule::semimonthly(),
+            Rule::yearly_on(2, 29, AnchorPolicy::SkipMissing),
+            Rule::yearly_ordinal(-366),
+        ];
+        for rule in valid {
+            assert_eq!(rule.validate(), Ok(()), "{:?}", rule);
+        }
+    }
+
+    #[test]
+    fn test_aligned_to_period() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let aligned = |rule, start| {
+            Recurrence::with_start(rule, start)
+                .aligned_to_period()
+                .take(3)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            aligned(Rule::quarterly(), date(2022, 2, 10)),
+            vec![date(2022, 4, 1), date(2022, 7, 1), date(2022, 10, 1)]
+        );
+        assert_eq!(
+            aligned(Rule::monthly(), date(2022, 1, 1)),
+            vec![date(2022, 1, 1), date(2022, 2, 1), date(2022, 3, 1)]
+        );
+        assert_eq!(
+            aligned(
+                Rule::Offset(RelativeDuration::months(1), -1),
+                date(2022, 1, 15)
+            ),
+            vec![date(2022, 1, 31), date(2022, 2, 28), date(2022, 3, 31)]
+        );
+        // 2022-01-05 is a Wednesday
+        assert_eq!(
+            aligned(Rule::biweekly(), date(2022, 1, 5)),
+            vec![date(2022, 1, 17), date(2022, 1, 31), date(2022, 2, 14)]
+        );
+        assert_eq!(
+            aligned(Rule::daily(), date(2022, 1, 5)),
+            vec![date(2022, 1, 5), date(2022, 1, 6), date(2022, 1, 7)]
+        );
+        assert_eq!(
+            aligned(Rule::semimonthly(), date(2022, 1, 5)),
+            vec![date(2022, 1, 15), date(2022, 2, 1), date(2022, 2, 15)]
+        );
+
+        // A series in progress continues after its next occurence, 2022-03-15
+        let mut recur = Recurrence::with_start(Rule::monthly(), date(2022, 1, 15));
+        recur.next();
+        recur.next();
+        assert_eq!(recur.aligned_to_period().next(), Some(date(2022, 4, 1)));
+
+        let kept = Recurrence::with_start(Rule::quarterly(), date(2022, 2, 10))
+            .aligned_to_period_keeping_offset();
+        assert_eq!(kept.rule(), &Rule::Offset(RelativeDuration::months(3), 40));
+        assert_eq!(
+            kept.take(3).collect::<Vec<_>>(),
+            vec![date(2022, 2, 10), date(2022, 5, 11), date(2022, 8, 10)]
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        let start = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let rules = vec![
+            Rule::monthly(),
+            Rule::biweekly(),
+            Rule::Offset(RelativeDuration::months(1), -1),
+            Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::SkipMissing),
+            Rule::Occurence(RelativeDuration::months(1), 2, Weekday::Tue),
+            Rule::weekly_on(&[Weekday::Mon, Weekday::Fri]),
+            Rule::monthly_on_days(&[1, 31]),
+            Rule::yearly_on(2, 29, AnchorPolicy::RollForward),
+            Rule::yearly_week(10, Weekday::Wed),
+            Rule::yearly_ordinal(-1),
+        ];
+
+        for rule in rules {
+            let recur = Recurrence::with_start(rule.clone(), start);
+            let dates: Vec<NaiveDate> = recur.clone().until_and_including(end).collect();
+
+            for date in start
+                .pred_opt()
+                .unwrap()
+                .iter_days()
+                .take_while(|d| *d <= end)
+            {
+                assert_eq!(
+                    recur.contains(date),
+                    dates.contains(&date),
+                    "{} {}",
+                    rule,
+                    date
+                );
+                assert_eq!(rule.matches(start, date), recur.contains(date));
+            }
+        }
+
+        let limited = Recurrence::with_start(Rule::monthly(), start).with_max_occurrences(2);
+        assert!(limited.contains(NaiveDate::from_ymd_opt(2022, 2, 28).unwrap()));
+        assert!(!limited.contains(NaiveDate::from_ymd_opt(2022, 3, 31).unwrap()));
+
+        let limited = Recurrence::with_start(Rule::monthly(), start).with_horizon(end);
+        assert!(!limited.contains(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()));
+    }
+
+    #[test]
+    fn test_week_start() {
+        // 2022-01-05 is a Wednesday
+        let start = NaiveDate::from_ymd_opt(2022, 1, 5).unwrap();
+        let rule = Rule::Weekdays(
+            RelativeDuration::weeks(3),
+            vec![Weekday::Mon, Weekday::Sat, Weekday::Sun],
+        );
+
+        for week_start in [WeekStart::MONDAY, WeekStart::SUNDAY, WeekStart::SATURDAY] {
+            let recur = Recurrence::with_start(rule.clone(), start).with_week_start(week_start);
+            let all: Vec<NaiveDate> = recur.clone().take(30).collect();
+
+            assert!(all.windows(2).all(|w| w[0] < w[1]));
+            // Every date falls in every third week counting from the week of the start
+            let first_week = beginning_of_week_starting(&start, week_start);
+            for date in &all {
+                let week = beginning_of_week_starting(date, week_start);
+                assert_eq!((week - first_week).num_days() % 21, 0, "{}", date);
+            }
+            assert_eq!(
+                recur.resume_from(all[19]).take(10).collect::<Vec<_>>(),
+                all[20..]
+            );
+        }
+    }
+
+    #[test]
+    fn test_week_anchor() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+        let rule = Rule::Weekdays(RelativeDuration::weeks(2), vec![Weekday::Tue, Weekday::Fri]);
+        let paydays = |start, anchor| {
+            Recurrence::with_start(rule.clone(), start)
+                .with_week_anchor(anchor)
+                .take(20)
+                .collect::<Vec<_>>()
+        };
+
+        // Anchors in the same cycle give the same series whichever side of the start they fall
+        let from_march = paydays(date(3, 2), date(1, 7));
+        assert_eq!(from_march[..2], [date(3, 4), date(3, 15)]);
+        assert_eq!(paydays(date(3, 2), date(12, 23)), from_march);
+        assert_eq!(
+            paydays(date(1, 1), date(1, 7))
+                .into_iter()
+                .skip_while(|d| *d < date(3, 2))
+                .take(10)
+                .collect::<Vec<_>>(),
+            from_march[..10]
+        );
+
+        let recur = Recurrence::with_start(rule.clone(), date(3, 2)).with_week_anchor(date(1, 7));
+        assert_eq!(
+            recur
+                .resume_from(from_march[9])
+                .take(10)
+                .collect::<Vec<_>>(),
+            from_march[10..]
+        );
+    }
+
+    #[test]
+    fn test_page_and_resume() {
+        let start = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+        let rules = vec![
+            Rule::daily(),
+            Rule::monthly(),
+            Rule::Occurence(RelativeDuration::months(1), -1, Weekday::Fri),
+            Rule::weekly_on(&[Weekday::Mon, Weekday::Thu]),
+        ];
+
+        for rule in rules {
+            let recur = Recurrence::with_start(rule, start);
+            let all: Vec<NaiveDate> = recur.clone().take(50).collect();
+
+            let pages: Vec<NaiveDate> = (0..5).flat_map(|index| recur.page(10, index)).collect();
+            assert_eq!(pages, all);
+
+            let resumed: Vec<NaiveDate> = recur.resume_from(all[19]).take(30).collect();
+            assert_eq!(resumed, all[20..]);
+        }
+
+        let limited = Recurrence::with_start(Rule::monthly(), start).with_max_occurrences(5);
+        assert_eq!(limited.page(2, 2).len(), 1);
+        assert!(limited.page(2, 3).is_empty());
+        assert_eq!(
+            limited
+                .resume_from(NaiveDate::from_ymd_opt(2022, 3, 1).unwrap())
+                .last(),
+            NaiveDate::from_ymd_opt(2022, 5, 31)
+        );
+    }
+
+    #[test]
+    fn test_every() {
+        assert_eq!(
+            Rule::try_every(3, Grain::Week),
+            Some(Rule::Offset(RelativeDuration::weeks(3), 0))
+        );
+        assert_eq!(
+            Rule::try_every(1, Grain::Decade),
+            Some(Rule::Offset(RelativeDuration::months(120), 0))
+        );
+        assert_eq!(Rule::try_every(1, Grain::Year), Some(Rule::yearly()));
+        assert_eq!(Rule::try_every(0, Grain::Day), None);
+        assert_eq!(Rule::try_every(u32::MAX, Grain::Day), None);
+        assert_eq!(Rule::try_every(1 << 20, Grain::Month), None);
+    }
+
+    #[test]
+    fn test_between() {
+        let start = NaiveDate::from_ymd_opt(2010, 1, 31).unwrap();
+        let window_start = NaiveDate::from_ymd_opt(2030, 2, 1).unwrap();
+        let window_end = NaiveDate::from_ymd_opt(2030, 6, 30).unwrap();
+
+        let rules = vec![
+            Rule::monthly(),
+            Rule::Offset(RelativeDuration::days(10), 0),
+            Rule::Offset(RelativeDuration::months(1), -1),
+            Rule::Offset(RelativeDuration::months(2), 3),
+            Rule::Occurence(RelativeDuration::months(1), 2, Weekday::Tue),
+            Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::RollForward),
+            Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::SkipMissing),
+            Rule::weekly_on(&[Weekday::Mon, Weekday::Thu]),
+            Rule::Weekdays(RelativeDuration::weeks(3), vec![Weekday::Sun]),
+            Rule::semimonthly(),
+            Rule::MonthDays(RelativeDuration::months(2), vec![-1, 30]),
+            Rule::yearly_on(3, 15, AnchorPolicy::SkipMissing),
+            Rule::yearly_week(10, Weekday::Tue),
+            Rule::yearly_ordinal(-200),
+        ];
+
+        for rule in rules {
+            for start in [start, NaiveDate::from_ymd_opt(2010, 1, 5).unwrap()] {
+                let recur = Recurrence::with_start(rule.clone(), start);
+                let expected: Vec<NaiveDate> = recur
+                    .until_and_including(window_end)
+                    .filter(|d| *d >= window_start)
+                    .collect();
+
+                assert!(!expected.is_empty());
+                assert_eq!(
+                    recur.between(window_start, window_end).collect::<Vec<_>>(),
+                    expected,
+                    "{:?} from {}",
+                    rule,
+                    start
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_nth_and_neighbours() {
+        let start = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+        let rules = vec![
+            Rule::daily(),
+            Rule::monthly(),
+            Rule::Offset(RelativeDuration::months(1), 27),
+            Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::EndOfMonth),
+            Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::SkipMissing),
+            Rule::Occurence(RelativeDuration::months(1), 5, Weekday::Fri),
+        ];
+
+        for rule in rules {
+            let mut recur = Recurrence::with_start(rule, start);
+            recur.next();
+
+            let dates: Vec<NaiveDate> = recur.clone().take(20).collect();
+            for (n, date) in dates.iter().enumerate() {
+                assert_eq!(recur.nth(n), Some(*date));
+            }
+
+            for pair in dates.windows(2) {
+                assert_eq!(recur.next_after(pair[0]), Some(pair[1]));
+                assert_eq!(recur.previous_before(pair[1]), Some(pair[0]));
+            }
+            assert_eq!(recur.previous_before(dates[0]), None);
+        }
+    }
+
+    #[test]
+    fn test_count() {
+        let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let recur = Recurrence::with_start(Rule::weekly(), date);
+
+        let mut limited = recur.clone().count(3);
+        assert_eq!(limited.size_hint(), (3, Some(3)));
+        assert_eq!(limited.nth(2), NaiveDate::from_ymd_opt(2022, 1, 15));
+        assert_eq!(limited.next(), None);
+
+        let end = NaiveDate::from_ymd_opt(2022, 1, 15).unwrap();
+        assert_eq!(recur.until_or_count(end, 10).size_hint(), (3, Some(3)));
+        assert_eq!(
+            recur.until_or_count(end, 2).last(),
+            NaiveDate::from_ymd_opt(2022, 1, 8)
+        );
+    }
+
+    #[test]
+    fn test_until_size_hint() {
+        let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+
+        let weekly = Recurrence::with_start(Rule::weekly(), date).until_and_including(end);
+        assert_eq!(weekly.size_hint(), (53, Some(53)));
+        assert_eq!(weekly.count(), 53);
+
+        let mut monthly = Recurrence::with_start(Rule::monthly(), date).until(end);
+        assert_eq!(monthly.size_hint(), (12, Some(12)));
+        monthly.next();
+        assert_eq!(monthly.size_hint(), (11, Some(11)));
+        assert_eq!(monthly.count(), 11);
+
+        let monthly = Recurrence::with_start(Rule::monthly(), date)
+            .until(NaiveDate::from_ymd_opt(2022, 3, 15).unwrap());
+        assert_eq!(monthly.size_hint(), (3, Some(3)));
+        assert_eq!(monthly.count(), 3);
+
+        let month_ends = Recurrence::with_start(
+            Rule::monthly(),
+            NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+        )
+        .until(NaiveDate::from_ymd_opt(2022, 3, 15).unwrap());
+        assert_eq!(month_ends.size_hint(), (2, Some(3)));
+        assert_eq!(month_ends.count(), 2);
+
+        // The end of February is pinned to the end of the following months
+        let day = |m, d| NaiveDate::from_ymd_opt(2023, m, d).unwrap();
+        for rule in [
+            Rule::monthly(),
+            Rule::Anchored(RelativeDuration::months(1), AnchorPolicy::EndOfMonth),
+        ] {
+            let feb_end = Recurrence::with_start(rule, day(2, 28)).until_and_including(day(4, 29));
+            let (lower, upper) = feb_end.size_hint();
+            assert!(lower <= 2 && upper == Some(3));
+            assert_eq!(feb_end.count(), 2);
+        }
+        assert_eq!(
+            Recurrence::with_start(Rule::monthly(), day(1, 28)).nth(2),
+            Some(day(3, 31))
+        );
+
+        let before = Recurrence::with_start(Rule::daily(), date).until(date);
+        assert_eq!(before.size_hint(), (0, Some(0)));
+
+        let mut end_of_time =
+            Recurrence::with_start(Rule::daily(), NaiveDate::MAX.pred_opt().unwrap());
+        assert_eq!(end_of_time.size_hint(), (2, Some(2)));
+        assert_eq!(end_of_time.nth(1), Some(NaiveDate::MAX));
+        assert_eq!(end_of_time.nth(2), None);
+        assert_eq!(Iterator::nth(&mut end_of_time, 1), Some(NaiveDate::MAX));
+        assert_eq!(end_of_time.next(), None);
+    }
 
     #[test]
     fn test_recur_monthly_until_inclusive() {
@@ -0,0 +1,664 @@
+//! RFC 5545 recurrence rules
+//!
+//! Converts between `RRULE` values such as `FREQ=MONTHLY;INTERVAL=2;BYMONTHDAY=15;COUNT=10` and
+//! [Rule]. Only the subset of RRULE which can be represented by a [Rule] is supported, anything
+//! else is reported as [RRuleError::Unsupported].
+//!
+//! | RRULE                            | Rule                                   |
+//! |----------------------------------|----------------------------------------|
+//! | `FREQ=DAILY` / `FREQ=WEEKLY`     | [Rule::Offset] with an offset of `0`   |
+//! | `FREQ=MONTHLY` / `FREQ=YEARLY`   | [Rule::Anchored] with [AnchorPolicy::SkipMissing] |
+//! | `FREQ=WEEKLY;BYDAY=MO,WE`        | [Rule::Weekdays]                       |
+//! | `FREQ=MONTHLY;BYDAY=-1FR`        | [Rule::Occurence]                      |
+//! | `FREQ=MONTHLY;BYMONTHDAY=15`     | [Rule::Offset]                         |
+//! | `FREQ=MONTHLY;BYMONTHDAY=1,15`   | [Rule::MonthDays]                      |
+//! | `FREQ=YEARLY;BYYEARDAY=100`      | [Rule::Offset]                         |
+//! | `FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29` | [Rule::Annual] with [AnchorPolicy::SkipMissing] |
+//! | `FREQ=YEARLY;BYWEEKNO=26;BYDAY=MO`   | [Rule::YearWeekday]                |
+use std::{fmt::Write, str::FromStr};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{
+    duration::RelativeDuration,
+    util::{beginning_of_month, beginning_of_year},
+    EomPolicy,
+};
+
+use super::{AnchorPolicy, Recurrence, Rule};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RRuleError {
+    #[error("the rule is missing FREQ")]
+    MissingFrequency,
+
+    #[error("invalid part {0:?}, expected NAME=VALUE")]
+    InvalidPart(String),
+
+    #[error("invalid value {value:?} for {name}")]
+    InvalidValue { name: String, value: String },
+
+    #[error("COUNT and UNTIL can't be used together")]
+    CountAndUntil,
+
+    #[error("{0} is not supported")]
+    Unsupported(String),
+}
+
+/// A recurrence rule along with how the series ends
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RRule {
+    pub rule: Rule,
+    /// Number of occurences in the series
+    pub count: Option<usize>,
+    /// Last date of the series (inclusive)
+    pub until: Option<NaiveDate>,
+}
+
+impl RRule {
+    /// A rule which repeats forever
+    pub fn new(rule: Rule) -> Self {
+        RRule {
+            rule,
+            count: None,
+            until: None,
+        }
+    }
+
+    /// Iterate the series from a start date, the equivalent of `DTSTART`
+    ///
+    /// Rules which pick a day within a month or year are evaluated within calendar months or years
+    /// and occurences before the start are skipped, as in RFC 5545.
+    ///
+    /// ```
+    /// use calends::recurrence::rrule::RRule;
+    /// use chrono::NaiveDate;
+    ///
+    /// let rrule: RRule = "FREQ=MONTHLY;INTERVAL=2;BYMONTHDAY=15;COUNT=3".parse().unwrap();
+    /// let dates: Vec<NaiveDate> = rrule
+    ///     .with_start(NaiveDate::from_ymd_opt(2022, 1, 20).unwrap())
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     dates,
+    ///     vec![
+    ///         NaiveDate::from_ymd_opt(2022, 3, 15).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2022, 5, 15).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2022, 7, 15).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn with_start(&self, start: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+        let aligned = match &self.rule {
+            Rule::Offset(duration, offset) if *offset != 0 => align(duration, start),
+            Rule::Occurence(duration, ..) => align(duration, start),
+            _ => start,
+        };
+        let until = self.until.unwrap_or(NaiveDate::MAX);
+
+        Recurrence::with_start(self.rule.clone(), aligned)
+            .skip_while(move |d| *d < start)
+            .take_while(move |d| *d <= until)
+            .take(self.count.unwrap_or(usize::MAX))
+    }
+
+    /// Format the rule as an RRULE value
+    ///
+    /// Rules stepping by months from the start of the series, such as [Rule::monthly], depend on
+    /// the day of the start, see [RRule::to_rrule_with_start].
+    ///
+    /// ```
+    /// use calends::{recurrence::rrule::RRule, Rule};
+    /// use chrono::Weekday;
+    ///
+    /// let mut rrule = RRule::new(Rule::weekly_on(&[Weekday::Mon, Weekday::Fri]));
+    /// rrule.count = Some(10);
+    ///
+    /// assert_eq!(rrule.to_rrule().unwrap(), "FREQ=WEEKLY;BYDAY=MO,FR;COUNT=10");
+    /// ```
+    pub fn to_rrule(&self) -> Result<String, RRuleError> {
+        self.format(None)
+    }
+
+    /// Format the rule as an RRULE value for a series from the start, the equivalent of `DTSTART`
+    ///
+    /// A rule stepping by months from the start is written with the day of the start, or the last
+    /// day of the month for a start at the end of the month. Starts whose day is moved by the
+    /// clamping of short months are [RRuleError::Unsupported].
+    ///
+    /// ```
+    /// use calends::{recurrence::rrule::RRule, Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+    /// let value = RRule::new(Rule::quarterly()).to_rrule_with_start(start).unwrap();
+    /// assert_eq!(value, "FREQ=MONTHLY;INTERVAL=3;BYMONTHDAY=-1");
+    ///
+    /// let rrule: RRule = value.parse().unwrap();
+    /// assert!(rrule
+    ///     .with_start(start)
+    ///     .take(8)
+    ///     .eq(Recurrence::with_start(Rule::quarterly(), start).take(8)));
+    /// ```
+    pub fn to_rrule_with_start(&self, start: NaiveDate) -> Result<String, RRuleError> {
+        self.format(Some(start))
+    }
+
+    fn format(&self, start: Option<NaiveDate>) -> Result<String, RRuleError> {
+        let mut out = format_rule(&self.rule, start)?;
+
+        if let Some(count) = self.count {
+            write!(out, ";COUNT={}", count).unwrap();
+        }
+
+        if let Some(until) = self.until {
+            write!(out, ";UNTIL={}", until.format("%Y%m%d")).unwrap();
+        }
+
+        Ok(out)
+    }
+}
+
+/// Parse an RRULE value, optionally prefixed with `RRULE:`
+impl FromStr for RRule {
+    type Err = RRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix("RRULE:").unwrap_or(s);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = None;
+        let mut by_month_day = None;
+        let mut by_year_day = None;
+        let mut by_month = None;
+        let mut by_week_no = None;
+
+        for part in s.split(';').filter(|p| !p.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| RRuleError::InvalidPart(part.to_string()))?;
+            let name = name.to_ascii_uppercase();
+            let invalid = || RRuleError::InvalidValue {
+                name: name.clone(),
+                value: value.to_string(),
+            };
+
+            match name.as_str() {
+                "FREQ" => frequency = Some(Frequency::parse(value)?),
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<i32>()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(invalid)?
+                }
+                "COUNT" => count = Some(value.parse::<usize>().map_err(|_| invalid())?),
+                "UNTIL" => until = Some(parse_until(value).ok_or_else(invalid)?),
+                "BYDAY" => {
+                    let days = value
+                        .split(',')
+                        .map(parse_by_day)
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(invalid)?;
+                    by_day = Some(days);
+                }
+                "BYMONTHDAY" => {
+                    let days = value
+                        .split(',')
+                        .map(|d| parse_day(d, 31))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(invalid)?;
+                    by_month_day = Some(days);
+                }
+                "BYMONTH" => {
+                    let month = value.parse::<u32>().ok().filter(|m| (1..=12).contains(m));
+                    by_month = Some(month.ok_or_else(invalid)?);
+                }
+                "BYWEEKNO" => {
+                    let week = value.parse::<u32>().ok().filter(|w| (1..=53).contains(w));
+                    by_week_no = Some(week.ok_or_else(invalid)?);
+                }
+                "BYYEARDAY" => by_year_day = Some(parse_day(value, 366).ok_or_else(invalid)?),
+                "WKST" if value.eq_ignore_ascii_case("MO") => {}
+                _ => return Err(RRuleError::Unsupported(part.to_string())),
+            }
+        }
+
+        if count.is_some() && until.is_some() {
+            return Err(RRuleError::CountAndUntil);
+        }
+
+        let frequency = frequency.ok_or(RRuleError::MissingFrequency)?;
+        let duration = frequency
+            .duration(interval)
+            .ok_or_else(|| RRuleError::InvalidValue {
+                name: "INTERVAL".to_string(),
+                value: interval.to_string(),
+            })?;
+
+        // Rules which are fixed to a part of the year rather than the start of the series
+        if by_month.is_some() || by_week_no.is_some() {
+            let rule = match (
+                frequency,
+                interval,
+                by_month,
+                by_week_no,
+                by_day.as_deref(),
+                by_month_day.as_deref(),
+                by_year_day,
+            ) {
+                (Frequency::Yearly, 1, Some(month), None, None, Some(&[day]), None) if day > 0 => {
+                    Rule::Annual(month, day as u32, AnchorPolicy::SkipMissing)
+                }
+                (Frequency::Yearly, 1, None, Some(week), Some(&[(0, weekday)]), None, None) => {
+                    Rule::YearWeekday(week, weekday)
+                }
+                _ => return Err(RRuleError::Unsupported(s.to_string())),
+            };
+
+            return Ok(RRule { rule, count, until });
+        }
+
+        let rule = match (frequency, by_day, by_month_day, by_year_day) {
+            (Frequency::Daily | Frequency::Weekly, None, None, None) => Rule::Offset(duration, 0),
+            (Frequency::Monthly | Frequency::Yearly, None, None, None) => {
+                Rule::Anchored(duration, AnchorPolicy::SkipMissing)
+            }
+            (Frequency::Weekly, Some(days), None, None) if days.iter().all(|(n, _)| *n == 0) => {
+                let mut weekdays: Vec<Weekday> = days.into_iter().map(|(_, d)| d).collect();
+                weekdays.sort_by_key(|d| d.num_days_from_monday());
+                weekdays.dedup();
+                Rule::Weekdays(duration, weekdays)
+            }
+            (Frequency::Monthly | Frequency::Yearly, Some(days), None, None)
+                if days.len() == 1 && days[0].0 != 0 =>
+            {
+                Rule::Occurence(duration, days[0].0, days[0].1)
+            }
+            // An offset from the end counts from the end of the period rather than the month
+            (Frequency::Monthly, None, Some(days), None)
+                if days.len() == 1 && (days[0] > 0 || interval == 1) =>
+            {
+                let day = days[0];
+                Rule::Offset(duration, if day > 0 { day - 1 } else { day })
+            }
+            (Frequency::Monthly, None, Some(days), None) => Rule::MonthDays(duration, days),
+            (Frequency::Yearly, None, None, Some(day)) => {
+                Rule::Offset(duration, if day > 0 { day - 1 } else { day })
+            }
+            _ => return Err(RRuleError::Unsupported(s.to_string())),
+        };
+
+        Ok(RRule { rule, count, until })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn parse(value: &str) -> Result<Frequency, RRuleError> {
+        match value.to_ascii_uppercase().as_str() {
+            "DAILY" => Ok(Frequency::Daily),
+            "WEEKLY" => Ok(Frequency::Weekly),
+            "MONTHLY" => Ok(Frequency::Monthly),
+            "YEARLY" => Ok(Frequency::Yearly),
+            "SECONDLY" | "MINUTELY" | "HOURLY" => {
+                Err(RRuleError::Unsupported(format!("FREQ={}", value)))
+            }
+            _ => Err(RRuleError::InvalidValue {
+                name: "FREQ".to_string(),
+                value: value.to_string(),
+            }),
+        }
+    }
+
+    fn duration(&self, interval: i32) -> Option<RelativeDuration> {
+        match self {
+            Frequency::Daily => RelativeDuration::try_from_mwd(0, 0, interval),
+            Frequency::Weekly => RelativeDuration::try_from_mwd(0, interval, 0),
+            Frequency::Monthly => RelativeDuration::try_from_mwd(interval, 0, 0),
+            Frequency::Yearly => RelativeDuration::try_from_mwd(interval.checked_mul(12)?, 0, 0),
+        }
+    }
+
+    /// Frequency and interval of a duration made up of a single unit
+    fn of(duration: &RelativeDuration) -> Option<(Frequency, i32)> {
+        match (
+            duration.num_months(),
+            duration.num_weeks(),
+            duration.num_days(),
+        ) {
+            (0, 0, days) if days > 0 => Some((Frequency::Daily, days)),
+            (0, weeks, 0) if weeks > 0 => Some((Frequency::Weekly, weeks)),
+            (months, 0, 0) if months > 0 && months % 12 == 0 => {
+                Some((Frequency::Yearly, months / 12))
+            }
+            (months, 0, 0) if months > 0 => Some((Frequency::Monthly, months)),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+}
+
+/// Align the start of a series to the calendar month or year
+fn align(duration: &RelativeDuration, start: NaiveDate) -> NaiveDate {
+    match Frequency::of(duration) {
+        Some((Frequency::Yearly, _)) => beginning_of_year(&start),
+        Some((Frequency::Monthly, _)) => beginning_of_month(&start),
+        _ => start,
+    }
+}
+
+fn parse_until(value: &str) -> Option<NaiveDate> {
+    let date = value.get(..8)?;
+    match value.get(8..)? {
+        "" => {}
+        time if time.starts_with('T') => {}
+        _ => return None,
+    }
+
+    NaiveDate::parse_from_str(date, "%Y%m%d").ok()
+}
+
+fn parse_day(value: &str, max: i32) -> Option<i32> {
+    value
+        .parse::<i32>()
+        .ok()
+        .filter(|d| *d != 0 && d.abs() <= max)
+}
+
+fn parse_by_day(value: &str) -> Option<(i32, Weekday)> {
+    let split = value.len().checked_sub(2)?;
+    let (ordinal, day) = (value.get(..split)?, value.get(split..)?);
+
+    let ordinal = match ordinal {
+        "" => 0,
+        ordinal => ordinal
+            .trim_start_matches('+')
+            .parse::<i32>()
+            .ok()
+            .filter(|n| *n != 0 && n.abs() <= 53)?,
+    };
+
+    let day = match day.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+
+    Some((ordinal, day))
+}
+
+fn weekday_code(day: &Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn format_rule(rule: &Rule, start: Option<NaiveDate>) -> Result<String, RRuleError> {
+    let unsupported = |reason: &str| Err(RRuleError::Unsupported(reason.to_string()));
+
+    let duration = match rule {
+        Rule::Annual(month, day, AnchorPolicy::SkipMissing) => {
+            return Ok(format!("FREQ=YEARLY;BYMONTH={};BYMONTHDAY={}", month, day));
+        }
+        Rule::Annual(..) => return unsupported("an anchor policy other than SkipMissing"),
+        Rule::YearWeekday(week, weekday) => {
+            return Ok(format!(
+                "FREQ=YEARLY;BYWEEKNO={};BYDAY={}",
+                week,
+                weekday_code(weekday)
+            ));
+        }
+        Rule::YearDay(day) => return Ok(format!("FREQ=YEARLY;BYYEARDAY={}", day)),
+        Rule::Offset(duration, _)
+        | Rule::Occurence(duration, ..)
+        | Rule::Anchored(duration, _)
+        | Rule::Weekdays(duration, _)
+        | Rule::MonthDays(duration, _) => duration,
+    };
+    let (frequency, interval) = match Frequency::of(duration) {
+        Some(frequency) => frequency,
+        None => return unsupported("a duration mixing units"),
+    };
+
+    let mut out = format!("FREQ={}", frequency.name());
+    if interval != 1 {
+        write!(out, ";INTERVAL={}", interval).unwrap();
+    }
+
+    match (rule, frequency) {
+        (Rule::Offset(_, 0), Frequency::Daily | Frequency::Weekly) => {}
+        (Rule::Offset(duration, 0), _) => {
+            let Some(start) = start else {
+                return unsupported("an offset by months without the start of the series");
+            };
+            let policy = duration.eom_policy();
+            let last_day = start.succ_opt().is_none_or(|next| next.day() == 1);
+            // A 28th can land on the last day of February, which PreserveLastDay then keeps
+            let fixed = match policy {
+                EomPolicy::PreserveLastDay => 27,
+                _ => 28,
+            };
+
+            match frequency {
+                Frequency::Monthly if last_day && policy == EomPolicy::PreserveLastDay => {
+                    out.push_str(";BYMONTHDAY=-1")
+                }
+                Frequency::Monthly if start.day() <= fixed => {
+                    write!(out, ";BYMONTHDAY={}", start.day()).unwrap()
+                }
+                // The day of the start is in its month every year, other than at the end of
+                // February
+                Frequency::Yearly if start.month() != 2 || start.day() <= fixed => {}
+                _ => return unsupported("a start on a day moved by the clamping of short months"),
+            }
+        }
+        (Rule::Offset(_, offset), Frequency::Monthly | Frequency::Yearly) => {
+            let name = match frequency {
+                Frequency::Monthly => "BYMONTHDAY",
+                _ => "BYYEARDAY",
+            };
+            let day = if *offset >= 0 { offset + 1 } else { *offset };
+            write!(out, ";{}={}", name, day).unwrap();
+        }
+        (Rule::Offset(..), _) => return unsupported("an offset within a day or week"),
+        (Rule::Anchored(_, AnchorPolicy::SkipMissing), _) => {}
+        (Rule::Anchored(..), _) => return unsupported("an anchor policy other than SkipMissing"),
+        (Rule::Occurence(_, occurence, weekday), Frequency::Monthly | Frequency::Yearly) => {
+            write!(out, ";BYDAY={}{}", occurence, weekday_code(weekday)).unwrap();
+        }
+        (Rule::Occurence(..), _) => return unsupported("an occurence within a day or week"),
+        (Rule::Weekdays(_, weekdays), Frequency::Weekly) if !weekdays.is_empty() => {
+            let days: Vec<&str> = weekdays.iter().map(weekday_code).collect();
+            write!(out, ";BYDAY={}", days.join(",")).unwrap();
+        }
+        (Rule::Weekdays(..), _) => return unsupported("weekdays outside of a weekly rule"),
+        (Rule::MonthDays(_, days), Frequency::Monthly) if !days.is_empty() => {
+            let days: Vec<String> = days.iter().map(i32::to_string).collect();
+            write!(out, ";BYMONTHDAY={}", days.join(",")).unwrap();
+        }
+        (Rule::MonthDays(..), _) => {
+            return unsupported("days of the month outside of a monthly rule")
+        }
+        (Rule::Annual(..) | Rule::YearWeekday(..) | Rule::YearDay(_), _) => {
+            unreachable!("yearly rules are formatted without a duration")
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let rrule: RRule = "RRULE:FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20220430T000000Z"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            rrule.rule,
+            Rule::Occurence(RelativeDuration::months(1), -1, Weekday::Fri)
+        );
+        assert_eq!(rrule.until, Some(date(2022, 4, 30)));
+
+        let dates: Vec<NaiveDate> = rrule.with_start(date(2022, 1, 1)).collect();
+        assert_eq!(
+            dates,
+            vec![
+                date(2022, 1, 28),
+                date(2022, 2, 25),
+                date(2022, 3, 25),
+                date(2022, 4, 29)
+            ]
+        );
+
+        let rrule: RRule = "FREQ=YEARLY;COUNT=2".parse().unwrap();
+        assert_eq!(
+            rrule.rule,
+            Rule::Anchored(RelativeDuration::months(12), AnchorPolicy::SkipMissing)
+        );
+        assert_eq!(rrule.count, Some(2));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "INTERVAL=2".parse::<RRule>(),
+            Err(RRuleError::MissingFrequency)
+        );
+        assert_eq!(
+            "FREQ=HOURLY".parse::<RRule>(),
+            Err(RRuleError::Unsupported("FREQ=HOURLY".to_string()))
+        );
+        assert_eq!(
+            "FREQ=DAILY;BYSETPOS=1".parse::<RRule>(),
+            Err(RRuleError::Unsupported("BYSETPOS=1".to_string()))
+        );
+        assert_eq!(
+            "FREQ=DAILY;COUNT=1;UNTIL=20220101".parse::<RRule>(),
+            Err(RRuleError::CountAndUntil)
+        );
+        assert!(matches!(
+            "FREQ=MONTHLY;BYMONTHDAY=32".parse::<RRule>(),
+            Err(RRuleError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            "FREQ=DAILY;BYMONTHDAY=1".parse::<RRule>(),
+            Err(RRuleError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for s in [
+            "FREQ=DAILY;INTERVAL=3",
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE,FR;COUNT=10",
+            "FREQ=MONTHLY;BYMONTHDAY=-1;UNTIL=20221231",
+            "FREQ=MONTHLY;INTERVAL=2;BYMONTHDAY=15;COUNT=10",
+            "FREQ=MONTHLY;BYMONTHDAY=1,15",
+            "FREQ=MONTHLY;BYDAY=3WE",
+            "FREQ=YEARLY;INTERVAL=2",
+            "FREQ=YEARLY;BYYEARDAY=100",
+            "FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29;COUNT=3",
+            "FREQ=YEARLY;BYWEEKNO=26;BYDAY=MO",
+        ] {
+            let rrule: RRule = s.parse().unwrap();
+            assert_eq!(rrule.to_rrule().unwrap(), s);
+        }
+    }
+
+    #[test]
+    fn test_format_with_start() {
+        let rules = [
+            Rule::monthly(),
+            Rule::quarterly(),
+            Rule::yearly(),
+            Rule::Offset(
+                RelativeDuration::months(1).with_eom_policy(EomPolicy::PreserveDayClamped),
+                0,
+            ),
+        ];
+
+        let start = date(2023, 12, 1);
+        for start in std::iter::successors(Some(start), |d| d.succ_opt()).take(3 * 366) {
+            for rule in &rules {
+                let rrule = RRule::new(rule.clone());
+                let Ok(value) = rrule.to_rrule_with_start(start) else {
+                    assert!(start.day() >= 28, "{rule:?} from {start}");
+                    continue;
+                };
+
+                let parsed: RRule = value.parse().unwrap();
+                assert!(
+                    parsed.with_start(start).take(30).eq(Recurrence::with_start(
+                        rule.clone(),
+                        start
+                    )
+                    .take(30)),
+                    "{value} from {start}"
+                );
+            }
+        }
+
+        assert_eq!(
+            RRule::new(Rule::yearly()).to_rrule_with_start(date(2022, 1, 31)),
+            Ok("FREQ=YEARLY".to_string())
+        );
+        assert!(RRule::new(Rule::monthly())
+            .to_rrule_with_start(date(2022, 1, 30))
+            .is_err());
+        assert!(RRule::new(Rule::monthly()).to_rrule().is_err());
+    }
+
+    #[test]
+    fn test_format_unsupported() {
+        assert!(RRule::new(Rule::Offset(
+            RelativeDuration::try_from_mwd(1, 1, 0).unwrap(),
+            0
+        ))
+        .to_rrule()
+        .is_err());
+        assert!(RRule::new(Rule::Anchored(
+            RelativeDuration::months(1),
+            AnchorPolicy::ClampDay
+        ))
+        .to_rrule()
+        .is_err());
+    }
+}
@@ -0,0 +1,184 @@
+//! Combining several rules into a single series of dates
+use std::iter::{FusedIterator, Peekable};
+
+use chrono::NaiveDate;
+
+use super::{Recurrence, Rule};
+
+/// A set of rules which occurs whenever any of its rules occurs, like `RRULE` and `EXRULE` in
+/// RFC 2445
+///
+/// Dates produced by an exclusion rule are removed from the series. Every rule starts on the same
+/// date and the dates are produced in order without duplicates.
+///
+/// ```
+/// use calends::{RelativeDuration, RuleSet, Rule};
+/// use chrono::{NaiveDate, Weekday};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+///
+/// // Every Monday plus the 1st of the month, except New Year's day
+/// let set = RuleSet::new()
+///     .with_rule(Rule::weekly_on(&[Weekday::Mon]))
+///     .with_rule(Rule::monthly())
+///     .with_exclusion(Rule::yearly());
+///
+/// let dates: Vec<NaiveDate> = set.with_start(date(1, 1)).take(6).collect();
+/// assert_eq!(
+///     dates,
+///     vec![date(1, 3), date(1, 10), date(1, 17), date(1, 24), date(1, 31), date(2, 1)]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+    exclusions: Vec<Rule>,
+}
+
+impl RuleSet {
+    /// An empty set of rules which never occurs
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule to the set
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Add a rule whose dates are removed from the set
+    pub fn with_exclusion(mut self, rule: Rule) -> Self {
+        self.exclusions.push(rule);
+        self
+    }
+
+    /// Rules of the set
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Exclusion rules of the set
+    pub fn exclusions(&self) -> &[Rule] {
+        &self.exclusions
+    }
+
+    /// Starting point for the combined series
+    pub fn with_start(&self, start: NaiveDate) -> RuleSetRecurrence {
+        let recur = |rules: &[Rule]| {
+            rules
+                .iter()
+                .map(|rule| Recurrence::with_start(rule.clone(), start).peekable())
+                .collect()
+        };
+
+        RuleSetRecurrence {
+            rules: recur(&self.rules),
+            exclusions: recur(&self.exclusions),
+        }
+    }
+}
+
+/// Evaluate a [RuleSet]
+#[derive(Debug, Clone)]
+pub struct RuleSetRecurrence {
+    rules: Vec<Peekable<Recurrence>>,
+    exclusions: Vec<Peekable<Recurrence>>,
+}
+
+impl Iterator for RuleSetRecurrence {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let date = self
+                .rules
+                .iter_mut()
+                .filter_map(|r| r.peek().copied())
+                .min()?;
+
+            for recur in self.rules.iter_mut() {
+                recur.next_if_eq(&date);
+            }
+
+            let excluded = self.exclusions.iter_mut().any(|recur| {
+                while recur.next_if(|d| *d < date).is_some() {}
+                recur.peek() == Some(&date)
+            });
+
+            if !excluded {
+                return Some(date);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let upper = self
+            .rules
+            .iter()
+            .try_fold(0usize, |total, r| total.checked_add(r.size_hint().1?));
+
+        (0, upper)
+    }
+}
+
+impl FusedIterator for RuleSetRecurrence {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Weekday;
+
+    use super::*;
+    use crate::RelativeDuration;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_union_is_sorted_and_deduplicated() {
+        let set = RuleSet::new()
+            .with_rule(Rule::semimonthly())
+            .with_rule(Rule::weekly_on(&[Weekday::Fri]))
+            .with_rule(Rule::monthly());
+
+        // 2022-04-01 and 2022-04-15 are both Fridays
+        let dates: Vec<NaiveDate> = set.with_start(date(4, 1)).take(6).collect();
+        assert_eq!(
+            dates,
+            vec![
+                date(4, 1),
+                date(4, 8),
+                date(4, 15),
+                date(4, 22),
+                date(4, 29),
+                date(5, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exclusions() {
+        let set = RuleSet::new()
+            .with_rule(Rule::daily())
+            .with_exclusion(Rule::weekly_on(&[Weekday::Sat, Weekday::Sun]))
+            .with_exclusion(Rule::Offset(RelativeDuration::weeks(1), 0));
+
+        // 2022-01-03 is a Monday
+        let dates: Vec<NaiveDate> = set.with_start(date(1, 3)).take(5).collect();
+        assert_eq!(
+            dates,
+            vec![date(1, 4), date(1, 5), date(1, 6), date(1, 7), date(1, 11)]
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut recur = RuleSet::new()
+            .with_exclusion(Rule::daily())
+            .with_start(date(1, 1));
+
+        assert_eq!(recur.size_hint(), (0, Some(0)));
+        assert_eq!(recur.next(), None);
+    }
+}
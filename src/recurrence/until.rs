@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, ops::Bound};
+use std::{cmp::Ordering, iter::FusedIterator, ops::Bound};
 
 use chrono::NaiveDate;
 
@@ -36,6 +36,9 @@ where
 {
     pub until: Bound<NaiveDate>,
     pub iter: T,
+    /// Bounds on the number of remaining events when known upfront
+    hint: Option<(usize, Option<usize>)>,
+    done: bool,
 }
 
 impl<T> Until<T>
@@ -46,6 +49,8 @@ where
         Self {
             until: Bound::Included(until),
             iter,
+            hint: None,
+            done: false,
         }
     }
 
@@ -53,8 +58,16 @@ where
         Self {
             until: Bound::Excluded(until),
             iter,
+            hint: None,
+            done: false,
         }
     }
+
+    /// Provide bounds on the number of events that will be produced, used by `size_hint`
+    pub(crate) fn with_hint(mut self, hint: (usize, Option<usize>)) -> Self {
+        self.hint = Some(hint);
+        self
+    }
 }
 
 impl<T> Iterator for Until<T>
@@ -64,11 +77,54 @@ where
     type Item = NaiveDate;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let event = self.iter.next()?;
-        match cmp_bound(&Bound::Included(event), &self.until) {
-            std::cmp::Ordering::Less => Some(event),
-            std::cmp::Ordering::Equal => Some(event),
-            std::cmp::Ordering::Greater => None,
+        if self.done {
+            return None;
+        }
+
+        let event = self.iter.next();
+        match event {
+            Some(event) if cmp_bound(&Bound::Included(event), &self.until) != Ordering::Greater => {
+                if let Some((lower, upper)) = &mut self.hint {
+                    *lower = lower.saturating_sub(1);
+                    *upper = upper.map(|upper| upper.saturating_sub(1));
+                }
+                Some(event)
+            }
+            _ => {
+                self.done = true;
+                None
+            }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        match self.hint {
+            Some(hint) => hint,
+            None => (0, self.iter.size_hint().1),
+        }
+    }
+}
+
+impl<T> FusedIterator for Until<T> where T: Iterator<Item = NaiveDate> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fused() {
+        let dates = [(2022, 1, 1), (2022, 3, 1), (2022, 2, 1)]
+            .into_iter()
+            .map(|(y, m, d)| NaiveDate::from_ymd_opt(y, m, d).unwrap());
+
+        let mut until = Until::exclusive(NaiveDate::from_ymd_opt(2022, 2, 15).unwrap(), dates);
+        assert!(until.next().is_some());
+        assert_eq!(until.next(), None);
+        assert_eq!(until.next(), None);
+        assert_eq!(until.size_hint(), (0, Some(0)));
+    }
 }
@@ -0,0 +1,419 @@
+//! Retail calendars made up of whole weeks, such as the 4-4-5 calendar
+//!
+//! A retail year ends on the same weekday every year, close to the end of a month, so it is made
+//! up of 52 or 53 whole weeks. Each quarter has 13 weeks split into three periods following a
+//! [RetailPattern]. The extra week of a 53 week year is added to the last period of the year.
+//!
+//! ```
+//! use calends::{
+//!     retail::{RetailCalendar, RetailPattern, RetailYearEnd},
+//!     CalendarUnit,
+//! };
+//! use chrono::{NaiveDate, Weekday};
+//!
+//! // The NRF calendar ends on the Saturday nearest the end of January
+//! let nrf = RetailCalendar::new(RetailPattern::FourFiveFour, 1, Weekday::Sat)
+//!     .with_year_end(RetailYearEnd::NearestEndOfMonth)
+//!     .named_by_start_year();
+//!
+//! let date = NaiveDate::from_ymd_opt(2023, 3, 1).unwrap();
+//! let period = nrf.period_of(date);
+//! assert_eq!(period.unit(), CalendarUnit::Month(2023, 2));
+//! assert_eq!(period.start_date(), NaiveDate::from_ymd_opt(2023, 2, 26).unwrap());
+//! assert_eq!(period.end_date(), NaiveDate::from_ymd_opt(2023, 4, 1).unwrap());
+//!
+//! // Fiscal 2023 has 53 weeks
+//! assert_eq!(nrf.weeks_in_year(2023), 53);
+//! ```
+use std::fmt::Display;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::{
+    unit::CalendarUnitFormat,
+    util::{find_weekday_descending, month_end},
+    CalendarUnit, Interval, UnitKind,
+};
+
+/// Number of weeks in each of the three periods of a quarter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetailPattern {
+    FourFourFive,
+    FourFiveFour,
+    FiveFourFour,
+}
+
+impl RetailPattern {
+    /// Weeks in the periods of a quarter
+    pub fn weeks(&self) -> [u32; 3] {
+        match self {
+            RetailPattern::FourFourFive => [4, 4, 5],
+            RetailPattern::FourFiveFour => [4, 5, 4],
+            RetailPattern::FiveFourFour => [5, 4, 4],
+        }
+    }
+}
+
+/// How the last day of a retail year is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetailYearEnd {
+    /// The last occurence of the weekday in the month
+    LastOfMonth,
+    /// The occurence of the weekday nearest the last day of the month, which may fall in the
+    /// following month
+    NearestEndOfMonth,
+}
+
+/// A calendar of 52 or 53 week years split into 4-4-5 style periods, see the
+/// [module documentation](self)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RetailCalendar {
+    pattern: RetailPattern,
+    end_month: u32,
+    end_weekday: Weekday,
+    year_end: RetailYearEnd,
+    named_by_start: bool,
+}
+
+impl RetailCalendar {
+    /// A calendar whose years end on the last weekday of the month
+    ///
+    /// Years are named after the calendar year they end in. Panics if the month is not between 1
+    /// and 12.
+    pub fn new(pattern: RetailPattern, end_month: u32, end_weekday: Weekday) -> Self {
+        assert!((1..=12).contains(&end_month), "month out of range");
+
+        RetailCalendar {
+            pattern,
+            end_month,
+            end_weekday,
+            year_end: RetailYearEnd::LastOfMonth,
+            named_by_start: false,
+        }
+    }
+
+    /// Choose how the last day of each year is found
+    pub fn with_year_end(mut self, year_end: RetailYearEnd) -> Self {
+        self.year_end = year_end;
+        self
+    }
+
+    /// Name years after the calendar year they start in, like the NRF calendar
+    pub fn named_by_start_year(mut self) -> Self {
+        self.named_by_start = true;
+        self
+    }
+
+    /// Last day of the retail year
+    pub fn year_end(&self, year: i32) -> NaiveDate {
+        let end_year = year + i32::from(self.named_by_start);
+
+        match self.year_end {
+            RetailYearEnd::LastOfMonth => {
+                find_weekday_descending(self.end_weekday, end_year, self.end_month, 1)
+            }
+            RetailYearEnd::NearestEndOfMonth => {
+                let month_end = month_end(end_year, self.end_month);
+                let days_after = (7 + self.end_weekday.num_days_from_monday()
+                    - month_end.weekday().num_days_from_monday())
+                    % 7;
+                match days_after {
+                    0..=3 => month_end + Duration::days(days_after.into()),
+                    _ => month_end - Duration::days((7 - days_after).into()),
+                }
+            }
+        }
+    }
+
+    /// First day of the retail year
+    pub fn year_start(&self, year: i32) -> NaiveDate {
+        self.year_end(year - 1).succ_opt().unwrap()
+    }
+
+    /// Number of weeks in the retail year, 52 or 53
+    pub fn weeks_in_year(&self, year: i32) -> u32 {
+        let days = (self.year_end(year) - self.year_end(year - 1)).num_days();
+        (days / 7) as u32
+    }
+
+    /// The retail unit with the numbering of the unit, e.g. `Month(2023, 1)` for the first
+    /// period of 2023 or `Week(2023, 53)` for its extra week
+    ///
+    /// Returns [None] for biweeks and days and for numbers out of range.
+    pub fn unit(&self, unit: CalendarUnit) -> Option<RetailUnit> {
+        let valid = match unit {
+            CalendarUnit::Year(_) => true,
+            CalendarUnit::Half(_, half) => (1..=2).contains(&half),
+            CalendarUnit::Quarter(_, quarter) => (1..=4).contains(&quarter),
+            CalendarUnit::Month(_, period) => (1..=12).contains(&period),
+            CalendarUnit::Week(year, week) => (1..=self.weeks_in_year(year)).contains(&week.into()),
+            CalendarUnit::BiWeek(_, _) | CalendarUnit::Day(_) => false,
+        };
+
+        valid.then_some(RetailUnit {
+            calendar: *self,
+            unit,
+        })
+    }
+
+    /// The retail unit of the kind containing the date, [None] for biweeks and days
+    pub fn containing(&self, date: NaiveDate, kind: UnitKind) -> Option<RetailUnit> {
+        let mut year = date.year();
+        while date <= self.year_end(year - 1) {
+            year -= 1;
+        }
+        while date > self.year_end(year) {
+            year += 1;
+        }
+        let week = ((date - self.year_start(year)).num_days() / 7) as u32;
+
+        let unit = match kind {
+            UnitKind::Year => CalendarUnit::Year(year),
+            UnitKind::Half => CalendarUnit::Half(year, (week / 26).min(1) as u8 + 1),
+            UnitKind::Quarter => CalendarUnit::Quarter(year, (week / 13).min(3) as u8 + 1),
+            UnitKind::Month => CalendarUnit::Month(year, self.period_index(week) as u8 + 1),
+            UnitKind::Week => CalendarUnit::Week(year, week as u8 + 1),
+            UnitKind::BiWeek | UnitKind::Day => return None,
+        };
+
+        self.unit(unit)
+    }
+
+    /// Retail year containing the date
+    pub fn year_of(&self, date: NaiveDate) -> RetailUnit {
+        self.containing(date, UnitKind::Year).unwrap()
+    }
+
+    /// Retail quarter containing the date
+    pub fn quarter_of(&self, date: NaiveDate) -> RetailUnit {
+        self.containing(date, UnitKind::Quarter).unwrap()
+    }
+
+    /// Retail period containing the date
+    pub fn period_of(&self, date: NaiveDate) -> RetailUnit {
+        self.containing(date, UnitKind::Month).unwrap()
+    }
+
+    /// Retail week containing the date
+    pub fn week_of(&self, date: NaiveDate) -> RetailUnit {
+        self.containing(date, UnitKind::Week).unwrap()
+    }
+
+    /// First week of the period counted from zero, the period is counted from zero as well
+    fn period_start(&self, period: u32) -> u32 {
+        let weeks = self.pattern.weeks();
+        13 * (period / 3) + weeks[..(period % 3) as usize].iter().sum::<u32>()
+    }
+
+    /// Period containing the week, both counted from zero
+    fn period_index(&self, week: u32) -> u32 {
+        (0..12)
+            .rev()
+            .find(|period| self.period_start(*period) <= week)
+            .unwrap()
+    }
+}
+
+/// A year, half, quarter, period or week of a [RetailCalendar]
+///
+/// Periods are numbered as months from 1 to 12 and weeks from 1 to 53 within the retail year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RetailUnit {
+    calendar: RetailCalendar,
+    unit: CalendarUnit,
+}
+
+impl RetailUnit {
+    /// Retail numbering of the unit
+    pub fn unit(&self) -> CalendarUnit {
+        self.unit
+    }
+
+    /// Calendar the unit belongs to
+    pub fn calendar(&self) -> RetailCalendar {
+        self.calendar
+    }
+
+    /// First and one past the last week of the unit, counted from zero
+    fn week_range(&self) -> (i32, u32, u32) {
+        let calendar = &self.calendar;
+        let last = |year, end| match end {
+            52 => calendar.weeks_in_year(year),
+            end => end,
+        };
+
+        match self.unit {
+            CalendarUnit::Year(year) => (year, 0, calendar.weeks_in_year(year)),
+            CalendarUnit::Half(year, half) => {
+                let half = u32::from(half);
+                (year, 26 * (half - 1), last(year, 26 * half))
+            }
+            CalendarUnit::Quarter(year, quarter) => {
+                let quarter = u32::from(quarter);
+                (year, 13 * (quarter - 1), last(year, 13 * quarter))
+            }
+            CalendarUnit::Month(year, period) => {
+                let period = u32::from(period);
+                let end = match period {
+                    12 => 52,
+                    _ => calendar.period_start(period),
+                };
+                (year, calendar.period_start(period - 1), last(year, end))
+            }
+            CalendarUnit::Week(year, week) => (year, u32::from(week) - 1, u32::from(week)),
+            _ => unreachable!("retail units are validated on construction"),
+        }
+    }
+
+    /// First date of the unit
+    pub fn start_date(&self) -> NaiveDate {
+        let (year, first, _) = self.week_range();
+        self.calendar.year_start(year) + Duration::weeks(first.into())
+    }
+
+    /// Last date of the unit
+    pub fn end_date(&self) -> NaiveDate {
+        let (year, _, last) = self.week_range();
+        self.calendar.year_start(year) + Duration::weeks(last.into()) - Duration::days(1)
+    }
+
+    /// Number of weeks in the unit
+    pub fn weeks(&self) -> u32 {
+        let (_, first, last) = self.week_range();
+        last - first
+    }
+
+    /// Whether the date falls within the unit
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start_date() <= date && date <= self.end_date()
+    }
+
+    pub fn into_interval(&self) -> Interval {
+        Interval::closed_exact(self.start_date(), self.end_date())
+    }
+
+    /// The following unit of the same kind
+    pub fn succ(&self) -> RetailUnit {
+        let unit = match self.unit {
+            CalendarUnit::Week(year, week)
+                if u32::from(week) == self.calendar.weeks_in_year(year) =>
+            {
+                CalendarUnit::Week(year + 1, 1)
+            }
+            CalendarUnit::Week(year, week) => CalendarUnit::Week(year, week + 1),
+            unit => unit.succ(),
+        };
+
+        RetailUnit { unit, ..*self }
+    }
+
+    /// The preceding unit of the same kind
+    pub fn pred(&self) -> RetailUnit {
+        let unit = match self.unit {
+            CalendarUnit::Week(year, 1) => {
+                CalendarUnit::Week(year - 1, self.calendar.weeks_in_year(year - 1) as u8)
+            }
+            CalendarUnit::Week(year, week) => CalendarUnit::Week(year, week - 1),
+            unit => unit.pred(),
+        };
+
+        RetailUnit { unit, ..*self }
+    }
+
+    /// Iterate over this unit and the units of the same kind following it
+    pub fn onwards(&self) -> impl Iterator<Item = RetailUnit> {
+        std::iter::successors(Some(*self), |unit| Some(unit.succ()))
+    }
+}
+
+/// Fiscal form of the unit e.g. `FY2023-Q1`, periods are written like months
+impl Display for RetailUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.unit.format_with(CalendarUnitFormat::Fiscal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn nrf() -> RetailCalendar {
+        RetailCalendar::new(RetailPattern::FourFiveFour, 1, Weekday::Sat)
+            .with_year_end(RetailYearEnd::NearestEndOfMonth)
+            .named_by_start_year()
+    }
+
+    #[test]
+    fn test_year_ends() {
+        let nrf = nrf();
+        assert_eq!(nrf.year_end(2022), date(2023, 1, 28));
+        assert_eq!(nrf.year_end(2023), date(2024, 2, 3));
+        assert_eq!(nrf.year_start(2023), date(2023, 1, 29));
+        assert_eq!(nrf.weeks_in_year(2022), 52);
+        assert_eq!(nrf.weeks_in_year(2023), 53);
+
+        // The last Saturday of 2022 is December 31
+        let last = RetailCalendar::new(RetailPattern::FourFourFive, 12, Weekday::Sat);
+        assert_eq!(last.year_end(2022), date(2022, 12, 31));
+        assert_eq!(last.year_end(2023), date(2023, 12, 30));
+        assert_eq!(
+            last.year_of(date(2023, 12, 31)).unit(),
+            CalendarUnit::Year(2024)
+        );
+    }
+
+    #[test]
+    fn test_periods() {
+        let calendar = RetailCalendar::new(RetailPattern::FourFourFive, 12, Weekday::Sat);
+        let year = calendar.unit(CalendarUnit::Year(2023)).unwrap();
+
+        let weeks: Vec<u32> = calendar
+            .unit(CalendarUnit::Month(2023, 1))
+            .unwrap()
+            .onwards()
+            .take(12)
+            .map(|p| p.weeks())
+            .collect();
+        assert_eq!(weeks, vec![4, 4, 5, 4, 4, 5, 4, 4, 5, 4, 4, 5]);
+
+        // Every date of the year falls in the period, quarter and week which contain it
+        for day in year
+            .start_date()
+            .iter_days()
+            .take_while(|d| year.contains(*d))
+        {
+            for kind in [
+                UnitKind::Half,
+                UnitKind::Quarter,
+                UnitKind::Month,
+                UnitKind::Week,
+            ] {
+                assert!(calendar.containing(day, kind).unwrap().contains(day));
+            }
+        }
+    }
+
+    #[test]
+    fn test_extra_week() {
+        let nrf = nrf();
+
+        let last_period = nrf.unit(CalendarUnit::Month(2023, 12)).unwrap();
+        assert_eq!(last_period.weeks(), 5);
+        assert_eq!(last_period.end_date(), date(2024, 2, 3));
+        assert_eq!(nrf.quarter_of(date(2024, 2, 3)).weeks(), 14);
+
+        let extra = nrf.week_of(date(2024, 2, 1));
+        assert_eq!(extra.unit(), CalendarUnit::Week(2023, 53));
+        assert_eq!(extra.succ().unit(), CalendarUnit::Week(2024, 1));
+        assert_eq!(extra.succ().pred(), extra);
+        assert!(nrf.unit(CalendarUnit::Week(2022, 53)).is_none());
+
+        for week in extra.pred().onwards().take(60) {
+            assert_eq!(week.end_date().succ_opt(), Some(week.succ().start_date()));
+        }
+    }
+}
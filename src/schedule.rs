@@ -0,0 +1,406 @@
+//! Accrual schedules of bonds and loans
+//!
+//! A [Schedule] splits the time from a start date to an end date into accrual periods on the
+//! dates of a [Rule], each paid at its end. Periods which don't line up with the rule are stubs,
+//! see [StubPolicy], and the [RollConvention] moves dates onto business days of a
+//! [HolidayCalendar].
+//!
+//! ```
+//! use calends::{
+//!     calendar::AdjustRule,
+//!     schedule::{RollConvention, Schedule, StubPolicy},
+//!     Rule,
+//! };
+//! use chrono::NaiveDate;
+//!
+//! let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+//! let holidays = vec![date(7, 4)];
+//!
+//! let schedule = Schedule::generate(
+//!     date(1, 15),
+//!     date(11, 30),
+//!     Rule::quarterly(),
+//!     StubPolicy::ShortFinal,
+//!     RollConvention::PaymentOnly(AdjustRule::ModifiedFollowing),
+//!     &holidays,
+//! )
+//! .unwrap();
+//!
+//! let periods = schedule.periods();
+//! assert_eq!(periods.len(), 4);
+//! assert_eq!((periods[2].start, periods[2].end), (date(7, 15), date(10, 15)));
+//! // Saturday 15th October is paid on the Monday
+//! assert_eq!(periods[2].payment, date(10, 17));
+//! assert!(periods[3].stub);
+//! ```
+use chrono::NaiveDate;
+
+use crate::{
+    calendar::{AdjustRule, HolidayCalendar},
+    interval::ClosedInterval,
+    recurrence::RuleError,
+    Recurrence, RelativeDuration, Rule,
+};
+
+/// Where the periods which don't line up with the rule go
+///
+/// Rules stepping by a duration from their start, such as [Rule::quarterly], roll backwards from
+/// the end for initial stubs. Rules fixed to the calendar, such as [Rule::MonthDays], have the
+/// same dates either way so a schedule starting and ending off the rule has a stub at both ends
+/// and the policy only decides which of them is merged into its neighbour.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum StubPolicy {
+    /// Roll forwards from the start with a shorter period at the end
+    #[default]
+    ShortFinal,
+    /// Roll forwards from the start with the remainder added to the last period
+    LongFinal,
+    /// Roll backwards from the end with a shorter period at the start
+    ShortInitial,
+    /// Roll backwards from the end with the remainder added to the first period
+    LongInitial,
+}
+
+/// How the dates of a schedule falling on weekends or holidays are moved to business days
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RollConvention {
+    /// Keep every date as generated
+    #[default]
+    Unadjusted,
+    /// Move the period dates and the payment dates
+    Adjusted(AdjustRule),
+    /// Keep the period dates and only move the payment dates
+    PaymentOnly(AdjustRule),
+}
+
+impl RollConvention {
+    fn accrual_rule(&self) -> Option<AdjustRule> {
+        match self {
+            RollConvention::Adjusted(rule) => Some(*rule),
+            RollConvention::Unadjusted | RollConvention::PaymentOnly(_) => None,
+        }
+    }
+
+    fn payment_rule(&self) -> Option<AdjustRule> {
+        match self {
+            RollConvention::Adjusted(rule) | RollConvention::PaymentOnly(rule) => Some(*rule),
+            RollConvention::Unadjusted => None,
+        }
+    }
+}
+
+/// One period of a [Schedule]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AccrualPeriod {
+    /// First day of accrual
+    pub start: NaiveDate,
+    /// Day accrual stops, which is the start of the next period
+    pub end: NaiveDate,
+    /// Date the period is paid
+    pub payment: NaiveDate,
+    /// Whether the period is shorter or longer than the periods of the rule
+    pub stub: bool,
+}
+
+impl AccrualPeriod {
+    /// Number of days accrued
+    pub fn days(&self) -> i64 {
+        (self.end - self.start).num_days()
+    }
+
+    /// The days accrued, from the start up to the day before the end
+    pub fn accrual_interval(&self) -> ClosedInterval {
+        let last = self.end.pred_opt().expect("the end is after the start");
+        ClosedInterval::exact(self.start, last)
+    }
+}
+
+/// Reasons a [Schedule] can't be generated
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ScheduleError {
+    #[error("the end {end} is not after the start {start}")]
+    EmptyRange { start: NaiveDate, end: NaiveDate },
+    #[error(transparent)]
+    Rule(#[from] RuleError),
+    #[error("there is no business day within a year of {0}")]
+    NoBusinessDay(NaiveDate),
+}
+
+/// Accrual periods with their payment dates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    periods: Vec<AccrualPeriod>,
+}
+
+impl Schedule {
+    /// Accrual periods from the start to the end on the dates of the frequency
+    ///
+    /// Each period is paid on its end, moved to a business day unless the roll convention is
+    /// [RollConvention::Unadjusted]. Periods which an adjustment moves onto the same day are
+    /// dropped.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "strict"))] {
+    /// use calends::{
+    ///     schedule::{RollConvention, Schedule, StubPolicy},
+    ///     RelativeDuration, Rule,
+    /// };
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    ///
+    /// let schedule = Schedule::generate(
+    ///     date(2022, 1, 15),
+    ///     date(2023, 11, 30),
+    ///     Rule::Offset(RelativeDuration::months(6), 0),
+    ///     StubPolicy::LongInitial,
+    ///     RollConvention::Unadjusted,
+    ///     &Vec::<NaiveDate>::new(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let periods: Vec<_> = schedule.periods().iter().map(|p| (p.start, p.end)).collect();
+    /// assert_eq!(
+    ///     periods,
+    ///     [
+    ///         (date(2022, 1, 15), date(2022, 11, 30)),
+    ///         (date(2022, 11, 30), date(2023, 5, 31)),
+    ///         (date(2023, 5, 31), date(2023, 11, 30)),
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    pub fn generate<C>(
+        start: NaiveDate,
+        end: NaiveDate,
+        frequency: Rule,
+        stub: StubPolicy,
+        roll: RollConvention,
+        calendar: &C,
+    ) -> Result<Schedule, ScheduleError>
+    where
+        C: HolidayCalendar + ?Sized,
+    {
+        if end <= start {
+            return Err(ScheduleError::EmptyRange { start, end });
+        }
+        frequency.validate()?;
+
+        let regular = match (stub, &frequency) {
+            (
+                StubPolicy::ShortInitial | StubPolicy::LongInitial,
+                Rule::Offset(duration, 0) | Rule::Anchored(duration, _),
+            ) => rolled_back(*duration, start, end),
+            _ => Recurrence::with_start(frequency, start)
+                .take_while(|date| *date <= end)
+                .collect(),
+        };
+
+        let mut dates: Vec<NaiveDate> = regular
+            .iter()
+            .copied()
+            .filter(|date| start < *date && *date < end)
+            .collect();
+        match stub {
+            StubPolicy::LongFinal if regular.last() != Some(&end) => {
+                dates.pop();
+            }
+            StubPolicy::LongInitial if regular.first() != Some(&start) && !dates.is_empty() => {
+                dates.remove(0);
+            }
+            _ => {}
+        }
+
+        let boundaries: Vec<NaiveDate> = std::iter::once(start)
+            .chain(dates)
+            .chain(std::iter::once(end))
+            .collect();
+
+        let mut periods: Vec<AccrualPeriod> = Vec::with_capacity(boundaries.len() - 1);
+        for pair in boundaries.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let period = AccrualPeriod {
+                start: adjust(from, roll.accrual_rule(), calendar)?,
+                end: adjust(to, roll.accrual_rule(), calendar)?,
+                payment: adjust(to, roll.payment_rule(), calendar)?,
+                stub: !is_regular(&regular, from, to),
+            };
+
+            if period.start < period.end {
+                periods.push(period);
+            }
+        }
+
+        Ok(Schedule { periods })
+    }
+
+    /// The periods in order
+    pub fn periods(&self) -> &[AccrualPeriod] {
+        &self.periods
+    }
+
+    /// The payment dates in order
+    pub fn payment_dates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.periods.iter().map(|period| period.payment)
+    }
+
+    pub fn into_periods(self) -> Vec<AccrualPeriod> {
+        self.periods
+    }
+}
+
+impl IntoIterator for Schedule {
+    type Item = AccrualPeriod;
+    type IntoIter = std::vec::IntoIter<AccrualPeriod>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.periods.into_iter()
+    }
+}
+
+/// Whole multiples of the duration back from the end which are on or after the start, in order
+fn rolled_back(duration: RelativeDuration, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+    let mut dates: Vec<NaiveDate> = (0..)
+        .map_while(|n| duration.checked_mul(-n)?.checked_add_to(end))
+        .take_while(|date| *date >= start)
+        .collect();
+
+    dates.reverse();
+    dates
+}
+
+/// Whether the dates are consecutive dates of the rule
+fn is_regular(regular: &[NaiveDate], from: NaiveDate, to: NaiveDate) -> bool {
+    regular
+        .binary_search(&from)
+        .is_ok_and(|i| regular.get(i + 1) == Some(&to))
+}
+
+fn adjust<C>(
+    date: NaiveDate,
+    rule: Option<AdjustRule>,
+    calendar: &C,
+) -> Result<NaiveDate, ScheduleError>
+where
+    C: HolidayCalendar + ?Sized,
+{
+    match rule {
+        Some(rule) => rule
+            .adjust(date, calendar)
+            .ok_or(ScheduleError::NoBusinessDay(date)),
+        None => Ok(date),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnchorPolicy;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn generate(rule: Rule, stub: StubPolicy, roll: RollConvention) -> Vec<(NaiveDate, NaiveDate)> {
+        Schedule::generate(
+            date(2022, 1, 15),
+            date(2022, 11, 30),
+            rule,
+            stub,
+            roll,
+            &Vec::<NaiveDate>::new(),
+        )
+        .unwrap()
+        .into_iter()
+        .map(|period| (period.start, period.end))
+        .collect()
+    }
+
+    #[test]
+    fn test_stub_policies() {
+        let quarterly = || Rule::Anchored(RelativeDuration::months(3), AnchorPolicy::ClampDay);
+        let unadjusted = RollConvention::Unadjusted;
+
+        assert_eq!(
+            generate(quarterly(), StubPolicy::ShortFinal, unadjusted),
+            [
+                (date(2022, 1, 15), date(2022, 4, 15)),
+                (date(2022, 4, 15), date(2022, 7, 15)),
+                (date(2022, 7, 15), date(2022, 10, 15)),
+                (date(2022, 10, 15), date(2022, 11, 30)),
+            ]
+        );
+        assert_eq!(
+            generate(quarterly(), StubPolicy::LongFinal, unadjusted)[2],
+            (date(2022, 7, 15), date(2022, 11, 30))
+        );
+        assert_eq!(
+            generate(quarterly(), StubPolicy::ShortInitial, unadjusted),
+            [
+                (date(2022, 1, 15), date(2022, 2, 28)),
+                (date(2022, 2, 28), date(2022, 5, 31)),
+                (date(2022, 5, 31), date(2022, 8, 31)),
+                (date(2022, 8, 31), date(2022, 11, 30)),
+            ]
+        );
+
+        // Month ends are the same dates from either end
+        let month_ends = Rule::MonthDays(RelativeDuration::months(1), vec![-1]);
+        let short = generate(month_ends.clone(), StubPolicy::ShortInitial, unadjusted);
+        let long = generate(month_ends, StubPolicy::LongInitial, unadjusted);
+        assert_eq!(short.len(), 11);
+        assert_eq!(short[0], (date(2022, 1, 15), date(2022, 1, 31)));
+        assert_eq!(long[0], (date(2022, 1, 15), date(2022, 2, 28)));
+        assert_eq!(long[1..], short[2..]);
+    }
+
+    #[test]
+    fn test_roll_conventions() {
+        let holidays = vec![date(2022, 12, 26)];
+        let generate = |roll| {
+            Schedule::generate(
+                date(2022, 9, 25),
+                date(2022, 12, 25),
+                Rule::monthly(),
+                StubPolicy::ShortFinal,
+                roll,
+                &holidays,
+            )
+        };
+
+        let schedule = generate(RollConvention::Adjusted(AdjustRule::NextBusinessDay)).unwrap();
+        let periods = schedule.periods();
+        assert_eq!(periods[0].start, date(2022, 9, 26));
+        assert_eq!(periods[2].end, date(2022, 12, 27));
+        assert_eq!(periods[2].payment, date(2022, 12, 27));
+        assert!(periods.iter().all(|period| !period.stub));
+        assert_eq!(
+            periods[1].accrual_interval(),
+            ClosedInterval::exact(date(2022, 10, 25), date(2022, 11, 24))
+        );
+
+        let schedule =
+            generate(RollConvention::PaymentOnly(AdjustRule::ModifiedFollowing)).unwrap();
+        let periods = schedule.periods();
+        assert_eq!(
+            (periods[2].end, periods[2].payment),
+            (date(2022, 12, 25), date(2022, 12, 27))
+        );
+        assert_eq!(periods.iter().map(AccrualPeriod::days).sum::<i64>(), 91);
+
+        assert_eq!(
+            Schedule::generate(
+                date(2022, 1, 1),
+                date(2022, 1, 1),
+                Rule::monthly(),
+                StubPolicy::ShortFinal,
+                RollConvention::Unadjusted,
+                &holidays,
+            ),
+            Err(ScheduleError::EmptyRange {
+                start: date(2022, 1, 1),
+                end: date(2022, 1, 1)
+            })
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! [JsonSchema] implementations for the types with a string serialization
+//!
+//! Enable the `schemars` feature to use calends types in schemas generated with
+//! [schemars](https://docs.rs/schemars). Enums such as [crate::Rule] derive their schema, this
+//! module covers the types which serialize through [std::fmt::Display] or ISO8601-2:2019 strings.
+//!
+//! ```
+//! use calends::{CalendarUnit, RelativeDuration, Rule};
+//! use schemars::{schema_for, JsonSchema};
+//!
+//! #[derive(JsonSchema)]
+//! struct Plan {
+//!     period: CalendarUnit,
+//!     #[schemars(schema_with = "calends::rd_iso8601::schema")]
+//!     trial: RelativeDuration,
+//!     billing: Rule,
+//! }
+//!
+//! let schema = serde_json::to_value(schema_for!(Plan)).unwrap();
+//!
+//! assert_eq!(schema["properties"]["trial"]["type"], "string");
+//! assert_eq!(schema["definitions"]["CalendarUnit"]["type"], "string");
+//! ```
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Metadata, ObjectValidation, Schema, SchemaObject},
+    JsonSchema,
+};
+
+use crate::{
+    interval::{ClosedInterval, OpenEndInterval, OpenStartInterval},
+    CalendarUnit, RelativeDuration,
+};
+
+/// A string schema with a description and examples of the format
+pub(crate) fn string_schema(description: &str, examples: &[&str]) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.to_string()),
+            examples: examples.iter().map(|e| (*e).into()).collect(),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+/// The months, weeks and days struct of the default serialization, see
+/// [crate::rd_iso8601::schema] for the ISO8601-2:2019 string
+impl JsonSchema for RelativeDuration {
+    fn schema_name() -> String {
+        "RelativeDuration".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let properties =
+            ["months", "weeks", "days"].map(|name| (name.to_string(), gen.subschema_for::<i32>()));
+
+        SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(Box::new(ObjectValidation {
+                required: properties.iter().map(|(name, _)| name.clone()).collect(),
+                properties: properties.into_iter().collect(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl JsonSchema for ClosedInterval {
+    fn schema_name() -> String {
+        "ClosedInterval".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "ISO8601-2:2019 interval with a start and an end or duration",
+            &["2022-01-01/2022-03-31", "2022-01-01/P1M", "P1M/2022-01-31"],
+        )
+    }
+}
+
+impl JsonSchema for OpenStartInterval {
+    fn schema_name() -> String {
+        "OpenStartInterval".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "ISO8601-2:2019 interval without a start",
+            &["../2022-01-01"],
+        )
+    }
+}
+
+impl JsonSchema for OpenEndInterval {
+    fn schema_name() -> String {
+        "OpenEndInterval".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema("ISO8601-2:2019 interval without an end", &["2022-01-01/.."])
+    }
+}
+
+impl JsonSchema for CalendarUnit {
+    fn schema_name() -> String {
+        "CalendarUnit".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "A calendar year, half, quarter, month, two week period, ISO week or day",
+            &[
+                "2022",
+                "2022-H2",
+                "2022-Q1",
+                "2022-05",
+                "2022-B04",
+                "2022-W07",
+                "2022-05-17",
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::schema_for;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::{Interval, Rule};
+
+    fn schema<T: JsonSchema>() -> Value {
+        serde_json::to_value(schema_for!(T)).unwrap()
+    }
+
+    #[test]
+    fn test_schemas_match_serialization() {
+        assert_eq!(
+            schema::<RelativeDuration>()["required"],
+            json!(["days", "months", "weeks"])
+        );
+
+        let interval = schema::<Interval>();
+        assert_eq!(interval["anyOf"].as_array().unwrap().len(), 3);
+        for definition in interval["definitions"].as_object().unwrap().values() {
+            assert_eq!(definition["type"], "string");
+        }
+
+        let rule = schema::<Rule>();
+        let offset = rule["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|variant| variant["required"] == json!(["Offset"]))
+            .unwrap();
+        assert_eq!(offset["properties"]["Offset"]["items"][0]["type"], "string");
+        let policies = rule["definitions"]["AnchorPolicy"]["oneOf"]
+            .as_array()
+            .unwrap();
+        assert_eq!(policies[2]["enum"], json!(["SkipMissing"]));
+    }
+}
@@ -0,0 +1,44 @@
+use std::ops::Bound;
+
+use chrono::NaiveDate;
+use diesel::{
+    deserialize::{self, FromSql},
+    pg::{data_types::PgInterval, Pg, PgValue},
+    serialize::{self, Output, ToSql},
+    sql_types::{Date, Interval, Range},
+};
+
+use crate::{interval::ClosedInterval, RelativeDuration};
+
+impl ToSql<Interval, Pg> for RelativeDuration {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        let (months, days) = super::to_interval(self);
+        ToSql::<Interval, Pg>::to_sql(&PgInterval::new(0, days, months), &mut out.reborrow())
+    }
+}
+
+impl FromSql<Interval, Pg> for RelativeDuration {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        let interval: PgInterval = FromSql::<Interval, Pg>::from_sql(value)?;
+        Ok(super::from_interval(
+            interval.months,
+            interval.days,
+            interval.microseconds,
+        )?)
+    }
+}
+
+impl ToSql<Range<Date>, Pg> for ClosedInterval {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        let bounds = super::to_daterange(self)?;
+        ToSql::<Range<Date>, Pg>::to_sql(&bounds, &mut out.reborrow())
+    }
+}
+
+impl FromSql<Range<Date>, Pg> for ClosedInterval {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        let (lower, upper): (Bound<NaiveDate>, Bound<NaiveDate>) =
+            FromSql::<Range<Date>, Pg>::from_sql(value)?;
+        Ok(super::from_daterange(lower, upper)?)
+    }
+}
@@ -0,0 +1,172 @@
+//! Postgres column types for durations and intervals
+//!
+//! Enable the `diesel` or `sqlx` feature to read and write these types directly:
+//!
+//! - [RelativeDuration] <-> `INTERVAL`: months are stored as months, weeks are stored as 7 days.
+//!   Postgres does not keep weeks, so a duration read back holds its days in
+//!   [RelativeDuration::num_days]. Postgres has no end of month policy either, so durations read
+//!   back have the default one. Intervals with a time of day are rejected.
+//! - [ClosedInterval] <-> `daterange`: the inclusive end is stored as an exclusive upper bound,
+//!   the canonical form Postgres uses for `daterange`. Ranges read back are [ClosedInterval::exact]
+//!   and unbounded or empty ranges are rejected.
+use std::ops::Bound;
+
+use chrono::NaiveDate;
+
+use crate::{interval::ClosedInterval, IntervalLike, RelativeDuration};
+
+#[cfg(feature = "diesel")]
+mod diesel;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SqlError {
+    #[error("interval has a time of {0} microseconds, only months and days are supported")]
+    TimeOfDay(i64),
+    #[error("interval of {months} months and {days} days is out of range")]
+    IntervalOutOfRange { months: i32, days: i32 },
+    #[error("range must have a lower and an upper bound")]
+    Unbounded,
+    #[error("range is empty")]
+    Empty,
+    #[error("date is out of range")]
+    DateOutOfRange,
+}
+
+/// Months and days of the `INTERVAL` for a duration
+///
+/// The 20 bit components of a duration always fit within the 32 bit fields of an `INTERVAL`.
+pub(crate) fn to_interval(duration: &RelativeDuration) -> (i32, i32) {
+    (
+        duration.num_months(),
+        duration.num_weeks() * 7 + duration.num_days(),
+    )
+}
+
+/// The duration of an `INTERVAL` made up of months, days and microseconds
+pub(crate) fn from_interval(
+    months: i32,
+    days: i32,
+    microseconds: i64,
+) -> Result<RelativeDuration, SqlError> {
+    if microseconds != 0 {
+        return Err(SqlError::TimeOfDay(microseconds));
+    }
+
+    RelativeDuration::try_from_mwd(months, 0, days)
+        .ok_or(SqlError::IntervalOutOfRange { months, days })
+}
+
+/// Bounds of the `daterange` holding the dates of the interval
+pub(crate) fn to_daterange(
+    interval: &ClosedInterval,
+) -> Result<(Bound<NaiveDate>, Bound<NaiveDate>), SqlError> {
+    let (start, end) = interval
+        .start_opt()
+        .zip(interval.end_opt())
+        .ok_or(SqlError::Unbounded)?;
+
+    if end < start {
+        return Err(SqlError::Empty);
+    }
+
+    let upper = end.succ_opt().ok_or(SqlError::DateOutOfRange)?;
+
+    Ok((Bound::Included(start), Bound::Excluded(upper)))
+}
+
+/// The interval of the dates within a `daterange`
+pub(crate) fn from_daterange(
+    lower: Bound<NaiveDate>,
+    upper: Bound<NaiveDate>,
+) -> Result<ClosedInterval, SqlError> {
+    let start = match lower {
+        Bound::Included(date) => date,
+        Bound::Excluded(date) => date.succ_opt().ok_or(SqlError::DateOutOfRange)?,
+        Bound::Unbounded => return Err(SqlError::Unbounded),
+    };
+    let end = match upper {
+        Bound::Included(date) => date,
+        Bound::Excluded(date) => date.pred_opt().ok_or(SqlError::DateOutOfRange)?,
+        Bound::Unbounded => return Err(SqlError::Unbounded),
+    };
+
+    if end < start {
+        return Err(SqlError::Empty);
+    }
+
+    Ok(ClosedInterval::exact(start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_interval_parts() {
+        let duration = RelativeDuration::try_from_mwd(14, 2, -3).unwrap();
+        assert_eq!(to_interval(&duration), (14, 11));
+        assert_eq!(to_interval(&RelativeDuration::MIN), (-1048575, -8388600));
+        assert_eq!(
+            from_interval(14, 11, 0),
+            Ok(RelativeDuration::months(14).with_days(11))
+        );
+
+        assert_eq!(from_interval(0, 1, 1), Err(SqlError::TimeOfDay(1)));
+        assert_eq!(
+            from_interval(i32::MAX, 0, 0),
+            Err(SqlError::IntervalOutOfRange {
+                months: i32::MAX,
+                days: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_daterange_bounds() {
+        let interval = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 1, 31));
+        let (lower, upper) = to_daterange(&interval).unwrap();
+        assert_eq!(lower, Bound::Included(date(2022, 1, 1)));
+        assert_eq!(upper, Bound::Excluded(date(2022, 2, 1)));
+
+        let read = from_daterange(lower, upper).unwrap();
+        assert_eq!(read.start_opt(), interval.start_opt());
+        assert_eq!(read.end_opt(), interval.end_opt());
+
+        // Postgres normalises `(2021-12-31,2022-01-31]` to `[2022-01-01,2022-02-01)`
+        let read = from_daterange(
+            Bound::Excluded(date(2021, 12, 31)),
+            Bound::Included(date(2022, 1, 31)),
+        )
+        .unwrap();
+        assert_eq!(read.start_opt(), interval.start_opt());
+        assert_eq!(read.end_opt(), interval.end_opt());
+
+        let single = from_daterange(
+            Bound::Included(date(2022, 1, 1)),
+            Bound::Excluded(date(2022, 1, 2)),
+        );
+        assert_eq!(single.unwrap().end_opt(), Some(date(2022, 1, 1)));
+
+        assert_eq!(
+            from_daterange(
+                Bound::Excluded(date(2022, 1, 1)),
+                Bound::Excluded(date(2022, 1, 1))
+            ),
+            Err(SqlError::Empty)
+        );
+        assert_eq!(
+            from_daterange(Bound::Unbounded, Bound::Excluded(date(2022, 1, 1))),
+            Err(SqlError::Unbounded)
+        );
+        assert_eq!(
+            to_daterange(&ClosedInterval::exact(date(2022, 2, 1), date(2022, 1, 1))),
+            Err(SqlError::Empty)
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use chrono::NaiveDate;
+use sqlx::{
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{
+        types::{PgInterval, PgRange},
+        PgArgumentBuffer, PgTypeInfo, PgValueRef,
+    },
+    Decode, Encode, Postgres, Type,
+};
+
+use crate::{interval::ClosedInterval, RelativeDuration};
+
+impl Type<Postgres> for RelativeDuration {
+    fn type_info() -> PgTypeInfo {
+        PgInterval::type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for RelativeDuration {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let (months, days) = super::to_interval(self);
+        PgInterval {
+            months,
+            days,
+            microseconds: 0,
+        }
+        .encode_by_ref(buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for RelativeDuration {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let interval = PgInterval::decode(value)?;
+        Ok(super::from_interval(
+            interval.months,
+            interval.days,
+            interval.microseconds,
+        )?)
+    }
+}
+
+impl Type<Postgres> for ClosedInterval {
+    fn type_info() -> PgTypeInfo {
+        PgRange::<NaiveDate>::type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for ClosedInterval {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let (start, end) = super::to_daterange(self)?;
+        PgRange { start, end }.encode_by_ref(buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for ClosedInterval {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let range = PgRange::<NaiveDate>::decode(value)?;
+        Ok(super::from_daterange(range.start, range.end)?)
+    }
+}
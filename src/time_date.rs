@@ -0,0 +1,259 @@
+//! Use [time::Date] in place of [NaiveDate]
+//!
+//! Enable the `time` feature for `_time_date` versions of the date based constructors and
+//! accessors of durations, intervals and calendar units. Dates are converted through their
+//! Julian day. The years supported by `time` and chrono differ, especially with the
+//! `large-dates` feature of `time`, so a date without an equivalent gives [None].
+//!
+//! ```
+//! # #[cfg(not(feature = "strict"))] {
+//! use calends::{time_date::IntervalLikeTimeExt, CalendarUnit, Interval, RelativeDuration, UnitKind};
+//! use time::macros::date;
+//!
+//! let rd = RelativeDuration::months(1);
+//! assert_eq!(rd.checked_add_to_time_date(date!(2022 - 01 - 31)), Some(date!(2022 - 02 - 28)));
+//!
+//! let interval =
+//!     Interval::closed_with_time_dates(date!(2022 - 01 - 01), date!(2022 - 03 - 31)).unwrap();
+//! assert!(interval.within_time_date(date!(2022 - 02 - 14)));
+//!
+//! let quarter =
+//!     CalendarUnit::containing_time_date(date!(2022 - 02 - 14), UnitKind::Quarter).unwrap();
+//! assert_eq!(quarter.end_time_date(), Some(date!(2022 - 03 - 31)));
+//! # }
+//! ```
+#[cfg(not(feature = "strict"))]
+use std::ops::Add;
+
+use chrono::{Datelike, NaiveDate};
+use time::Date;
+
+use crate::{
+    interval::ClosedInterval, CalendarUnit, Interval, IntervalLike, RelativeDuration, UnitKind,
+};
+
+/// Julian day of 0000-12-31, the day before the first day of the common era
+const CE_JULIAN_DAY: i32 = 1_721_425;
+
+/// The [NaiveDate] of a [time::Date], [None] if the year is out of range for chrono
+///
+/// Only possible when the `large-dates` feature of `time` is enabled.
+pub fn to_naive_date(date: Date) -> Option<NaiveDate> {
+    let days = date.to_julian_day() - CE_JULIAN_DAY;
+    // chrono wraps days far beyond its range around instead of rejecting them
+    if !(NaiveDate::MIN.num_days_from_ce()..=NaiveDate::MAX.num_days_from_ce()).contains(&days) {
+        return None;
+    }
+
+    NaiveDate::from_num_days_from_ce_opt(days)
+}
+
+/// The [time::Date] of a [NaiveDate], [None] if the year is out of range for `time`
+pub fn from_naive_date(date: NaiveDate) -> Option<Date> {
+    Date::from_julian_day(date.num_days_from_ce() + CE_JULIAN_DAY).ok()
+}
+
+impl RelativeDuration {
+    /// See [RelativeDuration::from_duration_between], not available with the `strict` feature
+    ///
+    /// This also panics if either date is out of range for chrono.
+    #[cfg(not(feature = "strict"))]
+    pub fn from_duration_between_time_dates(start: Date, end: Date) -> RelativeDuration {
+        let convert = |date| to_naive_date(date).expect("date out of range");
+        RelativeDuration::from_duration_between(convert(start), convert(end))
+    }
+
+    /// See [RelativeDuration::checked_from_duration_between]
+    pub fn checked_from_duration_between_time_dates(
+        start: Date,
+        end: Date,
+    ) -> Option<RelativeDuration> {
+        RelativeDuration::checked_from_duration_between(to_naive_date(start)?, to_naive_date(end)?)
+    }
+
+    /// See [RelativeDuration::checked_add_to]
+    pub fn checked_add_to_time_date(&self, date: Date) -> Option<Date> {
+        to_naive_date(date)
+            .and_then(|date| self.checked_add_to(date))
+            .and_then(from_naive_date)
+    }
+}
+
+/// Add a duration to a [time::Date]
+///
+/// This panics if the resulting date is out of range and is not available with the `strict`
+/// feature, see [RelativeDuration::checked_add_to_time_date].
+#[cfg(not(feature = "strict"))]
+impl Add<RelativeDuration> for Date {
+    type Output = Date;
+
+    fn add(self, rhs: RelativeDuration) -> Date {
+        rhs.checked_add_to_time_date(self)
+            .expect("date out of range")
+    }
+}
+
+impl ClosedInterval {
+    /// See [ClosedInterval::with_dates], [None] if either date is out of range for chrono
+    pub fn with_time_dates(start: Date, end: Date) -> Option<Self> {
+        Some(ClosedInterval::with_dates(
+            to_naive_date(start)?,
+            to_naive_date(end)?,
+        ))
+    }
+
+    /// See [ClosedInterval::exact], [None] if either date is out of range for chrono
+    pub fn exact_time_dates(start: Date, end: Date) -> Option<Self> {
+        Some(ClosedInterval::exact(
+            to_naive_date(start)?,
+            to_naive_date(end)?,
+        ))
+    }
+}
+
+impl Interval {
+    /// See [Interval::closed_from_start], [None] if the date is out of range for chrono
+    pub fn closed_from_start_time_date(date: Date, duration: RelativeDuration) -> Option<Self> {
+        Some(Interval::closed_from_start(to_naive_date(date)?, duration))
+    }
+
+    /// See [Interval::closed_with_dates], [None] if either date is out of range for chrono
+    pub fn closed_with_time_dates(start: Date, end: Date) -> Option<Self> {
+        Some(Interval::closed_with_dates(
+            to_naive_date(start)?,
+            to_naive_date(end)?,
+        ))
+    }
+
+    /// See [Interval::open_start], [None] if the date is out of range for chrono
+    pub fn open_start_time_date(end: Date) -> Option<Self> {
+        Some(Interval::open_start(to_naive_date(end)?))
+    }
+
+    /// See [Interval::open_end], [None] if the date is out of range for chrono
+    pub fn open_end_time_date(start: Date) -> Option<Self> {
+        Some(Interval::open_end(to_naive_date(start)?))
+    }
+}
+
+/// [IntervalLike] accessors with [time::Date]s
+pub trait IntervalLikeTimeExt: IntervalLike {
+    /// See [IntervalLike::start_opt]
+    fn start_time_date(&self) -> Option<Date> {
+        self.start_opt().and_then(from_naive_date)
+    }
+
+    /// See [IntervalLike::end_opt]
+    fn end_time_date(&self) -> Option<Date> {
+        self.end_opt().and_then(from_naive_date)
+    }
+
+    /// See [IntervalLike::within], false if the date is out of range for chrono
+    fn within_time_date(&self, date: Date) -> bool {
+        to_naive_date(date).is_some_and(|date| self.within(date))
+    }
+}
+
+impl<T: IntervalLike + ?Sized> IntervalLikeTimeExt for T {}
+
+impl CalendarUnit {
+    /// See [CalendarUnit::containing], [None] if the date is out of range for chrono
+    pub fn containing_time_date(date: Date, kind: UnitKind) -> Option<CalendarUnit> {
+        Some(CalendarUnit::containing(to_naive_date(date)?, kind))
+    }
+
+    /// See [CalendarUnit::checked_start_date]
+    pub fn start_time_date(&self) -> Option<Date> {
+        self.checked_start_date().and_then(from_naive_date)
+    }
+
+    /// See [CalendarUnit::checked_end_date]
+    pub fn end_time_date(&self) -> Option<Date> {
+        self.checked_end_date().and_then(from_naive_date)
+    }
+
+    /// See [CalendarUnit::contains], false if the date is out of range for chrono
+    pub fn contains_time_date(&self, date: Date) -> bool {
+        to_naive_date(date).is_some_and(|date| self.contains(date))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{macros::date, Month};
+
+    use super::*;
+
+    #[test]
+    fn test_date_conversion() {
+        for date in [
+            date!(-9999 - 01 - 01),
+            date!(0000 - 03 - 01),
+            date!(2024 - 02 - 29),
+            date!(9999 - 12 - 31),
+        ] {
+            let naive = to_naive_date(date).unwrap();
+            assert_eq!(
+                (naive.year(), naive.month(), naive.day()),
+                (
+                    date.year(),
+                    u32::from(u8::from(date.month())),
+                    u32::from(date.day())
+                )
+            );
+            assert_eq!(from_naive_date(naive), Some(date));
+        }
+
+        // Out of range for `time`, unless its `large-dates` feature is enabled
+        assert_eq!(
+            from_naive_date(NaiveDate::MAX).is_some(),
+            NaiveDate::MAX.year() <= Date::MAX.year()
+        );
+        // Out of range for chrono with the `large-dates` feature of `time`
+        assert_eq!(
+            to_naive_date(Date::MAX).is_some(),
+            Date::MAX.year() <= NaiveDate::MAX.year()
+        );
+        assert_eq!(
+            to_naive_date(Date::MIN).is_some(),
+            Date::MIN.year() >= NaiveDate::MIN.year()
+        );
+        assert_eq!(
+            to_naive_date(Date::from_calendar_date(-1, Month::December, 31).unwrap()),
+            NaiveDate::from_ymd_opt(-1, 12, 31)
+        );
+    }
+
+    #[test]
+    fn test_mirrors_chrono() {
+        let (start, end) = (date!(2022 - 01 - 15), date!(2022 - 04 - 10));
+
+        let rd = RelativeDuration::checked_from_duration_between_time_dates(start, end).unwrap();
+        assert_eq!(rd.checked_add_to_time_date(start), Some(end));
+        assert_eq!(
+            RelativeDuration::months(1).checked_add_to_time_date(Date::MAX),
+            None
+        );
+
+        let interval = ClosedInterval::exact_time_dates(start, end).unwrap();
+        assert_eq!(interval.start_time_date(), Some(start));
+        assert_eq!(interval.end_time_date(), Some(end));
+        assert_eq!(
+            Interval::open_end_time_date(start).unwrap().end_time_date(),
+            None
+        );
+
+        let week = CalendarUnit::containing_time_date(start, UnitKind::Week).unwrap();
+        assert_eq!(week.start_time_date(), Some(date!(2022 - 01 - 10)));
+        assert!(week.contains_time_date(date!(2022 - 01 - 16)));
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn test_add() {
+        assert_eq!(
+            date!(2022 - 01 - 31) + RelativeDuration::months(1).with_days(1),
+            date!(2022 - 03 - 01)
+        );
+    }
+}
@@ -1,36 +1,30 @@
-use chrono::{Datelike, NaiveDate};
+use chrono::NaiveDate;
 
 use super::domain::CalendarUnit;
 
 /// Convert a date into a year
 pub fn convert_to_year(date: NaiveDate) -> CalendarUnit {
-    CalendarUnit::Year(date.year())
+    CalendarUnit::year_of(date)
 }
 
 /// Convert a date into a quarter
 pub fn convert_to_quarter(date: NaiveDate) -> CalendarUnit {
-    CalendarUnit::Quarter(
-        date.year(),
-        ((date.month() - 1) / 3 + 1).try_into().unwrap(),
-    )
+    CalendarUnit::quarter_of(date)
 }
 
 /// Convert a date into a half
 pub fn convert_to_half(date: NaiveDate) -> CalendarUnit {
-    CalendarUnit::Half(
-        date.year(),
-        ((date.month() - 1) / 6 + 1).try_into().unwrap(),
-    )
+    CalendarUnit::half_of(date)
 }
 
 /// Convert a date into a month
 pub fn convert_to_month(date: NaiveDate) -> CalendarUnit {
-    CalendarUnit::Month(date.year(), date.month().try_into().unwrap())
+    CalendarUnit::month_of(date)
 }
 
 /// Convert a date into an ISO week
 pub fn convert_to_iso_week(date: NaiveDate) -> CalendarUnit {
-    CalendarUnit::Week(date.year(), date.iso_week().week().try_into().unwrap())
+    CalendarUnit::week_of(date)
 }
 
 #[cfg(test)]
@@ -47,6 +41,12 @@ mod tests {
         assert_eq!(
             convert_to_iso_week(NaiveDate::from_ymd_opt(2022, 12, 31).unwrap()),
             CalendarUnit::Week(2022, 52)
+        );
+
+        // The first days of the year can belong to the last ISO week of the previous year
+        assert_eq!(
+            convert_to_iso_week(NaiveDate::from_ymd_opt(2021, 1, 3).unwrap()),
+            CalendarUnit::Week(2020, 53)
         )
     }
 
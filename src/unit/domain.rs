@@ -1,9 +1,11 @@
-use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Display, iter::FusedIterator, str::FromStr};
 
-use chrono::NaiveDate;
-use serde::{Serialize, Serializer};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{interval::ClosedInterval, Interval, RelativeDuration};
+use crate::{interval::ClosedInterval, util, Interval, RelativeDuration};
+
+use super::UnitKind;
 
 /// A unit in time
 ///
@@ -17,8 +19,8 @@ use crate::{interval::ClosedInterval, Interval, RelativeDuration};
 /// searching and may be a little confusing. It may also be advantageous for the consumer of the
 /// API to do things like iterate by actual quarters.
 ///
-/// This will also likely be useful if custom fiscal calendars ever get added
-///
+/// Fiscal calendars reuse the same units numbered within the fiscal year, see
+/// [FiscalCalendar](crate::FiscalCalendar).
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum CalendarUnit {
     Year(i32),
@@ -26,39 +28,350 @@ pub enum CalendarUnit {
     Half(i32, u8),
     Month(i32, u8),
     Week(i32, u8),
+    /// A single day, the degenerate unit
+    Day(NaiveDate),
+    /// Two ISO weeks starting on an odd week, the last biweek of a year with 53 ISO weeks is the
+    /// single week 53
+    BiWeek(i32, u8),
 }
 
 impl CalendarUnit {
+    /// The first year with all of its dates in the range of [NaiveDate]
+    pub const MIN: CalendarUnit = CalendarUnit::Year(-262143);
+
+    /// The last year with all of its dates in the range of [NaiveDate]
+    pub const MAX: CalendarUnit = CalendarUnit::Year(262142);
+
+    /// The unit of the kind which contains the date
+    ///
+    /// ```
+    /// use calends::{CalendarUnit, UnitKind};
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    ///
+    /// assert_eq!(CalendarUnit::containing(date, UnitKind::Quarter), CalendarUnit::Quarter(2023, 1));
+    /// assert_eq!(CalendarUnit::containing(date, UnitKind::Week), CalendarUnit::Week(2022, 52));
+    /// ```
+    pub fn containing(date: NaiveDate, kind: UnitKind) -> CalendarUnit {
+        match kind {
+            UnitKind::Year => Self::year_of(date),
+            UnitKind::Half => Self::half_of(date),
+            UnitKind::Quarter => Self::quarter_of(date),
+            UnitKind::Month => Self::month_of(date),
+            UnitKind::BiWeek => Self::biweek_of(date),
+            UnitKind::Week => Self::week_of(date),
+            UnitKind::Day => CalendarUnit::Day(date),
+        }
+    }
+
+    /// Year containing the date
+    pub fn year_of(date: NaiveDate) -> CalendarUnit {
+        CalendarUnit::Year(date.year())
+    }
+
+    /// Half containing the date
+    pub fn half_of(date: NaiveDate) -> CalendarUnit {
+        CalendarUnit::Half(date.year(), (date.month0() / 6 + 1) as u8)
+    }
+
+    /// Quarter containing the date
+    pub fn quarter_of(date: NaiveDate) -> CalendarUnit {
+        CalendarUnit::Quarter(date.year(), (date.month0() / 3 + 1) as u8)
+    }
+
+    /// Month containing the date
+    pub fn month_of(date: NaiveDate) -> CalendarUnit {
+        CalendarUnit::Month(date.year(), date.month() as u8)
+    }
+
+    /// ISO week containing the date, numbered within its ISO year which can differ from the
+    /// calendar year around New Year
+    pub fn week_of(date: NaiveDate) -> CalendarUnit {
+        let week = date.iso_week();
+        CalendarUnit::Week(week.year(), week.week() as u8)
+    }
+
+    /// Biweek containing the date, numbered within its ISO year
+    pub fn biweek_of(date: NaiveDate) -> CalendarUnit {
+        let week = date.iso_week();
+        CalendarUnit::BiWeek(week.year(), week.week().div_ceil(2) as u8)
+    }
+
     pub fn into_interval(&self) -> Interval {
-        let res = match self {
-            CalendarUnit::Year(year) => ClosedInterval::from_start(
-                NaiveDate::from_yo_opt(*year, 1).unwrap(),
-                RelativeDuration::months(12).with_days(-1),
-            ),
-            CalendarUnit::Quarter(year, quarter) => ClosedInterval::from_start(
-                NaiveDate::from_ymd_opt(*year, (*quarter * 3 - 2).try_into().unwrap(), 1).unwrap(),
-                RelativeDuration::months(3).with_days(-1),
-            ),
-
-            CalendarUnit::Half(year, half) => ClosedInterval::from_start(
-                NaiveDate::from_ymd_opt(*year, (*half * 6 - 5).try_into().unwrap(), 1).unwrap(),
-                RelativeDuration::months(6).with_days(-1),
-            ),
-
-            CalendarUnit::Month(year, month) => ClosedInterval::from_start(
-                NaiveDate::from_ymd_opt(*year, (*month).try_into().unwrap(), 1).unwrap(),
-                RelativeDuration::months(1).with_days(-1),
-            ),
-
-            CalendarUnit::Week(year, week) => ClosedInterval::from_start(
-                NaiveDate::from_isoywd_opt(*year, (*week).into(), chrono::Weekday::Mon).unwrap(),
-                RelativeDuration::days(7),
-            ),
+        Interval::Closed(ClosedInterval::from_start(
+            self.start_date(),
+            self.unit_duration(),
+        ))
+    }
+
+    /// First date of the unit
+    pub fn start_date(&self) -> NaiveDate {
+        self.checked_start_date().expect("unit out of range")
+    }
+
+    /// First date of the unit, [None] when it is before [NaiveDate::MIN] or after
+    /// [NaiveDate::MAX]
+    pub fn checked_start_date(&self) -> Option<NaiveDate> {
+        match self {
+            CalendarUnit::Year(year) => NaiveDate::from_yo_opt(*year, 1),
+            CalendarUnit::Quarter(year, quarter) => {
+                NaiveDate::from_ymd_opt(*year, (*quarter * 3 - 2).into(), 1)
+            }
+            CalendarUnit::Half(year, half) => {
+                NaiveDate::from_ymd_opt(*year, (*half * 6 - 5).into(), 1)
+            }
+            CalendarUnit::Month(year, month) => NaiveDate::from_ymd_opt(*year, (*month).into(), 1),
+            CalendarUnit::Week(year, week) => {
+                NaiveDate::from_isoywd_opt(*year, (*week).into(), chrono::Weekday::Mon)
+            }
+            CalendarUnit::Day(date) => Some(*date),
+            CalendarUnit::BiWeek(year, biweek) => {
+                NaiveDate::from_isoywd_opt(*year, (*biweek * 2 - 1).into(), chrono::Weekday::Mon)
+            }
+        }
+    }
+
+    /// Units of the kind making up this unit, in order
+    ///
+    /// A week or biweek belongs to the unit containing its first Thursday, so the weeks of a year
+    /// are the weeks of its ISO year. There are no children of a longer kind.
+    ///
+    /// ```
+    /// use calends::{CalendarUnit, UnitKind};
+    ///
+    /// assert_eq!(
+    ///     CalendarUnit::Half(2023, 2).children(UnitKind::Quarter),
+    ///     vec![CalendarUnit::Quarter(2023, 3), CalendarUnit::Quarter(2023, 4)]
+    /// );
+    /// assert_eq!(CalendarUnit::Year(2020).children(UnitKind::Week).len(), 53);
+    /// ```
+    pub fn children(&self, kind: UnitKind) -> Vec<CalendarUnit> {
+        let own = self.kind();
+        if kind < own {
+            return Vec::new();
+        }
+
+        let end = self.end_date();
+        CalendarUnit::containing(self.start_date(), kind)
+            .onwards()
+            .take_while(|unit| unit.start_date() <= end)
+            .filter(|unit| unit.parent(own) == *self)
+            .collect()
+    }
+
+    /// Unit of the kind which this unit is part of
+    ///
+    /// A week or biweek is part of the units containing its first Thursday. For a shorter kind
+    /// this is the unit containing the first day of this unit.
+    ///
+    /// ```
+    /// use calends::{CalendarUnit, UnitKind};
+    ///
+    /// assert_eq!(CalendarUnit::Month(2023, 5).parent(UnitKind::Quarter), CalendarUnit::Quarter(2023, 2));
+    /// assert_eq!(CalendarUnit::Week(2020, 53).parent(UnitKind::Year), CalendarUnit::Year(2020));
+    /// ```
+    pub fn parent(&self, kind: UnitKind) -> CalendarUnit {
+        let date = match self {
+            CalendarUnit::Week(_, _) | CalendarUnit::BiWeek(_, _) => {
+                self.start_date() + Duration::days(3)
+            }
+            _ => self.start_date(),
         };
 
-        Interval::Closed(res)
+        CalendarUnit::containing(date, kind)
+    }
+
+    /// Last date of the unit
+    pub fn end_date(&self) -> NaiveDate {
+        self.checked_end_date().expect("unit out of range")
+    }
+
+    /// Last date of the unit, [None] when it is before [NaiveDate::MIN] or after
+    /// [NaiveDate::MAX]
+    pub fn checked_end_date(&self) -> Option<NaiveDate> {
+        match self {
+            CalendarUnit::Year(year) => NaiveDate::from_ymd_opt(*year, 12, 31),
+            CalendarUnit::Half(year, half) => util::checked_month_end(*year, u32::from(*half) * 6),
+            CalendarUnit::Quarter(year, quarter) => {
+                util::checked_month_end(*year, u32::from(*quarter) * 3)
+            }
+            CalendarUnit::Month(year, month) => util::checked_month_end(*year, (*month).into()),
+            _ => self
+                .unit_duration()
+                .checked_add_to(self.checked_start_date()?),
+        }
+    }
+
+    /// Whether the date falls within the unit
+    ///
+    /// ```
+    /// use calends::CalendarUnit;
+    /// use chrono::NaiveDate;
+    ///
+    /// let q1 = CalendarUnit::Quarter(2023, 1);
+    ///
+    /// assert!(q1.contains(NaiveDate::from_ymd_opt(2023, 3, 31).unwrap()));
+    /// assert!(!q1.contains(NaiveDate::from_ymd_opt(2023, 4, 1).unwrap()));
+    /// ```
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start_date() <= date && date <= self.end_date()
+    }
+
+    /// Whether the units share at least one date, the units can be of different kinds
+    ///
+    /// ```
+    /// use calends::CalendarUnit;
+    ///
+    /// let q1 = CalendarUnit::Quarter(2023, 1);
+    ///
+    /// assert!(CalendarUnit::Week(2023, 5).overlaps(&q1));
+    /// // Runs from 2023-03-27 to 2023-04-02
+    /// assert!(CalendarUnit::Week(2023, 13).overlaps(&q1));
+    /// assert!(!CalendarUnit::Month(2023, 4).overlaps(&q1));
+    /// ```
+    pub fn overlaps(&self, other: &CalendarUnit) -> bool {
+        self.start_date() <= other.end_date() && other.start_date() <= self.end_date()
+    }
+
+    /// Compare units by where they fall on the timeline rather than by variant
+    ///
+    /// The derived [Ord] compares the variants first so every year sorts before every quarter.
+    /// This orders units by their first date instead, when two units start on the same date the
+    /// longer one comes first.
+    ///
+    /// ```
+    /// use calends::CalendarUnit;
+    ///
+    /// let mut units = vec![
+    ///     CalendarUnit::Year(2030),
+    ///     CalendarUnit::Month(2030, 1),
+    ///     CalendarUnit::Quarter(1999, 1),
+    /// ];
+    /// units.sort_by(CalendarUnit::chronological_cmp);
+    ///
+    /// assert_eq!(
+    ///     units,
+    ///     vec![
+    ///         CalendarUnit::Quarter(1999, 1),
+    ///         CalendarUnit::Year(2030),
+    ///         CalendarUnit::Month(2030, 1),
+    ///     ]
+    /// );
+    /// ```
+    pub fn chronological_cmp(&self, other: &CalendarUnit) -> Ordering {
+        self.start_date()
+            .cmp(&other.start_date())
+            .then_with(|| self.kind().cmp(&other.kind()))
+    }
+
+    /// Duration from the first date of the unit to the last date of the unit
+    fn unit_duration(&self) -> RelativeDuration {
+        match self {
+            CalendarUnit::Year(_) => RelativeDuration::months(12).with_days(-1),
+            CalendarUnit::Quarter(_, _) => RelativeDuration::months(3).with_days(-1),
+            CalendarUnit::Half(_, _) => RelativeDuration::months(6).with_days(-1),
+            CalendarUnit::Month(_, _) => RelativeDuration::months(1).with_days(-1),
+            CalendarUnit::Week(_, _) => RelativeDuration::days(6),
+            CalendarUnit::Day(_) => RelativeDuration::days(0),
+            // Only years with 53 ISO weeks have a 27th biweek
+            CalendarUnit::BiWeek(_, 27) => RelativeDuration::days(6),
+            CalendarUnit::BiWeek(_, _) => RelativeDuration::days(13),
+        }
+    }
+
+    /// Number of months in a unit, [None] for units that are not made up of months
+    fn months_in_unit(&self) -> Option<u32> {
+        match self {
+            CalendarUnit::Year(_) => Some(12),
+            CalendarUnit::Half(_, _) => Some(6),
+            CalendarUnit::Quarter(_, _) => Some(3),
+            CalendarUnit::Month(_, _) => Some(1),
+            CalendarUnit::Week(_, _) | CalendarUnit::BiWeek(_, _) | CalendarUnit::Day(_) => None,
+        }
+    }
+
+    /// Intervals for every unit from `start` up to and including `end`
+    ///
+    /// All of the boundaries are computed in a single pass which is considerably cheaper than
+    /// calling [CalendarUnit::into_interval] for each unit. Both units have to be the same variant,
+    /// otherwise or if `end` precedes `start` no intervals are returned.
+    ///
+    /// ```
+    /// use calends::{CalendarUnit, IntervalLike};
+    /// use chrono::{Datelike, Duration, NaiveDate};
+    ///
+    /// let intervals = CalendarUnit::intervals_for_range(
+    ///     CalendarUnit::Month(2022, 11),
+    ///     CalendarUnit::Month(2023, 2),
+    /// );
+    ///
+    /// assert_eq!(intervals.len(), 4);
+    /// assert_eq!(intervals[3].start_opt(), NaiveDate::from_ymd_opt(2023, 2, 1));
+    /// assert_eq!(intervals[3].end_opt(), NaiveDate::from_ymd_opt(2023, 2, 28));
+    /// ```
+    pub fn intervals_for_range(start: CalendarUnit, end: CalendarUnit) -> Vec<ClosedInterval> {
+        if std::mem::discriminant(&start) != std::mem::discriminant(&end) || end < start {
+            return Vec::new();
+        }
+
+        let duration = start.unit_duration();
+        let first = start.start_date();
+        let last = end.start_date();
+
+        match start.months_in_unit() {
+            Some(step) => {
+                let (mut year, mut month) = (first.year(), first.month());
+                let capacity = ((last.year() - year) * 12 + last.month() as i32 - month as i32)
+                    as usize
+                    / step as usize
+                    + 1;
+                let mut intervals = Vec::with_capacity(capacity);
+
+                while (year, month) <= (last.year(), last.month()) {
+                    intervals.push(ClosedInterval::from_start(
+                        NaiveDate::from_ymd_opt(year, month, 1).unwrap(),
+                        duration,
+                    ));
+
+                    month += step;
+                    if month > 12 {
+                        month -= 12;
+                        year += 1;
+                    }
+                }
+
+                intervals
+            }
+            None => start
+                .range_to(end)
+                .map(|unit| ClosedInterval::from_start(unit.start_date(), unit.unit_duration()))
+                .collect(),
+        }
+    }
+
+    /// The unit after this one, [None] when it would end after [NaiveDate::MAX]
+    ///
+    /// ```
+    /// use calends::CalendarUnit;
+    ///
+    /// assert_eq!(CalendarUnit::MAX.checked_succ(), None);
+    /// assert_eq!(CalendarUnit::Month(2022, 12).checked_succ(), Some(CalendarUnit::Month(2023, 1)));
+    /// ```
+    pub fn checked_succ(&self) -> Option<CalendarUnit> {
+        let next = self.checked_end_date()?.succ_opt()?;
+        let unit = CalendarUnit::containing(next, self.kind());
+        unit.checked_end_date().map(|_| unit)
+    }
+
+    /// The unit before this one, [None] when it would start before [NaiveDate::MIN]
+    pub fn checked_pred(&self) -> Option<CalendarUnit> {
+        let previous = self.checked_start_date()?.pred_opt()?;
+        let unit = CalendarUnit::containing(previous, self.kind());
+        unit.checked_start_date().map(|_| unit)
     }
 
+    /// The unit after this one
     pub fn succ(&self) -> CalendarUnit {
         match self {
             CalendarUnit::Year(year) => CalendarUnit::Year(year + 1),
@@ -95,19 +408,240 @@ impl CalendarUnit {
                 }
                 CalendarUnit::Month(year, month)
             }
-            CalendarUnit::Week(_, _) => todo!(),
+            CalendarUnit::Week(_, _) => Self::week_of(self.start_date() + Duration::weeks(1)),
+            CalendarUnit::Day(date) => CalendarUnit::Day(date.succ_opt().unwrap()),
+            CalendarUnit::BiWeek(_, _) => Self::biweek_of(self.end_date().succ_opt().unwrap()),
+        }
+    }
+
+    /// The unit before this one
+    ///
+    /// ```
+    /// use calends::CalendarUnit;
+    ///
+    /// assert_eq!(CalendarUnit::Quarter(2022, 1).pred(), CalendarUnit::Quarter(2021, 4));
+    /// assert_eq!(CalendarUnit::Week(2021, 1).pred(), CalendarUnit::Week(2020, 53));
+    /// ```
+    pub fn pred(&self) -> CalendarUnit {
+        match self {
+            CalendarUnit::Year(year) => CalendarUnit::Year(year - 1),
+            CalendarUnit::Quarter(year, quarter) => {
+                if *quarter == 1 {
+                    CalendarUnit::Quarter(year - 1, 4)
+                } else {
+                    CalendarUnit::Quarter(*year, quarter - 1)
+                }
+            }
+            CalendarUnit::Half(year, half) => {
+                if *half == 1 {
+                    CalendarUnit::Half(year - 1, 2)
+                } else {
+                    CalendarUnit::Half(*year, half - 1)
+                }
+            }
+            CalendarUnit::Month(year, month) => {
+                if *month == 1 {
+                    CalendarUnit::Month(year - 1, 12)
+                } else {
+                    CalendarUnit::Month(*year, month - 1)
+                }
+            }
+            CalendarUnit::Week(_, _) => Self::week_of(self.start_date() - Duration::weeks(1)),
+            CalendarUnit::Day(date) => CalendarUnit::Day(date.pred_opt().unwrap()),
+            CalendarUnit::BiWeek(_, _) => Self::biweek_of(self.start_date().pred_opt().unwrap()),
+        }
+    }
+
+    /// Every unit from this one up to and including the end
+    ///
+    /// The range is empty when the end is a different kind of unit or comes before this unit.
+    ///
+    /// ```
+    /// use calends::CalendarUnit;
+    ///
+    /// let months = CalendarUnit::Month(2020, 1).range_to(CalendarUnit::Month(2023, 12));
+    /// assert_eq!(months.len(), 48);
+    /// assert_eq!(months.last(), Some(CalendarUnit::Month(2023, 12)));
+    /// ```
+    pub fn range_to(&self, end: CalendarUnit) -> UnitRange {
+        let valid = self.units_between(&end).is_some_and(|units| units >= 0);
+        UnitRange {
+            next: valid.then_some(*self),
+            last: end,
+        }
+    }
+
+    /// Every unit from this one onwards without an end
+    ///
+    /// ```
+    /// use calends::CalendarUnit;
+    ///
+    /// let mut quarters = CalendarUnit::Quarter(2022, 4).onwards();
+    /// assert_eq!(quarters.nth(1), Some(CalendarUnit::Quarter(2023, 1)));
+    /// ```
+    pub fn onwards(&self) -> impl Iterator<Item = CalendarUnit> {
+        std::iter::successors(Some(*self), |unit| Some(unit.succ()))
+    }
+
+    /// The unit `n` units later, or earlier for a negative `n`
+    ///
+    /// ```
+    /// use calends::CalendarUnit;
+    ///
+    /// assert_eq!(CalendarUnit::Quarter(2024, 3).add(-4), CalendarUnit::Quarter(2023, 3));
+    /// assert_eq!(CalendarUnit::Month(2022, 11).add(3), CalendarUnit::Month(2023, 2));
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(&self, n: i32) -> CalendarUnit {
+        let (months, ordinal) = match (self, self.months_in_unit(), self.ordinal()) {
+            (_, Some(months), Some(ordinal)) => (months, ordinal),
+            (CalendarUnit::BiWeek(year, biweek), _, _) => {
+                let (mut year, mut index) = (*year, i64::from(*biweek - 1) + i64::from(n));
+                while index < 0 {
+                    year -= 1;
+                    index += biweeks_in_year(year);
+                }
+                while index >= biweeks_in_year(year) {
+                    index -= biweeks_in_year(year);
+                    year += 1;
+                }
+                return CalendarUnit::BiWeek(year, index as u8 + 1);
+            }
+            (CalendarUnit::Day(date), _, _) => {
+                return CalendarUnit::Day(*date + Duration::days(n.into()))
+            }
+            _ => return Self::week_of(self.start_date() + Duration::weeks(n.into())),
+        };
+
+        let per_year = 12 / i64::from(months);
+        let index = ordinal + i64::from(n);
+        let year = i32::try_from(index.div_euclid(per_year)).expect("year out of range");
+        let within = (index.rem_euclid(per_year) + 1) as u8;
+
+        match self {
+            CalendarUnit::Year(_) => CalendarUnit::Year(year),
+            CalendarUnit::Quarter(_, _) => CalendarUnit::Quarter(year, within),
+            CalendarUnit::Half(_, _) => CalendarUnit::Half(year, within),
+            CalendarUnit::Month(_, _) => CalendarUnit::Month(year, within),
+            CalendarUnit::Week(_, _) | CalendarUnit::BiWeek(_, _) | CalendarUnit::Day(_) => {
+                unreachable!()
+            }
+        }
+    }
+
+    /// Number of units from this unit to the other, [None] when they are different kinds of units
+    ///
+    /// ```
+    /// use calends::CalendarUnit;
+    ///
+    /// assert_eq!(
+    ///     CalendarUnit::Quarter(2023, 3).units_between(&CalendarUnit::Quarter(2024, 3)),
+    ///     Some(4)
+    /// );
+    /// assert_eq!(CalendarUnit::Year(2023).units_between(&CalendarUnit::Month(2023, 1)), None);
+    /// ```
+    pub fn units_between(&self, other: &CalendarUnit) -> Option<i32> {
+        if std::mem::discriminant(self) != std::mem::discriminant(other) {
+            return None;
+        }
+
+        let units = match (self, other, self.ordinal(), other.ordinal()) {
+            (_, _, Some(start), Some(end)) => end - start,
+            (CalendarUnit::BiWeek(start_year, start), CalendarUnit::BiWeek(end_year, end), ..) => {
+                let years: i64 = match start_year <= end_year {
+                    true => (*start_year..*end_year).map(biweeks_in_year).sum(),
+                    false => -(*end_year..*start_year).map(biweeks_in_year).sum::<i64>(),
+                };
+                years + i64::from(*end) - i64::from(*start)
+            }
+            (CalendarUnit::Day(start), CalendarUnit::Day(end), ..) => (*end - *start).num_days(),
+            _ => (other.start_date() - self.start_date()).num_weeks(),
+        };
+        i32::try_from(units).ok()
+    }
+
+    /// Position of the unit counting from year zero, [None] for units not made up of months
+    fn ordinal(&self) -> Option<i64> {
+        let months = i64::from(self.months_in_unit()?);
+        let year = match self {
+            CalendarUnit::Year(year)
+            | CalendarUnit::Quarter(year, _)
+            | CalendarUnit::Half(year, _)
+            | CalendarUnit::Month(year, _)
+            | CalendarUnit::Week(year, _)
+            | CalendarUnit::BiWeek(year, _) => i64::from(*year),
+            CalendarUnit::Day(date) => i64::from(date.year()),
+        };
+
+        Some(year * 12 / months + i64::from(self.index_in_year()?))
+    }
+
+    /// Position of the unit within its year counting from zero, [None] for units not made up of
+    /// months
+    fn index_in_year(&self) -> Option<u8> {
+        match self {
+            CalendarUnit::Year(_) => Some(0),
+            CalendarUnit::Quarter(_, n) | CalendarUnit::Half(_, n) | CalendarUnit::Month(_, n) => {
+                Some(n - 1)
+            }
+            CalendarUnit::Week(_, _) | CalendarUnit::BiWeek(_, _) | CalendarUnit::Day(_) => None,
         }
     }
 }
 
-impl Iterator for CalendarUnit {
+/// Units from a start to an end unit (inclusive), see [CalendarUnit::range_to]
+#[derive(Debug, Clone)]
+pub struct UnitRange {
+    next: Option<CalendarUnit>,
+    last: CalendarUnit,
+}
+
+impl Iterator for UnitRange {
     type Item = CalendarUnit;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let cur = *self;
-        *self = cur.succ();
+        let cur = self.next?;
+        self.next = (cur != self.last).then(|| cur.succ());
         Some(cur)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for UnitRange {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let first = self.next?;
+        let cur = self.last;
+        if cur == first {
+            self.next = None;
+        } else {
+            self.last = cur.pred();
+        }
+        Some(cur)
+    }
+}
+
+impl ExactSizeIterator for UnitRange {
+    fn len(&self) -> usize {
+        self.next
+            .and_then(|first| first.units_between(&self.last))
+            .map_or(0, |units| units as usize + 1)
+    }
+}
+
+impl FusedIterator for UnitRange {}
+
+/// Number of ISO weeks in the ISO year, 52 or 53
+fn weeks_in_year(year: i32) -> u32 {
+    super::IsoWeek::weeks_in_year(year)
+}
+
+/// Number of biweeks in the ISO year, 26 or 27
+fn biweeks_in_year(year: i32) -> i64 {
+    i64::from(weeks_in_year(year).div_ceil(2))
 }
 
 impl Display for CalendarUnit {
@@ -118,6 +652,8 @@ impl Display for CalendarUnit {
             CalendarUnit::Half(y, h) => write!(f, "{}-H{}", y, h),
             CalendarUnit::Month(y, m) => write!(f, "{}-{:0>2}", y, m),
             CalendarUnit::Week(y, w) => write!(f, "{}-W{:0>2}", y, w),
+            CalendarUnit::Day(date) => write!(f, "{}", date),
+            CalendarUnit::BiWeek(y, b) => write!(f, "{}-B{:0>2}", y, b),
         }
     }
 }
@@ -132,6 +668,137 @@ impl Serialize for CalendarUnit {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CalendarUnitParseError {
+    #[error(
+        "expected a unit such as 2022, 2022-Q1, 2022-H2, 2022-05, 2022-B04, 2022-W07 or 2022-05-17, found `{0}`"
+    )]
+    InvalidFormat(String),
+    #[error("quarter {0} is out of range, expected 1 to 4")]
+    QuarterOutOfRange(u32),
+    #[error("half {0} is out of range, expected 1 or 2")]
+    HalfOutOfRange(u32),
+    #[error("month {0} is out of range, expected 1 to 12")]
+    MonthOutOfRange(u32),
+    #[error("week {1} is out of range for the ISO year {0}")]
+    WeekOutOfRange(i32, u32),
+    #[error("biweek {1} is out of range for the ISO year {0}")]
+    BiWeekOutOfRange(i32, u32),
+    #[error("day {0} is out of range for the month")]
+    DayOutOfRange(u32),
+}
+
+/// Parse a unit from its [Display] form
+///
+/// ```
+/// use calends::CalendarUnit;
+///
+/// assert_eq!("2022-Q1".parse(), Ok(CalendarUnit::Quarter(2022, 1)));
+/// assert_eq!("2022-W07".parse(), Ok(CalendarUnit::Week(2022, 7)));
+/// assert_eq!("2022-B04".parse(), Ok(CalendarUnit::BiWeek(2022, 4)));
+/// assert!("2022-W53".parse::<CalendarUnit>().is_err());
+/// ```
+impl FromStr for CalendarUnit {
+    type Err = CalendarUnitParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CalendarUnitParseError::InvalidFormat(s.to_string());
+        let number = |value: &str| -> Result<u32, CalendarUnitParseError> {
+            match value.bytes().all(|b| b.is_ascii_digit()) {
+                true => value.parse().map_err(|_| invalid()),
+                false => Err(invalid()),
+            }
+        };
+
+        // The sign of the year isn't a separator
+        let sign_len = usize::from(s.starts_with(['-', '+']));
+        let (year, unit) = match s[sign_len..].split_once('-') {
+            Some((year, unit)) => (&s[..sign_len + year.len()], Some(unit)),
+            None => (s, None),
+        };
+        if number(&year[sign_len..]).is_err() {
+            return Err(invalid());
+        }
+        let year: i32 = year.parse().map_err(|_| invalid())?;
+
+        let unit = match unit {
+            None => return Ok(CalendarUnit::Year(year)),
+            Some(unit) => unit,
+        };
+
+        if let Some(quarter) = unit.strip_prefix('Q') {
+            match number(quarter)? {
+                quarter @ 1..=4 => Ok(CalendarUnit::Quarter(year, quarter as u8)),
+                quarter => Err(CalendarUnitParseError::QuarterOutOfRange(quarter)),
+            }
+        } else if let Some(half) = unit.strip_prefix('H') {
+            match number(half)? {
+                half @ 1..=2 => Ok(CalendarUnit::Half(year, half as u8)),
+                half => Err(CalendarUnitParseError::HalfOutOfRange(half)),
+            }
+        } else if let Some(week) = unit.strip_prefix('W') {
+            let week = number(week)?;
+            match NaiveDate::from_isoywd_opt(year, week, chrono::Weekday::Mon) {
+                Some(_) => Ok(CalendarUnit::Week(year, week as u8)),
+                None => Err(CalendarUnitParseError::WeekOutOfRange(year, week)),
+            }
+        } else if let Some(biweek) = unit.strip_prefix('B') {
+            let biweek = number(biweek)?;
+            match i64::from(biweek) {
+                1.. if i64::from(biweek) <= biweeks_in_year(year) => {
+                    Ok(CalendarUnit::BiWeek(year, biweek as u8))
+                }
+                _ => Err(CalendarUnitParseError::BiWeekOutOfRange(year, biweek)),
+            }
+        } else if let Some((month, day)) = unit.split_once('-') {
+            if month.len() != 2 || day.len() != 2 {
+                return Err(invalid());
+            }
+            let (month, day) = (number(month)?, number(day)?);
+            if !(1..=12).contains(&month) {
+                return Err(CalendarUnitParseError::MonthOutOfRange(month));
+            }
+            NaiveDate::from_ymd_opt(year, month, day)
+                .map(CalendarUnit::Day)
+                .ok_or(CalendarUnitParseError::DayOutOfRange(day))
+        } else if unit.len() == 2 {
+            match number(unit)? {
+                month @ 1..=12 => Ok(CalendarUnit::Month(year, month as u8)),
+                month => Err(CalendarUnitParseError::MonthOutOfRange(month)),
+            }
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+struct CalendarUnitVisitor;
+
+impl<'de> de::Visitor<'de> for CalendarUnitVisitor {
+    type Value = CalendarUnit;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a year, quarter, half, month, biweek, week or day such as 2022-Q1")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse().map_err(E::custom)
+    }
+}
+
+/// Deserialize a `CalendarUnit` from its [Display] form
+impl<'de> Deserialize<'de> for CalendarUnit {
+    fn deserialize<D>(deserializer: D) -> Result<CalendarUnit, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(CalendarUnitVisitor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::IntervalLike;
@@ -140,7 +807,7 @@ mod tests {
 
     #[test]
     fn test_quarter_iterator() {
-        let mut c = CalendarUnit::Quarter(2022, 1);
+        let mut c = CalendarUnit::Quarter(2022, 1).onwards();
         assert_eq!(c.next(), Some(CalendarUnit::Quarter(2022, 1)));
         assert_eq!(c.next(), Some(CalendarUnit::Quarter(2022, 2)));
         assert_eq!(c.next(), Some(CalendarUnit::Quarter(2022, 3)));
@@ -148,9 +815,150 @@ mod tests {
         assert_eq!(c.next(), Some(CalendarUnit::Quarter(2023, 1)));
     }
 
+    #[test]
+    fn test_week_iterator() {
+        // 2020 has 53 ISO weeks, 2021 has 52
+        let weeks: Vec<CalendarUnit> = CalendarUnit::Week(2020, 52).onwards().take(4).collect();
+        assert_eq!(
+            weeks,
+            vec![
+                CalendarUnit::Week(2020, 52),
+                CalendarUnit::Week(2020, 53),
+                CalendarUnit::Week(2021, 1),
+                CalendarUnit::Week(2021, 2),
+            ]
+        );
+        assert_eq!(
+            CalendarUnit::Week(2021, 52).succ(),
+            CalendarUnit::Week(2022, 1)
+        );
+        assert_eq!(
+            CalendarUnit::Week(2022, 1).pred(),
+            CalendarUnit::Week(2021, 52)
+        );
+    }
+
+    #[test]
+    fn test_pred_inverts_succ() {
+        let units = [
+            CalendarUnit::Year(2022),
+            CalendarUnit::Quarter(2022, 1),
+            CalendarUnit::Half(2022, 1),
+            CalendarUnit::Month(2022, 1),
+            CalendarUnit::Week(2020, 53),
+        ];
+
+        for unit in units {
+            for unit in unit.onwards().take(60) {
+                assert_eq!(unit.succ().pred(), unit);
+                assert_eq!(unit.pred().succ(), unit);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_and_units_between() {
+        let units = [
+            CalendarUnit::Year(2022),
+            CalendarUnit::Quarter(2022, 2),
+            CalendarUnit::Half(2022, 2),
+            CalendarUnit::Month(2022, 12),
+            CalendarUnit::Week(2020, 50),
+            CalendarUnit::BiWeek(2020, 20),
+            CalendarUnit::Day(NaiveDate::from_ymd_opt(2024, 2, 20).unwrap()),
+        ];
+
+        for unit in units {
+            for n in -30..30 {
+                let stepped = match n {
+                    0.. => unit.onwards().nth(n as usize).unwrap(),
+                    _ => (0..-n).fold(unit, |u, _| u.pred()),
+                };
+
+                assert_eq!(unit.add(n), stepped, "{} {}", unit, n);
+                assert_eq!(unit.units_between(&stepped), Some(n));
+            }
+        }
+
+        assert_eq!(CalendarUnit::Half(-1, 1).add(1), CalendarUnit::Half(-1, 2));
+        assert_eq!(CalendarUnit::Half(-1, 1).add(-1), CalendarUnit::Half(-2, 2));
+        assert_eq!(
+            CalendarUnit::Week(2022, 1).units_between(&CalendarUnit::Month(2022, 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_range_to() {
+        let weeks = CalendarUnit::Week(2020, 52).range_to(CalendarUnit::Week(2021, 2));
+        assert_eq!(weeks.len(), 4);
+        assert_eq!(
+            weeks.rev().collect::<Vec<_>>(),
+            vec![
+                CalendarUnit::Week(2021, 2),
+                CalendarUnit::Week(2021, 1),
+                CalendarUnit::Week(2020, 53),
+                CalendarUnit::Week(2020, 52),
+            ]
+        );
+
+        let mut single = CalendarUnit::Year(2022).range_to(CalendarUnit::Year(2022));
+        assert_eq!(single.next_back(), Some(CalendarUnit::Year(2022)));
+        assert_eq!(single.next(), None);
+
+        assert_eq!(
+            CalendarUnit::Month(2022, 2)
+                .range_to(CalendarUnit::Month(2022, 1))
+                .next(),
+            None
+        );
+        assert_eq!(
+            CalendarUnit::Year(2022)
+                .range_to(CalendarUnit::Month(2022, 1))
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_day_and_biweek() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        let day = CalendarUnit::Day(date(2024, 2, 29));
+        assert_eq!(day.end_date(), day.start_date());
+        assert_eq!(day.succ(), CalendarUnit::Day(date(2024, 3, 1)));
+        assert_eq!(day.parent(UnitKind::BiWeek), CalendarUnit::BiWeek(2024, 5));
+        assert_eq!(
+            CalendarUnit::Month(2024, 2).children(UnitKind::Day).len(),
+            29
+        );
+
+        // 2020 has 53 ISO weeks so its last biweek is a single week
+        let biweeks: Vec<CalendarUnit> = CalendarUnit::BiWeek(2020, 26)
+            .range_to(CalendarUnit::BiWeek(2021, 1))
+            .collect();
+        assert_eq!(biweeks.len(), 3);
+        assert_eq!(biweeks[1].start_date(), date(2020, 12, 28));
+        assert_eq!(biweeks[1].end_date(), date(2021, 1, 3));
+        assert_eq!(biweeks[2].start_date(), date(2021, 1, 4));
+        assert_eq!(
+            CalendarUnit::BiWeek(2020, 27).children(UnitKind::Week),
+            vec![CalendarUnit::Week(2020, 53)]
+        );
+
+        assert_eq!(
+            CalendarUnit::Year(2020).children(UnitKind::BiWeek).len(),
+            27
+        );
+        for unit in CalendarUnit::BiWeek(2019, 1).onwards().take(120) {
+            assert_eq!(unit.end_date().succ_opt(), Some(unit.succ().start_date()));
+            assert_eq!(unit.children(UnitKind::Day).len() % 7, 0);
+        }
+    }
+
     #[test]
     fn test_half_iterator() {
-        let mut c = CalendarUnit::Half(2022, 1);
+        let mut c = CalendarUnit::Half(2022, 1).onwards();
         assert_eq!(c.next(), Some(CalendarUnit::Half(2022, 1)));
         assert_eq!(c.next(), Some(CalendarUnit::Half(2022, 2)));
         assert_eq!(c.next(), Some(CalendarUnit::Half(2023, 1)));
@@ -179,6 +987,162 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_intervals_for_range() {
+        let intervals = CalendarUnit::intervals_for_range(
+            CalendarUnit::Quarter(2021, 3),
+            CalendarUnit::Quarter(2022, 2),
+        );
+
+        let expected: Vec<_> = CalendarUnit::Quarter(2021, 3)
+            .range_to(CalendarUnit::Quarter(2022, 2))
+            .map(|unit| match unit.into_interval() {
+                Interval::Closed(closed) => closed,
+                _ => unreachable!(),
+            })
+            .collect();
+
+        assert_eq!(intervals, expected);
+    }
+
+    #[test]
+    fn test_intervals_for_range_weeks() {
+        let intervals = CalendarUnit::intervals_for_range(
+            CalendarUnit::Week(2020, 52),
+            CalendarUnit::Week(2021, 2),
+        );
+
+        assert_eq!(intervals.len(), 4);
+        assert_eq!(
+            intervals[1].start_opt(),
+            NaiveDate::from_ymd_opt(2020, 12, 28)
+        );
+    }
+
+    #[test]
+    fn test_intervals_for_range_mismatched() {
+        assert!(CalendarUnit::intervals_for_range(
+            CalendarUnit::Year(2020),
+            CalendarUnit::Month(2021, 1)
+        )
+        .is_empty());
+
+        assert!(CalendarUnit::intervals_for_range(
+            CalendarUnit::Month(2021, 2),
+            CalendarUnit::Month(2021, 1)
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_start_and_end_dates() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        let week = CalendarUnit::Week(2020, 53);
+        assert_eq!(week.start_date(), date(2020, 12, 28));
+        assert_eq!(week.end_date(), date(2021, 1, 3));
+        assert_eq!(week.into_interval().end_opt(), Some(week.end_date()));
+
+        assert_eq!(CalendarUnit::Month(2024, 2).end_date(), date(2024, 2, 29));
+        assert_eq!(CalendarUnit::Half(2024, 1).end_date(), date(2024, 6, 30));
+        assert_eq!(CalendarUnit::Year(2024).end_date(), date(2024, 12, 31));
+
+        // Consecutive units leave no gaps
+        for unit in CalendarUnit::Week(2019, 50).onwards().take(120) {
+            assert_eq!(unit.end_date().succ_opt(), Some(unit.succ().start_date()));
+            assert!(unit.contains(unit.end_date()));
+            assert!(!unit.overlaps(&unit.succ()));
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(CalendarUnit::MIN.start_date(), NaiveDate::MIN);
+        assert_eq!(CalendarUnit::MAX.end_date(), NaiveDate::MAX);
+        assert_eq!(CalendarUnit::MIN.checked_pred(), None);
+        assert_eq!(CalendarUnit::Year(i32::MAX).checked_start_date(), None);
+
+        for kind in [
+            UnitKind::Year,
+            UnitKind::Half,
+            UnitKind::Quarter,
+            UnitKind::Month,
+            UnitKind::BiWeek,
+            UnitKind::Week,
+            UnitKind::Day,
+        ] {
+            let last = CalendarUnit::containing(NaiveDate::MAX, kind);
+            let first = CalendarUnit::containing(NaiveDate::MIN, kind);
+
+            assert_eq!(last.checked_succ(), None, "{:?}", kind);
+            assert_eq!(first.checked_pred(), None, "{:?}", kind);
+            if let Some(previous) = last.checked_pred() {
+                assert_eq!(
+                    previous.checked_succ(),
+                    last.checked_end_date().map(|_| last)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_chronological_cmp() {
+        let date = NaiveDate::from_ymd_opt(2020, 12, 28).unwrap();
+        let mut units = vec![
+            CalendarUnit::Day(date),
+            CalendarUnit::Week(2020, 53),
+            CalendarUnit::Year(2021),
+            CalendarUnit::Month(2020, 12),
+            CalendarUnit::BiWeek(2020, 27),
+        ];
+        units.sort_by(CalendarUnit::chronological_cmp);
+
+        assert_eq!(
+            units,
+            vec![
+                CalendarUnit::Month(2020, 12),
+                CalendarUnit::BiWeek(2020, 27),
+                CalendarUnit::Week(2020, 53),
+                CalendarUnit::Day(date),
+                CalendarUnit::Year(2021),
+            ]
+        );
+        assert_eq!(
+            CalendarUnit::Week(2020, 53).chronological_cmp(&CalendarUnit::Week(2020, 53)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_hierarchy() {
+        let year = CalendarUnit::Year(2022);
+        assert_eq!(year.children(UnitKind::Year), vec![year]);
+        assert_eq!(year.children(UnitKind::Half).len(), 2);
+        assert_eq!(year.children(UnitKind::Month).len(), 12);
+        assert!(CalendarUnit::Month(2022, 1)
+            .children(UnitKind::Year)
+            .is_empty());
+
+        // 2022-01-01 is a Saturday so the first week of 2022 starts on 2022-01-03
+        let weeks = year.children(UnitKind::Week);
+        assert_eq!(weeks.len(), 52);
+        assert_eq!(weeks[0], CalendarUnit::Week(2022, 1));
+        assert_eq!(weeks[51], CalendarUnit::Week(2022, 52));
+
+        // Weeks are split between months by their Thursday
+        let weeks = CalendarUnit::Month(2022, 9).children(UnitKind::Week);
+        assert_eq!(weeks.first(), Some(&CalendarUnit::Week(2022, 35)));
+        assert_eq!(weeks.len(), 5);
+
+        for month in year.children(UnitKind::Month) {
+            for week in month.children(UnitKind::Week) {
+                assert_eq!(week.parent(UnitKind::Month), month);
+                assert_eq!(week.parent(UnitKind::Year), year);
+            }
+            assert_eq!(month.parent(UnitKind::Quarter).parent(UnitKind::Year), year);
+        }
+    }
+
     #[test]
     fn test_half_interval() {
         let interval = CalendarUnit::Half(2022, 2).into_interval();
@@ -191,4 +1155,70 @@ mod tests {
             NaiveDate::from_ymd_opt(2022, 12, 31).unwrap()
         );
     }
+    #[test]
+    fn test_parse_round_trip() {
+        let units = vec![
+            CalendarUnit::Year(2022),
+            CalendarUnit::Year(-44),
+            CalendarUnit::Quarter(2022, 4),
+            CalendarUnit::Half(-1, 2),
+            CalendarUnit::Month(2022, 5),
+            CalendarUnit::Week(2020, 53),
+            CalendarUnit::BiWeek(2020, 27),
+            CalendarUnit::Day(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()),
+            CalendarUnit::Day(NaiveDate::from_ymd_opt(-44, 3, 15).unwrap()),
+        ];
+
+        for unit in units {
+            assert_eq!(unit.to_string().parse(), Ok(unit));
+
+            let json = serde_json::to_string(&unit).unwrap();
+            assert_eq!(serde_json::from_str::<CalendarUnit>(&json).unwrap(), unit);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = |s: &str| s.parse::<CalendarUnit>().unwrap_err();
+
+        assert_eq!(
+            parse("2022-Q5"),
+            CalendarUnitParseError::QuarterOutOfRange(5)
+        );
+        assert_eq!(parse("2022-H0"), CalendarUnitParseError::HalfOutOfRange(0));
+        assert_eq!(
+            parse("2022-13"),
+            CalendarUnitParseError::MonthOutOfRange(13)
+        );
+        assert_eq!(
+            parse("2021-W53"),
+            CalendarUnitParseError::WeekOutOfRange(2021, 53)
+        );
+        assert_eq!(
+            parse("2021-B27"),
+            CalendarUnitParseError::BiWeekOutOfRange(2021, 27)
+        );
+        assert_eq!(
+            parse("2023-02-29"),
+            CalendarUnitParseError::DayOutOfRange(29)
+        );
+        for invalid in [
+            "",
+            "Q1",
+            "2022-",
+            "2022-5",
+            "2022-q1",
+            "2022-Q+1",
+            "2022/05",
+            "2022-1-05",
+        ] {
+            assert_eq!(
+                parse(invalid),
+                CalendarUnitParseError::InvalidFormat(invalid.to_string())
+            );
+        }
+
+        let error = serde_json::from_str::<CalendarUnit>(r#""2022-M1""#).unwrap_err();
+        assert!(error.to_string().contains("2022-M1"));
+    }
 }
@@ -0,0 +1,237 @@
+//! Alternate string forms of calendar units
+//!
+//! The [Display](std::fmt::Display) form of a [CalendarUnit] is the ISO style `2022-Q1`, other
+//! systems label the same units differently. A [CalendarUnitFormat] selects one of the supported
+//! styles for both [CalendarUnit::format_with] and [CalendarUnit::parse_with].
+//!
+//! | Unit    | `Iso`        | `Label`       | `Compact`  | `Fiscal`       |
+//! |---------|--------------|---------------|------------|----------------|
+//! | Year    | `2022`       | `2022`        | `2022`     | `FY2022`       |
+//! | Half    | `2022-H1`    | `H1 2022`     | `2022H1`   | `FY2022-H1`    |
+//! | Quarter | `2022-Q1`    | `Q1 2022`     | `2022Q1`   | `FY2022-Q1`    |
+//! | Month   | `2022-01`    | `Jan 2022`    | `2022M01`  | `FY2022-01`    |
+//! | BiWeek  | `2022-B03`   | `B03 2022`    | `2022B03`  | `FY2022-B03`   |
+//! | Week    | `2022-W05`   | `W05 2022`    | `2022W05`  | `FY2022-W05`   |
+//! | Day     | `2022-01-17` | `17 Jan 2022` | `20220117` | `FY2022-01-17` |
+use chrono::{Datelike, Month};
+
+use super::{CalendarUnit, CalendarUnitParseError};
+
+/// Style of the string form of a [CalendarUnit], see the [module documentation](self)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CalendarUnitFormat {
+    /// The [Display](std::fmt::Display) form, e.g. `2022-Q1`
+    #[default]
+    Iso,
+    /// Period before the year separated by a space, e.g. `Q1 2022` or `Jan 2022`
+    Label,
+    /// No separators, e.g. `2022Q1` or `2022W05`
+    Compact,
+    /// The [Display](std::fmt::Display) form prefixed with `FY`, e.g. `FY2022-Q1`
+    Fiscal,
+}
+
+impl CalendarUnit {
+    /// String form of the unit in the style of the format
+    ///
+    /// ```
+    /// use calends::{unit::CalendarUnitFormat, CalendarUnit};
+    ///
+    /// let quarter = CalendarUnit::Quarter(2022, 1);
+    /// assert_eq!(quarter.format_with(CalendarUnitFormat::Label), "Q1 2022");
+    /// assert_eq!(quarter.format_with(CalendarUnitFormat::Compact), "2022Q1");
+    /// assert_eq!(quarter.format_with(CalendarUnitFormat::Fiscal), "FY2022-Q1");
+    /// assert_eq!(
+    ///     CalendarUnit::Month(2022, 1).format_with(CalendarUnitFormat::Label),
+    ///     "Jan 2022"
+    /// );
+    /// ```
+    pub fn format_with(&self, format: CalendarUnitFormat) -> String {
+        match format {
+            CalendarUnitFormat::Iso => self.to_string(),
+            CalendarUnitFormat::Fiscal => format!("FY{}", self),
+            CalendarUnitFormat::Label => match self {
+                CalendarUnit::Year(y) => y.to_string(),
+                CalendarUnit::Quarter(y, q) => format!("Q{} {}", q, y),
+                CalendarUnit::Half(y, h) => format!("H{} {}", h, y),
+                CalendarUnit::Month(y, m) => format!("{} {}", month_abbreviation(*m as u32), y),
+                CalendarUnit::Week(y, w) => format!("W{:0>2} {}", w, y),
+                CalendarUnit::BiWeek(y, b) => format!("B{:0>2} {}", b, y),
+                CalendarUnit::Day(date) => format!(
+                    "{} {} {}",
+                    date.day(),
+                    month_abbreviation(date.month()),
+                    date.year()
+                ),
+            },
+            CalendarUnitFormat::Compact => match self {
+                CalendarUnit::Year(y) => y.to_string(),
+                CalendarUnit::Quarter(y, q) => format!("{}Q{}", y, q),
+                CalendarUnit::Half(y, h) => format!("{}H{}", y, h),
+                CalendarUnit::Month(y, m) => format!("{}M{:0>2}", y, m),
+                CalendarUnit::Week(y, w) => format!("{}W{:0>2}", y, w),
+                CalendarUnit::BiWeek(y, b) => format!("{}B{:0>2}", y, b),
+                CalendarUnit::Day(date) => date.format("%Y%m%d").to_string(),
+            },
+        }
+    }
+
+    /// Parse a unit written in the style of the format
+    ///
+    /// Month names may be abbreviated or in full and are not case sensitive.
+    ///
+    /// ```
+    /// use calends::{unit::CalendarUnitFormat, CalendarUnit};
+    ///
+    /// assert_eq!(
+    ///     CalendarUnit::parse_with("Q1 2022", CalendarUnitFormat::Label),
+    ///     Ok(CalendarUnit::Quarter(2022, 1))
+    /// );
+    /// assert_eq!(
+    ///     CalendarUnit::parse_with("january 2022", CalendarUnitFormat::Label),
+    ///     Ok(CalendarUnit::Month(2022, 1))
+    /// );
+    /// assert_eq!(
+    ///     CalendarUnit::parse_with("2022W05", CalendarUnitFormat::Compact),
+    ///     Ok(CalendarUnit::Week(2022, 5))
+    /// );
+    /// assert!(CalendarUnit::parse_with("2022-Q1", CalendarUnitFormat::Fiscal).is_err());
+    /// ```
+    pub fn parse_with(
+        s: &str,
+        format: CalendarUnitFormat,
+    ) -> Result<CalendarUnit, CalendarUnitParseError> {
+        let invalid = || CalendarUnitParseError::InvalidFormat(s.to_string());
+
+        let iso = match format {
+            CalendarUnitFormat::Iso => s.to_string(),
+            CalendarUnitFormat::Fiscal => s.strip_prefix("FY").ok_or_else(invalid)?.to_string(),
+            CalendarUnitFormat::Label => label_to_iso(s).ok_or_else(invalid)?,
+            CalendarUnitFormat::Compact => compact_to_iso(s).ok_or_else(invalid)?,
+        };
+
+        // Report the string as it was written rather than its normalized form
+        iso.parse().map_err(|e| match e {
+            CalendarUnitParseError::InvalidFormat(_) => invalid(),
+            e => e,
+        })
+    }
+}
+
+fn month_abbreviation(month: u32) -> &'static str {
+    let name = Month::try_from(month as u8).map_or("", |m| m.name());
+    &name[..3]
+}
+
+/// Rewrite `Q1 2022`, `Jan 2022` or `17 Jan 2022` in the [Display](std::fmt::Display) form
+fn label_to_iso(s: &str) -> Option<String> {
+    let parts: Vec<&str> = s.split_whitespace().collect();
+
+    match parts[..] {
+        [year] => Some(year.to_string()),
+        [period, year] => {
+            let iso = match period.parse::<Month>() {
+                Ok(month) => format!("{}-{:0>2}", year, month.number_from_month()),
+                Err(_) if period.starts_with(['Q', 'H', 'W', 'B']) => {
+                    format!("{}-{}", year, period)
+                }
+                Err(_) => return None,
+            };
+            Some(iso)
+        }
+        [day, month, year] => {
+            let month = month.parse::<Month>().ok()?;
+            Some(format!(
+                "{}-{:0>2}-{:0>2}",
+                year,
+                month.number_from_month(),
+                day
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Rewrite `2022Q1`, `2022M01` or `20220117` in the [Display](std::fmt::Display) form
+fn compact_to_iso(s: &str) -> Option<String> {
+    let sign_len = usize::from(s.starts_with(['-', '+']));
+    let digits = s[sign_len..].find(|c: char| !c.is_ascii_digit());
+
+    match digits {
+        Some(len) => {
+            let (year, period) = s.split_at(sign_len + len);
+            match period.strip_prefix('M') {
+                Some(month) => Some(format!("{}-{}", year, month)),
+                None => Some(format!("{}-{}", year, period)),
+            }
+        }
+        // A day is the only all digit form longer than a year
+        None if s.len() - sign_len == 8 => {
+            let (year, rest) = s.split_at(s.len() - 4);
+            Some(format!("{}-{}-{}", year, &rest[..2], &rest[2..]))
+        }
+        None => Some(s.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let units = [
+            CalendarUnit::Year(2022),
+            CalendarUnit::Half(2022, 2),
+            CalendarUnit::Quarter(2022, 1),
+            CalendarUnit::Month(2022, 12),
+            CalendarUnit::BiWeek(2020, 27),
+            CalendarUnit::Week(2022, 5),
+            CalendarUnit::Day(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap()),
+            CalendarUnit::Quarter(-44, 1),
+        ];
+        let formats = [
+            CalendarUnitFormat::Iso,
+            CalendarUnitFormat::Label,
+            CalendarUnitFormat::Compact,
+            CalendarUnitFormat::Fiscal,
+        ];
+
+        for unit in units {
+            for format in formats {
+                let s = unit.format_with(format);
+                assert_eq!(CalendarUnit::parse_with(&s, format), Ok(unit), "{}", s);
+            }
+        }
+
+        assert_eq!(
+            CalendarUnit::Day(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap())
+                .format_with(CalendarUnitFormat::Label),
+            "7 Jan 2022"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            CalendarUnit::parse_with("Q5 2022", CalendarUnitFormat::Label),
+            Err(CalendarUnitParseError::QuarterOutOfRange(5))
+        );
+        assert_eq!(
+            CalendarUnit::parse_with("Smarch 2022", CalendarUnitFormat::Label),
+            Err(CalendarUnitParseError::InvalidFormat(
+                "Smarch 2022".to_string()
+            ))
+        );
+        assert_eq!(
+            CalendarUnit::parse_with("2022M13", CalendarUnitFormat::Compact),
+            Err(CalendarUnitParseError::MonthOutOfRange(13))
+        );
+        assert_eq!(
+            CalendarUnit::parse_with("2022X1", CalendarUnitFormat::Compact),
+            Err(CalendarUnitParseError::InvalidFormat("2022X1".to_string()))
+        );
+    }
+}
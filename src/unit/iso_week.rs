@@ -0,0 +1,248 @@
+//! ISO 8601 weeks numbered within their week-based year
+use std::{fmt::Display, str::FromStr};
+
+#[cfg(not(feature = "strict"))]
+use std::ops::{Add, Sub};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use super::{CalendarUnit, CalendarUnitParseError};
+use crate::Interval;
+
+/// An ISO week from Monday to Sunday
+///
+/// Weeks belong to the year containing their Thursday, so a year has 52 or 53 weeks and the first
+/// days of January can fall in the last week of the previous year.
+///
+/// ```
+/// use calends::unit::IsoWeek;
+/// use chrono::NaiveDate;
+///
+/// let week = IsoWeek::of(NaiveDate::from_ymd_opt(2021, 1, 3).unwrap());
+///
+/// assert_eq!(week, IsoWeek::new(2020, 53).unwrap());
+/// assert_eq!(week.monday(), NaiveDate::from_ymd_opt(2020, 12, 28).unwrap());
+/// assert_eq!(week.succ(), IsoWeek::new(2021, 1).unwrap());
+/// assert_eq!(week.to_string(), "2020-W53");
+/// assert!(IsoWeek::new(2021, 53).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IsoWeek {
+    year: i32,
+    week: u8,
+}
+
+impl IsoWeek {
+    /// The week of the ISO year, [None] if the year doesn't have the week
+    pub fn new(year: i32, week: u32) -> Option<IsoWeek> {
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?;
+
+        Some(IsoWeek {
+            year,
+            week: week as u8,
+        })
+    }
+
+    /// Week containing the date
+    pub fn of(date: NaiveDate) -> IsoWeek {
+        let week = date.iso_week();
+        IsoWeek {
+            year: week.year(),
+            week: week.week() as u8,
+        }
+    }
+
+    /// Number of weeks in the ISO year, 52 or 53
+    pub fn weeks_in_year(year: i32) -> u32 {
+        match NaiveDate::from_isoywd_opt(year, 53, Weekday::Mon) {
+            Some(_) => 53,
+            None => 52,
+        }
+    }
+
+    /// ISO week-based year, which can differ from the calendar year of the dates around New Year
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Week within the ISO year from 1 to 53
+    pub fn week(&self) -> u32 {
+        self.week.into()
+    }
+
+    /// Date of the weekday within the week, [None] if it is out of range
+    pub fn day(&self, weekday: Weekday) -> Option<NaiveDate> {
+        NaiveDate::from_isoywd_opt(self.year, self.week(), weekday)
+    }
+
+    /// First day of the week
+    pub fn monday(&self) -> NaiveDate {
+        self.day(Weekday::Mon).expect("week out of range")
+    }
+
+    /// Last day of the week
+    pub fn sunday(&self) -> NaiveDate {
+        self.day(Weekday::Sun).expect("week out of range")
+    }
+
+    /// The week as an [Interval], see [CalendarUnit::into_interval]
+    pub fn into_interval(&self) -> Interval {
+        self.into_unit().into_interval()
+    }
+
+    pub fn into_unit(&self) -> CalendarUnit {
+        CalendarUnit::Week(self.year, self.week)
+    }
+
+    pub fn succ(&self) -> IsoWeek {
+        self.checked_add(1).expect("week out of range")
+    }
+
+    pub fn pred(&self) -> IsoWeek {
+        self.checked_add(-1).expect("week out of range")
+    }
+
+    /// The week `n` weeks later, or earlier for a negative `n`, returning [None] if any of its days
+    /// would be out of range
+    pub fn checked_add(&self, n: i32) -> Option<IsoWeek> {
+        let monday = self
+            .day(Weekday::Mon)?
+            .checked_add_signed(Duration::weeks(n.into()))?;
+        // The whole week has to be in range for its Sunday to exist
+        monday.checked_add_signed(Duration::days(6))?;
+
+        Some(IsoWeek::of(monday))
+    }
+
+    /// Number of weeks from this week to the other, negative when the other is earlier
+    ///
+    /// ```
+    /// use calends::unit::IsoWeek;
+    ///
+    /// let first = IsoWeek::new(2020, 52).unwrap();
+    /// assert_eq!(first.weeks_until(&IsoWeek::new(2021, 2).unwrap()), 3);
+    /// ```
+    pub fn weeks_until(&self, other: &IsoWeek) -> i64 {
+        (other.monday() - self.monday()).num_weeks()
+    }
+}
+
+impl From<IsoWeek> for CalendarUnit {
+    fn from(week: IsoWeek) -> Self {
+        week.into_unit()
+    }
+}
+
+impl TryFrom<CalendarUnit> for IsoWeek {
+    type Error = CalendarUnit;
+
+    fn try_from(unit: CalendarUnit) -> Result<Self, Self::Error> {
+        match unit {
+            CalendarUnit::Week(year, week) => IsoWeek::new(year, week.into()).ok_or(unit),
+            unit => Err(unit),
+        }
+    }
+}
+
+impl From<chrono::IsoWeek> for IsoWeek {
+    fn from(week: chrono::IsoWeek) -> Self {
+        IsoWeek {
+            year: week.year(),
+            week: week.week() as u8,
+        }
+    }
+}
+
+impl Display for IsoWeek {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.into_unit().fmt(f)
+    }
+}
+
+/// Parse a week in the [Display] form of a [CalendarUnit] such as `2022-W07`
+impl FromStr for IsoWeek {
+    type Err = CalendarUnitParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<CalendarUnit>()?
+            .try_into()
+            .map_err(|_| CalendarUnitParseError::InvalidFormat(s.to_string()))
+    }
+}
+
+#[cfg(not(feature = "strict"))]
+impl Add<i32> for IsoWeek {
+    type Output = IsoWeek;
+
+    fn add(self, rhs: i32) -> IsoWeek {
+        self.checked_add(rhs).expect("week out of range")
+    }
+}
+
+#[cfg(not(feature = "strict"))]
+impl Sub<i32> for IsoWeek {
+    type Output = IsoWeek;
+
+    fn sub(self, rhs: i32) -> IsoWeek {
+        rhs.checked_neg()
+            .and_then(|n| self.checked_add(n))
+            .expect("week out of range")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weeks_of_years() {
+        let mut week = IsoWeek::new(2014, 1).unwrap();
+
+        for year in 2014..2030 {
+            let weeks = IsoWeek::weeks_in_year(year);
+            assert_eq!(weeks == 53, [2015, 2020, 2026].contains(&year));
+
+            for n in 1..=weeks {
+                assert_eq!(week, IsoWeek::new(year, n).unwrap());
+                assert_eq!(IsoWeek::of(week.sunday()), week);
+                assert_eq!(week.sunday() - week.monday(), Duration::days(6));
+                assert_eq!(week.into_unit().succ(), week.succ().into_unit());
+                assert_eq!(week.succ().pred(), week);
+                week = week.succ();
+            }
+        }
+
+        assert_eq!(IsoWeek::new(2014, 1).unwrap().weeks_until(&week), 835);
+        assert_eq!(IsoWeek::new(2014, 1).unwrap().checked_add(835), Some(week));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let last = IsoWeek::of(NaiveDate::MAX);
+        let first = IsoWeek::of(NaiveDate::MIN);
+
+        assert_eq!(last.pred().checked_add(1), None);
+        assert_eq!(first.checked_add(-1), None);
+        assert_eq!(IsoWeek::new(2022, 0), None);
+        assert_eq!(
+            IsoWeek::try_from(CalendarUnit::Week(2021, 53)),
+            Err(CalendarUnit::Week(2021, 53))
+        );
+        assert_eq!(
+            IsoWeek::try_from(CalendarUnit::Week(2020, 53)),
+            Ok(IsoWeek::new(2020, 53).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let week = IsoWeek::new(2022, 7).unwrap();
+
+        assert_eq!("2022-W07".parse(), Ok(week));
+        assert_eq!(
+            "2021-W53".parse::<IsoWeek>(),
+            Err(CalendarUnitParseError::WeekOutOfRange(2021, 53))
+        );
+        assert!("2022-07".parse::<IsoWeek>().is_err());
+    }
+}
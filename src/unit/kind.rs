@@ -0,0 +1,113 @@
+//! Granularity of a [CalendarUnit]
+use crate::{grain::Grain, CalendarUnit, RelativeDuration};
+
+/// The kinds of [CalendarUnit], from the longest to the shortest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UnitKind {
+    Year,
+    Half,
+    Quarter,
+    Month,
+    /// Two ISO weeks
+    BiWeek,
+    /// ISO week
+    Week,
+    Day,
+}
+
+impl UnitKind {
+    /// Nominal length of a unit of this kind, a biweek is two weeks even when it is cut short at
+    /// the end of a year
+    pub fn duration(&self) -> RelativeDuration {
+        match self {
+            UnitKind::Year => RelativeDuration::months(12),
+            UnitKind::Half => RelativeDuration::months(6),
+            UnitKind::Quarter => RelativeDuration::months(3),
+            UnitKind::Month => RelativeDuration::months(1),
+            UnitKind::BiWeek => RelativeDuration::weeks(2),
+            UnitKind::Week => RelativeDuration::weeks(1),
+            UnitKind::Day => RelativeDuration::days(1),
+        }
+    }
+
+    /// The [Grain] of the same length, [None] for biweeks
+    pub fn grain(&self) -> Option<Grain> {
+        match self {
+            UnitKind::Year => Some(Grain::Year),
+            UnitKind::Half => Some(Grain::Half),
+            UnitKind::Quarter => Some(Grain::Quarter),
+            UnitKind::Month => Some(Grain::Month),
+            UnitKind::BiWeek => None,
+            UnitKind::Week => Some(Grain::Week),
+            UnitKind::Day => Some(Grain::Day),
+        }
+    }
+
+    /// The kind of unit of the same length as the grain, [None] for grains longer than a year
+    pub fn from_grain(grain: Grain) -> Option<UnitKind> {
+        match grain {
+            Grain::Day => Some(UnitKind::Day),
+            Grain::Week => Some(UnitKind::Week),
+            Grain::Month => Some(UnitKind::Month),
+            Grain::Quarter => Some(UnitKind::Quarter),
+            Grain::Half => Some(UnitKind::Half),
+            Grain::Year => Some(UnitKind::Year),
+            Grain::Lustrum | Grain::Decade | Grain::Century => None,
+        }
+    }
+}
+
+impl CalendarUnit {
+    /// The kind of the unit, to match on its granularity without destructuring it
+    ///
+    /// ```
+    /// use calends::{CalendarUnit, UnitKind};
+    ///
+    /// assert_eq!(CalendarUnit::Quarter(2022, 3).kind(), UnitKind::Quarter);
+    /// assert!(CalendarUnit::Week(2022, 3).kind() > UnitKind::Month);
+    /// ```
+    pub fn kind(&self) -> UnitKind {
+        match self {
+            CalendarUnit::Year(_) => UnitKind::Year,
+            CalendarUnit::Half(_, _) => UnitKind::Half,
+            CalendarUnit::Quarter(_, _) => UnitKind::Quarter,
+            CalendarUnit::Month(_, _) => UnitKind::Month,
+            CalendarUnit::BiWeek(_, _) => UnitKind::BiWeek,
+            CalendarUnit::Week(_, _) => UnitKind::Week,
+            CalendarUnit::Day(_) => UnitKind::Day,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn test_grain_round_trip() {
+        let date = NaiveDate::from_ymd_opt(2022, 8, 17).unwrap();
+        let kinds = [
+            UnitKind::Year,
+            UnitKind::Half,
+            UnitKind::Quarter,
+            UnitKind::Month,
+            UnitKind::Week,
+            UnitKind::Day,
+        ];
+
+        for kind in kinds {
+            let grain = kind.grain().unwrap();
+            assert_eq!(UnitKind::from_grain(grain), Some(kind));
+            assert_eq!(grain.into_duration(), kind.duration());
+
+            let unit = CalendarUnit::containing(date, kind);
+            assert_eq!(unit.kind(), kind);
+            assert_eq!(grain.unit_of(date), Some(unit));
+        }
+
+        assert_eq!(UnitKind::BiWeek.grain(), None);
+        assert_eq!(UnitKind::from_grain(Grain::Decade), None);
+    }
+}
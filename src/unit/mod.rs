@@ -1,5 +1,13 @@
 pub mod convert;
 pub mod domain;
+pub mod format;
+pub mod iso_week;
+pub mod kind;
+pub mod quarter;
 
 pub use convert::*;
-pub use domain::CalendarUnit;
+pub use domain::{CalendarUnit, CalendarUnitParseError, UnitRange};
+pub use format::CalendarUnitFormat;
+pub use iso_week::IsoWeek;
+pub use kind::UnitKind;
+pub use quarter::{Half, Quarter};
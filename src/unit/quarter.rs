@@ -0,0 +1,391 @@
+//! Quarters and halves of a calendar year as their own types
+//!
+//! [CalendarUnit::Quarter] and [CalendarUnit::Half] hold a bare `(year, n)` pair, these types keep
+//! the two apart and only hold valid quarters and halves.
+use std::{fmt::Display, str::FromStr};
+
+#[cfg(not(feature = "strict"))]
+use std::ops::{Add, Sub};
+
+use chrono::{Datelike, NaiveDate};
+
+use super::{CalendarUnit, CalendarUnitParseError};
+use crate::{Interval, YearMonth};
+
+/// A quarter of a calendar year, January to March is the first
+///
+/// ```
+/// use calends::unit::Quarter;
+/// use chrono::NaiveDate;
+///
+/// let q4 = Quarter::new(2022, 4).unwrap();
+///
+/// assert_eq!(q4.first_month(), 10);
+/// assert_eq!(q4.succ(), Quarter::new(2023, 1).unwrap());
+/// assert_eq!(q4.end_date(), NaiveDate::from_ymd_opt(2022, 12, 31).unwrap());
+/// assert_eq!(q4.to_string(), "2022-Q4");
+/// assert_eq!("2022-Q4".parse(), Ok(q4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Quarter {
+    year: i32,
+    quarter: u8,
+}
+
+impl Quarter {
+    /// The quarter of the year, [None] unless the quarter is from 1 to 4
+    pub fn new(year: i32, quarter: u32) -> Option<Quarter> {
+        match quarter {
+            1..=4 => Some(Quarter {
+                year,
+                quarter: quarter as u8,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Quarter containing the date
+    pub fn of(date: NaiveDate) -> Quarter {
+        Quarter {
+            year: date.year(),
+            quarter: (date.month0() / 3 + 1) as u8,
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Quarter within the year from 1 to 4
+    pub fn quarter(&self) -> u32 {
+        self.quarter.into()
+    }
+
+    /// First month of the quarter, 1, 4, 7 or 10
+    pub fn first_month(&self) -> u32 {
+        self.quarter() * 3 - 2
+    }
+
+    /// The three months of the quarter in order
+    pub fn months(&self) -> impl Iterator<Item = YearMonth> {
+        let first = YearMonth::new(self.year, self.first_month()).expect("month is valid");
+        std::iter::successors(Some(first), |month| month.checked_add(1)).take(3)
+    }
+
+    pub fn start_date(&self) -> NaiveDate {
+        self.into_unit().start_date()
+    }
+
+    pub fn end_date(&self) -> NaiveDate {
+        self.into_unit().end_date()
+    }
+
+    /// The quarter as an [Interval], see [CalendarUnit::into_interval]
+    pub fn into_interval(&self) -> Interval {
+        self.into_unit().into_interval()
+    }
+
+    pub fn into_unit(&self) -> CalendarUnit {
+        CalendarUnit::Quarter(self.year, self.quarter)
+    }
+
+    pub fn succ(&self) -> Quarter {
+        self.checked_add(1).expect("quarter out of range")
+    }
+
+    pub fn pred(&self) -> Quarter {
+        self.checked_add(-1).expect("quarter out of range")
+    }
+
+    /// The quarter `n` quarters later, or earlier for a negative `n`, returning [None] if the year
+    /// is out of range
+    pub fn checked_add(&self, n: i32) -> Option<Quarter> {
+        let (year, index) = shift_index(self.year, self.quarter, 4, n)?;
+        Quarter::new(year, index)
+    }
+}
+
+impl From<Quarter> for CalendarUnit {
+    fn from(quarter: Quarter) -> Self {
+        quarter.into_unit()
+    }
+}
+
+impl TryFrom<CalendarUnit> for Quarter {
+    type Error = CalendarUnit;
+
+    fn try_from(unit: CalendarUnit) -> Result<Self, Self::Error> {
+        match unit {
+            CalendarUnit::Quarter(year, quarter) => Quarter::new(year, quarter.into()).ok_or(unit),
+            unit => Err(unit),
+        }
+    }
+}
+
+impl Display for Quarter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.into_unit().fmt(f)
+    }
+}
+
+/// Parse a quarter in the [Display] form of a [CalendarUnit] such as `2022-Q1`
+impl FromStr for Quarter {
+    type Err = CalendarUnitParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<CalendarUnit>()?
+            .try_into()
+            .map_err(|_| CalendarUnitParseError::InvalidFormat(s.to_string()))
+    }
+}
+
+#[cfg(not(feature = "strict"))]
+impl Add<i32> for Quarter {
+    type Output = Quarter;
+
+    fn add(self, rhs: i32) -> Quarter {
+        self.checked_add(rhs).expect("quarter out of range")
+    }
+}
+
+#[cfg(not(feature = "strict"))]
+impl Sub<i32> for Quarter {
+    type Output = Quarter;
+
+    fn sub(self, rhs: i32) -> Quarter {
+        rhs.checked_neg()
+            .and_then(|n| self.checked_add(n))
+            .expect("quarter out of range")
+    }
+}
+
+/// A half of a calendar year, January to June is the first
+///
+/// ```
+/// use calends::unit::Half;
+/// use chrono::NaiveDate;
+///
+/// let h2 = Half::of(NaiveDate::from_ymd_opt(2022, 8, 17).unwrap());
+///
+/// assert_eq!(h2, Half::new(2022, 2).unwrap());
+/// assert_eq!(h2.first_month(), 7);
+/// assert_eq!(h2.checked_add(-2), Half::new(2021, 2));
+/// assert_eq!(h2.to_string(), "2022-H2");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Half {
+    year: i32,
+    half: u8,
+}
+
+impl Half {
+    /// The half of the year, [None] unless the half is 1 or 2
+    pub fn new(year: i32, half: u32) -> Option<Half> {
+        match half {
+            1..=2 => Some(Half {
+                year,
+                half: half as u8,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Half containing the date
+    pub fn of(date: NaiveDate) -> Half {
+        Half {
+            year: date.year(),
+            half: (date.month0() / 6 + 1) as u8,
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Half within the year, 1 or 2
+    pub fn half(&self) -> u32 {
+        self.half.into()
+    }
+
+    /// First month of the half, 1 or 7
+    pub fn first_month(&self) -> u32 {
+        self.half() * 6 - 5
+    }
+
+    /// The six months of the half in order
+    pub fn months(&self) -> impl Iterator<Item = YearMonth> {
+        let first = YearMonth::new(self.year, self.first_month()).expect("month is valid");
+        std::iter::successors(Some(first), |month| month.checked_add(1)).take(6)
+    }
+
+    /// The two quarters of the half in order
+    pub fn quarters(&self) -> [Quarter; 2] {
+        let first = self.half * 2 - 1;
+        [first, first + 1].map(|quarter| Quarter {
+            year: self.year,
+            quarter,
+        })
+    }
+
+    pub fn start_date(&self) -> NaiveDate {
+        self.into_unit().start_date()
+    }
+
+    pub fn end_date(&self) -> NaiveDate {
+        self.into_unit().end_date()
+    }
+
+    /// The half as an [Interval], see [CalendarUnit::into_interval]
+    pub fn into_interval(&self) -> Interval {
+        self.into_unit().into_interval()
+    }
+
+    pub fn into_unit(&self) -> CalendarUnit {
+        CalendarUnit::Half(self.year, self.half)
+    }
+
+    pub fn succ(&self) -> Half {
+        self.checked_add(1).expect("half out of range")
+    }
+
+    pub fn pred(&self) -> Half {
+        self.checked_add(-1).expect("half out of range")
+    }
+
+    /// The half `n` halves later, or earlier for a negative `n`, returning [None] if the year is
+    /// out of range
+    pub fn checked_add(&self, n: i32) -> Option<Half> {
+        let (year, index) = shift_index(self.year, self.half, 2, n)?;
+        Half::new(year, index)
+    }
+}
+
+impl From<Half> for CalendarUnit {
+    fn from(half: Half) -> Self {
+        half.into_unit()
+    }
+}
+
+impl TryFrom<CalendarUnit> for Half {
+    type Error = CalendarUnit;
+
+    fn try_from(unit: CalendarUnit) -> Result<Self, Self::Error> {
+        match unit {
+            CalendarUnit::Half(year, half) => Half::new(year, half.into()).ok_or(unit),
+            unit => Err(unit),
+        }
+    }
+}
+
+impl Display for Half {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.into_unit().fmt(f)
+    }
+}
+
+/// Parse a half in the [Display] form of a [CalendarUnit] such as `2022-H1`
+impl FromStr for Half {
+    type Err = CalendarUnitParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<CalendarUnit>()?
+            .try_into()
+            .map_err(|_| CalendarUnitParseError::InvalidFormat(s.to_string()))
+    }
+}
+
+#[cfg(not(feature = "strict"))]
+impl Add<i32> for Half {
+    type Output = Half;
+
+    fn add(self, rhs: i32) -> Half {
+        self.checked_add(rhs).expect("half out of range")
+    }
+}
+
+#[cfg(not(feature = "strict"))]
+impl Sub<i32> for Half {
+    type Output = Half;
+
+    fn sub(self, rhs: i32) -> Half {
+        rhs.checked_neg()
+            .and_then(|n| self.checked_add(n))
+            .expect("half out of range")
+    }
+}
+
+/// Move the `n`th of `per_year` periods of the year by a number of periods
+fn shift_index(year: i32, n: u8, per_year: i64, delta: i32) -> Option<(i32, u32)> {
+    let index = i64::from(year) * per_year + i64::from(n) - 1 + i64::from(delta);
+    let year = i32::try_from(index.div_euclid(per_year)).ok()?;
+
+    Some((year, (index.rem_euclid(per_year) + 1) as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quarter_matches_unit() {
+        let start = Quarter::new(-3, 2).unwrap();
+
+        for n in -20..20 {
+            let quarter = start.checked_add(n).unwrap();
+            assert_eq!(quarter.into_unit(), start.into_unit().add(n));
+            assert_eq!(Quarter::of(quarter.start_date()), quarter);
+            assert_eq!(Quarter::of(quarter.end_date()), quarter);
+            assert_eq!(quarter.succ().pred(), quarter);
+            assert_eq!(quarter.to_string().parse(), Ok(quarter));
+
+            let months: Vec<_> = quarter.months().map(CalendarUnit::from).collect();
+            assert_eq!(months, quarter.into_unit().children(crate::UnitKind::Month));
+        }
+
+        assert_eq!(Quarter::new(2022, 5), None);
+        assert_eq!(
+            Quarter::try_from(CalendarUnit::Quarter(2022, 5)),
+            Err(CalendarUnit::Quarter(2022, 5))
+        );
+        assert_eq!(Quarter::new(i32::MAX, 4).unwrap().checked_add(1), None);
+        assert_eq!(
+            "2022-H1".parse::<Quarter>(),
+            Err(CalendarUnitParseError::InvalidFormat("2022-H1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_half_matches_unit() {
+        let start = Half::new(2020, 1).unwrap();
+
+        for n in -9..9 {
+            let half = start.checked_add(n).unwrap();
+            assert_eq!(half.into_unit(), start.into_unit().add(n));
+            assert_eq!(Half::of(half.end_date()), half);
+            assert_eq!(half.quarters()[0].start_date(), half.start_date());
+            assert_eq!(half.quarters()[1].end_date(), half.end_date());
+            assert_eq!(half.months().count(), 6);
+            assert_eq!(half.to_string().parse(), Ok(half));
+        }
+
+        assert_eq!(Half::new(2022, 0), None);
+        assert_eq!(
+            Half::try_from(CalendarUnit::Half(2022, 3)),
+            Err(CalendarUnit::Half(2022, 3))
+        );
+        assert_eq!(
+            Half::try_from(CalendarUnit::Half(2022, 2)).ok(),
+            Half::new(2022, 2)
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn test_arithmetic() {
+        let quarter = Quarter::new(2022, 1).unwrap();
+
+        assert_eq!(quarter + 5, Quarter::new(2023, 2).unwrap());
+        assert_eq!(quarter - 1, Quarter::new(2021, 4).unwrap());
+        assert_eq!(Half::new(2022, 1).unwrap() - 3, Half::new(2020, 2).unwrap());
+    }
+}
@@ -0,0 +1,165 @@
+//! Ages from a date of birth
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    duration::SpanStrategy, month_day::LeapPolicy, EomPolicy, NaiveDateExt, RelativeDuration,
+};
+
+/// Whole years, months and days since a date of birth
+///
+/// Months follow the day of birth, clamped to the end of shorter months, e.g. someone born on
+/// January 31 is a month older on February 28.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Age {
+    pub years: u32,
+    pub months: u32,
+    pub days: u32,
+}
+
+impl Age {
+    /// Age in completed years
+    pub fn age_in_years(&self) -> u32 {
+        self.years
+    }
+
+    /// The age as a duration of months and days with the day of birth clamped to the end of
+    /// shorter months
+    ///
+    /// Added to the date of birth this gives the date of an age from [age].
+    pub fn to_duration(&self) -> RelativeDuration {
+        RelativeDuration::months((self.years * 12 + self.months) as i32)
+            .with_days(self.days as i32)
+            .with_eom_policy(EomPolicy::PreserveDayClamped)
+    }
+}
+
+/// Age on a date, with birthdays on February 29 celebrated on February 28 outside leap years
+///
+/// Returns [None] when the date is before the date of birth, see [age_with] for other leap day
+/// policies.
+///
+/// ```
+/// use calends::{age, Age};
+/// use chrono::NaiveDate;
+///
+/// let birth = NaiveDate::from_ymd_opt(1990, 8, 17).unwrap();
+/// let age = age(birth, NaiveDate::from_ymd_opt(2022, 8, 16).unwrap()).unwrap();
+///
+/// assert_eq!(age, Age { years: 31, months: 11, days: 30 });
+/// assert_eq!(age.age_in_years(), 31);
+/// ```
+pub fn age(birth: NaiveDate, on: NaiveDate) -> Option<Age> {
+    age_with(birth, on, LeapPolicy::Feb28)
+}
+
+/// Age on a date with a policy for birthdays on February 29 outside leap years
+///
+/// [LeapPolicy::Mar1] makes the birthday March 1. [LeapPolicy::Skip] is the same as
+/// [LeapPolicy::Mar1], as a year isn't complete until February has passed.
+///
+/// ```
+/// use calends::{age_with, month_day::LeapPolicy};
+/// use chrono::NaiveDate;
+///
+/// let birth = NaiveDate::from_ymd_opt(2004, 2, 29).unwrap();
+/// let on = NaiveDate::from_ymd_opt(2022, 2, 28).unwrap();
+///
+/// assert_eq!(age_with(birth, on, LeapPolicy::Feb28).unwrap().years, 18);
+/// assert_eq!(age_with(birth, on, LeapPolicy::Mar1).unwrap().years, 17);
+/// ```
+pub fn age_with(birth: NaiveDate, on: NaiveDate, policy: LeapPolicy) -> Option<Age> {
+    if on < birth {
+        return None;
+    }
+
+    let span = on.checked_since(birth, SpanStrategy::YearsMonthsDays)?;
+    let (mut months, mut days) = (span.num_months(), span.num_days());
+
+    let leap_day = birth.month() == 2 && birth.day() == 29;
+    if leap_day && policy != LeapPolicy::Feb28 {
+        let anniversary = clamped_months(months).checked_add_to(birth)?;
+
+        // Clamped to February 28, the anniversary is the day after
+        if anniversary.month() == 2 && anniversary.day() == 28 {
+            if days == 0 {
+                months -= 1;
+                days = (on - clamped_months(months).checked_add_to(birth)?).num_days() as i32;
+            } else {
+                days -= 1;
+            }
+        }
+    }
+
+    Some(Age {
+        years: (months / 12) as u32,
+        months: (months % 12) as u32,
+        days: days as u32,
+    })
+}
+
+/// Completed years on a date, see [age]
+///
+/// ```
+/// use calends::age_in_years;
+/// use chrono::NaiveDate;
+///
+/// let birth = NaiveDate::from_ymd_opt(1990, 8, 17).unwrap();
+///
+/// assert_eq!(age_in_years(birth, NaiveDate::from_ymd_opt(2022, 8, 17).unwrap()), Some(32));
+/// ```
+pub fn age_in_years(birth: NaiveDate, on: NaiveDate) -> Option<u32> {
+    age(birth, on).map(|age| age.years)
+}
+
+fn clamped_months(months: i32) -> RelativeDuration {
+    RelativeDuration::months(months).with_eom_policy(EomPolicy::PreserveDayClamped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn ymd(age: Age) -> (u32, u32, u32) {
+        (age.years, age.months, age.days)
+    }
+
+    #[test]
+    fn test_leap_day_birthdays() {
+        let birth = date(2000, 2, 29);
+        let at = |y, m, d, policy| ymd(age_with(birth, date(y, m, d), policy).unwrap());
+
+        assert_eq!(at(2001, 2, 27, LeapPolicy::Feb28), (0, 11, 29));
+        assert_eq!(at(2001, 2, 28, LeapPolicy::Feb28), (1, 0, 0));
+        assert_eq!(at(2001, 3, 1, LeapPolicy::Feb28), (1, 0, 1));
+        assert_eq!(at(2001, 3, 29, LeapPolicy::Feb28), (1, 1, 0));
+
+        assert_eq!(at(2001, 2, 28, LeapPolicy::Mar1), (0, 11, 30));
+        assert_eq!(at(2001, 3, 1, LeapPolicy::Mar1), (1, 0, 0));
+        assert_eq!(at(2001, 3, 10, LeapPolicy::Mar1), (1, 0, 9));
+        assert_eq!(at(2001, 3, 29, LeapPolicy::Mar1), (1, 1, 0));
+        assert_eq!(at(2001, 2, 28, LeapPolicy::Skip), (0, 11, 30));
+
+        // Leap years have the birthday for every policy
+        assert_eq!(at(2004, 2, 29, LeapPolicy::Mar1), (4, 0, 0));
+        assert_eq!(at(2004, 2, 28, LeapPolicy::Feb28), (3, 11, 30));
+    }
+
+    #[test]
+    fn test_age() {
+        let birth = date(1990, 1, 31);
+
+        assert_eq!(age(birth, birth).map(ymd), Some((0, 0, 0)));
+        assert_eq!(age(birth, date(1990, 1, 30)), None);
+        assert_eq!(age(birth, date(1990, 2, 28)).map(ymd), Some((0, 1, 0)));
+        assert_eq!(age(birth, date(2023, 3, 30)).map(ymd), Some((33, 1, 30)));
+
+        for on in [date(1990, 2, 28), date(2000, 2, 29), date(2023, 3, 30)] {
+            let age = age(birth, on).unwrap();
+            assert_eq!(age.to_duration().checked_add_to(birth), Some(on));
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use chrono::NaiveDate;
+
+use crate::calendar::HolidayCalendar;
+
+/// Longest run of days without a business day before a search gives up
+const MAX_NON_BUSINESS_DAYS: usize = 366;
+
+/// Whether the date is a business day of the calendar
+///
+/// ```
+/// use calends::is_business_day;
+/// use chrono::NaiveDate;
+///
+/// let holidays = vec![NaiveDate::from_ymd_opt(2022, 12, 26).unwrap()];
+///
+/// assert!(is_business_day(NaiveDate::from_ymd_opt(2022, 12, 27).unwrap(), &holidays));
+/// assert!(!is_business_day(NaiveDate::from_ymd_opt(2022, 12, 26).unwrap(), &holidays));
+/// ```
+pub fn is_business_day<C>(date: NaiveDate, calendar: &C) -> bool
+where
+    C: HolidayCalendar + ?Sized,
+{
+    calendar.is_business_day(date)
+}
+
+/// First business day after the date
+///
+/// Returns [None] if there isn't a business day within a year of the date.
+pub fn next_business_day<C>(date: NaiveDate, calendar: &C) -> Option<NaiveDate>
+where
+    C: HolidayCalendar + ?Sized,
+{
+    step_business_day(date, calendar, NaiveDate::succ_opt)
+}
+
+/// Last business day before the date
+///
+/// Returns [None] if there isn't a business day within a year of the date.
+pub fn previous_business_day<C>(date: NaiveDate, calendar: &C) -> Option<NaiveDate>
+where
+    C: HolidayCalendar + ?Sized,
+{
+    step_business_day(date, calendar, NaiveDate::pred_opt)
+}
+
+fn step_business_day<C, F>(date: NaiveDate, calendar: &C, step: F) -> Option<NaiveDate>
+where
+    C: HolidayCalendar + ?Sized,
+    F: Fn(&NaiveDate) -> Option<NaiveDate>,
+{
+    std::iter::successors(step(&date), |d| step(d))
+        .take(MAX_NON_BUSINESS_DAYS)
+        .find(|d| calendar.is_business_day(*d))
+}
+
+/// Move the date by a number of business days, backwards when negative
+///
+/// The date itself doesn't need to be a business day, so two business days after a Saturday is
+/// the Tuesday. Shifting by zero days returns the date unchanged. Returns [None] if the result is
+/// out of range or a search goes a year without a business day.
+///
+/// ```
+/// use calends::shift_business_days;
+/// use chrono::NaiveDate;
+///
+/// let holidays = vec![NaiveDate::from_ymd_opt(2022, 12, 26).unwrap()];
+/// // Friday 23rd December
+/// let trade = NaiveDate::from_ymd_opt(2022, 12, 23).unwrap();
+///
+/// assert_eq!(
+///     shift_business_days(trade, 2, &holidays),
+///     NaiveDate::from_ymd_opt(2022, 12, 28)
+/// );
+/// assert_eq!(
+///     shift_business_days(trade, -1, &holidays),
+///     NaiveDate::from_ymd_opt(2022, 12, 22)
+/// );
+/// ```
+pub fn shift_business_days<C>(date: NaiveDate, n: i32, calendar: &C) -> Option<NaiveDate>
+where
+    C: HolidayCalendar + ?Sized,
+{
+    let step = match n {
+        0.. => next_business_day,
+        _ => previous_business_day,
+    };
+
+    (0..n.unsigned_abs()).try_fold(date, |date, _| step(date, calendar))
+}
+
+/// Number of business days from the start up to the end (exclusive), negative when the end is
+/// before the start
+///
+/// ```
+/// use calends::business_days_between;
+/// use chrono::NaiveDate;
+///
+/// let holidays = vec![NaiveDate::from_ymd_opt(2022, 12, 26).unwrap()];
+/// let start = NaiveDate::from_ymd_opt(2022, 12, 19).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2023, 1, 2).unwrap();
+///
+/// assert_eq!(business_days_between(start, end, &holidays), 9);
+/// assert_eq!(business_days_between(end, start, &holidays), -9);
+/// ```
+pub fn business_days_between<C>(start: NaiveDate, end: NaiveDate, calendar: &C) -> i64
+where
+    C: HolidayCalendar + ?Sized,
+{
+    let (from, to, sign) = match start <= end {
+        true => (start, end, 1),
+        false => (end, start, -1),
+    };
+
+    let count = from
+        .iter_days()
+        .take_while(|d| *d < to)
+        .filter(|d| calendar.is_business_day(*d))
+        .count() as i64;
+
+    sign * count
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Weekday;
+
+    use super::*;
+    use crate::calendar::Workweek;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_shift_inverts() {
+        let holidays = vec![date(4, 15), date(4, 18), date(12, 26)];
+
+        for day in date(1, 1).iter_days().take(365) {
+            for n in [1, 2, 5, 20] {
+                let shifted = shift_business_days(day, n, &holidays).unwrap();
+                assert!(is_business_day(shifted, &holidays));
+                assert_eq!(
+                    business_days_between(
+                        day.succ_opt().unwrap(),
+                        shifted.succ_opt().unwrap(),
+                        &holidays
+                    ),
+                    i64::from(n)
+                );
+                if is_business_day(day, &holidays) {
+                    assert_eq!(shift_business_days(shifted, -n, &holidays), Some(day));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_workweek() {
+        let holidays: Vec<NaiveDate> = vec![];
+        let calendar = (&holidays).with_workweek(Workweek::SUNDAY_TO_THURSDAY);
+
+        // Thursday 6th January
+        assert_eq!(next_business_day(date(1, 6), &calendar), Some(date(1, 9)));
+        assert_eq!(business_days_between(date(1, 1), date(1, 8), &calendar), 5);
+        assert_eq!(
+            Workweek::new(&[
+                Weekday::Sun,
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu
+            ]),
+            Workweek::SUNDAY_TO_THURSDAY
+        );
+
+        let closed = (&holidays).with_workweek(Workweek::new(&[]));
+        assert_eq!(next_business_day(date(1, 1), &closed), None);
+        assert_eq!(
+            shift_business_days(date(1, 1), 0, &closed),
+            Some(date(1, 1))
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::iter::Peekable;
+
+use chrono::NaiveDate;
+
+use crate::{CalendarUnit, UnitKind};
+
+/// Group a sorted iterator of dates into the calendar units that contain them
+///
+/// Consecutive dates that fall within the same unit are collected together, so the input is
+/// expected to be sorted. For unsorted input see [GroupByPeriodExt::group_by_period_unsorted].
+#[derive(Debug, Clone)]
+pub struct GroupByPeriod<I>
+where
+    I: Iterator<Item = NaiveDate>,
+{
+    iter: Peekable<I>,
+    kind: UnitKind,
+}
+
+impl<I> GroupByPeriod<I>
+where
+    I: Iterator<Item = NaiveDate>,
+{
+    pub fn new(iter: I, kind: UnitKind) -> Self {
+        GroupByPeriod {
+            iter: iter.peekable(),
+            kind,
+        }
+    }
+
+    /// Count the dates in each unit instead of collecting them
+    pub fn counts(self) -> CountByPeriod<I> {
+        CountByPeriod { groups: self }
+    }
+}
+
+impl<I> Iterator for GroupByPeriod<I>
+where
+    I: Iterator<Item = NaiveDate>,
+{
+    type Item = (CalendarUnit, Vec<NaiveDate>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let kind = self.kind;
+        let first = self.iter.next()?;
+        let unit = CalendarUnit::containing(first, kind);
+        let mut dates = vec![first];
+
+        while let Some(date) = self
+            .iter
+            .next_if(|d| CalendarUnit::containing(*d, kind) == unit)
+        {
+            dates.push(date);
+        }
+
+        Some((unit, dates))
+    }
+}
+
+/// Count a sorted iterator of dates per calendar unit, see [GroupByPeriod]
+#[derive(Debug, Clone)]
+pub struct CountByPeriod<I>
+where
+    I: Iterator<Item = NaiveDate>,
+{
+    groups: GroupByPeriod<I>,
+}
+
+impl<I> Iterator for CountByPeriod<I>
+where
+    I: Iterator<Item = NaiveDate>,
+{
+    type Item = (CalendarUnit, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let kind = self.groups.kind;
+        let first = self.groups.iter.next()?;
+        let unit = CalendarUnit::containing(first, kind);
+        let mut count = 1;
+
+        while self
+            .groups
+            .iter
+            .next_if(|d| CalendarUnit::containing(*d, kind) == unit)
+            .is_some()
+        {
+            count += 1;
+        }
+
+        Some((unit, count))
+    }
+}
+
+/// Extension for grouping iterators of dates by calendar periods
+///
+/// # Example
+///
+/// ```
+/// use calends::{CalendarUnit, GroupByPeriodExt, UnitKind};
+/// use chrono::NaiveDate;
+///
+/// let dates = vec![
+///     NaiveDate::from_ymd_opt(2022, 1, 3).unwrap(),
+///     NaiveDate::from_ymd_opt(2022, 1, 20).unwrap(),
+///     NaiveDate::from_ymd_opt(2022, 3, 1).unwrap(),
+/// ];
+///
+/// let counts: Vec<_> = dates.into_iter().group_by_period(UnitKind::Month).counts().collect();
+/// assert_eq!(
+///     counts,
+///     vec![(CalendarUnit::Month(2022, 1), 2), (CalendarUnit::Month(2022, 3), 1)]
+/// );
+/// ```
+pub trait GroupByPeriodExt: Iterator<Item = NaiveDate> + Sized {
+    /// Group sorted dates by period in a streaming fashion
+    fn group_by_period(self, kind: UnitKind) -> GroupByPeriod<Self> {
+        GroupByPeriod::new(self, kind)
+    }
+
+    /// Group dates in any order by period, the groups are returned in chronological order
+    fn group_by_period_unsorted(
+        self,
+        kind: UnitKind,
+    ) -> std::collections::btree_map::IntoIter<CalendarUnit, Vec<NaiveDate>> {
+        crate::grain::bucket(self, kind).into_iter()
+    }
+}
+
+impl<I> GroupByPeriodExt for I where I: Iterator<Item = NaiveDate> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dates() -> Vec<NaiveDate> {
+        vec![
+            NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2022, 2, 14).unwrap(),
+            NaiveDate::from_ymd_opt(2022, 3, 31).unwrap(),
+            NaiveDate::from_ymd_opt(2022, 4, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_group_by_quarter() {
+        let groups: Vec<_> = dates()
+            .into_iter()
+            .group_by_period(UnitKind::Quarter)
+            .collect();
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].0, CalendarUnit::Quarter(2022, 1));
+        assert_eq!(groups[0].1.len(), 3);
+        assert_eq!(groups[1].0, CalendarUnit::Quarter(2022, 2));
+        assert_eq!(groups[2].0, CalendarUnit::Quarter(2023, 1));
+    }
+
+    #[test]
+    fn test_group_by_biweek() {
+        let counts: Vec<_> = dates()
+            .into_iter()
+            .group_by_period(UnitKind::BiWeek)
+            .counts()
+            .collect();
+
+        assert_eq!(counts[0], (CalendarUnit::BiWeek(2021, 26), 1));
+        assert_eq!(counts[2], (CalendarUnit::BiWeek(2022, 7), 2));
+    }
+
+    #[test]
+    fn test_count_by_year() {
+        let counts: Vec<_> = dates()
+            .into_iter()
+            .group_by_period(UnitKind::Year)
+            .counts()
+            .collect();
+
+        assert_eq!(
+            counts,
+            vec![(CalendarUnit::Year(2022), 4), (CalendarUnit::Year(2023), 1)]
+        );
+    }
+
+    #[test]
+    fn test_group_unsorted() {
+        let groups: Vec<_> = dates()
+            .into_iter()
+            .rev()
+            .group_by_period_unsorted(UnitKind::Half)
+            .map(|(unit, dates)| (unit, dates.len()))
+            .collect();
+
+        assert_eq!(
+            groups,
+            vec![
+                (CalendarUnit::Half(2022, 1), 4),
+                (CalendarUnit::Half(2023, 1), 1)
+            ]
+        );
+    }
+}
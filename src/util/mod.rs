@@ -1,5 +1,19 @@
+pub mod age;
+pub mod business;
+pub mod group;
+pub mod normalize;
+pub mod prorate;
 pub mod search;
 pub mod shift;
+pub mod week;
+pub mod work;
 
+pub use age::*;
+pub use business::*;
+pub use group::*;
+pub use normalize::*;
+pub use prorate::*;
 pub use search::*;
 pub use shift::*;
+pub use week::*;
+pub use work::*;
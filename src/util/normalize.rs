@@ -0,0 +1,95 @@
+//! Cleaning up lists of dates
+use chrono::NaiveDate;
+
+use crate::interval::{ClosedInterval, IntervalSet};
+
+/// The dates sorted with duplicates removed
+///
+/// ```
+/// use calends::util::normalize_dates;
+/// use chrono::NaiveDate;
+///
+/// let date = |d| NaiveDate::from_ymd_opt(2022, 1, d).unwrap();
+///
+/// assert_eq!(
+///     normalize_dates([date(3), date(1), date(3), date(2)]),
+///     [date(1), date(2), date(3)]
+/// );
+/// ```
+pub fn normalize_dates<I>(dates: I) -> Vec<NaiveDate>
+where
+    I: IntoIterator<Item = NaiveDate>,
+{
+    let mut dates: Vec<NaiveDate> = dates.into_iter().collect();
+    dates.sort_unstable();
+    dates.dedup();
+    dates
+}
+
+/// The dates as a set of intervals, each run of consecutive days becoming one interval
+///
+/// The dates can be in any order and repeat.
+///
+/// ```
+/// use calends::{interval::ClosedInterval, util::to_intervals};
+/// use chrono::NaiveDate;
+///
+/// let date = |d| NaiveDate::from_ymd_opt(2022, 1, d).unwrap();
+/// let attended = [date(4), date(3), date(5), date(10), date(4), date(11)];
+///
+/// assert_eq!(
+///     to_intervals(attended).intervals(),
+///     [
+///         ClosedInterval::exact(date(3), date(5)),
+///         ClosedInterval::exact(date(10), date(11)),
+///     ]
+/// );
+/// ```
+pub fn to_intervals<I>(dates: I) -> IntervalSet
+where
+    I: IntoIterator<Item = NaiveDate>,
+{
+    let mut set = IntervalSet::new();
+    let mut run: Option<(NaiveDate, NaiveDate)> = None;
+
+    for date in normalize_dates(dates) {
+        run = match run {
+            Some((start, end)) if end.succ_opt() == Some(date) => Some((start, date)),
+            Some((start, end)) => {
+                set.insert(&ClosedInterval::exact(start, end));
+                Some((date, date))
+            }
+            None => Some((date, date)),
+        };
+    }
+    if let Some((start, end)) = run {
+        set.insert(&ClosedInterval::exact(start, end));
+    }
+
+    set
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_intervals() {
+        assert!(to_intervals([]).is_empty());
+
+        // Runs across the end of a year and the last representable day
+        let dates = [
+            NaiveDate::from_ymd_opt(2022, 12, 31).unwrap(),
+            NaiveDate::MAX,
+            NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
+            NaiveDate::MAX.pred_opt().unwrap(),
+        ];
+        assert_eq!(
+            to_intervals(dates).intervals(),
+            [
+                ClosedInterval::exact(dates[0], dates[2]),
+                ClosedInterval::exact(dates[3], dates[1]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Splitting amounts in minor units over the days of a period
+use std::cmp::{Ordering, Reverse};
+
+use crate::interval::{
+    marker::{End, Start},
+    ClosedInterval,
+};
+
+/// How a fraction of a minor unit is rounded to a whole one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Towards negative infinity
+    Floor,
+    /// Towards positive infinity
+    Ceil,
+    /// Towards zero, dropping the fraction
+    TowardZero,
+    /// To the nearest unit with halves away from zero
+    #[default]
+    HalfUp,
+    /// To the nearest unit with halves to the even unit, also known as banker's rounding
+    HalfEven,
+}
+
+impl Rounding {
+    /// The quotient rounded to a whole number, the divisor must be positive
+    fn divide(&self, n: i128, d: i128) -> i128 {
+        let (floor, rem) = (n.div_euclid(d), n.rem_euclid(d));
+        if rem == 0 {
+            return floor;
+        }
+
+        let half = (2 * rem).cmp(&d);
+        let up = match self {
+            Rounding::Floor => false,
+            Rounding::Ceil => true,
+            Rounding::TowardZero => n < 0,
+            Rounding::HalfUp => half == Ordering::Greater || (half == Ordering::Equal && n > 0),
+            Rounding::HalfEven => {
+                half == Ordering::Greater || (half == Ordering::Equal && floor % 2 != 0)
+            }
+        };
+
+        floor + i128::from(up)
+    }
+}
+
+fn days_in(interval: &ClosedInterval) -> i64 {
+    ((interval.end() - interval.start()).num_days() + 1).max(0)
+}
+
+fn overlap_days(full_period: &ClosedInterval, partial: &ClosedInterval) -> i64 {
+    let start = full_period.start().max(partial.start());
+    let end = full_period.end().min(partial.end());
+
+    ((end - start).num_days() + 1).max(0)
+}
+
+/// The share of an amount for the days of the partial period within the full period
+///
+/// Both periods include their last day. Days of the partial period outside the full period are
+/// not charged. The arithmetic is exact before the single rounding step.
+///
+/// ```
+/// use calends::{interval::ClosedInterval, prorate, Rounding};
+/// use chrono::NaiveDate;
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// // $10.00 for April, used from the 11th
+/// let april = ClosedInterval::exact(date(4, 1), date(4, 30));
+/// let used = ClosedInterval::exact(date(4, 11), date(4, 30));
+///
+/// assert_eq!(prorate(1000, &april, &used, Rounding::HalfUp), 667);
+/// assert_eq!(prorate(1000, &april, &used, Rounding::Floor), 666);
+/// assert_eq!(prorate(-1000, &april, &used, Rounding::HalfUp), -667);
+/// ```
+pub fn prorate(
+    amount: i64,
+    full_period: &ClosedInterval,
+    partial: &ClosedInterval,
+    rounding: Rounding,
+) -> i64 {
+    let full = days_in(full_period);
+    if full == 0 {
+        return 0;
+    }
+
+    let used = overlap_days(full_period, partial);
+    rounding.divide(i128::from(amount) * i128::from(used), i128::from(full)) as i64
+}
+
+/// The amount charged for each day of the period
+///
+/// ```
+/// use calends::{daily_rate, interval::ClosedInterval, Rounding};
+/// use chrono::NaiveDate;
+///
+/// let february = ClosedInterval::exact(
+///     NaiveDate::from_ymd_opt(2022, 2, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(),
+/// );
+///
+/// assert_eq!(daily_rate(1000, &february, Rounding::HalfUp), 36);
+/// ```
+pub fn daily_rate(amount: i64, period: &ClosedInterval, rounding: Rounding) -> i64 {
+    match days_in(period) {
+        0 => 0,
+        days => rounding.divide(i128::from(amount), i128::from(days)) as i64,
+    }
+}
+
+/// Split an amount in proportion to the weights so that the shares add up to the amount
+///
+/// Each share is rounded towards zero and the units left over go one each to the shares with the
+/// largest remainders, the earlier share winning a tie. Every share is zero when the weights are.
+///
+/// ```
+/// use calends::largest_remainder;
+///
+/// assert_eq!(largest_remainder(100, &[1, 1, 1]), [34, 33, 33]);
+/// assert_eq!(largest_remainder(-10, &[2, 1]), [-7, -3]);
+/// ```
+pub fn largest_remainder(amount: i64, weights: &[u64]) -> Vec<i64> {
+    let total: u128 = weights.iter().map(|w| u128::from(*w)).sum();
+    if total == 0 {
+        return vec![0; weights.len()];
+    }
+
+    let magnitude = u128::from(amount.unsigned_abs());
+    let mut shares: Vec<(u128, u128)> = weights
+        .iter()
+        .map(|w| {
+            let n = magnitude * u128::from(*w);
+            (n / total, n % total)
+        })
+        .collect();
+
+    let allocated: u128 = shares.iter().map(|(share, _)| share).sum();
+    let mut order: Vec<usize> = (0..shares.len()).collect();
+    order.sort_by_key(|i| Reverse(shares[*i].1));
+    for i in order.into_iter().take((magnitude - allocated) as usize) {
+        shares[i].0 += 1;
+    }
+
+    shares
+        .into_iter()
+        .map(|(share, _)| match amount < 0 {
+            true => -(share as i128) as i64,
+            false => share as i64,
+        })
+        .collect()
+}
+
+/// Split an amount for the full period over parts of it by their days, see [largest_remainder]
+///
+/// Parts which cover the full period exactly once share the whole amount, so the shares always
+/// add up to it. Days of the full period outside every part keep their share of the amount.
+///
+/// ```
+/// use calends::{interval::ClosedInterval, prorate_split};
+/// use chrono::NaiveDate;
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// let quarter = ClosedInterval::exact(date(1, 1), date(3, 31));
+/// let months = [
+///     ClosedInterval::exact(date(1, 1), date(1, 31)),
+///     ClosedInterval::exact(date(2, 1), date(2, 28)),
+///     ClosedInterval::exact(date(3, 1), date(3, 31)),
+/// ];
+///
+/// let shares = prorate_split(10000, &quarter, &months);
+/// assert_eq!(shares, [3445, 3111, 3444]);
+/// assert_eq!(shares.iter().sum::<i64>(), 10000);
+/// ```
+pub fn prorate_split(
+    amount: i64,
+    full_period: &ClosedInterval,
+    parts: &[ClosedInterval],
+) -> Vec<i64> {
+    let mut weights: Vec<u64> = parts
+        .iter()
+        .map(|part| overlap_days(full_period, part) as u64)
+        .collect();
+    let covered: u64 = weights.iter().sum();
+    weights.push((days_in(full_period) as u64).saturating_sub(covered));
+
+    let mut shares = largest_remainder(amount, &weights);
+    shares.pop();
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn interval(start: (u32, u32), end: (u32, u32)) -> ClosedInterval {
+        let date = |(m, d)| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+        ClosedInterval::exact(date(start), date(end))
+    }
+
+    #[test]
+    fn test_rounding() {
+        let round =
+            |rounding: Rounding| [25, -25, 35, -35, 31, -31].map(|n| rounding.divide(n, 10));
+
+        assert_eq!(round(Rounding::Floor), [2, -3, 3, -4, 3, -4]);
+        assert_eq!(round(Rounding::Ceil), [3, -2, 4, -3, 4, -3]);
+        assert_eq!(round(Rounding::TowardZero), [2, -2, 3, -3, 3, -3]);
+        assert_eq!(round(Rounding::HalfUp), [3, -3, 4, -4, 3, -3]);
+        assert_eq!(round(Rounding::HalfEven), [2, -2, 4, -4, 3, -3]);
+    }
+
+    #[test]
+    fn test_prorate() {
+        let june = interval((6, 1), (6, 30));
+
+        assert_eq!(prorate(3000, &june, &june, Rounding::Floor), 3000);
+        assert_eq!(
+            prorate(3000, &june, &interval((5, 20), (6, 10)), Rounding::Floor),
+            1000
+        );
+        assert_eq!(
+            prorate(3000, &june, &interval((7, 1), (7, 5)), Rounding::Ceil),
+            0
+        );
+        assert_eq!(
+            prorate(i64::MIN, &june, &june, Rounding::HalfEven),
+            i64::MIN
+        );
+        assert_eq!(daily_rate(-1000, &june, Rounding::TowardZero), -33);
+    }
+
+    #[test]
+    fn test_splits_add_up() {
+        let june = interval((6, 1), (6, 30));
+        let weeks = [
+            interval((6, 1), (6, 7)),
+            interval((6, 8), (6, 14)),
+            interval((6, 15), (6, 21)),
+            interval((6, 22), (6, 30)),
+        ];
+
+        for amount in [1, 999, -1001, i64::MAX, i64::MIN] {
+            assert_eq!(
+                prorate_split(amount, &june, &weeks).iter().sum::<i64>(),
+                amount
+            );
+        }
+
+        // The uncovered days keep their share
+        let halves = prorate_split(1000, &june, &weeks[..2]);
+        assert_eq!(halves, [234, 233]);
+        assert_eq!(largest_remainder(5, &[0, 0]), [0, 0]);
+        assert_eq!(largest_remainder(5, &[]), Vec::<i64>::new());
+    }
+}
@@ -1,47 +1,290 @@
 use chrono::{Datelike, Duration, NaiveDate, Weekday};
 
-use crate::shift;
+use crate::{grain::Grain, shift, WeekStart};
 
-// Borrowed from bdays
-pub fn days_in_month(year: i32, month: u32) -> u32 {
-    NaiveDate::from_ymd_opt(
-        match month {
-            12 => year + 1,
-            _ => year,
-        },
-        match month {
-            12 => 1,
-            _ => month + 1,
-        },
-        1,
-    )
-    .unwrap()
-    .signed_duration_since(NaiveDate::from_ymd_opt(year, month, 1).unwrap())
-    .num_days() as u32
+/// Whether the year of the proleptic Gregorian calendar is a leap year
+///
+/// ```
+/// use calends::is_leap_year;
+///
+/// assert!(is_leap_year(2024));
+/// assert!(!is_leap_year(1900));
+/// assert!(is_leap_year(2000));
+/// ```
+#[inline]
+pub fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
 }
 
-pub fn find_weekday_ascending(weekday: Weekday, yy: i32, mm: u32, occurrence: u32) -> NaiveDate {
-    let anchor = NaiveDate::from_ymd_opt(yy, mm, 1).unwrap();
-    let mut offset = (weekday.number_from_monday() + 7 - anchor.weekday().number_from_monday()) % 7;
+/// Number of days in the year, 365 or 366
+#[inline]
+pub fn days_in_year(year: i32) -> u32 {
+    match is_leap_year(year) {
+        true => 366,
+        false => 365,
+    }
+}
+
+/// Number of days in the month, for any year even outside the range of [NaiveDate]
+///
+/// # Panics
+///
+/// Panics when the month is not from 1 to 12, see [checked_days_in_month]
+#[inline]
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    checked_days_in_month(year, month).expect("month out of range")
+}
 
-    if occurrence > 1 {
-        offset += 7 * (occurrence - 1);
+/// Number of days in the month, returning [None] when the month is not from 1 to 12
+///
+/// ```
+/// use calends::checked_days_in_month;
+///
+/// assert_eq!(checked_days_in_month(2024, 2), Some(29));
+/// assert_eq!(checked_days_in_month(262143, 12), Some(31));
+/// assert_eq!(checked_days_in_month(2024, 13), None);
+/// ```
+pub fn checked_days_in_month(year: i32, month: u32) -> Option<u32> {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => Some(31),
+        4 | 6 | 9 | 11 => Some(30),
+        2 if is_leap_year(year) => Some(29),
+        2 => Some(28),
+        _ => None,
     }
+}
 
-    anchor + Duration::days(offset as i64)
+pub fn find_weekday_ascending(weekday: Weekday, yy: i32, mm: u32, occurrence: u32) -> NaiveDate {
+    let anchor = NaiveDate::from_ymd_opt(yy, mm, 1).unwrap();
+    anchor + Duration::days(weekday_offset_ascending(weekday, anchor, occurrence))
 }
 
 pub fn find_weekday_descending(weekday: Weekday, yy: i32, mm: u32, occurrence: u32) -> NaiveDate {
     let anchor = month_end(yy, mm);
-    let mut offset = (anchor.weekday().number_from_monday() + 7 - weekday.number_from_monday()) % 7;
+    anchor - Duration::days(weekday_offset_descending(weekday, anchor, occurrence))
+}
+// End Borrowed
+
+fn weekday_offset_ascending(weekday: Weekday, anchor: NaiveDate, occurrence: u32) -> i64 {
+    let offset = (weekday.number_from_monday() + 7 - anchor.weekday().number_from_monday()) % 7;
+    offset as i64 + 7 * occurrence.saturating_sub(1) as i64
+}
+
+fn weekday_offset_descending(weekday: Weekday, anchor: NaiveDate, occurrence: u32) -> i64 {
+    let offset = (anchor.weekday().number_from_monday() + 7 - weekday.number_from_monday()) % 7;
+    offset as i64 + 7 * occurrence.saturating_sub(1) as i64
+}
+
+/// Find the nth weekday between two dates (inclusive)
+///
+/// Positive occurrences count from the start e.g. `1` is the first, negative occurrences count
+/// from the end e.g. `-1` is the last. Returns [None] for a zero occurrence or when the span does
+/// not contain enough of the weekday.
+///
+/// ```
+/// use calends::find_weekday_between;
+/// use chrono::{NaiveDate, Weekday};
+///
+/// let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2022, 3, 31).unwrap();
+///
+/// assert_eq!(
+///     find_weekday_between(Weekday::Fri, start, end, -1),
+///     NaiveDate::from_ymd_opt(2022, 3, 25)
+/// );
+/// assert_eq!(find_weekday_between(Weekday::Fri, start, end, 14), None);
+/// ```
+pub fn find_weekday_between(
+    weekday: Weekday,
+    start: NaiveDate,
+    end: NaiveDate,
+    occurrence: i32,
+) -> Option<NaiveDate> {
+    let found = match occurrence {
+        0 => return None,
+        1.. => start.checked_add_signed(Duration::days(weekday_offset_ascending(
+            weekday,
+            start,
+            occurrence.unsigned_abs(),
+        )))?,
+        _ => end.checked_sub_signed(Duration::days(weekday_offset_descending(
+            weekday,
+            end,
+            occurrence.unsigned_abs(),
+        )))?,
+    };
+
+    (start <= found && found <= end).then_some(found)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WeekdaySearchError {
+    #[error("{0}-{1:0>2} is not a valid month")]
+    InvalidMonth(i32, u32),
+    #[error("{year}-{month:0>2} does not have a {weekday} number {occurrence}")]
+    OccurrenceOutOfRange {
+        year: i32,
+        month: u32,
+        weekday: Weekday,
+        occurrence: u32,
+    },
+}
+
+/// The nth weekday of the month, e.g. the 4th Thursday of November
+///
+/// Unlike [find_weekday_ascending] an occurrence past the end of the month is an error rather than
+/// a date in the following month.
+///
+/// ```
+/// use calends::{nth_weekday_of_month, WeekdaySearchError};
+/// use chrono::{NaiveDate, Weekday};
+///
+/// assert_eq!(
+///     nth_weekday_of_month(2022, 11, 4, Weekday::Thu),
+///     Ok(NaiveDate::from_ymd_opt(2022, 11, 24).unwrap())
+/// );
+/// assert!(matches!(
+///     nth_weekday_of_month(2022, 11, 5, Weekday::Thu),
+///     Err(WeekdaySearchError::OccurrenceOutOfRange { .. })
+/// ));
+/// ```
+pub fn nth_weekday_of_month(
+    year: i32,
+    month: u32,
+    n: u32,
+    weekday: Weekday,
+) -> Result<NaiveDate, WeekdaySearchError> {
+    let (start, end) = month_bounds(year, month)?;
+
+    i32::try_from(n)
+        .ok()
+        .and_then(|n| find_weekday_between(weekday, start, end, n))
+        .ok_or(WeekdaySearchError::OccurrenceOutOfRange {
+            year,
+            month,
+            weekday,
+            occurrence: n,
+        })
+}
+
+/// The last weekday of the month, e.g. the last Monday of May
+///
+/// ```
+/// use calends::last_weekday_of_month;
+/// use chrono::{NaiveDate, Weekday};
+///
+/// assert_eq!(
+///     last_weekday_of_month(2022, 5, Weekday::Mon),
+///     Ok(NaiveDate::from_ymd_opt(2022, 5, 30).unwrap())
+/// );
+/// ```
+pub fn last_weekday_of_month(
+    year: i32,
+    month: u32,
+    weekday: Weekday,
+) -> Result<NaiveDate, WeekdaySearchError> {
+    let (start, end) = month_bounds(year, month)?;
+
+    Ok(find_weekday_between(weekday, start, end, -1).expect("every month has each weekday"))
+}
 
-    if occurrence > 1 {
-        offset += 7 * (occurrence - 1);
+fn month_bounds(year: i32, month: u32) -> Result<(NaiveDate, NaiveDate), WeekdaySearchError> {
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .zip(checked_month_end(year, month))
+        .ok_or(WeekdaySearchError::InvalidMonth(year, month))
+}
+
+/// The closest day from Monday to Friday, Saturdays move to the Friday before and Sundays to the
+/// Monday after
+///
+/// ```
+/// use calends::nearest_weekday;
+/// use chrono::NaiveDate;
+///
+/// // 2022-07-04 is a Monday
+/// let date = |d| NaiveDate::from_ymd_opt(2022, 7, d).unwrap();
+///
+/// assert_eq!(nearest_weekday(date(4)), Some(date(4)));
+/// assert_eq!(nearest_weekday(date(2)), Some(date(1)));
+/// assert_eq!(nearest_weekday(date(3)), Some(date(4)));
+/// ```
+pub fn nearest_weekday(date: NaiveDate) -> Option<NaiveDate> {
+    match date.weekday() {
+        Weekday::Sat => date.pred_opt(),
+        Weekday::Sun => date.succ_opt(),
+        _ => Some(date),
     }
+}
 
-    anchor - Duration::days(offset as i64)
+/// The first date after the date falling on the weekday, a week later if the date already falls on
+/// it
+///
+/// ```
+/// use calends::next_weekday;
+/// use chrono::{NaiveDate, Weekday};
+///
+/// // 2022-07-04 is a Monday
+/// let date = |d| NaiveDate::from_ymd_opt(2022, 7, d).unwrap();
+///
+/// assert_eq!(next_weekday(date(4), Weekday::Fri), Some(date(8)));
+/// assert_eq!(next_weekday(date(4), Weekday::Mon), Some(date(11)));
+/// ```
+pub fn next_weekday(date: NaiveDate, weekday: Weekday) -> Option<NaiveDate> {
+    let days = 7 - date.weekday().days_since(weekday);
+    date.checked_add_signed(Duration::days(days.into()))
+}
+
+/// Week of the month from 1 to 6, weeks start on Monday and the first week is the one containing
+/// the 1st of the month
+///
+/// ```
+/// use calends::week_of_month;
+/// use chrono::NaiveDate;
+///
+/// // 2022-05-01 is a Sunday
+/// let date = |d| NaiveDate::from_ymd_opt(2022, 5, d).unwrap();
+///
+/// assert_eq!(week_of_month(date(1)), 1);
+/// assert_eq!(week_of_month(date(2)), 2);
+/// assert_eq!(week_of_month(date(31)), 6);
+/// ```
+pub fn week_of_month(date: NaiveDate) -> u32 {
+    let first = date.with_day(1).expect("first of the month is in range");
+    (date.day0() + first.weekday().num_days_from_monday()) / 7 + 1
+}
+
+/// Which occurrence of its weekday the date is within its month, 3 for the 3rd Tuesday
+///
+/// ```
+/// use calends::occurrence_of_weekday_in_month;
+/// use chrono::NaiveDate;
+///
+/// // The 3rd Tuesday of May 2022
+/// let date = NaiveDate::from_ymd_opt(2022, 5, 17).unwrap();
+///
+/// assert_eq!(occurrence_of_weekday_in_month(date), 3);
+/// ```
+pub fn occurrence_of_weekday_in_month(date: NaiveDate) -> u32 {
+    date.day0() / 7 + 1
+}
+
+/// Days left in the period of the grain after the date, 0 on the last day of the period
+///
+/// Periods ending after [NaiveDate::MAX] are cut short at it.
+///
+/// ```
+/// use calends::{days_remaining_in, grain::Grain};
+/// use chrono::NaiveDate;
+///
+/// let date = NaiveDate::from_ymd_opt(2022, 2, 20).unwrap();
+///
+/// assert_eq!(days_remaining_in(Grain::Month, date), 8);
+/// assert_eq!(days_remaining_in(Grain::Year, date), 314);
+/// assert_eq!(days_remaining_in(Grain::Day, date), 0);
+/// ```
+pub fn days_remaining_in(grain: Grain, date: NaiveDate) -> u32 {
+    (grain.end_of(date) - date).num_days() as u32
 }
-// End Borrowed
 
 /// Weeks in year
 pub fn weeks_in_year(date: &NaiveDate) -> u32 {
@@ -57,19 +300,15 @@ pub fn quarter_month(date: &NaiveDate) -> u32 {
     1 + 3 * ((date.month() - 1) / 3)
 }
 
+/// Last day of the month, returning [None] if the date is out of range
 #[inline]
-pub fn month_end(mut yy: i32, mut mm: u32) -> NaiveDate {
-    if mm == 12 {
-        yy += 1;
-        mm = 1;
-    } else {
-        mm += 1;
-    }
+pub fn checked_month_end(yy: i32, mm: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(yy, mm, checked_days_in_month(yy, mm)?)
+}
 
-    NaiveDate::from_ymd_opt(yy, mm, 1)
-        .unwrap()
-        .pred_opt()
-        .unwrap()
+#[inline]
+pub fn month_end(yy: i32, mm: u32) -> NaiveDate {
+    checked_month_end(yy, mm).unwrap()
 }
 
 #[inline]
@@ -82,6 +321,35 @@ pub fn beginning_of_year(d: &NaiveDate) -> NaiveDate {
     NaiveDate::from_ymd_opt(d.year(), 1, 1).unwrap()
 }
 
+/// Beginning of a half year, either January 1 or July 1
+#[inline]
+pub fn beginning_of_half(d: &NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd_opt(d.year(), 1 + 6 * (d.month0() / 6), 1).unwrap()
+}
+
+/// Beginning of an ISO week-based year, the Monday of its first week
+///
+/// ```
+/// use calends::{beginning_of_iso_year, end_of_iso_year};
+/// use chrono::NaiveDate;
+///
+/// // 2021-01-01 belongs to the last week of 2020
+/// let date = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+///
+/// assert_eq!(
+///     beginning_of_iso_year(&date),
+///     NaiveDate::from_ymd_opt(2019, 12, 30).unwrap()
+/// );
+/// assert_eq!(
+///     end_of_iso_year(&date),
+///     NaiveDate::from_ymd_opt(2021, 1, 3).unwrap()
+/// );
+/// ```
+#[inline]
+pub fn beginning_of_iso_year(d: &NaiveDate) -> NaiveDate {
+    NaiveDate::from_isoywd_opt(d.iso_week().year(), 1, Weekday::Mon).unwrap()
+}
+
 #[inline]
 pub fn beginning_of_month(d: &NaiveDate) -> NaiveDate {
     NaiveDate::from_ymd_opt(d.year(), d.month(), 1).unwrap()
@@ -92,11 +360,12 @@ pub fn beginning_of_month(d: &NaiveDate) -> NaiveDate {
 /// Biweek 1: week 1 - week 2
 /// Biweek 26: week 51 - week 52
 ///
-/// N.B. This makes the assumption that weekdays start on Monday
+/// N.B. This makes the assumption that weekdays start on Monday, see
+/// [beginning_of_biweek_anchored] for biweeks following another cycle such as a payroll
 ///
 #[inline]
 pub fn beginning_of_biweek(d: &NaiveDate) -> NaiveDate {
-    let beginning = if d.iso_week().week() % 2 == 0 {
+    let beginning = if d.iso_week().week().is_multiple_of(2) {
         NaiveDate::from_isoywd_opt(d.iso_week().year(), d.iso_week().week(), Weekday::Mon).unwrap()
             - Duration::weeks(1)
     } else {
@@ -122,23 +391,89 @@ pub fn beginning_of_week(d: &NaiveDate) -> NaiveDate {
     NaiveDate::from_isoywd_opt(d.iso_week().year(), d.iso_week().week(), Weekday::Mon).unwrap()
 }
 
+/// Beginning of a biweek in a cycle of biweeks, one of which starts on the anchor
+///
+/// Returns [None] if the biweek starts before [NaiveDate::MIN].
+///
+/// ```
+/// use calends::{beginning_of_biweek_anchored, end_of_biweek_anchored};
+/// use chrono::NaiveDate;
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// // The first payday of the company
+/// let anchor = date(1, 7);
+///
+/// assert_eq!(beginning_of_biweek_anchored(&date(3, 1), anchor), Some(date(2, 18)));
+/// assert_eq!(end_of_biweek_anchored(&date(3, 1), anchor), Some(date(3, 3)));
+/// // Biweeks before the anchor follow the same cycle
+/// assert_eq!(
+///     beginning_of_biweek_anchored(&date(1, 6), anchor),
+///     NaiveDate::from_ymd_opt(2021, 12, 24)
+/// );
+/// ```
+#[inline]
+pub fn beginning_of_biweek_anchored(d: &NaiveDate, anchor: NaiveDate) -> Option<NaiveDate> {
+    d.checked_sub_signed(Duration::days((*d - anchor).num_days().rem_euclid(14)))
+}
+
+/// End of a biweek in a cycle of biweeks, one of which starts on the anchor
+///
+/// Returns [None] if the biweek starts before [NaiveDate::MIN] or ends after [NaiveDate::MAX].
+#[inline]
+pub fn end_of_biweek_anchored(d: &NaiveDate, anchor: NaiveDate) -> Option<NaiveDate> {
+    beginning_of_biweek_anchored(d, anchor)?.checked_add_signed(Duration::days(13))
+}
+
+/// Beginning of a week which starts on another day than Monday
+///
+/// ```
+/// use calends::{beginning_of_week_starting, end_of_week_starting, WeekStart};
+/// use chrono::NaiveDate;
+///
+/// // 2022-01-01 is a Saturday
+/// let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+///
+/// assert_eq!(beginning_of_week_starting(&date, WeekStart::SATURDAY), date);
+/// assert_eq!(
+///     end_of_week_starting(&date, WeekStart::SUNDAY),
+///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()
+/// );
+/// ```
+#[inline]
+pub fn beginning_of_week_starting(d: &NaiveDate, start: WeekStart) -> NaiveDate {
+    *d - Duration::days(start.days_from_start(d.weekday()).into())
+}
+
 #[inline]
 pub fn end_of_year(d: &NaiveDate) -> NaiveDate {
     NaiveDate::from_ymd_opt(d.year(), 12, 31).unwrap()
 }
 
+/// End of a half year, either June 30 or December 31
+#[inline]
+pub fn end_of_half(d: &NaiveDate) -> NaiveDate {
+    month_end(d.year(), 6 + 6 * (d.month0() / 6))
+}
+
+/// End of an ISO week-based year, the Sunday of its last week
+#[inline]
+pub fn end_of_iso_year(d: &NaiveDate) -> NaiveDate {
+    let year = d.iso_week().year();
+    NaiveDate::from_isoywd_opt(year, 53, Weekday::Sun)
+        .or_else(|| NaiveDate::from_isoywd_opt(year, 52, Weekday::Sun))
+        .unwrap()
+}
+
 #[inline]
 pub fn end_of_quarter(d: &NaiveDate) -> NaiveDate {
-    shift::shift_quarters(*d, 1).pred_opt().unwrap()
+    shift::shift_quarters(beginning_of_quarter(d), 1)
+        .pred_opt()
+        .unwrap()
 }
 
 #[inline]
 pub fn end_of_month(d: &NaiveDate) -> NaiveDate {
-    let month = d.month();
-    let year = d.year();
-    let days_in_month = days_in_month(year, month);
-
-    NaiveDate::from_ymd_opt(year, month, days_in_month).unwrap()
+    month_end(d.year(), d.month())
 }
 
 #[inline]
@@ -153,6 +488,12 @@ pub fn end_of_week(d: &NaiveDate) -> NaiveDate {
     NaiveDate::from_isoywd_opt(d.iso_week().year(), d.iso_week().week(), Weekday::Sun).unwrap()
 }
 
+/// End of a week which starts on another day than Monday
+#[inline]
+pub fn end_of_week_starting(d: &NaiveDate, start: WeekStart) -> NaiveDate {
+    *d + Duration::days((6 - start.days_from_start(d.weekday())).into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,12 +511,146 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_biweek_anchored_range() {
+        let anchor = NaiveDate::from_ymd_opt(2022, 1, 7).unwrap();
+
+        for date in [NaiveDate::MIN, NaiveDate::MAX] {
+            let start = beginning_of_biweek_anchored(&date, anchor);
+            let end = end_of_biweek_anchored(&date, anchor);
+            assert!(start.is_none_or(|start| start <= date));
+            assert!(end.is_none_or(|end| date <= end));
+        }
+
+        let second = NaiveDate::MIN.succ_opt().unwrap();
+        assert_eq!(beginning_of_biweek_anchored(&second, second), Some(second));
+        assert_eq!(beginning_of_biweek_anchored(&NaiveDate::MIN, second), None);
+        assert_eq!(
+            end_of_biweek_anchored(&NaiveDate::MAX, NaiveDate::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_end_of_quarter() {
+        assert_eq!(
+            end_of_quarter(&NaiveDate::from_ymd_opt(2022, 2, 15).unwrap()),
+            NaiveDate::from_ymd_opt(2022, 3, 31).unwrap()
+        );
+        assert_eq!(
+            end_of_quarter(&NaiveDate::from_ymd_opt(2022, 12, 31).unwrap()),
+            NaiveDate::from_ymd_opt(2022, 12, 31).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_month_lengths() {
+        for year in [-400, -1, 0, 1900, 2000, 2023, 2024] {
+            let days: u32 = (1..=12).map(|month| days_in_month(year, month)).sum();
+            assert_eq!(days, days_in_year(year));
+
+            if let Some(first) = NaiveDate::from_yo_opt(year, 1) {
+                assert_eq!(is_leap_year(year), first.leap_year());
+            }
+        }
+
+        assert_eq!(days_in_month(NaiveDate::MAX.year(), 12), 31);
+        assert_eq!(checked_days_in_month(2022, 0), None);
+    }
+
+    #[test]
+    fn test_week_of_month() {
+        for date in NaiveDate::from_ymd_opt(2022, 1, 1)
+            .unwrap()
+            .iter_days()
+            .take(400)
+        {
+            let first = date.with_day(1).unwrap();
+            let weeks = (date - beginning_of_week(&first)).num_days() / 7 + 1;
+            assert_eq!(i64::from(week_of_month(date)), weeks);
+
+            let occurrence = occurrence_of_weekday_in_month(date);
+            assert_eq!(
+                nth_weekday_of_month(date.year(), date.month(), occurrence, date.weekday()),
+                Ok(date)
+            );
+            assert_eq!(
+                i64::from(days_remaining_in(Grain::Month, date)),
+                (end_of_month(&date) - date).num_days()
+            );
+        }
+    }
+
+    #[test]
+    fn test_half_and_iso_year() {
+        for day in NaiveDate::from_ymd_opt(2020, 1, 1)
+            .unwrap()
+            .iter_days()
+            .take(800)
+        {
+            let half = beginning_of_half(&day);
+            assert!(half <= day && day <= end_of_half(&day));
+            assert_eq!(end_of_half(&day).succ_opt().unwrap().day(), 1);
+            assert!([1, 7].contains(&half.month()));
+
+            let start = beginning_of_iso_year(&day);
+            let end = end_of_iso_year(&day);
+            assert!(start <= day && day <= end);
+            assert!([364, 371].contains(&((end - start).num_days() + 1)));
+            assert_eq!(end.succ_opt().unwrap().iso_week().week(), 1);
+        }
+    }
+
+    #[test]
+    fn test_week_starting() {
+        let date = NaiveDate::from_ymd_opt(2022, 1, 5).unwrap();
+
+        for start in [Weekday::Mon, Weekday::Wed, Weekday::Thu, Weekday::Sun] {
+            let start = WeekStart::new(start);
+            let beginning = beginning_of_week_starting(&date, start);
+            let end = end_of_week_starting(&date, start);
+
+            assert_eq!(beginning.weekday(), start.weekday());
+            assert_eq!(end.weekday(), start.last_weekday());
+            assert_eq!((end - beginning).num_days(), 6);
+            assert!(beginning <= date && date <= end);
+        }
+
+        assert_eq!(
+            beginning_of_week_starting(&date, WeekStart::MONDAY),
+            beginning_of_week(&date)
+        );
+        assert_eq!(
+            end_of_week_starting(&date, WeekStart::MONDAY),
+            end_of_week(&date)
+        );
+    }
+
+    #[test]
+    fn test_nth_weekday_of_month() {
+        for month in 1..=12 {
+            let last = last_weekday_of_month(2022, month, Weekday::Wed).unwrap();
+            let fourth = nth_weekday_of_month(2022, month, 4, Weekday::Wed).unwrap();
+
+            match nth_weekday_of_month(2022, month, 5, Weekday::Wed) {
+                Ok(fifth) => assert_eq!(fifth, last),
+                Err(_) => assert_eq!(fourth, last),
+            }
+            assert_eq!(last.month(), month);
+        }
+
+        assert_eq!(
+            nth_weekday_of_month(2022, 13, 1, Weekday::Wed),
+            Err(WeekdaySearchError::InvalidMonth(2022, 13))
+        );
+        assert!(nth_weekday_of_month(2022, 1, 0, Weekday::Wed).is_err());
+    }
+
     #[quickcheck]
     fn test_add_month_quickcheck(d: NaiveDateWrapper) {
         shift::shift_months(d.0, 1);
     }
 
-    #[ignore = "broken af"]
     #[quickcheck]
     fn test_add_quarter_quickcheck(d: NaiveDateWrapper) {
         shift::shift_quarters(d.0, 1);
@@ -188,27 +663,11 @@ mod tests {
 
     impl Arbitrary for NaiveDateWrapper {
         fn arbitrary(g: &mut Gen) -> NaiveDateWrapper {
-            #[allow(clippy::min_max)]
-            let year = std::cmp::max(std::cmp::min(i32::arbitrary(g), 1584), 2800);
+            let year = 1584 + (u32::arbitrary(g) % 1216) as i32;
             let month = 1 + u32::arbitrary(g) % 12;
-            let day = 1 + u32::arbitrary(g) % 31;
-
-            let first_date = NaiveDate::from_ymd_opt(year, month, day);
-            if day > 27 {
-                let result = vec![
-                    first_date,
-                    NaiveDate::from_ymd_opt(year, month, day - 1),
-                    NaiveDate::from_ymd_opt(year, month, day - 2),
-                ]
-                .into_iter()
-                .flatten()
-                .next()
-                .unwrap();
-
-                NaiveDateWrapper(result)
-            } else {
-                NaiveDateWrapper(first_date.unwrap())
-            }
+            let day = 1 + u32::arbitrary(g) % days_in_month(year, month);
+
+            NaiveDateWrapper(NaiveDate::from_ymd_opt(year, month, day).unwrap())
         }
     }
 }
@@ -51,48 +51,123 @@ use crate::util;
 ///
 #[inline]
 pub fn shift_months(date: NaiveDate, months: i32) -> NaiveDate {
-    let mut year = date.year() + (date.month() as i32 + months) / 12;
-    let mut month = (date.month() as i32 + months) % 12;
+    shift_months_with_report(date, months).0
+}
+
+/// Shift a month duration to the current date, see [shift_months]
+///
+/// Returns [None] if the resulting date is out of range
+#[inline]
+pub fn checked_shift_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    checked_shift_months_with_report(date, months).map(|(date, _)| date)
+}
+
+/// The adjustment that was made to the day of the month when shifting by months
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MonthAdjustment {
+    /// The day of the month was kept as is
+    None,
+    /// The day does not exist in the target month so it was clamped to the last day of the month
+    ClampedToMonthEnd,
+    /// The date was the last day of its month so it was moved to the last day of the target month
+    PinnedToMonthEnd,
+}
+
+/// Shift a date by months and report what adjustment happened to the day of the month
+///
+/// This follows the same rules as [shift_months] and allows surfacing messages such as "we moved
+/// your date from the 31st to the 30th".
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// use calends::util::{shift_months_with_report, MonthAdjustment};
+///
+/// assert_eq!(
+///     shift_months_with_report(NaiveDate::from_ymd_opt(2022, 1, 30).unwrap(), 1),
+///     (NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(), MonthAdjustment::ClampedToMonthEnd)
+/// );
+/// assert_eq!(
+///     shift_months_with_report(NaiveDate::from_ymd_opt(2022, 4, 30).unwrap(), 1),
+///     (NaiveDate::from_ymd_opt(2022, 5, 31).unwrap(), MonthAdjustment::PinnedToMonthEnd)
+/// );
+/// assert_eq!(
+///     shift_months_with_report(NaiveDate::from_ymd_opt(2022, 4, 15).unwrap(), 1),
+///     (NaiveDate::from_ymd_opt(2022, 5, 15).unwrap(), MonthAdjustment::None)
+/// );
+/// ```
+pub fn shift_months_with_report(date: NaiveDate, months: i32) -> (NaiveDate, MonthAdjustment) {
+    checked_shift_months_with_report(date, months).expect("date out of range")
+}
+
+/// Shift a date by months and report the adjustment, see [shift_months_with_report]
+///
+/// Returns [None] if the resulting date is out of range
+pub fn checked_shift_months_with_report(
+    date: NaiveDate,
+    months: i32,
+) -> Option<(NaiveDate, MonthAdjustment)> {
+    let total = (date.month() as i32).checked_add(months)?;
+    let mut year = date.year().checked_add(total / 12)?;
+    let mut month = total % 12;
 
     if month < 1 {
         year -= 1;
         month += 12;
     }
 
-    let date_end_of_month = util::month_end(date.year(), date.month());
-    let day = if date_end_of_month.day() == date.day() {
+    let date_end_of_month = util::checked_month_end(date.year(), date.month())?;
+    let target_end_of_month = util::checked_month_end(year, month as u32)?.day();
+    let (day, adjustment) = if date_end_of_month.day() == date.day() {
         // if the current date is the last date of the month, the next month will need to be the
         // last date as well
-        util::month_end(year, month as u32).day()
-    } else {
+        if target_end_of_month == date.day() {
+            (target_end_of_month, MonthAdjustment::None)
+        } else {
+            (target_end_of_month, MonthAdjustment::PinnedToMonthEnd)
+        }
+    } else if date.day() > target_end_of_month {
         // get the maximum of the month and clamp it to that, we cannot exceed the end of the current
         // month
-        std::cmp::min(date.day(), util::month_end(year, month as u32).day())
+        (target_end_of_month, MonthAdjustment::ClampedToMonthEnd)
+    } else {
+        (date.day(), MonthAdjustment::None)
     };
-    NaiveDate::from_ymd_opt(year, month as u32, day).unwrap()
+
+    Some((
+        NaiveDate::from_ymd_opt(year, month as u32, day)?,
+        adjustment,
+    ))
 }
 
-/// Add a quarter to the date supplied
-///
-/// A quarter refers to one-fourth of a year and is typically expressed as Q1 for the first
-/// quarter, etc., and can be expressed with the year, such as Q1 2021 (or Q121).
+/// Shift the date by quarters of three months, backwards when negative
 ///
-/// If the current date falls in the last quarter of the year, this will shift to the first quarter
-/// of the next year.
+/// The day of the month follows the same rules as [shift_months], so the end of a month stays at
+/// the end of a month.
 ///
 /// # Examples
 ///
-/// ```ignore
+/// ```
 /// # use chrono::NaiveDate;
-/// # use dateutil::addition;
+/// # use calends::shift_quarters;
 ///
-/// assert_eq!(addition::add_quarter_duration(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()), NaiveDate::from_ymd_opt(2022, 4, 1).unwrap());
-/// assert_eq!(addition::add_quarter_duration(NaiveDate::from_ymd_opt(2022, 11, 3).unwrap()), NaiveDate::from_ymd_opt(2023, 2, 3).unwrap());
+/// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
 ///
+/// assert_eq!(shift_quarters(date(2022, 1, 1), 1), date(2022, 4, 1));
+/// assert_eq!(shift_quarters(date(2022, 11, 3), 1), date(2023, 2, 3));
+/// assert_eq!(shift_quarters(date(2022, 2, 15), -5), date(2020, 11, 15));
+/// assert_eq!(shift_quarters(date(2022, 5, 31), 3), date(2023, 2, 28));
 /// ```
 #[inline]
 pub fn shift_quarters(date: NaiveDate, quarters: i32) -> NaiveDate {
-    shift_months(date, 3 * quarters)
+    checked_shift_quarters(date, quarters).expect("date out of range")
+}
+
+/// Shift the date by quarters, returning [None] if the resulting date is out of range
+#[inline]
+pub fn checked_shift_quarters(date: NaiveDate, quarters: i32) -> Option<NaiveDate> {
+    checked_shift_months(date, quarters.checked_mul(3)?)
 }
 
 /// Adds a year to the current date
@@ -112,7 +187,171 @@ pub fn shift_quarters(date: NaiveDate, quarters: i32) -> NaiveDate {
 /// ```
 #[inline]
 pub fn shift_years(date: NaiveDate, years: i32) -> NaiveDate {
-    shift_months(date, years * 12)
+    checked_shift_years(date, years).expect("date out of range")
+}
+
+/// Shift the date by years, returning [None] if the resulting date is out of range
+#[inline]
+pub fn checked_shift_years(date: NaiveDate, years: i32) -> Option<NaiveDate> {
+    checked_shift_months(date, years.checked_mul(12)?)
+}
+
+/// How the day of the month is handled when shifting by months
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ShiftPolicy {
+    /// Clamp days past the end of the target month and keep the last day of a month at the end,
+    /// the behavior of [shift_months]
+    #[default]
+    Clamp,
+    /// Keep the day of the month as is and fail when the target month doesn't have it
+    PreserveDay,
+}
+
+/// What happens to the day of the month when shifting by months and the day is at or past the end
+/// of the target month
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EomPolicy {
+    /// Keep the day, clamping it to the end of shorter months: Feb 28 + 1 month is Mar 28
+    PreserveDayClamped,
+    /// Like [EomPolicy::PreserveDayClamped], except the last day of a month stays the last day:
+    /// Feb 28 + 1 month is Mar 31. This is the behavior of [shift_months]
+    #[default]
+    PreserveLastDay,
+    /// Keep the day and fail when the target month doesn't have it
+    Strict,
+}
+
+impl EomPolicy {
+    /// Name of the policy in the `[eom=...]` suffix of an ISO8601 duration
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            EomPolicy::PreserveDayClamped => "clamped",
+            EomPolicy::PreserveLastDay => "last-day",
+            EomPolicy::Strict => "strict",
+        }
+    }
+
+    pub(crate) fn from_name(name: &[u8]) -> Option<EomPolicy> {
+        [
+            EomPolicy::PreserveDayClamped,
+            EomPolicy::PreserveLastDay,
+            EomPolicy::Strict,
+        ]
+        .into_iter()
+        .find(|policy| policy.name().as_bytes() == name)
+    }
+}
+
+impl From<ShiftPolicy> for EomPolicy {
+    fn from(policy: ShiftPolicy) -> Self {
+        match policy {
+            ShiftPolicy::Clamp => EomPolicy::PreserveLastDay,
+            ShiftPolicy::PreserveDay => EomPolicy::Strict,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ShiftError {
+    #[error("shifted date is out of range")]
+    OutOfRange,
+    #[error("{year}-{month:0>2} does not have a day {day}")]
+    DayOutOfRange { year: i32, month: u32, day: u32 },
+}
+
+/// Shift the date by months following the policy, backwards when negative
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// use calends::{try_shift_months, ShiftError, ShiftPolicy};
+///
+/// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+///
+/// assert_eq!(try_shift_months(date(2022, 1, 31), 1, ShiftPolicy::Clamp), Ok(date(2022, 2, 28)));
+/// assert_eq!(
+///     try_shift_months(date(2022, 1, 31), 1, ShiftPolicy::PreserveDay),
+///     Err(ShiftError::DayOutOfRange { year: 2022, month: 2, day: 31 })
+/// );
+/// // The last day of February isn't moved to the end of March
+/// assert_eq!(
+///     try_shift_months(date(2022, 2, 28), 1, ShiftPolicy::PreserveDay),
+///     Ok(date(2022, 3, 28))
+/// );
+/// ```
+pub fn try_shift_months(
+    date: NaiveDate,
+    months: i32,
+    policy: ShiftPolicy,
+) -> Result<NaiveDate, ShiftError> {
+    shift_months_with(date, months, policy.into())
+}
+
+/// Shift the date by months with an end of month policy, backwards when negative
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// use calends::{shift_months_with, EomPolicy, ShiftError};
+///
+/// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+/// let feb = date(2022, 2, 28);
+///
+/// assert_eq!(shift_months_with(feb, 1, EomPolicy::PreserveDayClamped), Ok(date(2022, 3, 28)));
+/// assert_eq!(shift_months_with(feb, 1, EomPolicy::PreserveLastDay), Ok(date(2022, 3, 31)));
+/// assert_eq!(shift_months_with(feb, 1, EomPolicy::Strict), Ok(date(2022, 3, 28)));
+///
+/// let jan = date(2022, 1, 31);
+/// assert_eq!(shift_months_with(jan, 1, EomPolicy::PreserveDayClamped), Ok(date(2022, 2, 28)));
+/// assert_eq!(
+///     shift_months_with(jan, 1, EomPolicy::Strict),
+///     Err(ShiftError::DayOutOfRange { year: 2022, month: 2, day: 31 })
+/// );
+/// ```
+pub fn shift_months_with(
+    date: NaiveDate,
+    months: i32,
+    policy: EomPolicy,
+) -> Result<NaiveDate, ShiftError> {
+    if policy == EomPolicy::PreserveLastDay {
+        return checked_shift_months(date, months).ok_or(ShiftError::OutOfRange);
+    }
+
+    let start = date.with_day(1).expect("first of the month is in range");
+    let target = checked_shift_months(start, months).ok_or(ShiftError::OutOfRange)?;
+    let end =
+        util::checked_month_end(target.year(), target.month()).ok_or(ShiftError::OutOfRange)?;
+
+    match policy {
+        EomPolicy::Strict if date.day() > end.day() => Err(ShiftError::DayOutOfRange {
+            year: target.year(),
+            month: target.month(),
+            day: date.day(),
+        }),
+        _ => Ok(end.with_day(date.day().min(end.day())).unwrap()),
+    }
+}
+
+/// Shift the date by quarters following the policy, see [try_shift_months]
+pub fn try_shift_quarters(
+    date: NaiveDate,
+    quarters: i32,
+    policy: ShiftPolicy,
+) -> Result<NaiveDate, ShiftError> {
+    let months = quarters.checked_mul(3).ok_or(ShiftError::OutOfRange)?;
+    try_shift_months(date, months, policy)
+}
+
+/// Shift the date by years following the policy, see [try_shift_months]
+pub fn try_shift_years(
+    date: NaiveDate,
+    years: i32,
+    policy: ShiftPolicy,
+) -> Result<NaiveDate, ShiftError> {
+    let months = years.checked_mul(12).ok_or(ShiftError::OutOfRange)?;
+    try_shift_months(date, months, policy)
 }
 
 /// Add a week
@@ -120,13 +359,25 @@ pub fn shift_years(date: NaiveDate, years: i32) -> NaiveDate {
 /// Simple enough
 #[inline]
 pub fn shift_weeks(date: NaiveDate, delta: i32) -> NaiveDate {
-    date + chrono::Duration::weeks(delta as i64)
+    checked_shift_weeks(date, delta).expect("date out of range")
+}
+
+/// Add a week, returning [None] if the resulting date is out of range
+#[inline]
+pub fn checked_shift_weeks(date: NaiveDate, delta: i32) -> Option<NaiveDate> {
+    date.checked_add_signed(chrono::Duration::weeks(delta as i64))
 }
 
 /// Add a day
 #[inline]
 pub fn shift_days(date: NaiveDate, days: i32) -> NaiveDate {
-    date + chrono::Duration::days(days.into())
+    checked_shift_days(date, days).expect("date out of range")
+}
+
+/// Add a day, returning [None] if the resulting date is out of range
+#[inline]
+pub fn checked_shift_days(date: NaiveDate, days: i32) -> Option<NaiveDate> {
+    date.checked_add_signed(chrono::Duration::days(days.into()))
 }
 
 #[cfg(test)]
@@ -146,12 +397,122 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_shift_months_with_report() {
+        assert_eq!(
+            shift_months_with_report(NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(), 2),
+            (
+                NaiveDate::from_ymd_opt(2022, 3, 31).unwrap(),
+                MonthAdjustment::None
+            )
+        );
+
+        assert_eq!(
+            shift_months_with_report(NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(), 1),
+            (
+                NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(),
+                MonthAdjustment::PinnedToMonthEnd
+            )
+        );
+
+        assert_eq!(
+            shift_months_with_report(NaiveDate::from_ymd_opt(2024, 3, 30).unwrap(), -1),
+            (
+                NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+                MonthAdjustment::ClampedToMonthEnd
+            )
+        );
+    }
+
+    #[test]
+    fn test_checked_shift() {
+        assert_eq!(checked_shift_months(NaiveDate::MAX, 1), None);
+        assert_eq!(checked_shift_months(NaiveDate::MIN, -1), None);
+        assert_eq!(checked_shift_days(NaiveDate::MAX, 1), None);
+        assert_eq!(
+            checked_shift_weeks(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), 1),
+            NaiveDate::from_ymd_opt(2022, 1, 8)
+        );
+    }
+
     #[test]
     fn test_shift_quarters() {
         assert_eq!(
             shift_quarters(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), 1),
             NaiveDate::from_ymd_opt(2022, 4, 1).unwrap()
         );
+
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        for date in start.iter_days().take(800) {
+            for quarters in [-9, -4, -1, 0, 1, 3, 4, 41] {
+                let shifted = shift_quarters(date, quarters);
+                let months = shifted.year() * 12 + shifted.month0() as i32
+                    - (date.year() * 12 + date.month0() as i32);
+                assert_eq!(months, 3 * quarters);
+
+                if date.day() < 28 {
+                    assert_eq!(shifted.day(), date.day());
+                    assert_eq!(shift_quarters(shifted, -quarters), date);
+                }
+            }
+        }
+
+        assert_eq!(checked_shift_quarters(NaiveDate::MAX, 1), None);
+        assert_eq!(checked_shift_quarters(NaiveDate::MIN, i32::MAX), None);
+    }
+
+    #[test]
+    fn test_shift_months_with() {
+        let start = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+        for date in start.iter_days().take(400) {
+            for months in [-13, -1, 1, 2, 12] {
+                let clamped =
+                    shift_months_with(date, months, EomPolicy::PreserveDayClamped).unwrap();
+                let last_day = shift_months_with(date, months, EomPolicy::PreserveLastDay).unwrap();
+
+                assert_eq!(clamped.with_day(1), last_day.with_day(1));
+                assert!(clamped.day() <= date.day());
+                match shift_months_with(date, months, EomPolicy::Strict) {
+                    Ok(strict) => assert_eq!(strict, clamped),
+                    Err(_) => assert!(clamped.day() < date.day()),
+                }
+            }
+        }
+
+        assert_eq!(
+            shift_months_with(NaiveDate::MAX, 1, EomPolicy::PreserveDayClamped),
+            Err(ShiftError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_try_shift() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(
+            try_shift_years(date(2024, 2, 29), 1, ShiftPolicy::PreserveDay),
+            Err(ShiftError::DayOutOfRange {
+                year: 2025,
+                month: 2,
+                day: 29
+            })
+        );
+        assert_eq!(
+            try_shift_years(date(2024, 2, 29), -4, ShiftPolicy::PreserveDay),
+            Ok(date(2020, 2, 29))
+        );
+        assert_eq!(
+            try_shift_quarters(date(2022, 4, 30), -1, ShiftPolicy::PreserveDay),
+            Ok(date(2022, 1, 30))
+        );
+        assert_eq!(
+            try_shift_quarters(date(2022, 4, 30), -1, ShiftPolicy::Clamp),
+            Ok(date(2022, 1, 31))
+        );
+        assert_eq!(
+            try_shift_months(NaiveDate::MAX, 1, ShiftPolicy::PreserveDay),
+            Err(ShiftError::OutOfRange)
+        );
     }
 
     #[test]
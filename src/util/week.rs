@@ -0,0 +1,55 @@
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+
+/// Day a week starts on, Monday as in ISO 8601 unless configured otherwise
+///
+/// ```
+/// use calends::{beginning_of_week_starting, WeekStart};
+/// use chrono::{NaiveDate, Weekday};
+///
+/// // 2022-01-05 is a Wednesday
+/// let date = NaiveDate::from_ymd_opt(2022, 1, 5).unwrap();
+///
+/// assert_eq!(
+///     beginning_of_week_starting(&date, WeekStart::SUNDAY),
+///     NaiveDate::from_ymd_opt(2022, 1, 2).unwrap()
+/// );
+/// assert_eq!(WeekStart::SUNDAY.days_from_start(Weekday::Mon), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WeekStart(Weekday);
+
+impl WeekStart {
+    /// Weeks starting on Monday, as in ISO 8601 and most of Europe
+    pub const MONDAY: WeekStart = WeekStart(Weekday::Mon);
+    /// Weeks starting on Sunday, as in the US
+    pub const SUNDAY: WeekStart = WeekStart(Weekday::Sun);
+    /// Weeks starting on Saturday, as in much of the Middle East
+    pub const SATURDAY: WeekStart = WeekStart(Weekday::Sat);
+
+    /// Weeks starting on the weekday
+    pub fn new(weekday: Weekday) -> Self {
+        WeekStart(weekday)
+    }
+
+    /// First day of the week
+    pub fn weekday(&self) -> Weekday {
+        self.0
+    }
+
+    /// Last day of the week
+    pub fn last_weekday(&self) -> Weekday {
+        self.0.pred()
+    }
+
+    /// Position of the weekday within the week, from 0 for the first day to 6 for the last
+    pub fn days_from_start(&self, weekday: Weekday) -> u32 {
+        weekday.days_since(self.0)
+    }
+}
+
+impl Default for WeekStart {
+    fn default() -> Self {
+        WeekStart::MONDAY
+    }
+}
@@ -0,0 +1,256 @@
+//! Working hours and the time worked between two moments
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::calendar::HolidayCalendar;
+
+/// Longest run of days without working hours before a search gives up
+const MAX_NON_WORKING_DAYS: usize = 366;
+
+/// Working hours for each day of the week, with no work on the holidays of a calendar
+///
+/// The days with hours make up the workweek, so the workweek of the calendar isn't used.
+///
+/// ```
+/// use calends::WorkSchedule;
+/// use chrono::{Duration, NaiveDate};
+///
+/// let holidays = vec![NaiveDate::from_ymd_opt(2022, 12, 26).unwrap()];
+/// let schedule = WorkSchedule::nine_to_five(holidays);
+///
+/// // Friday 23rd December at 15:00
+/// let raised = NaiveDate::from_ymd_opt(2022, 12, 23).unwrap().and_hms_opt(15, 0, 0).unwrap();
+/// let due = schedule.add_working_hours(raised, 8).unwrap();
+///
+/// assert_eq!(due, NaiveDate::from_ymd_opt(2022, 12, 27).unwrap().and_hms_opt(15, 0, 0).unwrap());
+/// assert_eq!(schedule.working_time_between(raised, due), Duration::hours(8));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WorkSchedule<C> {
+    /// Sorted ranges which neither overlap nor touch, for each weekday starting from Monday
+    hours: [Vec<(NaiveTime, NaiveTime)>; 7],
+    calendar: C,
+}
+
+impl<C: HolidayCalendar> WorkSchedule<C> {
+    /// A schedule without any working hours, see [WorkSchedule::with_hours]
+    pub fn new(calendar: C) -> Self {
+        WorkSchedule {
+            hours: Default::default(),
+            calendar,
+        }
+    }
+
+    /// Monday to Friday from 09:00 to 17:00
+    pub fn nine_to_five(calendar: C) -> Self {
+        let (start, end) = (
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+        );
+
+        [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]
+        .into_iter()
+        .fold(WorkSchedule::new(calendar), |schedule, weekday| {
+            schedule.with_hours(weekday, start, end)
+        })
+    }
+
+    /// Add working hours on a day of the week
+    ///
+    /// A weekday can have several ranges, for example either side of lunch, and ranges which
+    /// overlap are merged. A range which doesn't end after it starts is ignored.
+    ///
+    /// ```
+    /// use calends::WorkSchedule;
+    /// use chrono::{NaiveDate, NaiveTime, Weekday};
+    ///
+    /// let time = |h| NaiveTime::from_hms_opt(h, 0, 0).unwrap();
+    /// let schedule = WorkSchedule::new(Vec::new())
+    ///     .with_hours(Weekday::Sat, time(13), time(17))
+    ///     .with_hours(Weekday::Sat, time(8), time(12));
+    ///
+    /// // Saturday 1st January
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// assert_eq!(schedule.hours_on(date), [(time(8), time(12)), (time(13), time(17))]);
+    /// ```
+    pub fn with_hours(mut self, weekday: Weekday, start: NaiveTime, end: NaiveTime) -> Self {
+        if start >= end {
+            return self;
+        }
+
+        let ranges = &mut self.hours[weekday.num_days_from_monday() as usize];
+        ranges.push((start, end));
+        ranges.sort_unstable();
+        *ranges = ranges.iter().fold(Vec::new(), |mut merged, &(start, end)| {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+            merged
+        });
+
+        self
+    }
+
+    /// Working hours on the date in order, none on a holiday
+    pub fn hours_on(&self, date: NaiveDate) -> &[(NaiveTime, NaiveTime)] {
+        match self.calendar.is_holiday(date) {
+            true => &[],
+            false => &self.hours[date.weekday().num_days_from_monday() as usize],
+        }
+    }
+
+    fn spans_on(
+        &self,
+        date: NaiveDate,
+    ) -> impl Iterator<Item = (NaiveDateTime, NaiveDateTime)> + '_ {
+        self.hours_on(date)
+            .iter()
+            .map(move |(start, end)| (date.and_time(*start), date.and_time(*end)))
+    }
+
+    /// Working time from the start to the end, negative when the end is before the start
+    pub fn working_time_between(&self, start: NaiveDateTime, end: NaiveDateTime) -> Duration {
+        if end < start {
+            return -self.working_time_between(end, start);
+        }
+
+        std::iter::successors(Some(start.date()), NaiveDate::succ_opt)
+            .take_while(|date| *date <= end.date())
+            .flat_map(|date| self.spans_on(date))
+            .map(|(from, to)| (to.min(end) - from.max(start)).max(Duration::zero()))
+            .fold(Duration::zero(), |total, worked| total + worked)
+    }
+
+    /// The moment the duration of working time after `from` is reached
+    ///
+    /// Time outside working hours doesn't count, so work ending exactly at the end of a range
+    /// ends there rather than at the start of the next one. Returns [None] for a negative
+    /// duration, when the result is out of range or a search goes a year without working hours.
+    pub fn add_working_time(
+        &self,
+        from: NaiveDateTime,
+        duration: Duration,
+    ) -> Option<NaiveDateTime> {
+        if duration < Duration::zero() {
+            return None;
+        }
+
+        let mut remaining = duration;
+        let mut idle_days = 0;
+        for date in std::iter::successors(Some(from.date()), NaiveDate::succ_opt) {
+            let mut spans = self.spans_on(date).peekable();
+            idle_days = match spans.peek() {
+                Some(_) => 0,
+                None => idle_days + 1,
+            };
+            if idle_days > MAX_NON_WORKING_DAYS {
+                return None;
+            }
+
+            for (start, end) in spans.filter(|(_, end)| *end > from) {
+                let available = end - start.max(from);
+                if remaining <= available {
+                    return Some(start.max(from) + remaining);
+                }
+                remaining -= available;
+            }
+        }
+
+        None
+    }
+
+    /// The moment a number of working hours after `from` is reached, see
+    /// [WorkSchedule::add_working_time]
+    pub fn add_working_hours(&self, from: NaiveDateTime, hours: u32) -> Option<NaiveDateTime> {
+        self.add_working_time(from, Duration::hours(hours.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(d: u32, h: u32, m: u32) -> NaiveDateTime {
+        // January 2022 starts on a Saturday
+        NaiveDate::from_ymd_opt(2022, 1, d)
+            .unwrap()
+            .and_hms_opt(h, m, 0)
+            .unwrap()
+    }
+
+    fn time(h: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_working_time_between() {
+        let schedule =
+            WorkSchedule::nine_to_five(vec![NaiveDate::from_ymd_opt(2022, 1, 4).unwrap()]);
+
+        assert_eq!(
+            schedule.working_time_between(at(3, 16, 30), at(3, 16, 45)),
+            Duration::minutes(15)
+        );
+        // Over a weekend and a holiday
+        assert_eq!(
+            schedule.working_time_between(at(1, 0, 0), at(5, 10, 0)),
+            Duration::hours(9)
+        );
+        assert_eq!(
+            schedule.working_time_between(at(5, 10, 0), at(3, 18, 0)),
+            Duration::hours(-1)
+        );
+        assert_eq!(
+            schedule.working_time_between(at(3, 18, 0), at(3, 20, 0)),
+            Duration::zero()
+        );
+    }
+
+    #[test]
+    fn test_add_working_time() {
+        let schedule = WorkSchedule::nine_to_five(Vec::new())
+            .with_hours(Weekday::Mon, time(12), time(13))
+            .with_hours(Weekday::Sat, time(10), time(12))
+            .with_hours(Weekday::Sun, time(12), time(10));
+
+        assert_eq!(
+            schedule.add_working_hours(at(1, 11, 0), 1),
+            Some(at(1, 12, 0))
+        );
+        assert_eq!(
+            schedule.add_working_hours(at(1, 11, 0), 2),
+            Some(at(3, 10, 0))
+        );
+        assert_eq!(
+            schedule.add_working_hours(at(2, 23, 0), 0),
+            Some(at(3, 9, 0))
+        );
+        assert_eq!(
+            schedule.add_working_time(at(3, 16, 0), Duration::minutes(90)),
+            Some(at(4, 9, 30))
+        );
+        assert_eq!(
+            schedule.add_working_time(at(3, 16, 0), Duration::minutes(-1)),
+            None
+        );
+
+        for hours in 0..40 {
+            let due = schedule.add_working_hours(at(1, 0, 0), hours).unwrap();
+            assert_eq!(
+                schedule.working_time_between(at(1, 0, 0), due),
+                Duration::hours(hours.into())
+            );
+        }
+
+        assert_eq!(
+            WorkSchedule::new(Vec::new()).add_working_hours(at(1, 0, 0), 1),
+            None
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! JavaScript bindings through [mod@wasm_bindgen]
+//!
+//! Enable the `wasm` feature to export [JsRelativeDuration], [JsInterval] and [JsRecurrence] as
+//! the `RelativeDuration`, `Interval` and `Recurrence` classes. Values cross the boundary as
+//! strings: durations and intervals in their ISO8601-2:2019 form, dates as `YYYY-MM-DD` and
+//! recurrence rules as RRULE values. Occurrences are returned as arrays of date strings.
+//!
+//! ```js
+//! import { RelativeDuration, Recurrence } from "calends";
+//!
+//! new RelativeDuration("P1M").addTo("2022-01-31"); // "2022-02-28"
+//! new Recurrence("FREQ=MONTHLY;BYMONTHDAY=15", "2022-01-01").take(2); // ["2022-01-15", "2022-02-15"]
+//! ```
+use chrono::NaiveDate;
+use nom::combinator::all_consuming;
+use wasm_bindgen::prelude::*;
+
+use crate::{parser, recurrence::rrule::RRule, Interval, IntervalLike, RelativeDuration};
+
+fn js_error(e: impl ToString) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, JsError> {
+    date.parse().map_err(js_error)
+}
+
+fn format_dates(dates: impl Iterator<Item = NaiveDate>) -> Vec<String> {
+    dates.map(|date| date.to_string()).collect()
+}
+
+/// A [RelativeDuration] exported as `RelativeDuration`
+#[wasm_bindgen(js_name = RelativeDuration)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsRelativeDuration(RelativeDuration);
+
+#[wasm_bindgen(js_class = RelativeDuration)]
+impl JsRelativeDuration {
+    /// Parse an ISO8601-2:2019 duration such as `P1M2D`
+    #[wasm_bindgen(constructor)]
+    pub fn new(iso8601: &str) -> Result<JsRelativeDuration, JsError> {
+        all_consuming(parser::relative_duration)(iso8601)
+            .map(|(_, duration)| JsRelativeDuration(duration))
+            .map_err(|_| js_error(format!("invalid duration `{}`", iso8601)))
+    }
+
+    #[wasm_bindgen(js_name = fromMwd)]
+    pub fn from_mwd(months: i32, weeks: i32, days: i32) -> Result<JsRelativeDuration, JsError> {
+        RelativeDuration::try_from_mwd(months, weeks, days)
+            .map(JsRelativeDuration)
+            .ok_or_else(|| js_error("duration is out of range"))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn months(&self) -> i32 {
+        self.0.num_months()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn weeks(&self) -> i32 {
+        self.0.num_weeks()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn days(&self) -> i32 {
+        self.0.num_days()
+    }
+
+    pub fn iso8601(&self) -> String {
+        self.0.iso8601()
+    }
+
+    /// The date which is the duration after `date`
+    #[wasm_bindgen(js_name = addTo)]
+    pub fn add_to(&self, date: &str) -> Result<String, JsError> {
+        self.0
+            .checked_add_to(parse_date(date)?)
+            .map(|date| date.to_string())
+            .ok_or_else(|| js_error("date is out of range"))
+    }
+}
+
+impl From<RelativeDuration> for JsRelativeDuration {
+    fn from(duration: RelativeDuration) -> Self {
+        JsRelativeDuration(duration)
+    }
+}
+
+impl From<JsRelativeDuration> for RelativeDuration {
+    fn from(duration: JsRelativeDuration) -> Self {
+        duration.0
+    }
+}
+
+/// An [Interval] exported as `Interval`
+#[wasm_bindgen(js_name = Interval)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsInterval(Interval);
+
+#[wasm_bindgen(js_class = Interval)]
+impl JsInterval {
+    /// Parse an ISO8601-2:2019 interval such as `2022-01-01/P1M`, `../2022-01-01` or
+    /// `2022-01-01/..`
+    #[wasm_bindgen(constructor)]
+    pub fn new(iso8601: &str) -> Result<JsInterval, JsError> {
+        all_consuming(parser::closed_interval)(iso8601)
+            .map(|(_, i)| Interval::Closed(i))
+            .or_else(|_| {
+                all_consuming(parser::open_start_interval)(iso8601)
+                    .map(|(_, i)| Interval::OpenStart(i))
+            })
+            .or_else(|_| {
+                all_consuming(parser::open_end_interval)(iso8601).map(|(_, i)| Interval::OpenEnd(i))
+            })
+            .map(JsInterval)
+            .map_err(|_| js_error(format!("invalid interval `{}`", iso8601)))
+    }
+
+    /// First date of the interval, `undefined` without a start
+    #[wasm_bindgen(getter)]
+    pub fn start(&self) -> Option<String> {
+        self.0.start_opt().map(|date| date.to_string())
+    }
+
+    /// Last date of the interval, `undefined` without an end
+    #[wasm_bindgen(getter)]
+    pub fn end(&self) -> Option<String> {
+        self.0.end_opt().map(|date| date.to_string())
+    }
+
+    pub fn iso8601(&self) -> String {
+        self.0.iso8601()
+    }
+
+    pub fn within(&self, date: &str) -> Result<bool, JsError> {
+        Ok(self.0.within(parse_date(date)?))
+    }
+}
+
+impl From<Interval> for JsInterval {
+    fn from(interval: Interval) -> Self {
+        JsInterval(interval)
+    }
+}
+
+impl From<JsInterval> for Interval {
+    fn from(interval: JsInterval) -> Self {
+        interval.0
+    }
+}
+
+/// An RRULE series from a start date exported as `Recurrence`, see [RRule::with_start]
+#[wasm_bindgen(js_name = Recurrence)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsRecurrence {
+    rrule: RRule,
+    start: NaiveDate,
+}
+
+#[wasm_bindgen(js_class = Recurrence)]
+impl JsRecurrence {
+    /// A series from an RRULE value such as `FREQ=WEEKLY;BYDAY=MO` and a start date
+    #[wasm_bindgen(constructor)]
+    pub fn new(rrule: &str, start: &str) -> Result<JsRecurrence, JsError> {
+        Ok(JsRecurrence {
+            rrule: rrule.parse().map_err(js_error)?,
+            start: parse_date(start)?,
+        })
+    }
+
+    /// The first `n` occurrences
+    pub fn take(&self, n: usize) -> Vec<String> {
+        format_dates(self.rrule.with_start(self.start).take(n))
+    }
+
+    /// The occurrences from `start` up to and including `end`
+    pub fn between(&self, start: &str, end: &str) -> Result<Vec<String>, JsError> {
+        let (start, end) = (parse_date(start)?, parse_date(end)?);
+
+        Ok(format_dates(
+            self.rrule
+                .with_start(self.start)
+                .skip_while(|date| *date < start)
+                .take_while(|date| *date <= end),
+        ))
+    }
+
+    pub fn rrule(&self) -> Result<String, JsError> {
+        self.rrule.to_rrule().map_err(js_error)
+    }
+
+    /// The rule in English, see [crate::Rule::describe]
+    pub fn describe(&self) -> String {
+        self.rrule.rule.describe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_round_trips() {
+        let duration = JsRelativeDuration::new("P1M-2D").unwrap();
+        assert_eq!(
+            (duration.months(), duration.weeks(), duration.days()),
+            (1, 0, -2)
+        );
+        assert_eq!(duration.iso8601(), "P1M-2D");
+        assert_eq!(duration.add_to("2022-01-31").unwrap(), "2022-02-26");
+        assert_eq!(
+            JsRelativeDuration::from_mwd(0, 2, 0).unwrap(),
+            RelativeDuration::weeks(2).into()
+        );
+
+        let interval = JsInterval::new("2022-01-01/P1M").unwrap();
+        assert_eq!(interval.start().as_deref(), Some("2022-01-01"));
+        assert_eq!(interval.end().as_deref(), Some("2022-02-01"));
+        assert!(interval.within("2022-01-15").unwrap());
+
+        let open = JsInterval::new("../2022-01-01").unwrap();
+        assert_eq!(open.start(), None);
+        assert_eq!(open.iso8601(), "../2022-01-01");
+
+        let open = JsInterval::new("2022-01-01/..").unwrap();
+        assert_eq!(open.end(), None);
+        assert_eq!(open.iso8601(), "2022-01-01/..");
+    }
+
+    #[test]
+    fn test_occurrences() {
+        let recurrence = JsRecurrence::new("FREQ=MONTHLY;BYMONTHDAY=15", "2022-01-20").unwrap();
+
+        assert_eq!(recurrence.take(2), ["2022-02-15", "2022-03-15"]);
+        assert_eq!(
+            recurrence.between("2022-04-01", "2022-06-15").unwrap(),
+            ["2022-04-15", "2022-05-15", "2022-06-15"]
+        );
+        assert_eq!(recurrence.rrule().unwrap(), "FREQ=MONTHLY;BYMONTHDAY=15");
+    }
+}
@@ -0,0 +1,239 @@
+//! A month of a specific year
+use std::{fmt::Display, str::FromStr};
+
+#[cfg(not(feature = "strict"))]
+use std::ops::{Add, Sub};
+
+use chrono::{Datelike, NaiveDate};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    unit::CalendarUnitParseError,
+    util::{checked_days_in_month, checked_month_end},
+    CalendarUnit,
+};
+
+/// A month of a year such as July 2024, for values kept at month precision
+///
+/// The [Display] form is the ISO 8601 `YYYY-MM`, which is also the serialized form.
+///
+/// ```
+/// use calends::YearMonth;
+/// use chrono::NaiveDate;
+///
+/// let month = YearMonth::new(2024, 2).unwrap();
+///
+/// assert_eq!(month.last_day(), NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+/// assert_eq!(month.checked_add(11), YearMonth::new(2025, 1));
+/// assert_eq!(month.to_string(), "2024-02");
+/// assert_eq!("2024-02".parse(), Ok(month));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct YearMonth {
+    year: i32,
+    month: u8,
+}
+
+impl YearMonth {
+    /// The month of the year, [None] unless the month is from 1 to 12
+    pub fn new(year: i32, month: u32) -> Option<YearMonth> {
+        checked_days_in_month(year, month)?;
+
+        Some(YearMonth {
+            year,
+            month: month as u8,
+        })
+    }
+
+    /// Month containing the date
+    pub fn of(date: NaiveDate) -> YearMonth {
+        YearMonth {
+            year: date.year(),
+            month: date.month() as u8,
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Month of the year from 1 to 12
+    pub fn month(&self) -> u32 {
+        self.month.into()
+    }
+
+    /// Number of days in the month
+    pub fn days_in_month(&self) -> u32 {
+        checked_days_in_month(self.year, self.month()).expect("month is valid")
+    }
+
+    /// Date of the day in the month, [None] if the month doesn't have the day
+    pub fn day(&self, day: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.year, self.month(), day)
+    }
+
+    pub fn first_day(&self) -> NaiveDate {
+        self.day(1).expect("month out of range")
+    }
+
+    pub fn last_day(&self) -> NaiveDate {
+        checked_month_end(self.year, self.month()).expect("month out of range")
+    }
+
+    pub fn into_unit(&self) -> CalendarUnit {
+        CalendarUnit::Month(self.year, self.month)
+    }
+
+    pub fn succ(&self) -> YearMonth {
+        self.checked_add(1).expect("month out of range")
+    }
+
+    pub fn pred(&self) -> YearMonth {
+        self.checked_add(-1).expect("month out of range")
+    }
+
+    /// The month `n` months later, or earlier for a negative `n`, returning [None] if the year is
+    /// out of range
+    pub fn checked_add(&self, n: i32) -> Option<YearMonth> {
+        let index = i64::from(self.year) * 12 + i64::from(self.month0()) + i64::from(n);
+        let year = i32::try_from(index.div_euclid(12)).ok()?;
+
+        YearMonth::new(year, index.rem_euclid(12) as u32 + 1)
+    }
+
+    /// Number of months from this month to the other, negative when the other is earlier
+    ///
+    /// ```
+    /// use calends::YearMonth;
+    ///
+    /// let start = YearMonth::new(2022, 11).unwrap();
+    /// assert_eq!(start.months_until(&YearMonth::new(2024, 2).unwrap()), 15);
+    /// ```
+    pub fn months_until(&self, other: &YearMonth) -> i64 {
+        (i64::from(other.year) - i64::from(self.year)) * 12 + i64::from(other.month0())
+            - i64::from(self.month0())
+    }
+
+    fn month0(&self) -> u32 {
+        self.month() - 1
+    }
+}
+
+impl From<YearMonth> for CalendarUnit {
+    fn from(month: YearMonth) -> Self {
+        month.into_unit()
+    }
+}
+
+impl TryFrom<CalendarUnit> for YearMonth {
+    type Error = CalendarUnit;
+
+    fn try_from(unit: CalendarUnit) -> Result<Self, Self::Error> {
+        match unit {
+            CalendarUnit::Month(year, month) => YearMonth::new(year, month.into()).ok_or(unit),
+            unit => Err(unit),
+        }
+    }
+}
+
+impl Display for YearMonth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.into_unit().fmt(f)
+    }
+}
+
+/// Parse a month in the [Display] form of a [CalendarUnit] such as `2024-07`
+impl FromStr for YearMonth {
+    type Err = CalendarUnitParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<CalendarUnit>()?
+            .try_into()
+            .map_err(|_| CalendarUnitParseError::InvalidFormat(s.to_string()))
+    }
+}
+
+impl Serialize for YearMonth {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for YearMonth {
+    fn deserialize<D>(deserializer: D) -> Result<YearMonth, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(not(feature = "strict"))]
+impl Add<i32> for YearMonth {
+    type Output = YearMonth;
+
+    fn add(self, rhs: i32) -> YearMonth {
+        self.checked_add(rhs).expect("month out of range")
+    }
+}
+
+#[cfg(not(feature = "strict"))]
+impl Sub<i32> for YearMonth {
+    type Output = YearMonth;
+
+    fn sub(self, rhs: i32) -> YearMonth {
+        rhs.checked_neg()
+            .and_then(|n| self.checked_add(n))
+            .expect("month out of range")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_unit() {
+        let start = YearMonth::new(-1, 11).unwrap();
+
+        for n in -30..30 {
+            let month = start.checked_add(n).unwrap();
+            assert_eq!(month.into_unit(), start.into_unit().add(n));
+            assert_eq!(start.months_until(&month), i64::from(n));
+            assert_eq!(YearMonth::of(month.last_day()), month);
+            assert_eq!(month.first_day(), month.into_unit().start_date());
+            assert_eq!(month.last_day(), month.into_unit().end_date());
+            assert_eq!(month.to_string().parse(), Ok(month));
+        }
+
+        assert_eq!(YearMonth::new(2022, 13), None);
+        assert_eq!(
+            YearMonth::try_from(CalendarUnit::Month(2022, 13)),
+            Err(CalendarUnit::Month(2022, 13))
+        );
+        assert_eq!(YearMonth::new(i32::MIN, 1).unwrap().checked_add(-1), None);
+        assert!("2022-Q1".parse::<YearMonth>().is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let month = YearMonth::new(2024, 7).unwrap();
+        let json = serde_json::to_string(&month).unwrap();
+
+        assert_eq!(json, r#""2024-07""#);
+        assert_eq!(serde_json::from_str::<YearMonth>(&json).unwrap(), month);
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn test_arithmetic() {
+        let month = YearMonth::new(2024, 1).unwrap();
+
+        assert_eq!(month - 1, YearMonth::new(2023, 12).unwrap());
+        assert_eq!(month + 25, YearMonth::new(2026, 2).unwrap());
+    }
+}